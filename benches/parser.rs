@@ -303,9 +303,65 @@ fn bench_regex(c: &mut Criterion) {
 #[cfg(not(feature = "regex"))]
 fn bench_regex(_: &mut Criterion) {}
 
+#[cfg(feature = "pratt")]
+fn bench_pratt(c: &mut Criterion) {
+    use chumsky::pratt::*;
+
+    fn atom<'src>() -> impl Parser<'src, &'src str, i64> + Clone {
+        text::int(10).from_str().unwrapped()
+    }
+
+    let src = "1+2*-3^2!-4/5&6*7+8".repeat(64) + "0";
+    let src = src.as_str();
+
+    let tuple = atom().pratt((
+        prefix(5, just('-'), |_, x: i64, _| -x),
+        postfix(6, just('!'), |x: i64, _, _| x + 1),
+        infix(right(4), just('^'), |l: i64, _, r: i64, _| {
+            l.wrapping_pow(r as u32 % 4)
+        }),
+        infix(left(3), just('*'), |l: i64, _, r: i64, _| l.wrapping_mul(r)),
+        infix(left(3), just('/'), |l: i64, _, r: i64, _| l / r.max(1)),
+        infix(left(2), just('+'), |l: i64, _, r: i64, _| l.wrapping_add(r)),
+        infix(left(2), just('-'), |l: i64, _, r: i64, _| l.wrapping_sub(r)),
+        infix(left(1), just('&'), |l: i64, _, r: i64, _| l & r),
+    ));
+
+    let vec = atom().pratt(vec![
+        prefix(5, just('-'), |_, x: i64, _| -x).boxed(),
+        postfix(6, just('!'), |x: i64, _, _| x + 1).boxed(),
+        infix(right(4), just('^'), |l: i64, _, r: i64, _| {
+            l.wrapping_pow(r as u32 % 4)
+        })
+        .boxed(),
+        infix(left(3), just('*'), |l: i64, _, r: i64, _| l.wrapping_mul(r)).boxed(),
+        infix(left(3), just('/'), |l: i64, _, r: i64, _| l / r.max(1)).boxed(),
+        infix(left(2), just('+'), |l: i64, _, r: i64, _| l.wrapping_add(r)).boxed(),
+        infix(left(2), just('-'), |l: i64, _, r: i64, _| l.wrapping_sub(r)).boxed(),
+        infix(left(1), just('&'), |l: i64, _, r: i64, _| l & r).boxed(),
+    ]);
+
+    let mut group = c.benchmark_group("pratt");
+
+    group.bench_function(BenchmarkId::new("tuple", src.len()), |b| {
+        b.iter(|| {
+            black_box(tuple.parse(black_box(src)))
+                .into_result()
+                .unwrap()
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("vec_boxed", src.len()), |b| {
+        b.iter(|| black_box(vec.parse(black_box(src))).into_result().unwrap())
+    });
+}
+
+#[cfg(not(feature = "pratt"))]
+fn bench_pratt(_: &mut Criterion) {}
+
 criterion_group!(
     name = benches;
     config = utils::make_criterion();
-    targets = bench_choice, bench_or, bench_group, bench_then, bench_regex,
+    targets = bench_choice, bench_or, bench_group, bench_then, bench_regex, bench_pratt,
 );
 criterion_main!(benches);
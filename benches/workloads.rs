@@ -0,0 +1,50 @@
+use chumsky::{bench::criterion_bench, prelude::*};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+mod utils;
+
+fn bench_dispatch(c: &mut Criterion) {
+    let stmts = "let x = 5; while x; print x;\n".repeat(1000);
+
+    let ident = text::ascii::ident::<_, extra::Default>().padded();
+    let num = text::int(10).padded();
+
+    let stmt = dispatch! {
+        'l' => text::ascii::keyword("let").ignore_then(ident).then_ignore(just('=')).then(num).ignored(),
+        'p' => text::ascii::keyword("print").ignore_then(ident).ignored(),
+        'w' => text::ascii::keyword("while").ignore_then(ident).ignored(),
+    }
+    .then_ignore(just(';').padded())
+    .repeated();
+
+    assert!(!stmt.parse(stmts.as_str()).has_errors());
+    criterion_bench(c, "dispatch", &stmt, stmts.as_str());
+}
+
+fn bench_float(c: &mut Criterion) {
+    let floats = text::float::<_, extra::Default>()
+        .integer(true)
+        .separated_by(just(',').padded())
+        .collect::<Vec<_>>();
+
+    let input = ["3.14159", "2.0e-3", "42", "0.5", "1e10"]
+        .repeat(1000)
+        .join(", ");
+    assert!(!floats.parse(input.as_str()).has_errors());
+    criterion_bench(c, "float", &floats, input.as_str());
+}
+
+fn bench_balanced(c: &mut Criterion) {
+    let bodies = balanced::<_, extra::Default>('{', '}').padded().repeated();
+
+    let input = "{ a { b { c } } d { e } }\n".repeat(1000);
+    assert!(!bodies.parse(input.as_str()).has_errors());
+    criterion_bench(c, "balanced", &bodies, input.as_str());
+}
+
+criterion_group!(
+    name = benches;
+    config = utils::make_criterion();
+    targets = bench_dispatch, bench_float, bench_balanced
+);
+criterion_main!(benches);
@@ -0,0 +1,685 @@
+//! Tools for measuring the performance of your parsers.
+//!
+//! *“Time is an illusion. Lunchtime doubly so.”*
+//!
+//! The [`run`] function parses an input repeatedly with a parser of your choosing and produces a [`Report`] containing
+//! the throughput of the parser along with a few statistics that are useful for diagnosing performance problems, such
+//! as how often the parser had to backtrack.
+//!
+//! Allocation statistics are only gathered if [`CountingAlloc`] is installed as the program's global allocator.
+//! Because installing a global allocator affects the whole program, this is best done in a dedicated benchmark binary.
+//! To find out *which* parts of your parser are allocating, wrap them with [`track_allocs`]: a breakdown of
+//! allocations per named parser will then be included in the report.
+//!
+//! Large grammars can also be slow to *compile*. [`profile_type`] reports the nesting depth and size of a parser's
+//! type, along with the places where inserting [`Parser::boxed`] would most reduce them.
+//!
+//! With the `criterion` feature enabled, [`criterion_bench`] registers a parser and input as a benchmark with
+//! [`criterion`], for tracking the performance of a grammar over time.
+//!
+//! # Examples
+//!
+//! ```
+//! use chumsky::prelude::*;
+//!
+//! use chumsky::bench::Counted;
+//!
+//! let parser = text::int::<_, extra::State<Counted>>(10)
+//!     .padded()
+//!     .separated_by(just(','))
+//!     .collect::<Vec<_>>();
+//!
+//! let report = chumsky::bench::run(&parser, "1, 2, 3, 4, 5", 100);
+//!
+//! assert_eq!(report.iterations, 100);
+//! assert!(report.tokens > 0);
+//! assert_eq!(report.failures, 0);
+//! ```
+
+use super::*;
+use crate::inspector::Inspector;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    time::{Duration, Instant},
+};
+
+std::thread_local! {
+    // Allocation totals per named parser, and a stack of the totals of tracked children of currently-running parsers
+    static NAMED_ALLOCS: RefCell<Vec<(&'static str, AllocStats)>> = const { RefCell::new(Vec::new()) };
+    static TRACKED_CHILDREN: RefCell<Vec<AllocStats>> = const { RefCell::new(Vec::new()) };
+}
+
+static ALLOC_INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator wrapper that counts allocations, allowing [`run`] to report allocation statistics.
+///
+/// Counts are global: allocations made by other threads while a benchmark is running will be included in the
+/// report.
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::bench::CountingAlloc;
+/// use std::alloc::System;
+///
+/// #[global_allocator]
+/// static ALLOC: CountingAlloc<System> = CountingAlloc(System);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CountingAlloc<A>(pub A);
+
+#[inline(always)]
+fn record_alloc(size: usize) {
+    ALLOC_INSTALLED.store(true, AtomicOrdering::Relaxed);
+    ALLOCATIONS.fetch_add(1, AtomicOrdering::Relaxed);
+    BYTES_ALLOCATED.fetch_add(size, AtomicOrdering::Relaxed);
+}
+
+// SAFETY: All operations are forwarded to the inner allocator, which upholds the `GlobalAlloc` contract.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAlloc<A> {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size());
+        self.0.alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size());
+        self.0.alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_alloc(new_size);
+        self.0.realloc(ptr, layout, new_size)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+}
+
+/// Allocation statistics gathered by [`CountingAlloc`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// The number of allocations (including reallocations) performed.
+    pub allocations: usize,
+    /// The total number of bytes requested by those allocations.
+    pub bytes: usize,
+}
+
+impl AllocStats {
+    fn now() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(AtomicOrdering::Relaxed),
+            bytes: BYTES_ALLOCATED.load(AtomicOrdering::Relaxed),
+        }
+    }
+
+    fn since(self, before: Self) -> Self {
+        Self {
+            allocations: self.allocations.saturating_sub(before.allocations),
+            bytes: self.bytes.saturating_sub(before.bytes),
+        }
+    }
+
+    fn add(&mut self, other: Self) {
+        self.allocations += other.allocations;
+        self.bytes += other.bytes;
+    }
+}
+
+/// The results of running a benchmark with [`run`].
+///
+/// All counts are totals across every iteration.
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// The number of times the input was parsed.
+    pub iterations: usize,
+    /// The total time spent parsing.
+    pub elapsed: Duration,
+    /// The number of tokens pulled from the input, including tokens that were read again after backtracking.
+    pub tokens: usize,
+    /// The number of times the parser rewound to an earlier position in the input.
+    pub backtracks: usize,
+    /// The number of parses that produced errors.
+    pub failures: usize,
+    /// Allocation statistics, if [`CountingAlloc`] is installed as the global allocator.
+    pub allocs: Option<AllocStats>,
+    /// Allocation statistics for each parser wrapped with [`track_allocs`], in the order they were first run.
+    ///
+    /// Allocations are attributed to the innermost tracked parser that was running when they occurred. This is
+    /// empty if [`CountingAlloc`] is not installed as the global allocator.
+    pub named_allocs: Vec<(&'static str, AllocStats)>,
+}
+
+impl Report {
+    /// The average time taken to parse the input once.
+    pub fn per_iteration(&self) -> Duration {
+        self.elapsed / self.iterations.max(1) as u32
+    }
+
+    /// The number of tokens pulled from the input per second.
+    pub fn tokens_per_sec(&self) -> f64 {
+        self.tokens as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} iterations in {:?} ({:?}/iter)",
+            self.iterations,
+            self.elapsed,
+            self.per_iteration()
+        )?;
+        writeln!(
+            f,
+            "tokens: {} ({:.0}/s), backtracks: {}, failures: {}",
+            self.tokens,
+            self.tokens_per_sec(),
+            self.backtracks,
+            self.failures
+        )?;
+        match &self.allocs {
+            Some(allocs) => write!(
+                f,
+                "allocations: {} ({} bytes)",
+                allocs.allocations, allocs.bytes
+            ),
+            None => write!(f, "allocations: unknown (`CountingAlloc` is not installed)"),
+        }?;
+        for (name, allocs) in &self.named_allocs {
+            write!(
+                f,
+                "\n  {name}: {} ({} bytes)",
+                allocs.allocations, allocs.bytes
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A parser state that counts the tokens pulled from the input and the number of times that the parser backtracks.
+/// See [`run`].
+///
+/// Events are forwarded to an inner state, `S`, which is available through [`Deref`](core::ops::Deref) and
+/// [`DerefMut`](core::ops::DerefMut), so parsers that need their own state can still be benchmarked.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Counted<S = ()> {
+    inner: S,
+    tokens: usize,
+    backtracks: usize,
+    // The number of tokens between the start of the input and the current position
+    position: usize,
+}
+
+impl<S> Counted<S> {
+    /// Count the events of a parse that uses the given state.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            tokens: 0,
+            backtracks: 0,
+            position: 0,
+        }
+    }
+
+    /// The number of tokens pulled from the input, including tokens that were read again after backtracking.
+    pub fn tokens(&self) -> usize {
+        self.tokens
+    }
+
+    /// The number of times the parser rewound to an earlier position in the input.
+    pub fn backtracks(&self) -> usize {
+        self.backtracks
+    }
+
+    /// Take the inner state.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> core::ops::Deref for Counted<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<S> core::ops::DerefMut for Counted<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<'src, I: Input<'src>, S: Inspector<'src, I>> Inspector<'src, I> for Counted<S> {
+    type Checkpoint = (usize, S::Checkpoint);
+
+    #[inline(always)]
+    fn on_token(&mut self, token: &I::Token) {
+        self.tokens += 1;
+        self.position += 1;
+        self.inner.on_token(token);
+    }
+
+    #[inline(always)]
+    fn on_save<'parse>(&self, cursor: &input::Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+        (self.position, self.inner.on_save(cursor))
+    }
+
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, marker: &input::Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        let position = marker.inspector().0;
+        if position < self.position {
+            self.backtracks += 1;
+        }
+        self.position = position;
+        self.inner
+            .on_rewind(&marker.map_inspector(|(_, inner)| *inner));
+    }
+
+    #[inline(always)]
+    fn on_commit<'parse>(&mut self, marker: &input::Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        self.inner
+            .on_commit(&marker.map_inspector(|(_, inner)| *inner));
+    }
+
+    fn on_cover_enter(&mut self, name: &'static str) {
+        self.inner.on_cover_enter(name);
+    }
+
+    fn on_cover_exit(&mut self, name: &'static str, matched: bool) {
+        self.inner.on_cover_exit(name, matched);
+    }
+}
+
+/// Parse the given input with the given parser `iterations` times, measuring its performance.
+///
+/// Tokens and backtracking are counted by the parser's state, which must be [`Counted`]. Each iteration starts from
+/// the default state.
+///
+/// See the [module-level documentation](self) for an example.
+pub fn run<'src, P, I, O, E, S>(parser: &P, input: I, iterations: usize) -> Report
+where
+    P: Parser<'src, I, O, E>,
+    I: Input<'src> + Clone,
+    E: ParserExtra<'src, I, State = Counted<S>>,
+    S: Inspector<'src, I> + Default,
+    E::Context: Default,
+{
+    let named_before = take_named_allocs();
+    let allocs_before = AllocStats::now();
+    let mut failures = 0;
+    let mut tokens = 0;
+    let mut backtracks = 0;
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let mut state = Counted::default();
+        if parser
+            .parse_with_state(input.clone(), &mut state)
+            .has_errors()
+        {
+            failures += 1;
+        }
+        tokens += state.tokens;
+        backtracks += state.backtracks;
+    }
+    let elapsed = start.elapsed();
+    let allocs_after = AllocStats::now();
+    let named_allocs = take_named_allocs();
+    NAMED_ALLOCS.with(|named| *named.borrow_mut() = named_before);
+    let installed = ALLOC_INSTALLED.load(AtomicOrdering::Relaxed);
+
+    Report {
+        iterations,
+        elapsed,
+        tokens,
+        backtracks,
+        failures,
+        allocs: installed.then(|| allocs_after.since(allocs_before)),
+        named_allocs: if installed { named_allocs } else { Vec::new() },
+    }
+}
+
+/// Register a benchmark with [`criterion`] that parses the given input with the given parser.
+///
+/// This is a convenient way to track the performance of a grammar over time with `cargo bench`, alongside the
+/// statistics produced by [`run`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use chumsky::prelude::*;
+/// use criterion::{criterion_group, criterion_main, Criterion};
+///
+/// fn bench_ints(c: &mut Criterion) {
+///     let ints = text::int::<_, extra::Default>(10)
+///         .separated_by(just(", "))
+///         .collect::<Vec<_>>();
+///
+///     chumsky::bench::criterion_bench(c, "ints", &ints, "1, 2, 3, 4, 5");
+/// }
+///
+/// criterion_group!(benches, bench_ints);
+/// criterion_main!(benches);
+/// ```
+#[cfg(feature = "criterion")]
+pub fn criterion_bench<'src, P, I, O, E>(
+    c: &mut criterion::Criterion,
+    name: &str,
+    parser: &P,
+    input: I,
+) where
+    P: Parser<'src, I, O, E>,
+    I: Input<'src> + Clone,
+    E: ParserExtra<'src, I>,
+    E::State: Default,
+    E::Context: Default,
+{
+    c.bench_function(name, |b| {
+        b.iter(|| parser.parse(criterion::black_box(input.clone())))
+    });
+}
+
+fn take_named_allocs() -> Vec<(&'static str, AllocStats)> {
+    NAMED_ALLOCS.with(|named| core::mem::take(&mut *named.borrow_mut()))
+}
+
+/// See [`track_allocs`].
+#[derive(Copy, Clone)]
+pub struct TrackAllocs<A> {
+    name: &'static str,
+    parser: A,
+}
+
+/// Attribute allocations performed by the given parser to the given name, for reporting by [`run`].
+///
+/// This is useful for finding the parts of a grammar that allocate heavily (for example, by collecting into
+/// containers, boxing, or constructing errors) and that might benefit from using [`Parser::to_slice`] or an arena
+/// allocator.
+///
+/// Tracked parsers may be nested: allocations are attributed to the innermost tracked parser only. Allocations are
+/// only counted if [`CountingAlloc`] is installed as the global allocator.
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::{prelude::*, bench::track_allocs};
+///
+/// let word = track_allocs(
+///     "word",
+///     text::ascii::ident::<_, extra::State<chumsky::bench::Counted>>().map(String::from),
+/// );
+/// let words = track_allocs("words", word.padded().repeated().collect::<Vec<_>>());
+///
+/// let report = chumsky::bench::run(&words, "the quick brown fox", 10);
+/// // Only populated when `CountingAlloc` is the global allocator
+/// for (name, allocs) in &report.named_allocs {
+///     println!("{name}: {} allocations", allocs.allocations);
+/// }
+/// ```
+pub fn track_allocs<A>(name: &'static str, parser: A) -> TrackAllocs<A> {
+    TrackAllocs { name, parser }
+}
+
+impl<'src, I, O, E, A> Parser<'src, I, O, E> for TrackAllocs<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        TRACKED_CHILDREN.with(|children| children.borrow_mut().push(AllocStats::default()));
+        let before = AllocStats::now();
+        let res = self.parser.go::<M>(inp);
+        let total = AllocStats::now().since(before);
+
+        TRACKED_CHILDREN.with(|children| {
+            let mut children = children.borrow_mut();
+            let own = total.since(children.pop().unwrap_or_default());
+            if let Some(parent) = children.last_mut() {
+                parent.add(total);
+            }
+            NAMED_ALLOCS.with(|named| {
+                let mut named = named.borrow_mut();
+                match named.iter_mut().find(|(name, _)| *name == self.name) {
+                    Some((_, stats)) => stats.add(own),
+                    None => named.push((self.name, own)),
+                }
+            });
+        });
+
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// The shape of a parser's type, as reported by [`profile_type`].
+#[derive(Clone, Debug)]
+pub struct TypeProfile {
+    /// The nesting depth of the parser: how many combinators are nested within each other, at most.
+    ///
+    /// This is a rough measure of how deep the call stack becomes while parsing, since each combinator usually calls
+    /// the parsers within it.
+    pub depth: usize,
+    /// The number of parsers that make up the parser, including the parser itself. Each of them is part of the
+    /// parser's type, so this is a rough measure of how much work the compiler does to check and monomorphize it.
+    pub nodes: usize,
+    /// The length of the type's name, in bytes.
+    pub name_len: usize,
+    /// The size of the parser value itself, in bytes.
+    pub value_size: usize,
+    /// Places within the parser where boxing would most reduce its size, largest first.
+    pub hotspots: Vec<Hotspot>,
+}
+
+/// A part of a parser that is a good candidate for boxing. See [`TypeProfile::hotspots`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hotspot {
+    /// The names of the parsers leading from the outermost parser to this part (see [`introspect::Node::name`]).
+    pub path: Vec<String>,
+    /// The nesting depth of this part of the parser.
+    pub depth: usize,
+    /// The number of parsers that make up this part of the parser.
+    pub nodes: usize,
+}
+
+impl fmt::Display for TypeProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "type depth: {}, type nodes: {}, type name: {} bytes, value: {} bytes",
+            self.depth, self.nodes, self.name_len, self.value_size
+        )?;
+        if !self.hotspots.is_empty() {
+            write!(f, "\nconsider boxing:")?;
+        }
+        for hotspot in &self.hotspots {
+            write!(
+                f,
+                "\n  {} ({} nodes, depth {})",
+                hotspot.path.join(" > "),
+                hotspot.nodes,
+                hotspot.depth
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Report the nesting depth and approximate complexity of a parser's type.
+///
+/// Every combinator embeds the types of the parsers that it is built from, so the types of large grammars can become
+/// very large, which slows down compilation and deepens the call stack during parsing. This function inspects the
+/// structure of the given parser (via [`introspect::describe`]) and points out where inserting [`Parser::boxed`] (or
+/// [`Parser::erased`]) would most reduce it.
+///
+/// Hotspots are chosen among the combinators that make up no more than half of the parser, since boxing a larger part
+/// only moves the complexity elsewhere. At most 5 are reported, and none of them contain each other. Parts of the
+/// parser that are already boxed (or [`Recursive`](recursive::Recursive)) only count as a single parser, as do parsers
+/// whose structure is [opaque](introspect::NodeKind::Opaque).
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::prelude::*;
+///
+/// let item = text::ascii::ident::<&str, extra::Default>()
+///     .then_ignore(just(':').padded())
+///     .then(text::int(10))
+///     .padded();
+/// let object = item
+///     .separated_by(just(','))
+///     .collect::<Vec<_>>()
+///     .delimited_by(just('{'), just('}'));
+///
+/// let profile = chumsky::bench::profile_type(&object);
+/// assert!(profile.to_string().contains("consider boxing:\n  DelimitedBy > Collect > SeparatedBy > Padded"));
+///
+/// // Boxing the suggested part of the parser reduces the size of its type
+/// let boxed = chumsky::bench::profile_type(&object.boxed());
+/// assert!(boxed.nodes < profile.nodes);
+/// ```
+pub fn profile_type<'src, P, I, O, E>(parser: &P) -> TypeProfile
+where
+    P: Parser<'src, I, O, E>,
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    // Tokens aren't part of the profile, so there's no need to spell them
+    let root = Shape::new(&introspect::describe(parser, &|_| String::new()));
+
+    let mut candidates = Vec::new();
+    root.candidates(&mut Vec::new(), root.nodes / 2, &mut candidates);
+    // Prefer the largest parts of the parser, then those that appear first
+    candidates.sort_by(|a, b| {
+        b.nodes
+            .cmp(&a.nodes)
+            .then_with(|| a.indices.cmp(&b.indices))
+    });
+
+    let mut chosen = Vec::<Candidate>::new();
+    for candidate in candidates {
+        if chosen.len() == 5 {
+            break;
+        }
+        let overlaps = chosen.iter().any(|other| {
+            other.indices.starts_with(&candidate.indices)
+                || candidate.indices.starts_with(&other.indices)
+        });
+        if !overlaps {
+            chosen.push(candidate);
+        }
+    }
+    let hotspots = chosen
+        .into_iter()
+        .map(|c| Hotspot {
+            path: root.path_to(&c.indices),
+            depth: c.depth,
+            nodes: c.nodes,
+        })
+        .collect();
+
+    TypeProfile {
+        depth: root.depth,
+        nodes: root.nodes,
+        name_len: core::any::type_name::<P>().len(),
+        value_size: core::mem::size_of::<P>(),
+        hotspots,
+    }
+}
+
+// The shape of a parser, as described by `introspect::describe`
+struct Shape {
+    name: &'static str,
+    children: Vec<Shape>,
+    depth: usize,
+    nodes: usize,
+}
+
+struct Candidate {
+    indices: Vec<usize>,
+    depth: usize,
+    nodes: usize,
+}
+
+impl Shape {
+    fn new(node: &introspect::Node) -> Self {
+        let children = match node.kind {
+            // Boxed and recursive parsers are behind a pointer, so they don't contribute the parsers within them to the
+            // type
+            introspect::NodeKind::Boxed | introspect::NodeKind::Recursive(_) => Vec::new(),
+            _ => node.children.iter().map(Self::new).collect::<Vec<_>>(),
+        };
+        Self {
+            name: node.name,
+            depth: children.iter().map(|c| c.depth + 1).max().unwrap_or(0),
+            nodes: 1 + children.iter().map(|c| c.nodes).sum::<usize>(),
+            children,
+        }
+    }
+
+    fn candidates(&self, indices: &mut Vec<usize>, max_nodes: usize, out: &mut Vec<Candidate>) {
+        // Only suggest boxing combinators of other parsers: boxing a single primitive doesn't shrink anything
+        if !indices.is_empty() && self.nodes <= max_nodes && !self.children.is_empty() {
+            out.push(Candidate {
+                indices: indices.clone(),
+                depth: self.depth,
+                nodes: self.nodes,
+            });
+        }
+        for (i, child) in self.children.iter().enumerate() {
+            indices.push(i);
+            child.candidates(indices, max_nodes, out);
+            indices.pop();
+        }
+    }
+
+    fn path_to(&self, indices: &[usize]) -> Vec<String> {
+        let mut path = vec![self.name.to_string()];
+        let mut node = self;
+        for &i in indices {
+            node = &node.children[i];
+            path.push(node.name.to_string());
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_type_hotspots() {
+        let profile = profile_type(&just::<_, &str, extra::Default>("ab"));
+        assert_eq!(profile.nodes, 1);
+        assert_eq!(profile.depth, 0);
+        assert!(profile.hotspots.is_empty());
+
+        let pair = just::<_, &str, extra::Default>('a').then(just('b'));
+        let list = pair.separated_by(just(',')).collect::<Vec<_>>();
+        let profile = profile_type(&list.delimited_by(just('['), just(']')));
+        assert_eq!(
+            profile.hotspots[0].path,
+            ["DelimitedBy", "Collect", "SeparatedBy", "Then"]
+        );
+        assert_eq!(profile.hotspots[0].nodes, profile_type(&pair).nodes);
+
+        let boxed = profile_type(&list.boxed().delimited_by(just('['), just(']')));
+        assert!(boxed.nodes < profile.nodes);
+        assert!(boxed.depth < profile.depth);
+    }
+}
@@ -13,6 +13,10 @@ where
         M::invoke(*self, inp)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        T::introspect(*self, cx)
+    }
+
     go_extra!(O);
 }
 
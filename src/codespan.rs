@@ -0,0 +1,80 @@
+//! A small module that converts [`Rich`] errors into
+//! [`codespan_reporting`](https://docs.rs/codespan-reporting/latest/codespan_reporting/) diagnostics.
+
+use super::*;
+use alloc::{format, string::ToString};
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use error::{RichReason, TokenDisplay};
+
+/// Displays a token using its [`TokenDisplay`] implementation.
+struct ShowToken<'a, T: ?Sized>(&'a T);
+
+impl<T: TokenDisplay + ?Sized> fmt::Display for ShowToken<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_token(f)
+    }
+}
+
+impl<T, S> Rich<'_, T, S>
+where
+    T: TokenDisplay,
+    S: Span<Offset = usize>,
+{
+    /// Convert this error into a [`codespan_reporting`] [`Diagnostic`] for the file with the given ID.
+    ///
+    /// The span of the error becomes the primary label of the diagnostic, and each of its contexts (see
+    /// [`Parser::labelled`]) becomes a secondary label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use codespan_reporting::{diagnostic::Severity, files::SimpleFile, term};
+    ///
+    /// let parser = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('['), just(']'))
+    ///     .labelled("list")
+    ///     .as_context();
+    ///
+    /// let src = "[1,2;3]";
+    /// let diagnostic = parser.parse(src).into_errors()[0].to_diagnostic(());
+    ///
+    /// assert_eq!(diagnostic.severity, Severity::Error);
+    /// assert_eq!(diagnostic.message, "found ';' expected digit, ',', or ']'");
+    /// assert_eq!(diagnostic.labels[0].range, 4..5);
+    /// assert_eq!(diagnostic.labels[1].message, "while parsing list");
+    ///
+    /// // Render the diagnostic as usual
+    /// let file = SimpleFile::new("example", src);
+    /// let mut out = term::termcolor::NoColor::new(Vec::new());
+    /// term::emit(&mut out, &term::Config::default(), &file, &diagnostic).unwrap();
+    /// ```
+    pub fn to_diagnostic<FileId: Clone>(&self, file_id: FileId) -> Diagnostic<FileId> {
+        let primary = Label::primary(file_id.clone(), self.span().start()..self.span().end());
+        let primary = match self.reason() {
+            RichReason::ExpectedFound {
+                found: Some(found), ..
+            } => primary.with_message(format!("unexpected {}", ShowToken(&**found))),
+            RichReason::ExpectedFound { found: None, .. } => {
+                primary.with_message("unexpected end of input")
+            }
+            RichReason::Custom(_) => primary,
+        };
+
+        Diagnostic {
+            code: self.code().map(ToString::to_string),
+            ..Diagnostic::error()
+                .with_message(self.reason().to_string())
+                .with_labels(
+                    core::iter::once(primary)
+                        .chain(self.contexts().map(|(label, span)| {
+                            Label::secondary(file_id.clone(), span.start()..span.end())
+                                .with_message(format!("while parsing {label}"))
+                        }))
+                        .collect(),
+                )
+        }
+    }
+}
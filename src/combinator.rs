@@ -5,7 +5,7 @@
 //! Although it's *sometimes* useful to be able to name their type, most of these parsers are much easier to work with
 //! when accessed through their respective methods on [`Parser`].
 
-use inspector::Inspector;
+use inspector::{Inspector, Interner, ScopedState};
 
 use super::*;
 
@@ -224,6 +224,10 @@ where
         Ok(M::bind(|| inp.slice_since(&before..)))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("ToSlice", self.parser.introspect(cx))
+    }
+
     go_extra!(I::Slice);
 }
 
@@ -264,6 +268,10 @@ where
         })
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Filter", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -299,6 +307,10 @@ where
         Ok(M::map(out, &self.mapper))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Map", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -336,6 +348,10 @@ where
             Err(()) => Err(()),
         }
     }
+
+    fn introspect_iter(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Map", self.parser.introspect_iter(cx))
+    }
 }
 
 /// See [`Parser::map_with`].
@@ -373,6 +389,10 @@ where
         }))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("MapWith", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -413,6 +433,10 @@ where
             Err(()) => Err(()),
         }
     }
+
+    fn introspect_iter(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("MapWith", self.parser.introspect_iter(cx))
+    }
 }
 
 /// See [`Parser::map_group`].
@@ -523,6 +547,10 @@ where
         Ok(M::bind(|| inp.span_since(&before)))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("ToSpan", self.parser.introspect(cx))
+    }
+
     go_extra!(I::Span);
 }
 
@@ -580,6 +608,81 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("TryMap", self.parser.introspect(cx))
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::checksummed`].
+pub struct Checksummed<A, T, OT, F> {
+    pub(crate) parser: A,
+    pub(crate) trailer: T,
+    pub(crate) check: F,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OT>,
+}
+
+impl<A: Copy, T: Copy, OT, F: Copy> Copy for Checksummed<A, T, OT, F> {}
+impl<A: Clone, T: Clone, OT, F: Clone> Clone for Checksummed<A, T, OT, F> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            trailer: self.trailer.clone(),
+            check: self.check.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'src, I, O, E, A, T, OT, F> Parser<'src, I, O, E> for Checksummed<A, T, OT, F>
+where
+    I: SliceInput<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+    T: Parser<'src, I, OT, E>,
+    F: Fn(I::Slice, OT, I::Span) -> Result<(), E::Error>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.cursor();
+        let out = self.parser.go::<M>(inp)?;
+        let data = inp.slice_since(&before..);
+
+        let before_trailer = inp.cursor();
+        // Remove the pre-trailer alt, to be reinserted later so we always preserve it
+        let old_alt = inp.errors.alt.take();
+
+        let trailer = match self.trailer.go::<Emit>(inp) {
+            Ok(trailer) => trailer,
+            Err(()) => {
+                let new_alt = inp.errors.alt.take();
+                inp.errors.alt = old_alt;
+                if let Some(new_alt) = new_alt {
+                    inp.add_alt_err(&new_alt.pos, new_alt.err);
+                }
+                return Err(());
+            }
+        };
+        let span = inp.span_since(&before_trailer);
+        let new_alt = inp.errors.alt.take();
+
+        inp.errors.alt = old_alt;
+        match (self.check)(data, trailer, span) {
+            Ok(()) => {
+                if let Some(new_alt) = new_alt {
+                    inp.add_alt_err(&before_trailer.inner, new_alt.err);
+                }
+                Ok(out)
+            }
+            Err(err) => {
+                inp.add_alt_err(&before_trailer.inner, err);
+                Err(())
+            }
+        }
+    }
+
     go_extra!(O);
 }
 
@@ -622,6 +725,65 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("TryMapWith", self.parser.introspect(cx))
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::or_fail`].
+pub struct OrFail<A, OA, F> {
+    pub(crate) parser: A,
+    pub(crate) fail: F,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OA>,
+}
+
+impl<A: Copy, OA, F: Copy> Copy for OrFail<A, OA, F> {}
+impl<A: Clone, OA, F: Clone> Clone for OrFail<A, OA, F> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            fail: self.fail.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'src, I, O, E, A, U, F> Parser<'src, I, O, E> for OrFail<A, Result<O, U>, F>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Result<O, U>, E>,
+    F: Fn(U, I::Span) -> E::Error,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.cursor();
+        // As with `TryMap`, the pre-inner alt is always preserved
+        let old_alt = inp.errors.alt.take();
+
+        let out = self.parser.go::<Emit>(inp)?;
+        let new_alt = inp.errors.alt.take();
+
+        match out {
+            Ok(out) => {
+                inp.errors.alt = old_alt;
+                if let Some(new_alt) = new_alt {
+                    inp.add_alt_err(&before.inner, new_alt.err);
+                }
+                Ok(M::bind(|| out))
+            }
+            Err(err) => {
+                let span = inp.span_since(&before);
+                inp.errors.alt = old_alt;
+                inp.add_alt_err(&before.inner, (self.fail)(err, span));
+                Err(())
+            }
+        }
+    }
+
     go_extra!(O);
 }
 
@@ -657,6 +819,10 @@ where
         Ok(M::bind(|| self.to.clone()))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("To", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -738,6 +904,10 @@ where
         Ok(M::bind(|| ()))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Ignored", self.parser.introspect(cx))
+    }
+
     go_extra!(());
 }
 
@@ -779,6 +949,10 @@ where
         }))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Unwrapped", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -800,6 +974,10 @@ where
         }))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Unwrapped", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -856,6 +1034,10 @@ where
         res
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Memoized", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -892,6 +1074,14 @@ where
         Ok(M::combine(a, b, |a: OA, b: OB| (a, b)))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Then",
+            introspect::NodeKind::Seq,
+            vec![self.parser_a.introspect(cx), self.parser_b.introspect(cx)],
+        )
+    }
+
     go_extra!((OA, OB));
 }
 
@@ -928,6 +1118,14 @@ where
         Ok(M::map(b, |b: OB| b))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "IgnoreThen",
+            introspect::NodeKind::Seq,
+            vec![self.parser_a.introspect(cx), self.parser_b.introspect(cx)],
+        )
+    }
+
     go_extra!(OB);
 }
 
@@ -964,6 +1162,14 @@ where
         Ok(M::map(a, |a: OA| a))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "ThenIgnore",
+            introspect::NodeKind::Seq,
+            vec![self.parser_a.introspect(cx), self.parser_b.introspect(cx)],
+        )
+    }
+
     go_extra!(OA);
 }
 
@@ -1026,6 +1232,82 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::embedded_in`].
+pub struct EmbeddedIn<A, B, L, G, J, F, O> {
+    pub(crate) parser: A,
+    pub(crate) region: B,
+    pub(crate) label: L,
+    pub(crate) map_err: G,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(J, F, O)>,
+}
+
+impl<A: Copy, B: Copy, L: Copy, G: Copy, J, F, O> Copy for EmbeddedIn<A, B, L, G, J, F, O> {}
+impl<A: Clone, B: Clone, L: Clone, G: Clone, J, F, O> Clone for EmbeddedIn<A, B, L, G, J, F, O> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            region: self.region.clone(),
+            label: self.label.clone(),
+            map_err: self.map_err.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'src, I, J, E, F, A, B, L, G, O> Parser<'src, I, O, E> for EmbeddedIn<A, B, L, G, J, F, O>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    B: Parser<'src, I, J, E>,
+    J: Input<'src>,
+    F: ParserExtra<'src, J, State = E::State, Context = E::Context>,
+    A: Parser<'src, J, O, F>,
+    L: Clone,
+    G: Fn(F::Error, I::Span) -> E::Error,
+    I::Span: Clone,
+    E::Error: LabelError<'src, I, L>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.cursor();
+        let inp2 = self.region.go::<Emit>(inp)?;
+        let region = inp.span_since(&before);
+
+        let alt = inp.errors.alt.take();
+
+        #[cfg(feature = "memoization")]
+        let mut memos = HashMap::default();
+        let (start, mut cache) = inp2.begin();
+        let res = inp.with_input_map_err(
+            start,
+            &mut cache,
+            &mut Default::default(),
+            |inp| (&self.parser).then_ignore(end()).go::<M>(inp),
+            |err| {
+                let mut err = (self.map_err)(err, region.clone());
+                LabelError::<'src, I, L>::in_context(&mut err, self.label.clone(), region.clone());
+                err
+            },
+            #[cfg(feature = "memoization")]
+            &mut memos,
+        );
+
+        // If the embedded parser failed, its error takes precedence over any alternatives at the end of the region
+        if res.is_ok() {
+            let new_alt = inp.errors.alt.take();
+            inp.errors.alt = alt;
+            if let Some(new_alt) = new_alt {
+                inp.add_alt_err(&inp.cursor().inner, new_alt.err);
+            }
+        }
+
+        res
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::ignore_with_ctx`].
 pub struct IgnoreWithCtx<A, B, OA, I, E> {
     pub(crate) parser: A,
@@ -1208,6 +1490,184 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::in_mode`].
+pub struct InMode<A, Md> {
+    pub(crate) parser: A,
+    pub(crate) mode: Md,
+}
+
+impl<A: Copy, Md: Copy> Copy for InMode<A, Md> {}
+impl<A: Clone, Md: Clone> Clone for InMode<A, Md> {
+    fn clone(&self) -> Self {
+        InMode {
+            parser: self.parser.clone(),
+            mode: self.mode.clone(),
+        }
+    }
+}
+
+impl<'src, I, O, E, A, Md> Parser<'src, I, O, E> for InMode<A, Md>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::State: BorrowMut<extra::ModeStack<Md>>,
+    A: Parser<'src, I, O, E>,
+    Md: Clone,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let modes: &mut extra::ModeStack<Md> = inp.state().borrow_mut();
+        let depth = modes.depth();
+        modes.push(self.mode.clone());
+        let res = self.parser.go::<M>(inp);
+        let modes: &mut extra::ModeStack<Md> = inp.state().borrow_mut();
+        // Restore the stack to the depth it had on entry, in case the inner parser left it unbalanced
+        while modes.depth() > depth && modes.pop().is_some() {}
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::with_scope`].
+#[derive(Copy, Clone)]
+pub struct WithScope<A> {
+    pub(crate) parser: A,
+}
+
+impl<'src, I, O, E, A> Parser<'src, I, O, E> for WithScope<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::State: inspector::ScopedState,
+    A: Parser<'src, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        inp.state().push_scope();
+        let res = self.parser.go::<M>(inp);
+        inp.state().pop_scope();
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::interned`].
+#[derive(Copy, Clone)]
+pub struct Interned<A> {
+    pub(crate) parser: A,
+}
+
+impl<'src, I, O, E, A> Parser<'src, I, O, E> for Interned<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::State: Interner<O>,
+    A: Parser<'src, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let out = self.parser.go::<M>(inp)?;
+        Ok(M::map(out, |out| inp.state().intern(out)))
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::when_mode`].
+pub struct WhenMode<A, Md> {
+    pub(crate) parser: A,
+    pub(crate) mode: Md,
+}
+
+impl<A: Copy, Md: Copy> Copy for WhenMode<A, Md> {}
+impl<A: Clone, Md: Clone> Clone for WhenMode<A, Md> {
+    fn clone(&self) -> Self {
+        WhenMode {
+            parser: self.parser.clone(),
+            mode: self.mode.clone(),
+        }
+    }
+}
+
+impl<'src, I, O, E, A, Md> Parser<'src, I, O, E> for WhenMode<A, Md>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::State: BorrowMut<extra::ModeStack<Md>>,
+    A: Parser<'src, I, O, E>,
+    Md: PartialEq,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let modes: &mut extra::ModeStack<Md> = inp.state().borrow_mut();
+        if modes.current() == &self.mode {
+            self.parser.go::<M>(inp)
+        } else {
+            let before = inp.cursor();
+            let found = inp.peek_maybe();
+            let span = inp.span_since(&before);
+            inp.add_alt([], found, span);
+            Err(())
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::update_ctx`].
+#[derive(Copy, Clone)]
+pub struct UpdateCtx<A, F> {
+    pub(crate) parser: A,
+    pub(crate) f: F,
+}
+
+impl<'src, I, O, E, A, F> Parser<'src, I, O, E> for UpdateCtx<A, F>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+    F: Fn(&E::Context) -> E::Context,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        inp.with_ctx::<E, _>(&(self.f)(inp.ctx()), |inp| self.parser.go::<M>(inp))
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::when_ctx`].
+#[derive(Copy, Clone)]
+pub struct WhenCtx<A, F> {
+    pub(crate) parser: A,
+    pub(crate) pred: F,
+}
+
+impl<'src, I, O, E, A, F> Parser<'src, I, O, E> for WhenCtx<A, F>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+    F: Fn(&E::Context) -> bool,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        if (self.pred)(inp.ctx()) {
+            self.parser.go::<M>(inp)
+        } else {
+            let before = inp.cursor();
+            let found = inp.peek_maybe();
+            let span = inp.span_since(&before);
+            inp.add_alt([], found, span);
+            Err(())
+        }
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::with_state`].
 pub struct WithState<A, State> {
     pub(crate) parser: A,
@@ -1276,6 +1736,18 @@ where
         Ok(a)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "DelimitedBy",
+            introspect::NodeKind::Seq,
+            vec![
+                self.start.introspect(cx),
+                self.parser.introspect(cx),
+                self.end.introspect(cx),
+            ],
+        )
+    }
+
     go_extra!(OA);
 }
 
@@ -1313,6 +1785,18 @@ where
         Ok(a)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "PaddedBy",
+            introspect::NodeKind::Seq,
+            vec![
+                self.padding.introspect(cx),
+                self.parser.introspect(cx),
+                self.padding.introspect(cx),
+            ],
+        )
+    }
+
     go_extra!(OA);
 }
 
@@ -1334,9 +1818,49 @@ where
         self.choice.go::<M>(inp)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node {
+            name: "Or",
+            ..self.choice.introspect(cx)
+        }
+    }
+
     go_extra!(O);
 }
 
+/// See [`Parser::or_different`].
+#[cfg(feature = "either")]
+#[derive(Copy, Clone)]
+pub struct OrDifferent<A, B> {
+    pub(crate) parser_a: A,
+    pub(crate) parser_b: B,
+}
+
+#[cfg(feature = "either")]
+impl<'src, I, OA, OB, E, A, B> Parser<'src, I, ::either::Either<OA, OB>, E> for OrDifferent<A, B>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, OA, E>,
+    B: Parser<'src, I, OB, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, '_, I, E>,
+    ) -> PResult<M, ::either::Either<OA, OB>> {
+        let before = inp.save();
+        match self.parser_a.go::<M>(inp) {
+            Ok(out) => return Ok(M::map(out, ::either::Either::Left)),
+            Err(()) => inp.rewind(before),
+        }
+        let out = self.parser_b.go::<M>(inp)?;
+        Ok(M::map(out, ::either::Either::Right))
+    }
+
+    go_extra!(::either::Either<OA, OB>);
+}
+
 /// Configuration for [`Parser::repeated`], used in [`ConfigParser::configure`].
 #[derive(Default)]
 pub struct RepeatedCfg {
@@ -1365,6 +1889,87 @@ impl RepeatedCfg {
     }
 }
 
+/// See [`Parser::non_empty`].
+#[derive(Copy, Clone)]
+pub struct NonEmpty<A> {
+    pub(crate) parser: A,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+}
+
+impl<'src, I, O, E, A> Parser<'src, I, O, E> for NonEmpty<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let out = self.parser.go::<M>(inp)?;
+        if *before.cursor() == inp.cursor() {
+            #[cfg(debug_assertions)]
+            panic!(
+                "found NonEmpty combinator that consumed no input at {}",
+                self.location,
+            );
+            #[cfg(not(debug_assertions))]
+            {
+                let span = inp.span_since(before.cursor());
+                inp.add_alt([DefaultExpected::SomethingElse], None, span);
+                return Err(());
+            }
+        }
+        Ok(out)
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("NonEmpty", self.parser.introspect(cx))
+    }
+
+    go_extra!(O);
+}
+
+impl<'src, I, O, E, A> NonEmptyParser<'src, I, O, E> for NonEmpty<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+{
+}
+
+/// Guard against a repetition looping forever.
+///
+/// Called after each successful item of an unbounded repetition: if the item consumed no input twice in a row, the
+/// repetition can never make progress again, so the input is rewound and an error is generated instead of hanging.
+/// Debug builds catch this earlier with a panic, but this check remains in release builds.
+#[inline(always)]
+fn guard_progress<'src, 'parse, I, E>(
+    inp: &mut InputRef<'src, 'parse, I, E>,
+    before: input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+    stalled: &mut bool,
+) -> Result<(), ()>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    if *before.cursor() != inp.cursor() {
+        *stalled = false;
+        inp.commit(before);
+        Ok(())
+    } else if !*stalled {
+        *stalled = true;
+        inp.commit(before);
+        Ok(())
+    } else {
+        let found = inp.peek_maybe();
+        let span = inp.span_since(before.cursor());
+        inp.rewind(before);
+        inp.add_alt([DefaultExpected::SomethingElse], found, span);
+        Err(())
+    }
+}
+
 /// See [`Parser::repeated`].
 pub struct Repeated<A, OA, I, E> {
     pub(crate) parser: A,
@@ -1456,6 +2061,24 @@ where
             ..self
         }
     }
+
+    /// Require, at compile time, that the pattern always consumes input when it succeeds, ruling out infinite loops.
+    ///
+    /// This does not change the behaviour of the parser. See [`NonEmptyParser`] and [`Parser::non_empty`].
+    ///
+    /// ```compile_fail
+    /// # use chumsky::prelude::*;
+    /// // `text::whitespace` can succeed without consuming input, so it cannot be repeated strictly
+    /// let spaces = text::whitespace::<_, extra::Default>()
+    ///     .repeated()
+    ///     .strict();
+    /// ```
+    pub fn strict(self) -> Self
+    where
+        A: NonEmptyParser<'src, I, OA, E>,
+    {
+        self
+    }
 }
 
 impl<'src, I, E, A, OA> Parser<'src, I, (), E> for Repeated<A, OA, I, E>
@@ -1468,12 +2091,14 @@ where
     #[allow(clippy::nonminimal_bool)] // TODO: Remove this, lint is currently buggy
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, ()> {
         if self.at_most == !0 && self.at_least == 0 {
+            let mut stalled = false;
             loop {
+                inp.safe_point()?;
                 let before = inp.save();
                 match self.parser.go::<Check>(inp) {
                     Ok(()) => {}
                     Err(()) => {
-                        inp.rewind(before);
+                        inp.rewind_commit(before);
                         break Ok(M::bind(|| ()));
                     }
                 }
@@ -1483,6 +2108,9 @@ where
                     "found Repeated combinator making no progress at {}",
                     self.location,
                 );
+                if guard_progress(inp, before, &mut stalled).is_err() {
+                    break Err(());
+                }
             }
         } else {
             let mut state = self.make_iter::<Check>(inp)?;
@@ -1507,6 +2135,16 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Repeated",
+            introspect::NodeKind::Repeated {
+                at_least: self.at_least,
+            },
+            vec![self.parser.introspect(cx)],
+        )
+    }
+
     go_extra!(());
 }
 
@@ -1516,34 +2154,41 @@ where
     E: ParserExtra<'src, I>,
     A: Parser<'src, I, O, E>,
 {
-    type IterState<M: Mode> = usize;
+    // The number of items parsed so far, and whether the last item consumed no input
+    type IterState<M: Mode> = (usize, bool);
 
     #[inline(always)]
     fn make_iter<M: Mode>(
         &self,
         _inp: &mut InputRef<'src, '_, I, E>,
     ) -> PResult<Emit, Self::IterState<M>> {
-        Ok(0)
+        Ok((0, false))
     }
 
     #[inline(always)]
     fn next<M: Mode>(
         &self,
         inp: &mut InputRef<'src, '_, I, E>,
-        count: &mut Self::IterState<M>,
+        (count, stalled): &mut Self::IterState<M>,
     ) -> IPResult<M, O> {
         if *count as u64 >= self.at_most {
             return Ok(None);
         }
 
+        inp.safe_point()?;
         let before = inp.save();
         match self.parser.go::<M>(inp) {
             Ok(item) => {
+                if self.at_most == !0 {
+                    guard_progress(inp, before, stalled)?;
+                } else {
+                    inp.commit(before);
+                }
                 *count += 1;
                 Ok(Some(item))
             }
             Err(()) => {
-                inp.rewind(before);
+                inp.rewind_commit(before);
                 if *count >= self.at_least {
                     Ok(None)
                 } else {
@@ -1552,6 +2197,16 @@ where
             }
         }
     }
+
+    fn introspect_iter(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Repeated",
+            introspect::NodeKind::Repeated {
+                at_least: self.at_least,
+            },
+            vec![self.parser.introspect(cx)],
+        )
+    }
 }
 
 impl<'src, A, O, I, E> ConfigIterParser<'src, I, O, E> for Repeated<A, O, I, E>
@@ -1566,7 +2221,7 @@ where
     fn next_cfg<M: Mode>(
         &self,
         inp: &mut InputRef<'src, '_, I, E>,
-        count: &mut Self::IterState<M>,
+        (count, stalled): &mut Self::IterState<M>,
         cfg: &Self::Config,
     ) -> IPResult<M, O> {
         let at_most = cfg.at_most.map(|x| x as u64).unwrap_or(self.at_most);
@@ -1576,14 +2231,20 @@ where
             return Ok(None);
         }
 
+        inp.safe_point()?;
         let before = inp.save();
         match self.parser.go::<M>(inp) {
             Ok(item) => {
+                if at_most == !0 {
+                    guard_progress(inp, before, stalled)?;
+                } else {
+                    inp.commit(before);
+                }
                 *count += 1;
                 Ok(Some(item))
             }
             Err(()) => {
-                inp.rewind(before);
+                inp.rewind_commit(before);
                 if *count >= at_least {
                     Ok(None)
                 } else {
@@ -1603,6 +2264,7 @@ pub struct SeparatedBy<A, B, OA, OB, I, E> {
     pub(crate) at_most: u64,
     pub(crate) allow_leading: bool,
     pub(crate) allow_trailing: bool,
+    pub(crate) strict_count: bool,
     #[cfg(debug_assertions)]
     pub(crate) location: Location<'static>,
     #[allow(dead_code)]
@@ -1619,6 +2281,7 @@ impl<A: Clone, B: Clone, OA, OB, I, E> Clone for SeparatedBy<A, B, OA, OB, I, E>
             at_most: self.at_most,
             allow_leading: self.allow_leading,
             allow_trailing: self.allow_trailing,
+            strict_count: self.strict_count,
             #[cfg(debug_assertions)]
             location: self.location,
             phantom: EmptyPhantom::new(),
@@ -1761,6 +2424,75 @@ where
             ..self
         }
     }
+
+    /// Require, at compile time, that the pattern always consumes input when it succeeds, ruling out infinite loops.
+    ///
+    /// This does not change the behaviour of the parser. See [`NonEmptyParser`] and [`Parser::non_empty`].
+    pub fn strict(self) -> Self
+    where
+        A: NonEmptyParser<'src, I, OA, E>,
+    {
+        self
+    }
+
+    /// Report violations of the bounds set by [`SeparatedBy::at_least`] and [`SeparatedBy::at_most`] as errors that
+    /// state the expected number of items and span the whole list.
+    ///
+    /// By default, a list that is too short produces an error for the missing item or separator, and a list that is
+    /// too long simply stops after the maximum number of items (leaving whatever parser comes next to complain about
+    /// the next separator). With this enabled, the remaining items of a list that is too long are consumed so that
+    /// they can be counted, and the parser fails with a [`DefaultExpected::Count`] error instead.
+    ///
+    /// If an item fails to parse after having consumed input, the error for that item is reported instead, since it
+    /// is likely to be more useful than the count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let args = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .at_most(4)
+    ///     .strict_count()
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('('), just(')'));
+    ///
+    /// assert_eq!(args.parse("(1, 2, 3)").into_result(), Ok(vec!["1", "2", "3"]));
+    ///
+    /// let errs = args.parse("(1, 2, 3, 4, 5, 6, 7)").into_errors();
+    /// assert_eq!(errs[0].to_string(), "expected at most 4 items, found 7");
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(1..20));
+    /// ```
+    pub fn strict_count(self) -> Self {
+        Self {
+            strict_count: true,
+            ..self
+        }
+    }
+
+    #[inline]
+    fn count_error(
+        &self,
+        inp: &mut InputRef<'src, '_, I, E>,
+        start: &Option<I::Cursor>,
+        found: usize,
+    ) {
+        if let Some(start) = start {
+            // SAFETY: `start` was taken from this input in `make_iter`
+            let span = unsafe { inp.span_since_raw(start) };
+            inp.take_alt();
+            inp.add_alt(
+                [DefaultExpected::Count {
+                    at_least: self.at_least,
+                    at_most: (self.at_most != !0).then_some(self.at_most as usize),
+                    found,
+                }],
+                None,
+                span,
+            );
+        }
+    }
 }
 
 impl<'src, I, E, A, B, OA, OB> IterParser<'src, I, OA, E> for SeparatedBy<A, B, OA, OB, I, E>
@@ -1770,45 +2502,65 @@ where
     A: Parser<'src, I, OA, E>,
     B: Parser<'src, I, OB, E>,
 {
+    // The number of items parsed so far, the start of the list if count violations are being reported, and whether
+    // the last item consumed no input
     type IterState<M: Mode>
-        = usize
+        = (usize, Option<I::Cursor>, bool)
     where
         I: 'src;
 
     #[inline(always)]
     fn make_iter<M: Mode>(
         &self,
-        _inp: &mut InputRef<'src, '_, I, E>,
+        inp: &mut InputRef<'src, '_, I, E>,
     ) -> PResult<Emit, Self::IterState<M>> {
-        Ok(0)
+        Ok((0, self.strict_count.then(|| inp.cursor().inner), false))
     }
 
     #[inline(always)]
     fn next<M: Mode>(
         &self,
         inp: &mut InputRef<'src, '_, I, E>,
-        state: &mut Self::IterState<M>,
+        (count, start, stalled): &mut Self::IterState<M>,
     ) -> IPResult<M, OA> {
-        if *state as u64 >= self.at_most {
+        if *count as u64 >= self.at_most {
+            if start.is_some() {
+                // Count any excess items so that we can report them
+                let mut extra = 0;
+                let mut end = inp.save();
+                while self.separator.go::<Check>(inp).is_ok()
+                    && self.parser.go::<Check>(inp).is_ok()
+                {
+                    extra += 1;
+                    end = inp.save();
+                }
+                inp.rewind(end);
+                if extra > 0 {
+                    self.count_error(inp, start, *count + extra);
+                    return Err(());
+                }
+            }
             return Ok(None);
         }
 
+        inp.safe_point()?;
         let before_separator = inp.save();
-        if *state == 0 && self.allow_leading {
+        if *count == 0 && self.allow_leading {
             if self.separator.go::<Check>(inp).is_err() {
                 inp.rewind(before_separator.clone());
             }
-        } else if *state > 0 {
+        } else if *count > 0 {
             match self.separator.go::<Check>(inp) {
                 Ok(()) => {
                     // Do nothing
                 }
-                Err(()) if *state < self.at_least => {
+                Err(()) if *count < self.at_least => {
                     inp.rewind(before_separator);
+                    self.count_error(inp, start, *count);
                     return Err(());
                 }
                 Err(()) => {
-                    inp.rewind(before_separator);
+                    inp.rewind_commit(before_separator);
                     return Ok(None);
                 }
             }
@@ -1817,14 +2569,27 @@ where
         let before_item = inp.save();
         match self.parser.go::<M>(inp) {
             Ok(item) => {
-                *state += 1;
+                inp.commit(before_item);
+                // The first item has no separator, so it is not expected to make progress
+                if self.at_most == !0 && *count > 0 {
+                    guard_progress(inp, before_separator, stalled)?;
+                } else {
+                    inp.commit(before_separator);
+                }
+                *count += 1;
                 Ok(Some(item))
             }
-            Err(()) if *state < self.at_least => {
+            Err(()) if *count < self.at_least => {
                 // We have errored before we have reached the count,
                 // and therefore should return this error, as we are
                 // still expecting items
+                let item_consumed = inp.errors.alt.as_ref().map_or(false, |alt| {
+                    I::cursor_location(&alt.pos) > I::cursor_location(&before_item.cursor().inner)
+                });
                 inp.rewind(before_separator);
+                if !item_consumed {
+                    self.count_error(inp, start, *count);
+                }
                 Err(())
             }
             Err(()) => {
@@ -1834,14 +2599,25 @@ where
                 // though if we don't allow trailing, we shouldn't have
                 // consumed the separator, so we need to rewind it.
                 if self.allow_trailing {
-                    inp.rewind(before_item);
+                    inp.rewind_commit(before_item);
+                    inp.commit(before_separator);
                 } else {
-                    inp.rewind(before_separator);
+                    inp.rewind_commit(before_separator);
                 }
                 Ok(None)
             }
         }
     }
+
+    fn introspect_iter(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "SeparatedBy",
+            introspect::NodeKind::Repeated {
+                at_least: self.at_least,
+            },
+            vec![self.parser.introspect(cx), self.separator.introspect(cx)],
+        )
+    }
 }
 
 impl<'src, I, E, A, B, OA, OB> Parser<'src, I, (), E> for SeparatedBy<A, B, OA, OB, I, E>
@@ -1874,6 +2650,16 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "SeparatedBy",
+            introspect::NodeKind::Repeated {
+                at_least: self.at_least,
+            },
+            vec![self.parser.introspect(cx), self.separator.introspect(cx)],
+        )
+    }
+
     go_extra!(());
 }
 
@@ -1933,6 +2719,7 @@ where
 /// See [`IterParser::collect`].
 pub struct Collect<A, O, C> {
     pub(crate) parser: A,
+    pub(crate) capacity: usize,
     #[cfg(debug_assertions)]
     pub(crate) location: Location<'static>,
     #[allow(dead_code)]
@@ -1944,6 +2731,7 @@ impl<A: Clone, O, C> Clone for Collect<A, O, C> {
     fn clone(&self) -> Self {
         Self {
             parser: self.parser.clone(),
+            capacity: self.capacity,
             #[cfg(debug_assertions)]
             location: self.location,
             phantom: EmptyPhantom::new(),
@@ -1960,7 +2748,13 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, C> {
-        let mut output = M::bind::<C, _>(|| C::default());
+        let mut output = M::bind::<C, _>(|| {
+            if self.capacity == 0 {
+                C::default()
+            } else {
+                C::with_capacity(self.capacity)
+            }
+        });
         let mut iter_state = self.parser.make_iter::<M>(inp)?;
         #[cfg(debug_assertions)]
         let mut i = 0;
@@ -1990,9 +2784,82 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Collect", self.parser.introspect_iter(cx))
+    }
+
     go_extra!(C);
 }
 
+/// See [`IterParser::fold_repeated`].
+pub struct FoldRepeated<A, O, B, F> {
+    pub(crate) parser: A,
+    pub(crate) init: B,
+    pub(crate) f: F,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<O>,
+}
+
+impl<A: Copy, O, B: Copy, F: Copy> Copy for FoldRepeated<A, O, B, F> {}
+impl<A: Clone, O, B: Clone, F: Clone> Clone for FoldRepeated<A, O, B, F> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            init: self.init.clone(),
+            f: self.f.clone(),
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'src, I, O, E, A, B, F> Parser<'src, I, B, E> for FoldRepeated<A, O, B, F>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: IterParser<'src, I, O, E>,
+    B: Clone,
+    F: Fn(B, O) -> B,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, B> {
+        let mut acc = M::bind(|| self.init.clone());
+        let mut iter_state = self.parser.make_iter::<M>(inp)?;
+        #[cfg(debug_assertions)]
+        let mut i = 0;
+        loop {
+            #[cfg(debug_assertions)]
+            let before = inp.cursor();
+            match self.parser.next::<M>(inp, &mut iter_state) {
+                Ok(Some(out)) => acc = M::combine(acc, out, |acc, out| (self.f)(acc, out)),
+                Ok(None) => break Ok(acc),
+                Err(()) => break Err(()),
+            }
+            // As with `Collect`, we can only be sure that progress must have been made from the second iteration.
+            #[cfg(debug_assertions)]
+            if !A::NONCONSUMPTION_IS_OK {
+                if i >= 1 {
+                    debug_assert!(
+                        before != inp.cursor(),
+                        "found FoldRepeated combinator making no progress at {}",
+                        self.location,
+                    );
+                }
+                i += 1;
+            }
+        }
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("FoldRepeated", self.parser.introspect_iter(cx))
+    }
+
+    go_extra!(B);
+}
+
 /// See [`IterParser::collect_exactly`]
 pub struct CollectExactly<A, O, C> {
     pub(crate) parser: A,
@@ -2019,7 +2886,6 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, C> {
-        // let before = inp.cursor();
         let mut output = M::bind(|| C::uninit());
         let mut iter_state = self.parser.make_iter::<M>(inp)?;
         for idx in 0..C::LEN {
@@ -2028,9 +2894,20 @@ where
                     M::combine_mut(&mut output, out, |c, out| C::write(c, idx, out));
                 }
                 Ok(None) => {
-                    // let span = inp.span_since(&before);
-                    // We don't add an alt here because we assume the inner parser will. Is this safe to assume?
-                    // inp.add_alt([ExpectedMoreElements(Some(C::LEN - idx))], None, span);
+                    // Say how many items were found, alongside whatever error the inner parser gave for the next item
+                    let before = inp.save();
+                    let found = inp.next_maybe_inner();
+                    let span = inp.span_since(before.cursor());
+                    inp.rewind(before);
+                    inp.add_alt(
+                        [DefaultExpected::Count {
+                            at_least: C::LEN,
+                            at_most: Some(C::LEN),
+                            found: idx,
+                        }],
+                        found.map(|f| f.into()),
+                        span,
+                    );
                     // SAFETY: We're guaranteed to have initialized up to `idx` values
                     M::map(output, |mut output| unsafe {
                         C::drop_before(&mut output, idx)
@@ -2050,6 +2927,10 @@ where
         Ok(M::map(output, |output| unsafe { C::take(output) }))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("CollectExactly", self.parser.introspect_iter(cx))
+    }
+
     go_extra!(C);
 }
 
@@ -2069,17 +2950,57 @@ where
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, Option<O>> {
         let before = inp.save();
         Ok(match self.parser.go::<M>(inp) {
-            Ok(out) => M::map::<O, _, _>(out, Some),
+            Ok(out) => {
+                inp.commit(before);
+                M::map::<O, _, _>(out, Some)
+            }
             Err(()) => {
-                inp.rewind(before);
+                inp.rewind_commit(before);
                 M::bind::<Option<O>, _>(|| None)
             }
         })
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "OrNot",
+            introspect::NodeKind::Optional,
+            vec![self.parser.introspect(cx)],
+        )
+    }
+
     go_extra!(Option<O>);
 }
 
+/// See [`Parser::or_default_ctx`].
+#[derive(Copy, Clone)]
+pub struct OrDefaultCtx<A, F> {
+    pub(crate) parser: A,
+    pub(crate) default: F,
+}
+
+impl<'src, I, O, E, A, F> Parser<'src, I, O, E> for OrDefaultCtx<A, F>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+    F: Fn(&E::Context) -> O,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        Ok(match self.parser.go::<M>(inp) {
+            Ok(out) => out,
+            Err(()) => {
+                inp.rewind(before);
+                M::bind(|| (self.default)(inp.ctx()))
+            }
+        })
+    }
+
+    go_extra!(O);
+}
+
 impl<'src, A, O, I, E> IterParser<'src, I, O, E> for OrNot<A>
 where
     I: Input<'src>,
@@ -2121,6 +3042,14 @@ where
             }
         }
     }
+
+    fn introspect_iter(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "OrNot",
+            introspect::NodeKind::Optional,
+            vec![self.parser.introspect(cx)],
+        )
+    }
 }
 
 /// See [`Parser::not`].
@@ -2176,16 +3105,13 @@ where
 }
 
 /// See [`IterParser::flatten`].
-#[cfg(feature = "nightly")]
 pub struct Flatten<A, O> {
     pub(crate) parser: A,
     #[allow(dead_code)]
     pub(crate) phantom: EmptyPhantom<O>,
 }
 
-#[cfg(feature = "nightly")]
 impl<A: Copy, O> Copy for Flatten<A, O> {}
-#[cfg(feature = "nightly")]
 impl<A: Clone, O> Clone for Flatten<A, O> {
     fn clone(&self) -> Self {
         Self {
@@ -2195,7 +3121,6 @@ impl<A: Clone, O> Clone for Flatten<A, O> {
     }
 }
 
-#[cfg(feature = "nightly")]
 impl<'src, A, O, I, E> IterParser<'src, I, O::Item, E> for Flatten<A, O>
 where
     I: Input<'src>,
@@ -2256,6 +3181,55 @@ where
     }
 }
 
+/// See [`IterParser::flatten_with_spans`].
+pub struct FlattenWithSpans<A, O, C, S> {
+    pub(crate) parser: A,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(O, C, S)>,
+}
+
+impl<A: Copy, O, C, S> Copy for FlattenWithSpans<A, O, C, S> {}
+impl<A: Clone, O, C, S> Clone for FlattenWithSpans<A, O, C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'src, I, O, E, A, C, S> Parser<'src, I, (C, S), E> for FlattenWithSpans<A, O, C, S>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: IterParser<'src, I, O, E>,
+    O: IntoIterator,
+    C: Container<O::Item>,
+    S: Container<I::Span>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, (C, S)> {
+        let mut output = M::bind(|| (C::default(), S::default()));
+        let mut iter_state = self.parser.make_iter::<M>(inp)?;
+        loop {
+            let before = inp.cursor();
+            match self.parser.next::<M>(inp, &mut iter_state) {
+                Ok(Some(chunk)) => {
+                    let span = inp.span_since(&before);
+                    M::combine_mut(&mut output, chunk, |(items, spans), chunk| {
+                        chunk.into_iter().for_each(|item| items.push(item));
+                        spans.push(span);
+                    });
+                }
+                Ok(None) => break Ok(output),
+                Err(()) => break Err(()),
+            }
+        }
+    }
+
+    go_extra!((C, S));
+}
+
 /// See [`Parser::and_is`].
 pub struct AndIs<A, B, OB> {
     pub(crate) parser_a: A,
@@ -2381,6 +3355,17 @@ where
         }))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Foldr",
+            introspect::NodeKind::Seq,
+            vec![
+                self.parser_a.introspect_iter(cx),
+                self.parser_b.introspect(cx),
+            ],
+        )
+    }
+
     go_extra!(O);
 }
 
@@ -2454,6 +3439,17 @@ where
         }))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "FoldrWith",
+            introspect::NodeKind::Seq,
+            vec![
+                self.parser_a.introspect_iter(cx),
+                self.parser_b.introspect(cx),
+            ],
+        )
+    }
+
     go_extra!(O);
 }
 
@@ -2518,6 +3514,17 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Foldl",
+            introspect::NodeKind::Seq,
+            vec![
+                self.parser_a.introspect(cx),
+                self.parser_b.introspect_iter(cx),
+            ],
+        )
+    }
+
     go_extra!(O);
 }
 
@@ -2585,6 +3592,17 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "FoldlWith",
+            introspect::NodeKind::Seq,
+            vec![
+                self.parser_a.introspect(cx),
+                self.parser_b.introspect_iter(cx),
+            ],
+        )
+    }
+
     go_extra!(O);
 }
 
@@ -2640,6 +3658,10 @@ where
             .go::<M>(inp)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("MapErr", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -2678,6 +3700,46 @@ where
 //     go_extra!(O);
 // }
 
+/// See [`Parser::with_code`].
+#[derive(Copy, Clone)]
+pub struct WithCode<A> {
+    pub(crate) parser: A,
+    pub(crate) code: &'static str,
+}
+
+impl<'src, I, O, E, A> Parser<'src, I, O, E> for WithCode<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+    E::Error: CodeError,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let old_alt = inp.errors.alt.take();
+        let before = inp.save();
+        let res = self.parser.go::<M>(inp);
+
+        let new_alt = inp.errors.alt.take();
+        inp.errors.alt = old_alt;
+        if let Some(mut new_alt) = new_alt {
+            new_alt.err.with_code(self.code);
+            inp.add_alt_err(&new_alt.pos, new_alt.err);
+        }
+        for err in inp.errors.secondary_errors_since(before.err_count) {
+            err.err.with_code(self.code);
+        }
+
+        res
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("WithCode", self.parser.introspect(cx))
+    }
+
+    go_extra!(O);
+}
+
 // TODO: Remove combinator, replace with map_err_with
 /// See [`Parser::map_err_with_state`].
 #[derive(Copy, Clone)]
@@ -2715,6 +3777,10 @@ where
         res
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("MapErrWithState", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
 
@@ -2760,6 +3826,10 @@ where
         Ok(M::bind(|| out))
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Validate", self.parser.introspect(cx))
+    }
+
     go_extra!(U);
 }
 
@@ -2809,6 +3879,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use super::guard_progress;
     use crate::prelude::*;
 
     #[test]
@@ -2884,4 +3955,160 @@ mod tests {
             Ok((vec!['-', '-', '-'], ',')),
         )
     }
+
+    #[test]
+    fn separated_by_strict_count() {
+        let parser = text::int::<_, extra::Err<Rich<char>>>(10)
+            .separated_by(just(','))
+            .at_least(2)
+            .at_most(3)
+            .strict_count()
+            .collect::<Vec<_>>()
+            .then_ignore(just(';'));
+
+        assert_eq!(parser.parse("1,2;").into_result(), Ok(vec!["1", "2"]));
+
+        let errs = parser.parse("1;").into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "expected between 2 and 3 items, found 1"
+        );
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+
+        let errs = parser.parse("1,2,3,4,5;").into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "expected between 2 and 3 items, found 5"
+        );
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..9));
+
+        // Errors within items take precedence over the count
+        let parser = just::<_, _, extra::Err<Rich<char>>>("ab")
+            .separated_by(just(','))
+            .exactly(2)
+            .strict_count()
+            .collect::<Vec<_>>();
+        let errs = parser.parse("ab,a").into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(4..4));
+        assert_eq!(errs[0].to_string(), "found end of input expected 'b'");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "consumed no input"]
+    fn non_empty_panics_without_progress() {
+        let parser = text::whitespace::<_, extra::Default>()
+            .non_empty()
+            .repeated()
+            .strict();
+
+        let _ = parser.parse("abc");
+    }
+
+    #[test]
+    fn guard_progress_fails_second_stall() {
+        // Debug builds panic on a stalled repetition before the guard is reached, so exercise the guard directly
+        let parser = custom::<_, &str, _, extra::Default>(|inp| {
+            let mut stalled = false;
+            let before = inp.save();
+            inp.next();
+            let progressed = guard_progress(inp, before, &mut stalled);
+            let before = inp.save();
+            let first = guard_progress(inp, before, &mut stalled);
+            let before = inp.save();
+            let second = guard_progress(inp, before, &mut stalled);
+            // Failing leaves an error behind, so that recovery can pick it up
+            let has_alt = inp.take_alt().is_some();
+            Ok((progressed, first, second, has_alt))
+        });
+        assert_eq!(
+            parser.parse("a").into_result(),
+            Ok((Ok(()), Ok(()), Err(()), true))
+        );
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn repeated_without_progress_fails() {
+        let parser = empty::<&str, extra::Default>()
+            .repeated()
+            .collect::<Vec<_>>();
+        assert!(parser.parse("").has_errors());
+
+        let parser = just::<_, _, extra::Default>('a')
+            .or_not()
+            .separated_by(just(',').or_not())
+            .collect::<Vec<_>>();
+        assert!(parser.parse("a,a").has_errors());
+    }
+
+    #[test]
+    fn collect_exactly_too_few() {
+        let shared = alloc::rc::Rc::new(());
+        let item = one_of::<_, &str, extra::Err<Rich<char>>>("abc").map(|c| (c, shared.clone()));
+        let triple = item.repeated().collect_exactly::<[_; 3]>();
+
+        let out = triple.parse("abc").into_result().unwrap();
+        assert_eq!(out.map(|(c, _)| c), ['a', 'b', 'c']);
+        assert_eq!(alloc::rc::Rc::strong_count(&shared), 1);
+
+        // Partially-parsed items are dropped, and the error for the missing item is kept
+        let errs = triple.then(any()).parse("abxy").into_errors();
+        assert_eq!(alloc::rc::Rc::strong_count(&shared), 1);
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(2..3));
+        assert_eq!(
+            errs[0].to_string(),
+            "found 'x' expected 'a', 'b', 'c', or exactly 3 items"
+        );
+
+        let errs = triple.parse("a").into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "found end of input expected 'a', 'b', 'c', or exactly 3 items"
+        );
+    }
+
+    #[test]
+    fn fold_repeated_without_collecting() {
+        use alloc::collections::BTreeSet;
+
+        let symbols = text::ascii::ident::<_, extra::Default>()
+            .separated_by(just(',').padded())
+            .allow_trailing()
+            .fold_repeated(BTreeSet::new(), |mut set, name| {
+                set.insert(name);
+                set
+            });
+
+        assert_eq!(
+            symbols.parse("b, a, b,").into_result(),
+            Ok(BTreeSet::from(["a", "b"])),
+        );
+        assert_eq!(
+            symbols.clone().to_slice().parse("x,y").into_result(),
+            Ok("x,y"),
+        );
+        assert!(symbols.parse("a,,b").has_errors());
+    }
+
+    #[test]
+    fn map_ctx_restored_on_backtrack() {
+        fn get<'src, C: Clone + 'src>() -> impl Parser<'src, &'src str, C, extra::Context<C>> + Clone
+        {
+            empty::<&str, extra::Context<C>>().map_with(|(), e| e.ctx().clone())
+        }
+
+        let parser = get::<u32>()
+            .map_ctx(|n: &u32| n + 1)
+            .then_ignore(just('!'))
+            .or(get::<String>()
+                .map_ctx(|n: &u32| format!("{n}0"))
+                .map(|s| s.parse().unwrap()))
+            .with_ctx(3u32)
+            .then_ignore(end::<_, extra::Default>());
+
+        assert_eq!(parser.parse("!").into_result(), Ok(4));
+        assert_eq!(parser.parse("").into_result(), Ok(30));
+    }
 }
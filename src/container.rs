@@ -1,7 +1,7 @@
 //! TODO
 
 use super::*;
-use alloc::collections::LinkedList;
+use alloc::{borrow::Cow, collections::LinkedList};
 use hashbrown::HashSet;
 
 /// A utility trait for types that can be constructed from a series of items.
@@ -76,6 +76,16 @@ impl<T> Container<T> for Vec<T> {
     }
 }
 
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Container<A::Item> for smallvec::SmallVec<A> {
+    fn with_capacity(n: usize) -> Self {
+        Self::with_capacity(n)
+    }
+    fn push(&mut self, item: A::Item) {
+        (*self).push(item);
+    }
+}
+
 impl<T> Container<T> for LinkedList<T> {
     fn push(&mut self, item: T) {
         (*self).push_back(item);
@@ -92,6 +102,51 @@ impl Container<char> for String {
     }
 }
 
+impl<'a> Container<&'a str> for String {
+    fn push(&mut self, item: &'a str) {
+        (*self).push_str(item)
+    }
+}
+
+impl Container<String> for String {
+    fn push(&mut self, item: String) {
+        if self.is_empty() {
+            // Reuse the first allocation rather than copying out of it
+            *self = item;
+        } else {
+            (*self).push_str(&item)
+        }
+    }
+}
+
+/// Collecting into a [`Cow`] only allocates if there is more than one piece (or a piece is itself owned), so strings
+/// made of a single slice of the input, like string literals without escape sequences, are borrowed.
+impl<'a> Container<&'a str> for Cow<'a, str> {
+    fn push(&mut self, item: &'a str) {
+        if self.is_empty() {
+            *self = Cow::Borrowed(item);
+        } else if !item.is_empty() {
+            self.to_mut().push_str(item)
+        }
+    }
+}
+
+impl<'a> Container<Cow<'a, str>> for Cow<'a, str> {
+    fn push(&mut self, item: Cow<'a, str>) {
+        if self.is_empty() {
+            *self = item;
+        } else if !item.is_empty() {
+            self.to_mut().push_str(&item)
+        }
+    }
+}
+
+impl Container<char> for Cow<'_, str> {
+    fn push(&mut self, item: char) {
+        self.to_mut().push(item)
+    }
+}
+
 impl<K: Eq + Hash, V> Container<(K, V)> for HashMap<K, V> {
     fn with_capacity(n: usize) -> Self {
         Self::with_capacity(n)
@@ -72,4 +72,19 @@ mod tests {
             assert!(parser.parse("[3, 4, 5, 67 89,]").has_errors());
         }
     }
+
+    #[test]
+    fn or_different() {
+        let parser = parser().or_different(just::<_, &str, _>("none"));
+
+        assert_eq!(
+            parser.parse("[1, 2]").into_result(),
+            Ok(Either::Left(vec![1, 2]))
+        );
+        assert_eq!(
+            parser.parse("none").into_result(),
+            Ok(Either::Right("none"))
+        );
+        assert!(parser.parse("nothing").has_errors());
+    }
 }
@@ -7,7 +7,8 @@
 //! like [`Cheap`], [`Simple`] or [`Rich`].
 
 use super::*;
-use alloc::{borrow::Cow, string::ToString};
+use alloc::{borrow::Cow, format, string::ToString};
+use span::{LineIndex, PositionEncoding};
 
 pub use label::LabelError;
 
@@ -84,6 +85,56 @@ pub trait Error<'a, I: Input<'a>>:
     }
 }
 
+/// A trait implemented by [`Error`]s that can carry a diagnostic code. See [`Parser::with_code`].
+///
+/// Codes give each kind of error a stable name (like `E0012`) that projects can document and that users can search
+/// for or suppress. Error types that have nowhere to store a code can implement this trait without overriding
+/// anything, which ignores codes.
+pub trait CodeError {
+    /// Give this error the given code.
+    ///
+    /// When parsers with codes are nested, this is called with the innermost code first. Implementations should
+    /// usually keep the first code that they are given, so that the most specific code wins.
+    fn with_code(&mut self, code: &'static str) {
+        #![allow(unused_variables)]
+    }
+}
+
+/// A trait implemented by errors that have an identity, allowing duplicates of the same error to be detected and merged.
+/// See [`ParseResult::dedup_errors`](crate::ParseResult::dedup_errors).
+///
+/// Duplicate errors can arise when the same region of input is parsed more than once, such as by an embedded parser
+/// or by several layers of validation.
+pub trait ErrorIdentity {
+    /// The identity of an error. Errors with equal identities are considered to be duplicates.
+    type Id: Eq + Hash;
+
+    /// Get the identity of this error.
+    fn identity(&self) -> Self::Id;
+
+    /// Merge a duplicate of this error into it. By default, the duplicate is discarded.
+    fn merge_duplicate(&mut self, other: Self)
+    where
+        Self: Sized,
+    {
+        #![allow(unused_variables)]
+    }
+}
+
+/// A trait implemented by [`Error`]s that can describe a literal whose value cannot be represented, like an integer
+/// literal that is too large for its type. See [`text::int_checked`](crate::text::int_checked).
+pub trait LiteralError<'a, I: Input<'a>>: Error<'a, I> {
+    /// Create an error for a `literal` (such as `"integer"`) that is out of range for the type `ty` (such as `"i64"`).
+    ///
+    /// `found` is the first token of the literal, and `span` is the span of the whole literal.
+    fn out_of_range(
+        literal: &'static str,
+        ty: &'static str,
+        found: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self;
+}
+
 /// A ZST error type that tracks only whether a parse error occurred at all. This type is for when
 /// you want maximum parse speed, at the cost of all error reporting.
 ///
@@ -103,6 +154,20 @@ pub struct EmptyErr(());
 
 impl<'a, I: Input<'a>> Error<'a, I> for EmptyErr {}
 
+impl CodeError for EmptyErr {}
+
+impl<'a, I: Input<'a>> LiteralError<'a, I> for EmptyErr {
+    #[inline(always)]
+    fn out_of_range(
+        _: &'static str,
+        _: &'static str,
+        _: Option<MaybeRef<'a, I::Token>>,
+        _: I::Span,
+    ) -> Self {
+        EmptyErr::default()
+    }
+}
+
 impl<'a, I: Input<'a>, L> LabelError<'a, I, L> for EmptyErr {
     #[inline(always)]
     fn expected_found<E: IntoIterator<Item = L>>(
@@ -146,10 +211,40 @@ impl<S> Cheap<S> {
     pub fn span(&self) -> &S {
         &self.span
     }
+
+    /// Transform this error's span using the given function.
+    ///
+    /// This is useful when moving errors between inputs, such as when parsing an embedded language (see
+    /// [`Parser::embedded_in`]).
+    pub fn map_span<S2, F: FnOnce(S) -> S2>(self, f: F) -> Cheap<S2> {
+        Cheap { span: f(self.span) }
+    }
 }
 
 impl<'a, I: Input<'a>> Error<'a, I> for Cheap<I::Span> {}
 
+impl<S> CodeError for Cheap<S> {}
+
+impl<S: Clone + Eq + Hash> ErrorIdentity for Cheap<S> {
+    type Id = S;
+
+    fn identity(&self) -> Self::Id {
+        self.span.clone()
+    }
+}
+
+impl<'a, I: Input<'a>> LiteralError<'a, I> for Cheap<I::Span> {
+    #[inline]
+    fn out_of_range(
+        _: &'static str,
+        _: &'static str,
+        _: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self {
+        Self { span }
+    }
+}
+
 impl<'a, I: Input<'a>, L> LabelError<'a, I, L> for Cheap<I::Span> {
     #[inline]
     fn expected_found<E: IntoIterator<Item = L>>(
@@ -229,10 +324,43 @@ impl<'a, T, S> Simple<'a, T, S> {
             found: self.found.map(|found| f(found.into_inner()).into()),
         }
     }
+
+    /// Transform this error's span using the given function.
+    ///
+    /// This is useful when moving errors between inputs, such as when parsing an embedded language (see
+    /// [`Parser::embedded_in`]).
+    pub fn map_span<S2, F: FnOnce(S) -> S2>(self, f: F) -> Simple<'a, T, S2> {
+        Simple {
+            span: f(self.span),
+            found: self.found,
+        }
+    }
 }
 
 impl<'a, I: Input<'a>> Error<'a, I> for Simple<'a, I::Token, I::Span> {}
 
+impl<T, S> CodeError for Simple<'_, T, S> {}
+
+impl<T, S: Clone + Eq + Hash> ErrorIdentity for Simple<'_, T, S> {
+    type Id = S;
+
+    fn identity(&self) -> Self::Id {
+        self.span.clone()
+    }
+}
+
+impl<'a, I: Input<'a>> LiteralError<'a, I> for Simple<'a, I::Token, I::Span> {
+    #[inline]
+    fn out_of_range(
+        _: &'static str,
+        _: &'static str,
+        found: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self {
+        Self { span, found }
+    }
+}
+
 impl<'a, I: Input<'a>, L> LabelError<'a, I, L> for Simple<'a, I::Token, I::Span> {
     #[inline]
     fn expected_found<E: IntoIterator<Item = L>>(
@@ -251,7 +379,7 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "found ")?;
-        write_token(f, T::fmt, self.found.as_deref())?;
+        write_token(f, debug_token, self.found.as_deref())?;
         write!(f, " at {:?}", self.span)?;
         Ok(())
     }
@@ -267,12 +395,298 @@ where
     }
 }
 
+/// A trait that controls how a token is shown in the messages of errors such as [`Rich`].
+///
+/// This is implemented for every type that implements [`fmt::Display`], which is shown in single quotes (`'x'`). Token
+/// types that don't implement [`fmt::Display`] can implement this trait instead, so that errors rendered for end users
+/// describe tokens by name rather than showing their [`fmt::Debug`] representation. Implementations are responsible
+/// for any quoting.
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::{prelude::*, error::TokenDisplay};
+/// use core::fmt;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Token {
+///     Fn,
+///     Ident(&'static str),
+///     OpenParen,
+/// }
+///
+/// impl TokenDisplay for Token {
+///     fn fmt_token(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             Token::Fn => write!(f, "keyword `fn`"),
+///             Token::Ident(name) => write!(f, "identifier `{name}`"),
+///             Token::OpenParen => write!(f, "`(`"),
+///         }
+///     }
+/// }
+///
+/// let parser = just::<_, _, extra::Err<Rich<Token>>>(Token::Fn).then(just(Token::OpenParen));
+/// let errs = parser.parse(&[Token::Ident("main"), Token::OpenParen]).into_errors();
+///
+/// assert_eq!(errs[0].to_string(), "found identifier `main` expected keyword `fn`");
+/// ```
+pub trait TokenDisplay {
+    /// Write a description of this token to the formatter.
+    fn fmt_token(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// The class of token that this is, if it belongs to one.
+    ///
+    /// When a token with a class is found where a single labelled pattern (such as `identifier`) was expected, the
+    /// error message explains that the token cannot be used as one, rather than just listing what was expected:
+    ///
+    /// ```
+    /// use chumsky::{prelude::*, error::{TokenClass, TokenDisplay}};
+    /// use core::fmt;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Token<'a> {
+    ///     Type,
+    ///     Ident(&'a str),
+    /// }
+    ///
+    /// impl TokenDisplay for Token<'_> {
+    ///     fn fmt_token(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         match self {
+    ///             Token::Type => write!(f, "keyword `type`"),
+    ///             Token::Ident(name) => write!(f, "`{name}`"),
+    ///         }
+    ///     }
+    ///
+    ///     fn class(&self) -> Option<TokenClass> {
+    ///         match self {
+    ///             Token::Type => Some(TokenClass::Keyword),
+    ///             Token::Ident(_) => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let ident = select! { Token::Ident(name) => name }
+    ///     .labelled("identifier")
+    ///     .then_ignore(end::<&[Token], extra::Err<Rich<Token>>>());
+    /// let errs = ident.parse(&[Token::Type]).into_errors();
+    ///
+    /// assert_eq!(errs[0].to_string(), "found keyword `type`, which cannot be used as an identifier");
+    /// ```
+    fn class(&self) -> Option<TokenClass> {
+        None
+    }
+}
+
+/// A class of tokens, as reported by [`TokenDisplay::class`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenClass {
+    /// A reserved word, like `fn` or `type`.
+    Keyword,
+    /// An operator or punctuation, like `+` or `;`.
+    Operator,
+    /// A literal value, like `42` or `"hello"`.
+    Literal,
+}
+
+impl fmt::Display for TokenClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keyword => write!(f, "keyword"),
+            Self::Operator => write!(f, "operator"),
+            Self::Literal => write!(f, "literal"),
+        }
+    }
+}
+
+/// The text used to display [`Rich`] errors, allowing messages to be localized. See [`Rich::display_with`].
+///
+/// Each method receives the structured parts of an error and writes the corresponding part of the message. Only
+/// [`RichMessages::token`] must be implemented: the other methods default to the English text that [`Rich`]'s
+/// [`fmt::Display`] implementation uses, so an implementation need only override the parts that it wants to change.
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::{prelude::*, error::{RichMessages, RichPattern}};
+/// use core::fmt;
+///
+/// struct French;
+///
+/// impl RichMessages<char> for French {
+///     fn token(&self, f: &mut fmt::Formatter<'_>, token: &char) -> fmt::Result {
+///         write!(f, "« {token} »")
+///     }
+///
+///     fn end_of_input(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "la fin de l'entrée")
+///     }
+///
+///     fn expected_found(
+///         &self,
+///         f: &mut fmt::Formatter<'_>,
+///         expected: &[RichPattern<'_, char>],
+///         found: Option<&char>,
+///     ) -> fmt::Result {
+///         write!(f, "trouvé ")?;
+///         match found {
+///             Some(token) => self.token(f, token)?,
+///             None => self.end_of_input(f)?,
+///         }
+///         write!(f, " au lieu de ")?;
+///         for (i, pattern) in expected.iter().enumerate() {
+///             if i > 0 {
+///                 write!(f, " ou ")?;
+///             }
+///             self.pattern(f, pattern)?;
+///         }
+///         Ok(())
+///     }
+///
+///     fn context(
+///         &self,
+///         f: &mut fmt::Formatter<'_>,
+///         label: &RichPattern<'_, char>,
+///         span: &dyn fmt::Display,
+///     ) -> fmt::Result {
+///         write!(f, " dans ")?;
+///         self.pattern(f, label)?;
+///         write!(f, " à {span}")
+///     }
+/// }
+///
+/// let parser = just::<_, _, extra::Err<Rich<char>>>('a')
+///     .or(just('b'))
+///     .repeated()
+///     .collect::<String>()
+///     .delimited_by(just('('), just(')'))
+///     .labelled("groupe")
+///     .as_context();
+/// let err = &parser.parse("(ab").into_errors()[0];
+///
+/// assert_eq!(
+///     err.display_with(&French).to_string(),
+///     "trouvé la fin de l'entrée au lieu de « a » ou « b » ou « ) » dans groupe à 0..3",
+/// );
+/// ```
+pub trait RichMessages<T> {
+    /// Write a token.
+    fn token(&self, f: &mut fmt::Formatter<'_>, token: &T) -> fmt::Result;
+
+    /// The class of a token, if it belongs to one. See [`TokenDisplay::class`].
+    fn class(&self, token: &T) -> Option<TokenClass> {
+        #![allow(unused_variables)]
+        None
+    }
+
+    /// Write the end of input, where a token was found or expected.
+    fn end_of_input(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "end of input")
+    }
+
+    /// Write a pattern that was expected, or a label that gives the context of an error.
+    fn pattern(&self, f: &mut fmt::Formatter<'_>, pattern: &RichPattern<'_, T>) -> fmt::Result {
+        match pattern {
+            RichPattern::EndOfInput => self.end_of_input(f),
+            pattern => pattern.write(f, |token, f| self.token(f, token)),
+        }
+    }
+
+    /// Write the message for the reason of an error.
+    ///
+    /// By default, this defers to [`RichMessages::expected_found`] and [`RichMessages::count`], and writes custom
+    /// messages as they are.
+    fn reason(&self, f: &mut fmt::Formatter<'_>, reason: &RichReason<'_, T>) -> fmt::Result {
+        match reason {
+            RichReason::ExpectedFound { expected, .. }
+                if matches!(&expected[..], [RichPattern::Count { .. }]) =>
+            {
+                self.count(f, &expected[0])
+            }
+            RichReason::ExpectedFound { expected, found } => {
+                self.expected_found(f, expected, found.as_deref())
+            }
+            RichReason::Custom(msg) => write!(f, "{msg}"),
+        }
+    }
+
+    /// Write the message for an error where one of the expected patterns was not found. A `found` value of `None`
+    /// means that the end of input was found.
+    fn expected_found(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        expected: &[RichPattern<'_, T>],
+        found: Option<&T>,
+    ) -> fmt::Result {
+        write!(f, "found ")?;
+        match found {
+            Some(token) => self.token(f, token)?,
+            None => self.end_of_input(f)?,
+        }
+        // A token of some class (like a keyword) can never be what a label (like `identifier`) describes
+        match (expected, found.and_then(|token| self.class(token))) {
+            ([RichPattern::Label(label)], Some(_)) => {
+                let article = if label.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                    "an"
+                } else {
+                    "a"
+                };
+                write!(f, ", which cannot be used as {article} {label}")
+            }
+            _ => {
+                write!(f, " expected ")?;
+                write_expected(f, expected, |pattern, f| self.pattern(f, pattern))
+            }
+        }
+    }
+
+    /// Write the message for an error where a list had the wrong number of items. The pattern is always a
+    /// [`RichPattern::Count`].
+    fn count(&self, f: &mut fmt::Formatter<'_>, pattern: &RichPattern<'_, T>) -> fmt::Result {
+        write_count(f, pattern, |pattern, f| self.pattern(f, pattern))
+    }
+
+    /// Write a context that an error occurred within, including any separator that should come before it.
+    fn context(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        label: &RichPattern<'_, T>,
+        span: &dyn fmt::Display,
+    ) -> fmt::Result {
+        write!(f, " in ")?;
+        self.pattern(f, label)?;
+        write!(f, " at {span}")
+    }
+}
+
+/// The messages used by [`Rich`]'s [`fmt::Display`] implementation.
+struct DefaultMessages;
+
+impl<T: TokenDisplay> RichMessages<T> for DefaultMessages {
+    fn token(&self, f: &mut fmt::Formatter<'_>, token: &T) -> fmt::Result {
+        token.fmt_token(f)
+    }
+
+    fn class(&self, token: &T) -> Option<TokenClass> {
+        token.class()
+    }
+}
+
+impl<T: fmt::Display + ?Sized> TokenDisplay for T {
+    fn fmt_token(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{self}'")
+    }
+}
+
 /// An expected pattern for a [`Rich`] error.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum RichPattern<'a, T> {
     /// A specific token.
     Token(MaybeRef<'a, T>),
+    /// A specific sequence of tokens.
+    Tokens(Vec<MaybeRef<'a, T>>),
     /// A labelled pattern.
     Label(Cow<'a, str>),
     /// A specific keyword.
@@ -283,15 +697,34 @@ pub enum RichPattern<'a, T> {
     SomethingElse,
     /// The end of input.
     EndOfInput,
+    /// A different number of repeated items.
+    Count {
+        /// The minimum number of items expected.
+        at_least: usize,
+        /// The maximum number of items expected, if any.
+        at_most: Option<usize>,
+        /// The number of items that were found.
+        found: usize,
+    },
 }
 
 impl<'a, T> From<DefaultExpected<'a, T>> for RichPattern<'a, T> {
     fn from(expected: DefaultExpected<'a, T>) -> Self {
         match expected {
             DefaultExpected::Token(tok) => Self::Token(tok),
+            DefaultExpected::Tokens(toks) => Self::Tokens(toks),
             DefaultExpected::Any => Self::Any,
             DefaultExpected::SomethingElse => Self::SomethingElse,
             DefaultExpected::EndOfInput => Self::EndOfInput,
+            DefaultExpected::Count {
+                at_least,
+                at_most,
+                found,
+            } => Self::Count {
+                at_least,
+                at_most,
+                found,
+            },
         }
     }
 }
@@ -310,6 +743,7 @@ where
             }
             text::TextExpected::Digit(_) => Self::Label(Cow::Borrowed("digit")),
             text::TextExpected::IdentifierPart => Self::Label(Cow::Borrowed("identifier")),
+            text::TextExpected::Float => Self::Label(Cow::Borrowed("float literal")),
             text::TextExpected::Identifier(i) => Self::Identifier(I::stringify(i)),
         }
     }
@@ -350,11 +784,23 @@ impl<'a, T> RichPattern<'a, T> {
     {
         match self {
             Self::Token(t) => RichPattern::Token(f(t.into_inner()).into()),
+            Self::Tokens(ts) => {
+                RichPattern::Tokens(ts.into_iter().map(|t| f(t.into_inner()).into()).collect())
+            }
             Self::Label(l) => RichPattern::Label(l),
             Self::Identifier(i) => RichPattern::Identifier(i),
             Self::Any => RichPattern::Any,
             Self::SomethingElse => RichPattern::SomethingElse,
             Self::EndOfInput => RichPattern::EndOfInput,
+            Self::Count {
+                at_least,
+                at_most,
+                found,
+            } => RichPattern::Count {
+                at_least,
+                at_most,
+                found,
+            },
         }
     }
 
@@ -365,11 +811,23 @@ impl<'a, T> RichPattern<'a, T> {
     {
         match self {
             Self::Token(tok) => RichPattern::Token(tok.into_owned()),
+            Self::Tokens(toks) => {
+                RichPattern::Tokens(toks.into_iter().map(MaybeRef::into_owned).collect())
+            }
             Self::Label(l) => RichPattern::Label(Cow::Owned(l.into_owned())),
             Self::Identifier(i) => RichPattern::Identifier(i),
             Self::Any => RichPattern::Any,
             Self::SomethingElse => RichPattern::SomethingElse,
             Self::EndOfInput => RichPattern::EndOfInput,
+            Self::Count {
+                at_least,
+                at_most,
+                found,
+            } => RichPattern::Count {
+                at_least,
+                at_most,
+                found,
+            },
         }
     }
 
@@ -379,16 +837,36 @@ impl<'a, T> RichPattern<'a, T> {
         mut fmt_token: impl FnMut(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
     ) -> fmt::Result {
         match self {
-            Self::Token(tok) => {
-                write!(f, "'")?;
-                fmt_token(tok, f)?;
-                write!(f, "'")
+            Self::Token(tok) => fmt_token(tok, f),
+            Self::Tokens(toks) => {
+                for (i, tok) in toks.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    fmt_token(tok, f)?;
+                }
+                Ok(())
             }
             Self::Label(l) => write!(f, "{l}"),
             Self::Identifier(i) => write!(f, "'{i}'"),
             Self::Any => write!(f, "any"),
             Self::SomethingElse => write!(f, "something else"),
             Self::EndOfInput => write!(f, "end of input"),
+            Self::Count {
+                at_least, at_most, ..
+            } => {
+                let items = |n: usize| if n == 1 { "item" } else { "items" };
+                match (*at_least, *at_most) {
+                    (0, Some(at_most)) => write!(f, "at most {at_most} {}", items(at_most)),
+                    (at_least, Some(at_most)) if at_least == at_most => {
+                        write!(f, "exactly {at_least} {}", items(at_least))
+                    }
+                    (at_least, Some(at_most)) => {
+                        write!(f, "between {at_least} and {at_most} items")
+                    }
+                    (at_least, None) => write!(f, "at least {at_least} {}", items(at_least)),
+                }
+            }
         }
     }
 }
@@ -398,16 +876,16 @@ where
     T: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.write(f, |t, f| write!(f, "{t:?}"))
+        self.write(f, debug_token)
     }
 }
 
 impl<T> fmt::Display for RichPattern<'_, T>
 where
-    T: fmt::Display,
+    T: TokenDisplay,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.write(f, |t, f| write!(f, "'{t}'"))
+        self.write(f, T::fmt_token)
     }
 }
 
@@ -481,37 +959,29 @@ impl<'a, T> RichReason<'a, T> {
         f: &mut fmt::Formatter<'_>,
         mut fmt_token: impl FnMut(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
         mut fmt_span: impl FnMut(&S, &mut fmt::Formatter<'_>) -> fmt::Result,
-        span: Option<&S>,
+        span: &S,
         context: &[(RichPattern<'a, T>, S)],
     ) -> fmt::Result {
         match self {
+            // Count violations describe the whole list rather than a single token
+            RichReason::ExpectedFound { expected, .. }
+                if matches!(&expected[..], [RichPattern::Count { .. }]) =>
+            {
+                write_count(f, &expected[0], |p, f| p.write(f, &mut fmt_token))?;
+                write!(f, " at ")?;
+                fmt_span(span, f)?;
+            }
             RichReason::ExpectedFound { expected, found } => {
                 write!(f, "found ")?;
                 write_token(f, &mut fmt_token, found.as_deref())?;
-                if let Some(span) = span {
-                    write!(f, " at ")?;
-                    fmt_span(span, f)?;
-                }
+                write!(f, " at ")?;
+                fmt_span(span, f)?;
                 write!(f, " expected ")?;
-                match &expected[..] {
-                    [] => write!(f, "something else")?,
-                    [expected] => expected.write(f, &mut fmt_token)?,
-                    _ => {
-                        for expected in &expected[..expected.len() - 1] {
-                            expected.write(f, &mut fmt_token)?;
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "or ")?;
-                        expected.last().unwrap().write(f, &mut fmt_token)?;
-                    }
-                }
+                write_expected(f, expected, |p, f| p.write(f, &mut fmt_token))?;
             }
             RichReason::Custom(msg) => {
-                write!(f, "{msg}")?;
-                if let Some(span) = span {
-                    write!(f, " at ")?;
-                    fmt_span(span, f)?;
-                }
+                write!(f, "{msg} at ")?;
+                fmt_span(span, f)?;
             }
         }
         for (l, s) in context {
@@ -564,10 +1034,10 @@ where
 
 impl<T> fmt::Display for RichReason<'_, T>
 where
-    T: fmt::Display,
+    T: TokenDisplay,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner_fmt(f, T::fmt, |_: &(), _| Ok(()), None, &[])
+        DefaultMessages.reason(f, self)
     }
 }
 
@@ -597,24 +1067,8 @@ pub struct Rich<'a, T, S = SimpleSpan<usize>> {
     span: S,
     reason: Box<RichReason<'a, T>>,
     context: Vec<(RichPattern<'a, T>, S)>,
-}
-
-impl<T, S> Rich<'_, T, S> {
-    fn inner_fmt(
-        &self,
-        f: &mut fmt::Formatter<'_>,
-        fmt_token: impl FnMut(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
-        fmt_span: impl FnMut(&S, &mut fmt::Formatter<'_>) -> fmt::Result,
-        with_spans: bool,
-    ) -> fmt::Result {
-        self.reason.inner_fmt(
-            f,
-            fmt_token,
-            fmt_span,
-            if with_spans { Some(&self.span) } else { None },
-            &self.context,
-        )
-    }
+    snippet: Option<Box<ErrorSnippet>>,
+    code: Option<&'static str>,
 }
 
 impl<'a, T, S> Rich<'a, T, S> {
@@ -625,6 +1079,8 @@ impl<'a, T, S> Rich<'a, T, S> {
             span,
             reason: Box::new(RichReason::Custom(msg.to_string())),
             context: Vec::new(),
+            snippet: None,
+            code: None,
         }
     }
 
@@ -674,6 +1130,90 @@ impl<'a, T, S> Rich<'a, T, S> {
         }
     }
 
+    /// Get the code given to this error by [`Parser::with_code`], if any.
+    pub fn code(&self) -> Option<&'static str> {
+        self.code
+    }
+
+    /// Get the code of this error, deriving one from its labels if it was not given one by [`Parser::with_code`].
+    ///
+    /// The labels are tried in turn, starting with the expected patterns and then the contexts from least general to
+    /// most, until `f` gives a code for one of them. This allows projects to assign codes to their existing labels in
+    /// one place rather than throughout their grammar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::RichPattern};
+    /// let parser = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .labelled("number")
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('['), just(']').with_code("E0002"))
+    ///     .labelled("list")
+    ///     .as_context();
+    ///
+    /// let code_of = |label: &RichPattern<char>| match label.to_string().as_str() {
+    ///     "number" => Some("E0001"),
+    ///     "list" => Some("E0003"),
+    ///     _ => None,
+    /// };
+    /// let code = |src| parser.parse(src).into_errors()[0].code_by_label(code_of);
+    ///
+    /// assert_eq!(code("[1,x]"), Some("E0001"));
+    /// assert_eq!(code("[1,2"), Some("E0002"));
+    /// assert_eq!(code("1,2]"), Some("E0003"));
+    /// ```
+    pub fn code_by_label(
+        &self,
+        mut f: impl FnMut(&RichPattern<'a, T>) -> Option<&'static str>,
+    ) -> Option<&'static str> {
+        self.code.or_else(|| {
+            self.expected()
+                .chain(self.context.iter().map(|(l, _)| l))
+                .filter(|l| matches!(l, RichPattern::Label(_)))
+                .find_map(&mut f)
+        })
+    }
+
+    /// Display this error using the given [`RichMessages`], such as to show the message in another language.
+    ///
+    /// See [`RichMessages`] for an example.
+    pub fn display_with<'b, M>(&'b self, messages: &'b M) -> DisplayWith<'b, 'a, T, S, M>
+    where
+        M: RichMessages<T> + ?Sized,
+    {
+        DisplayWith {
+            err: self,
+            messages,
+        }
+    }
+
+    /// Display this error in the given [`DisplayMode`].
+    ///
+    /// [`DisplayMode::Snapshot`] gives a compact form that does not depend on the order in which alternatives were
+    /// tried, making it suitable for snapshot testing with tools such as `insta`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::DisplayMode};
+    /// let list = just::<_, _, extra::Err<Rich<char>>>('[')
+    ///     .ignore_then(text::int(10).separated_by(just(',')).collect::<Vec<_>>())
+    ///     .then_ignore(just(']'))
+    ///     .labelled("list")
+    ///     .as_context();
+    ///
+    /// let errs = list.parse("[1;2]").into_errors();
+    /// assert_eq!(
+    ///     errs[0].display_mode(DisplayMode::Snapshot).to_string(),
+    ///     "2..3: expected ',' | ']' | digit, found ';' (in list at 0..2)",
+    /// );
+    /// ```
+    pub fn display_mode(&self, mode: DisplayMode) -> DisplayIn<'_, 'a, T, S> {
+        DisplayIn { err: self, mode }
+    }
+
     /// Get an iterator over the expected items associated with this error
     pub fn expected(&self) -> impl ExactSizeIterator<Item = &RichPattern<'a, T>> {
         match &*self.reason {
@@ -698,6 +1238,22 @@ impl<'a, T, S> Rich<'a, T, S> {
                 .into_iter()
                 .map(|(p, s)| (p.map_token(&mut f), s))
                 .collect(),
+            snippet: self.snippet,
+            code: self.code,
+        }
+    }
+
+    /// Transform this error's spans (including the spans of its contexts) using the given function.
+    ///
+    /// This is useful when moving errors between inputs, such as when parsing an embedded language (see
+    /// [`Parser::embedded_in`]).
+    pub fn map_span<S2, F: FnMut(S) -> S2>(self, mut f: F) -> Rich<'a, T, S2> {
+        Rich {
+            span: f(self.span),
+            reason: self.reason,
+            context: self.context.into_iter().map(|(p, s)| (p, f(s))).collect(),
+            snippet: self.snippet,
+            code: self.code,
         }
     }
 }
@@ -713,10 +1269,54 @@ where
             span: self.span,
             reason: Box::new(new_reason),
             context: self.context, // TOOD: Merge contexts
+            snippet: self.snippet,
+            code: self.code.or(other.code),
         }
     }
 }
 
+/// Rich errors are identified by their span and code (see [`Rich::code`]), and by their message if they are
+/// [custom](RichReason::Custom) errors without a code. The expected patterns of duplicates are merged.
+impl<T: PartialEq, S: Clone + Eq + Hash> ErrorIdentity for Rich<'_, T, S> {
+    type Id = (S, Option<&'static str>, Option<String>);
+
+    fn identity(&self) -> Self::Id {
+        // Distinct messages at the same location are different errors, unless a code says otherwise
+        let msg = match &*self.reason {
+            RichReason::Custom(msg) if self.code.is_none() => Some(msg.clone()),
+            _ => None,
+        };
+        (self.span.clone(), self.code, msg)
+    }
+
+    fn merge_duplicate(&mut self, other: Self) {
+        let reason = core::mem::replace(&mut *self.reason, RichReason::Custom(String::new()));
+        *self.reason = reason.flat_merge(*other.reason);
+    }
+}
+
+impl<T, S> CodeError for Rich<'_, T, S> {
+    #[inline]
+    fn with_code(&mut self, code: &'static str) {
+        self.code.get_or_insert(code);
+    }
+}
+
+impl<'a, I: Input<'a>> LiteralError<'a, I> for Rich<'a, I::Token, I::Span>
+where
+    I::Token: PartialEq,
+{
+    #[inline]
+    fn out_of_range(
+        literal: &'static str,
+        ty: &'static str,
+        _: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self {
+        Rich::custom(span, format!("{literal} literal out of range for {ty}"))
+    }
+}
+
 impl<'a, I: Input<'a>, L> LabelError<'a, I, L> for Rich<'a, I::Token, I::Span>
 where
     I::Token: PartialEq,
@@ -735,6 +1335,8 @@ where
                 found,
             }),
             context: Vec::new(),
+            snippet: None,
+            code: None,
         }
     }
 
@@ -795,10 +1397,10 @@ where
                 expected.push(label.into());
             }
             _ => {
-                self.reason = Box::new(RichReason::ExpectedFound {
+                *self.reason = RichReason::ExpectedFound {
                     expected: vec![label.into()],
                     found: self.reason.take_found(),
-                });
+                };
             }
         }
     }
@@ -818,31 +1420,472 @@ where
     S: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.inner_fmt(f, T::fmt, S::fmt, true)
+        self.reason
+            .inner_fmt(f, debug_token, S::fmt, &self.span, &self.context)
     }
 }
 
 impl<T, S> fmt::Display for Rich<'_, T, S>
 where
-    T: fmt::Display,
+    T: TokenDisplay,
+    S: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(&DefaultMessages).fmt(f)
+    }
+}
+
+impl<'a, T, S> Rich<'a, T, S> {
+    /// Render this error as plain text, including the relevant lines of the source code with the span of the error
+    /// underlined.
+    ///
+    /// This is a lightweight alternative to a full diagnostic rendering crate (such as `ariadne`) for tools that want
+    /// readable errors without extra dependencies. The output is wrapped to fit within a terminal width, which may be
+    /// configured with [`WithSource::width`] (80 columns by default).
+    ///
+    /// The source must be the `&str` that was parsed, and the error's span must be a byte range within it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let parser = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let src = "1,\n2,\nx";
+    /// let err = &parser.parse(src).into_errors()[0];
+    ///
+    /// assert_eq!(
+    ///     err.display_with_source(src).to_string(),
+    ///     "\
+    /// error: found 'x' expected non-zero digit, or '0'
+    ///  --> 3:1
+    ///   |
+    /// 2 | 2,
+    /// 3 | x
+    ///   | ^
+    /// ",
+    /// );
+    /// ```
+    pub fn display_with_source<'b>(&'b self, src: &'b str) -> WithSource<'b, 'a, T, S> {
+        WithSource {
+            err: self,
+            index: MaybeRef::Val(LineIndex::new(src)),
+            width: 80,
+            context_lines: 1,
+        }
+    }
+
+    /// Like [`Rich::display_with_source`], but using an existing [`LineIndex`] of the source.
+    ///
+    /// This avoids indexing the source again for each error that is rendered.
+    pub fn display_with_index<'b>(&'b self, index: &'b LineIndex<'b>) -> WithSource<'b, 'a, T, S> {
+        WithSource {
+            err: self,
+            index: MaybeRef::Ref(index),
+            width: 80,
+            context_lines: 1,
+        }
+    }
+
+    /// Attach a snippet of the source around this error, so that it can still be understood when reported somewhere
+    /// that the source is not available (such as telemetry or CI logs).
+    ///
+    /// The snippet covers the part of the error's span on the line where it starts, along with up to `radius`
+    /// characters either side of it on that line. Attaching a snippet again replaces the previous one.
+    ///
+    /// The source must be the `&str` that was parsed, and the error's span must be a byte range within it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let parser = text::ascii::ident::<_, extra::Err<Rich<char>>>()
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let src = "alpha, beta,\ngamma, 42, delta";
+    /// let err = parser
+    ///     .parse(src)
+    ///     .into_errors()
+    ///     .remove(0)
+    ///     .with_snippet(src, 4);
+    ///
+    /// let snippet = err.snippet().unwrap();
+    /// assert_eq!(snippet.text, "ma, 42, d");
+    /// assert_eq!((snippet.line, snippet.column), (1, 7));
+    /// assert_eq!(
+    ///     snippet.to_string(),
+    ///     "\
+    /// 2 | ...ma, 42, d...
+    ///   |        ^
+    /// ",
+    /// );
+    /// ```
+    pub fn with_snippet(self, src: &str, radius: usize) -> Self
+    where
+        S: Span<Offset = usize>,
+    {
+        Self {
+            snippet: Some(Box::new(ErrorSnippet::new(
+                src,
+                self.span.start()..self.span.end(),
+                radius,
+            ))),
+            ..self
+        }
+    }
+
+    /// Get the snippet of the source attached to this error by [`Rich::with_snippet`], if any.
+    pub fn snippet(&self) -> Option<&ErrorSnippet> {
+        self.snippet.as_deref()
+    }
+}
+
+/// A snippet of the source around an error, attached by [`Rich::with_snippet`].
+///
+/// Unlike [`span::Snippet`], this owns its text, so it can outlive the source (and be serialized, with the `serde`
+/// feature). Displaying it shows the text with the error's span underlined.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ErrorSnippet {
+    /// The text of the snippet.
+    pub text: String,
+    /// The byte offset in the source at which the snippet starts.
+    pub offset: usize,
+    /// The (zero-indexed) line on which the error starts.
+    pub line: usize,
+    /// The (zero-indexed) column, in characters, at which the error starts.
+    pub column: usize,
+    /// The range of bytes within the text covered by the error's span.
+    pub span: Range<usize>,
+    /// Whether the line continues before the start of the snippet.
+    pub truncated_start: bool,
+    /// Whether the line continues after the end of the snippet.
+    pub truncated_end: bool,
+}
+
+impl ErrorSnippet {
+    fn new(src: &str, range: Range<usize>, radius: usize) -> Self {
+        let start = span::floor_char_boundary(src, range.start);
+        let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+        let mut line_end = src[start..].find('\n').map_or(src.len(), |i| start + i);
+        if src[start..line_end].ends_with('\r') {
+            line_end -= 1;
+        }
+        let end = span::floor_char_boundary(src, range.end).clamp(start, line_end);
+
+        let lo = src[line_start..start]
+            .char_indices()
+            .rev()
+            .take(radius)
+            .last()
+            .map_or(start, |(i, _)| line_start + i);
+        let hi = src[end..line_end]
+            .char_indices()
+            .nth(radius)
+            .map_or(line_end, |(i, _)| end + i);
+
+        Self {
+            text: src[lo..hi].to_string(),
+            offset: lo,
+            line: src[..line_start].matches('\n').count(),
+            column: src[line_start..start].chars().count(),
+            span: start - lo..end - lo,
+            truncated_start: lo > line_start,
+            truncated_end: hi < line_end,
+        }
+    }
+}
+
+impl fmt::Display for ErrorSnippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = (self.line + 1).to_string().len();
+        let dots = if self.truncated_start { "..." } else { "" };
+        write!(f, "{} | {dots}", self.line + 1)?;
+        self.text
+            .chars()
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .try_for_each(|c| write!(f, "{c}"))?;
+        writeln!(f, "{}", if self.truncated_end { "..." } else { "" })?;
+        let pad = dots.len() + self.text[..self.span.start].chars().count();
+        let carets = self.text[self.span.clone()].chars().count().max(1);
+        writeln!(f, "{:gutter$} | {:pad$}{:^<carets$}", "", "", "")
+    }
+}
+
+/// An error displayed using custom [`RichMessages`]. See [`Rich::display_with`].
+pub struct DisplayWith<'b, 'a, T, S, M: ?Sized> {
+    err: &'b Rich<'a, T, S>,
+    messages: &'b M,
+}
+
+impl<T, S, M> fmt::Display for DisplayWith<'_, '_, T, S, M>
+where
     S: fmt::Display,
+    M: RichMessages<T> + ?Sized,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner_fmt(f, T::fmt, S::fmt, false)
+        self.messages.reason(f, &self.err.reason)?;
+        for (label, span) in &self.err.context {
+            self.messages.context(f, label, span)?;
+        }
+        Ok(())
     }
 }
 
+/// How a [`Rich`] error should be formatted. See [`Rich::display_mode`].
+///
+/// Only [`Rich`] errors have display modes: [`Simple`] and [`Cheap`] errors don't record the patterns that were
+/// expected, so their usual formatting is already independent of the order in which alternatives were tried. Parser
+/// outputs are formatted by their own [`fmt::Debug`] implementations.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DisplayMode {
+    /// The human-readable message, as produced by the error's [`fmt::Display`] implementation.
+    #[default]
+    Message,
+    /// A compact, deterministic form intended for snapshot tests.
+    ///
+    /// The output is a single line of the form `<span>: [<code>] expected <a> | <b>, found <token> (in <label> at
+    /// <span>, ...)`. Expected patterns are sorted and deduplicated by their displayed form, spans are always written
+    /// as `start..end`, and contexts are listed from innermost to outermost. This format is considered stable: it will
+    /// only change in a major release.
+    Snapshot,
+}
+
+/// An error displayed in a particular [`DisplayMode`]. See [`Rich::display_mode`].
+pub struct DisplayIn<'b, 'a, T, S> {
+    err: &'b Rich<'a, T, S>,
+    mode: DisplayMode,
+}
+
+impl<T, S> DisplayIn<'_, '_, T, S>
+where
+    T: TokenDisplay,
+    S: Span,
+    S::Offset: fmt::Display,
+{
+    fn fmt_snapshot(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let err = self.err;
+        write!(f, "{}..{}: ", err.span.start(), err.span.end())?;
+        if let Some(code) = err.code {
+            write!(f, "[{code}] ")?;
+        }
+        match &*err.reason {
+            RichReason::ExpectedFound { expected, found } => {
+                let mut expected = expected.iter().map(ToString::to_string).collect::<Vec<_>>();
+                expected.sort_unstable();
+                expected.dedup();
+                write!(f, "expected ")?;
+                if expected.is_empty() {
+                    write!(f, "something else")?;
+                }
+                for (i, pattern) in expected.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{pattern}")?;
+                }
+                write!(f, ", found ")?;
+                write_token(f, T::fmt_token, found.as_deref())?;
+            }
+            RichReason::Custom(msg) => write!(f, "{msg}")?,
+        }
+        for (i, (label, span)) in err.context.iter().enumerate() {
+            let sep = if i == 0 { " (in" } else { "," };
+            write!(f, "{sep} {label} at {}..{}", span.start(), span.end())?;
+        }
+        if !err.context.is_empty() {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, S> fmt::Display for DisplayIn<'_, '_, T, S>
+where
+    T: TokenDisplay,
+    S: Span + fmt::Display,
+    S::Offset: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            DisplayMode::Message => self.err.display_with(&DefaultMessages).fmt(f),
+            DisplayMode::Snapshot => self.fmt_snapshot(f),
+        }
+    }
+}
+
+/// An error rendered alongside the source code that it refers to. See [`Rich::display_with_source`].
+pub struct WithSource<'b, 'a, T, S> {
+    err: &'b Rich<'a, T, S>,
+    index: MaybeRef<'b, LineIndex<'b>>,
+    width: usize,
+    context_lines: usize,
+}
+
+impl<T, S> WithSource<'_, '_, T, S> {
+    /// Set the width, in columns, that the output should be wrapped to fit within.
+    ///
+    /// Lines of source code that are too long to fit are shortened to show only the part around the error.
+    pub fn width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Set the number of lines of source code to show before and after the line containing the error.
+    pub fn context_lines(self, context_lines: usize) -> Self {
+        Self {
+            context_lines,
+            ..self
+        }
+    }
+}
+
+/// Write `text` on lines no wider than `width`, breaking between words where possible. Lines after the first are
+/// preceded by `indent`.
+fn write_wrapped(f: &mut fmt::Formatter, text: &str, width: usize, indent: &str) -> fmt::Result {
+    let mut col = 0;
+    for word in text.split(' ') {
+        let len = word.chars().count();
+        if col > indent.len() && col + 1 + len > width {
+            write!(f, "\n{indent}")?;
+            col = indent.len();
+        } else if col > 0 {
+            write!(f, " ")?;
+            col += 1;
+        }
+        write!(f, "{word}")?;
+        col += len;
+    }
+    writeln!(f)
+}
+
+impl<T, S> fmt::Display for WithSource<'_, '_, T, S>
+where
+    T: TokenDisplay,
+    S: Span<Offset = usize>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const INDENT: &str = "       ";
+        match self.err.code {
+            Some(code) => write!(f, "error[{code}]: ")?,
+            None => write!(f, "error: ")?,
+        }
+        write_wrapped(f, &self.err.reason.to_string(), self.width, INDENT)?;
+
+        let index = &*self.index;
+        let (line, start_col) = index.position(self.err.span.start(), PositionEncoding::Utf32);
+        let first = line.saturating_sub(self.context_lines);
+        let last = (line + self.context_lines).min(index.line_count() - 1);
+        let gutter = (last + 1).to_string().len();
+
+        // If a line is too long to fit, only show a window around the error
+        let avail = self.width.saturating_sub(gutter + 3).max(16);
+        let snippet = |span: Range<usize>| {
+            let snippet = index.snippet(span.clone(), avail);
+            if snippet.truncated_start || snippet.truncated_end {
+                index.snippet(span, avail - 6)
+            } else {
+                snippet
+            }
+        };
+        let error = snippet(self.err.span.start()..self.err.span.end());
+        let skip = index.position(error.offset, PositionEncoding::Utf32).1;
+
+        writeln!(f, "{:gutter$}--> {}:{}", "", line + 1, start_col + 1)?;
+        writeln!(f, "{:gutter$} |", "")?;
+        for i in first..=last {
+            // Show the same columns of other lines as of the line containing the error
+            let snippet = if i == line {
+                error.clone()
+            } else {
+                let offset = index.offset(i, skip, PositionEncoding::Utf32).unwrap_or(0);
+                snippet(offset..offset)
+            };
+            write!(f, "{:>gutter$} |", i + 1)?;
+            if !snippet.text.is_empty() {
+                write!(f, " ")?;
+                if snippet.truncated_start {
+                    write!(f, "...")?;
+                }
+                snippet
+                    .text
+                    .chars()
+                    .map(|c| if c == '\t' { ' ' } else { c })
+                    .try_for_each(|c| write!(f, "{c}"))?;
+                if snippet.truncated_end {
+                    write!(f, "...")?;
+                }
+            }
+            writeln!(f)?;
+            if i == line {
+                let pad = error.carets.start + if error.truncated_start { 3 } else { 0 };
+                let carets = error.carets.len();
+                writeln!(f, "{:gutter$} | {:pad$}{:^<carets$}", "", "", "")?;
+            }
+        }
+
+        for (label, span) in &self.err.context {
+            let (line, col) = index.position(span.start(), PositionEncoding::Utf32);
+            write!(f, "{:gutter$} = ", "")?;
+            write_wrapped(
+                f,
+                &format!("while parsing {label} at {}:{}", line + 1, col + 1),
+                self.width,
+                INDENT,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn write_expected<T>(
+    f: &mut fmt::Formatter,
+    expected: &[RichPattern<'_, T>],
+    mut fmt_pattern: impl FnMut(&RichPattern<'_, T>, &mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    match expected {
+        [] => write!(f, "something else"),
+        [expected] => fmt_pattern(expected, f),
+        [init @ .., last] => {
+            for expected in init {
+                fmt_pattern(expected, f)?;
+                write!(f, ", ")?;
+            }
+            write!(f, "or ")?;
+            fmt_pattern(last, f)
+        }
+    }
+}
+
+fn write_count<T>(
+    f: &mut fmt::Formatter,
+    pattern: &RichPattern<'_, T>,
+    mut fmt_pattern: impl FnMut(&RichPattern<'_, T>, &mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    write!(f, "expected ")?;
+    fmt_pattern(pattern, f)?;
+    if let RichPattern::Count { found, .. } = pattern {
+        write!(f, ", found {found}")?;
+    }
+    Ok(())
+}
+
+fn debug_token<T: fmt::Debug>(tok: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "'{tok:?}'")
+}
+
 fn write_token<T>(
     f: &mut fmt::Formatter,
     mut fmt_token: impl FnMut(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
     tok: Option<&T>,
 ) -> fmt::Result {
     match tok {
-        Some(tok) => {
-            write!(f, "'")?;
-            fmt_token(tok, f)?;
-            write!(f, "'")
-        }
+        Some(tok) => fmt_token(tok, f),
         None => write!(f, "end of input"),
     }
 }
@@ -2,7 +2,9 @@
 //! Useful for custom allocation, error handling, context-specific parsers, and more.
 
 use inspector::Inspector;
-pub use inspector::SimpleState;
+pub use inspector::{
+    HashConsTable, ModeStack, Persistent, Shared, SimpleState, SymbolTable, Transactional,
+};
 
 use super::*;
 
@@ -57,7 +59,11 @@ pub type State<S> = Full<DefaultErr, S, DefaultCtx>;
 pub type Context<C> = Full<DefaultErr, DefaultState, C>;
 
 /// Specify all extra types. See [`ParserExtra`] for more details.
-pub struct Full<E, S, C>(PhantomData<(E, S, C)>);
+///
+/// To specify only some of them, leaving the others as their defaults, see the [`extra!`](crate::extra!) macro.
+///
+/// This type is only a marker, so it is always [`Send`] and [`Sync`], even when the types it specifies are not.
+pub struct Full<E, S, C>(PhantomData<fn() -> (E, S, C)>);
 
 impl<E, S, C> Sealed for Full<E, S, C> {}
 impl<'a, I, E, S, C> ParserExtra<'a, I> for Full<E, S, C>
@@ -71,3 +77,176 @@ where
     type State = S;
     type Context = C;
 }
+
+/// A set of syntactic restrictions, for use as (or as part of) the context of a parser.
+///
+/// Many grammars forbid certain constructs in certain positions, even though they would otherwise be valid there.
+/// Rust, for example, does not allow struct literals in the condition of an `if` (since `if x { ... }` would be
+/// ambiguous), unless they appear inside parentheses. Restrictions are set with [`with_restriction`], cleared with
+/// [`clear_restrictions_inside`], and queried with [`unless_restricted`]. Each restriction is a single bit, and
+/// grammars define the restrictions they need as constants.
+///
+/// If your parser needs other context too, implement [`RestrictionContext`] for your own context type.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::{Restrictions, with_restriction, clear_restrictions_inside, unless_restricted}};
+/// const NO_STRUCT_LITERAL: Restrictions = Restrictions::from_bits(1 << 0);
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Expr<'src> {
+///     Var(&'src str),
+///     Struct(&'src str),
+///     If(Box<Self>, Box<Self>),
+/// }
+///
+/// let expr = recursive(|expr| {
+///     let ident = text::ascii::ident::<_, extra::Context<Restrictions>>().padded();
+///     let block = expr.clone().delimited_by(just('{').padded(), just('}').padded());
+///     let struct_ = ident
+///         .then_ignore(just("{}").padded())
+///         .map(Expr::Struct);
+///     let parens = clear_restrictions_inside(expr.clone().delimited_by(just('('), just(')')));
+///     let if_ = text::ascii::keyword("if")
+///         .padded()
+///         .ignore_then(with_restriction(NO_STRUCT_LITERAL, expr.clone()))
+///         .then(block)
+///         .map(|(cond, body)| Expr::If(Box::new(cond), Box::new(body)));
+///
+///     choice((if_, unless_restricted(NO_STRUCT_LITERAL, struct_), parens, ident.map(Expr::Var)))
+/// });
+///
+/// // `S {}` is a struct literal...
+/// assert_eq!(expr.parse("S {}").into_result(), Ok(Expr::Struct("S")));
+/// // ...but not in the condition of an `if`...
+/// assert_eq!(
+///     expr.parse("if x { y }").into_result(),
+///     Ok(Expr::If(Box::new(Expr::Var("x")), Box::new(Expr::Var("y")))),
+/// );
+/// // ...unless it's in parentheses
+/// assert_eq!(
+///     expr.parse("if (S {}) { y }").into_result(),
+///     Ok(Expr::If(Box::new(Expr::Struct("S")), Box::new(Expr::Var("y")))),
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, core::default::Default)]
+pub struct Restrictions(u32);
+
+impl Restrictions {
+    /// No restrictions.
+    pub const NONE: Self = Self(0);
+
+    /// Create a set of restrictions from its bits.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Get the bits of this set of restrictions.
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if no restrictions are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all of the given restrictions are set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if any of the given restrictions are set.
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Combine two sets of restrictions.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Remove the given restrictions from this set.
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl core::ops::BitOr for Restrictions {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+/// A context type that carries a set of [`Restrictions`].
+pub trait RestrictionContext {
+    /// Get the restrictions that are currently set.
+    fn restrictions(&self) -> Restrictions;
+
+    /// Create a copy of this context with the given restrictions set instead.
+    fn with_restrictions(&self, restrictions: Restrictions) -> Self;
+}
+
+impl RestrictionContext for Restrictions {
+    fn restrictions(&self) -> Restrictions {
+        *self
+    }
+
+    fn with_restrictions(&self, restrictions: Restrictions) -> Self {
+        restrictions
+    }
+}
+
+/// Run a parser with the given restrictions set, in addition to any that are already set.
+///
+/// See [`Restrictions`] for an example.
+pub fn with_restriction<'src, P, O, I, E>(
+    restriction: Restrictions,
+    parser: P,
+) -> UpdateCtx<P, impl Fn(&E::Context) -> E::Context + Copy>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::Context: RestrictionContext,
+    P: Parser<'src, I, O, E>,
+{
+    parser.update_ctx(move |ctx: &E::Context| {
+        ctx.with_restrictions(ctx.restrictions().union(restriction))
+    })
+}
+
+/// Run a parser with all restrictions cleared.
+///
+/// This is usually used for delimited constructs, like parenthesised expressions, inside which restrictions
+/// imposed by the surrounding syntax no longer apply. See [`Restrictions`] for an example.
+pub fn clear_restrictions_inside<'src, P, O, I, E>(
+    parser: P,
+) -> UpdateCtx<P, impl Fn(&E::Context) -> E::Context + Copy>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::Context: RestrictionContext,
+    P: Parser<'src, I, O, E>,
+{
+    parser.update_ctx(|ctx: &E::Context| ctx.with_restrictions(Restrictions::NONE))
+}
+
+/// Only attempt a parser if none of the given restrictions are set, failing otherwise.
+///
+/// Like [`Parser::when_ctx`], the parser fails without consuming input or generating any expected patterns when
+/// it is restricted. See [`Restrictions`] for an example.
+pub fn unless_restricted<'src, P, O, I, E>(
+    restriction: Restrictions,
+    parser: P,
+) -> WhenCtx<P, impl Fn(&E::Context) -> bool + Copy>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::Context: RestrictionContext,
+    P: Parser<'src, I, O, E>,
+{
+    parser.when_ctx(move |ctx: &E::Context| !ctx.restrictions().intersects(restriction))
+}
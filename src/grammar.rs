@@ -0,0 +1,1150 @@
+//! Utilities for writing grammars that can be reused across different kinds of input.
+//!
+//! *“The ships hung in the sky in much the same way that bricks don't.”*
+//!
+//! It is often useful to write a grammar once and run it over several kinds of input: directly over characters for a
+//! quick scannerless prototype or a small embedded format, and over the output of a lexer for the 'real' language. The
+//! structure of such a grammar is the same in both cases, only the *terminals* (punctuation, keywords, identifiers,
+//! etc.) differ.
+//!
+//! This module makes that practical by letting the terminals of a grammar be named abstractly. A grammar that is
+//! generic over its input type refers to terminals by name with [`terminal`], and each token type describes how those
+//! names are spelled by implementing [`Spelling`]. Terminals that produce outputs, like identifiers, can be abstracted
+//! in the same way with a trait of your own, implemented for each input type (see the example below).
+//!
+//! Larger grammars can be split into reusable [`Module`]s: structs of boxed parsers, built from the parsers that they
+//! need from the rest of the grammar. Modules that depend on each other can be linked with an [`Import`].
+//!
+//! The structure of a grammar can also be derived from its parsers as a [`Grammar`], for generating documentation.
+//!
+//! # Examples
+//!
+//! ```
+//! use chumsky::{prelude::*, grammar::{terminal, Spelling}, input::ValueInput};
+//!
+//! // The abstract terminals of our grammar
+//! #[derive(Clone, Copy)]
+//! enum Punct { LParen, RParen, Comma }
+//!
+//! // Terminals spelled as text...
+//! impl<'src> Spelling<'src, Punct> for char {
+//!     type Seq = &'src str;
+//!     fn spell(punct: Punct) -> Self::Seq {
+//!         match punct { Punct::LParen => "(", Punct::RParen => ")", Punct::Comma => "," }
+//!     }
+//! }
+//!
+//! // ...and as tokens produced by a lexer
+//! #[derive(Clone, Debug, PartialEq)]
+//! enum Token<'src> { LParen, RParen, Comma, Ident(&'src str) }
+//!
+//! impl<'src> Spelling<'src, Punct> for Token<'src> {
+//!     type Seq = Self;
+//!     fn spell(punct: Punct) -> Self::Seq {
+//!         match punct { Punct::LParen => Token::LParen, Punct::RParen => Token::RParen, Punct::Comma => Token::Comma }
+//!     }
+//! }
+//!
+//! // Terminals with outputs are abstracted by a trait implemented for each input type
+//! trait Idents<'src>: ValueInput<'src> + Sized {
+//!     fn ident() -> Boxed<'src, 'src, Self, &'src str>;
+//! }
+//!
+//! impl<'src> Idents<'src> for &'src str {
+//!     fn ident() -> Boxed<'src, 'src, Self, &'src str> {
+//!         text::ascii::ident().padded().boxed()
+//!     }
+//! }
+//!
+//! impl<'src> Idents<'src> for &'src [Token<'src>] {
+//!     fn ident() -> Boxed<'src, 'src, Self, &'src str> {
+//!         select! { Token::Ident(name) => name }.boxed()
+//!     }
+//! }
+//!
+//! // The grammar is written once...
+//! fn call<'src, I>() -> impl Parser<'src, I, (&'src str, Vec<&'src str>)>
+//! where
+//!     I: Idents<'src>,
+//!     I::Token: Spelling<'src, Punct> + PartialEq,
+//! {
+//!     I::ident().then(
+//!         I::ident()
+//!             .separated_by(terminal(Punct::Comma))
+//!             .collect()
+//!             .delimited_by(terminal(Punct::LParen), terminal(Punct::RParen)),
+//!     )
+//! }
+//!
+//! // ...and used for both kinds of input
+//! assert_eq!(call().parse("f(a, b)").into_result(), Ok(("f", vec!["a", "b"])));
+//!
+//! use Token::*;
+//! let tokens = [Ident("f"), LParen, Ident("a"), Comma, Ident("b"), RParen];
+//! assert_eq!(call().parse(&tokens[..]).into_result(), Ok(("f", vec!["a", "b"])));
+//! ```
+
+use super::*;
+use alloc::{collections::BTreeSet, format, string::ToString};
+use introspect::{Node, NodeKind};
+
+/// Describes how a token type spells the abstract terminals named by `K`.
+///
+/// For text, terminals are usually spelled as strings (`"("`), while for the output of a lexer they are usually
+/// spelled as a single token (`Token::LParen`). See the [module-level documentation](self) for an example.
+pub trait Spelling<'src, K>: Sized {
+    /// The sequence of tokens that spells a terminal.
+    type Seq: OrderedSeq<'src, Self> + Clone;
+
+    /// Get the sequence of tokens that spells the given terminal.
+    fn spell(kind: K) -> Self::Seq;
+}
+
+/// A parser that accepts the given abstract terminal, as spelled by the input's token type (see [`Spelling`]).
+///
+/// This is equivalent to [`just`] with the spelling of the terminal, except that the output is `()`: the spelling of
+/// a terminal is not generally useful in a grammar that is generic over its input.
+///
+/// The output type of this parser is `()`.
+pub fn terminal<'src, K, I, E>(kind: K) -> impl Parser<'src, I, (), E> + Clone
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: Spelling<'src, K> + PartialEq,
+{
+    just(<I::Token as Spelling<'src, K>>::spell(kind)).ignored()
+}
+
+/// A reusable part of a grammar, packaged as a struct of parsers.
+///
+/// A module exports its rules as the fields of the struct, usually [`Boxed`] parsers, and is built from the parsers it
+/// needs from the grammar that uses it (its [imports](Module::Imports)), such as how to parse a type or an
+/// identifier. Because both are boxed, neither the module nor the grammar using it needs to name the types of the
+/// other's parsers, and the module can be shared between grammars (or crates) that spell those rules differently.
+///
+/// Modules that depend on each other are linked by building one of them with an [`Import`], defined once the other
+/// has been built.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, grammar::{Import, Module}};
+/// #[derive(Debug, PartialEq)]
+/// enum Type {
+///     Named(String),
+///     Array(Box<Type>, Box<Expr>),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Num(u64),
+///     Cast(Box<Expr>, Type),
+/// }
+///
+/// // An expression grammar, which needs to know how to parse a type
+/// struct Exprs<'src> {
+///     expr: Boxed<'src, 'src, &'src str, Expr>,
+/// }
+///
+/// impl<'src> Module for Exprs<'src> {
+///     type Imports = Boxed<'src, 'src, &'src str, Type>;
+///
+///     fn build(ty: Self::Imports) -> Self {
+///         let num = text::int(10).from_str().unwrapped().map(Expr::Num).padded();
+///         let expr = num.foldl(
+///             text::ascii::keyword("as").padded().ignore_then(ty).repeated(),
+///             |expr, ty| Expr::Cast(Box::new(expr), ty),
+///         );
+///         Self { expr: expr.boxed() }
+///     }
+/// }
+///
+/// // A type grammar, which needs to know how to parse an expression (for the length of arrays)
+/// struct Types<'src> {
+///     ty: Boxed<'src, 'src, &'src str, Type>,
+/// }
+///
+/// impl<'src> Module for Types<'src> {
+///     type Imports = Boxed<'src, 'src, &'src str, Expr>;
+///
+///     fn build(expr: Self::Imports) -> Self {
+///         let ty = recursive(|ty| {
+///             let named = text::ascii::ident().map(|name: &str| Type::Named(name.to_string()));
+///             let array = ty
+///                 .then_ignore(just(';'))
+///                 .then(expr)
+///                 .delimited_by(just('['), just(']'))
+///                 .map(|(ty, len)| Type::Array(Box::new(ty), Box::new(len)));
+///             named.or(array).padded()
+///         });
+///         Self { ty: ty.boxed() }
+///     }
+/// }
+///
+/// // The modules depend on each other, so one of them is built with an import that is defined later
+/// let ty = Import::new();
+/// let exprs = Exprs::build(ty.parser());
+/// let types = Types::build(exprs.expr.clone());
+/// ty.define(types.ty);
+///
+/// assert_eq!(
+///     exprs.expr.parse("4 as [u8; 2 as usize]").into_result(),
+///     Ok(Expr::Cast(
+///         Box::new(Expr::Num(4)),
+///         Type::Array(
+///             Box::new(Type::Named("u8".to_string())),
+///             Box::new(Expr::Cast(Box::new(Expr::Num(2)), Type::Named("usize".to_string()))),
+///         ),
+///     )),
+/// );
+/// ```
+pub trait Module: Sized {
+    /// The parsers that the module needs from the grammar that uses it.
+    ///
+    /// This is usually a single parser, a tuple of parsers, or a struct with a field for each.
+    type Imports;
+
+    /// Build the module's rules from its imports.
+    fn build(imports: Self::Imports) -> Self;
+}
+
+/// A parser that is used to build a [`Module`] before it has been defined, for linking modules that depend on each
+/// other.
+///
+/// This is a thin wrapper around [`Recursive::declare`], except that an import can only be defined once (since
+/// [`Import::define`] takes it by value). Parsers obtained from an import must not be used to parse input before it
+/// has been defined.
+pub struct Import<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Default> {
+    parser: Recursive<recursive::Indirect<'src, 'src, I, O, E>>,
+}
+
+impl<'src, I, O, E> Import<'src, I, O, E>
+where
+    I: Input<'src> + 'src,
+    O: 'src,
+    E: ParserExtra<'src, I> + 'src,
+{
+    /// Create an import that has not yet been defined.
+    pub fn new() -> Self {
+        Self {
+            parser: Recursive::declare(),
+        }
+    }
+
+    /// Get a parser that refers to the import, for building a module.
+    pub fn parser(&self) -> Boxed<'src, 'src, I, O, E> {
+        self.parser.clone().boxed()
+    }
+
+    /// Define the import, allowing parsers that refer to it to be used for parsing.
+    pub fn define<P: Parser<'src, I, O, E> + Clone + 'src>(mut self, parser: P) {
+        self.parser.define(parser);
+    }
+}
+
+impl<'src, I, O, E> Default for Import<'src, I, O, E>
+where
+    I: Input<'src> + 'src,
+    O: 'src,
+    E: ParserExtra<'src, I> + 'src,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Name a parser, making it a rule of the grammar derived from the parser that contains it (see
+/// [`Grammar::from_parser`]).
+///
+/// Naming a parser has no effect on what it parses.
+///
+/// The output type of this parser is the output type of the inner parser.
+pub fn named<A>(name: &'static str, parser: A) -> Named<A> {
+    Named { parser, name }
+}
+
+/// See [`named`].
+#[derive(Copy, Clone)]
+pub struct Named<A> {
+    parser: A,
+    name: &'static str,
+}
+
+impl<'src, I, O, E, A> Parser<'src, I, O, E> for Named<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        self.parser.go::<M>(inp)
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Named",
+            NodeKind::Rule(self.name.to_string()),
+            vec![self.parser.introspect(cx)],
+        )
+    }
+
+    go_extra!(O);
+}
+
+/// A description of the structure of a grammar, made of named [`Rule`]s.
+///
+/// Descriptions are usually derived from the parsers that implement a grammar (see [`Grammar::from_parser`]), so that
+/// they can't disagree, but they can also be written by hand. They are useful for generating documentation, such as
+/// railroad diagrams (see [`Grammar::to_svg`]).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, grammar::{named, Expr, Grammar}};
+/// let list = recursive(|list| {
+///     let number = named("number", text::int::<&str, extra::Default>(10));
+///     let item = named("item", number.ignored().or(list));
+///     item.separated_by(just(',')).delimited_by(just('['), just(']'))
+/// });
+///
+/// let grammar = Grammar::from_parser("list", &list);
+/// assert_eq!(grammar.rules[0].expr.to_string(), r#""[" (item % ",")? "]""#);
+/// assert_eq!(grammar.rules[1].expr.to_string(), "number | list");
+/// assert!(grammar.get("number").is_none());
+///
+/// let svg = grammar.to_svg();
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains(">list<"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grammar {
+    /// The rules of the grammar, in the order they were added.
+    pub rules: Vec<Rule>,
+}
+
+/// A named rule within a [`Grammar`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    /// The name of the rule.
+    pub name: String,
+    /// The body of the rule.
+    pub expr: Expr,
+}
+
+/// An expression describing part of a [`Grammar`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Expr {
+    /// Nothing at all.
+    Empty,
+    /// A terminal, spelled as it should be displayed.
+    Terminal(String),
+    /// A reference to another rule by name.
+    Rule(String),
+    /// Each of the expressions, one after another (see [`Parser::then`]).
+    Seq(Vec<Expr>),
+    /// Any one of the expressions (see [`choice`]).
+    Choice(Vec<Expr>),
+    /// The expression, or nothing (see [`Parser::or_not`]).
+    Optional(Box<Expr>),
+    /// One or more of the first expression, optionally separated by the second (see [`Parser::repeated`] and
+    /// [`Parser::separated_by`]).
+    Repeated(Box<Expr>, Option<Box<Expr>>),
+}
+
+impl Grammar {
+    /// Create a grammar with no rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derive a grammar from the structure of a parser (see [`introspect`]).
+    ///
+    /// The parser becomes the first rule of the grammar, with the given name. Parsers within it that were
+    /// [`named`] become rules of their own, as do recursive parsers: these take the name of the named parser directly
+    /// within or around them, or a generated name otherwise. Terminals are spelled with the [`Display`](fmt::Display)
+    /// implementation of the input's tokens.
+    ///
+    /// Named parsers whose structure is not known completely, such as identifiers built from [`any`] or tokens
+    /// selected with [`select!`], are left undefined: they only appear as references from other rules. Elsewhere,
+    /// parsers whose structure is not known (see [`NodeKind::Opaque`]) appear as references to undefined rules named
+    /// after their type, and [`any`] appears as a reference to `any`.
+    pub fn from_parser<'src, P, I, O, E>(name: &str, parser: &P) -> Self
+    where
+        P: Parser<'src, I, O, E> + ?Sized,
+        I: Input<'src>,
+        I::Token: fmt::Display,
+        E: ParserExtra<'src, I>,
+    {
+        let node = introspect::describe(parser, &|token: &I::Token| token.to_string());
+        let mut builder = Builder::default();
+        builder.count_references(&node);
+        builder.rule(name, &node);
+        Self {
+            rules: builder.rules,
+        }
+    }
+
+    /// Add a rule to the grammar.
+    pub fn rule(mut self, name: impl Into<String>, expr: Expr) -> Self {
+        self.rules.push(Rule {
+            name: name.into(),
+            expr,
+        });
+        self
+    }
+
+    /// Find a rule by name.
+    pub fn get(&self, name: &str) -> Option<&Rule> {
+        self.rules.iter().find(|rule| rule.name == name)
+    }
+
+    /// Render the grammar as an SVG document containing a railroad diagram for each rule, one below the other.
+    ///
+    /// Terminals are drawn in rounded boxes and references to other rules in square boxes. The document has no
+    /// external dependencies and carries its own styles, so it can be embedded directly in HTML documentation.
+    pub fn to_svg(&self) -> String {
+        const MARGIN: i32 = 20;
+        const TITLE: i32 = 24;
+
+        let mut body = String::new();
+        let mut y = MARGIN;
+        let mut width = 0;
+        for rule in &self.rules {
+            let (w, up, down) = railroad::measure(&rule.expr);
+            // The start and end of a diagram are marked with a short bar, with a connecting line either side
+            let line_y = y + TITLE + up.max(railroad::BAR);
+            railroad::text(&mut body, "title", MARGIN, y + 16, &rule.name);
+            railroad::path(
+                &mut body,
+                &format!(
+                    "M{} {}v{}m0 {}h{}",
+                    MARGIN,
+                    line_y - railroad::BAR,
+                    2 * railroad::BAR,
+                    -railroad::BAR,
+                    railroad::GAP,
+                ),
+            );
+            let end = MARGIN + railroad::GAP + w;
+            railroad::draw(&mut body, &rule.expr, MARGIN + railroad::GAP, line_y);
+            railroad::path(
+                &mut body,
+                &format!(
+                    "M{} {}h{}m0 {}v{}",
+                    end,
+                    line_y,
+                    railroad::GAP,
+                    -railroad::BAR,
+                    2 * railroad::BAR,
+                ),
+            );
+            width = width.max(end + railroad::GAP + MARGIN);
+            y = line_y + down.max(railroad::BAR) + MARGIN;
+        }
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{y}\" viewBox=\"0 0 {width} {y}\">\
+             <style>path,rect{{fill:none;stroke:#333;stroke-width:1.5}}\
+             text{{font:14px monospace;text-anchor:middle}}text.title{{font-weight:bold;text-anchor:start}}</style>\
+             {body}</svg>"
+        )
+    }
+}
+
+impl Expr {
+    /// A terminal, spelled as it should be displayed.
+    pub fn terminal(spelling: impl Into<String>) -> Self {
+        Self::Terminal(spelling.into())
+    }
+
+    /// A reference to another rule by name.
+    pub fn rule(name: impl Into<String>) -> Self {
+        Self::Rule(name.into())
+    }
+
+    /// Each of the expressions, one after another.
+    pub fn seq(exprs: impl IntoIterator<Item = Expr>) -> Self {
+        Self::Seq(exprs.into_iter().collect())
+    }
+
+    /// Any one of the expressions.
+    pub fn choice(exprs: impl IntoIterator<Item = Expr>) -> Self {
+        Self::Choice(exprs.into_iter().collect())
+    }
+
+    /// This expression, or nothing.
+    pub fn or_not(self) -> Self {
+        Self::Optional(Box::new(self))
+    }
+
+    /// One or more of this expression.
+    pub fn repeated(self) -> Self {
+        Self::Repeated(Box::new(self), None)
+    }
+
+    /// One or more of this expression, separated by another.
+    pub fn separated_by(self, separator: Expr) -> Self {
+        Self::Repeated(Box::new(self), Some(Box::new(separator)))
+    }
+}
+
+impl Grammar {
+    /// The spellings of all terminals that appear in the grammar, in sorted order.
+    pub fn terminals(&self) -> BTreeSet<&str> {
+        fn visit<'a>(expr: &'a Expr, terminals: &mut BTreeSet<&'a str>) {
+            match expr {
+                Expr::Empty | Expr::Rule(_) => {}
+                Expr::Terminal(spelling) => {
+                    terminals.insert(spelling);
+                }
+                Expr::Seq(exprs) | Expr::Choice(exprs) => {
+                    exprs.iter().for_each(|expr| visit(expr, terminals))
+                }
+                Expr::Optional(expr) => visit(expr, terminals),
+                Expr::Repeated(expr, sep) => {
+                    visit(expr, terminals);
+                    if let Some(sep) = sep {
+                        visit(sep, terminals);
+                    }
+                }
+            }
+        }
+
+        let mut terminals = BTreeSet::new();
+        for rule in &self.rules {
+            visit(&rule.expr, &mut terminals);
+        }
+        terminals
+    }
+
+    /// Compare this grammar against a newer version of it, producing the rules and terminals that were added, removed,
+    /// or changed.
+    ///
+    /// Rules are matched by name, so a renamed rule appears as one removed rule and one added rule. The order of rules
+    /// within a grammar is not significant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::grammar::{Expr, Grammar};
+    /// let old = Grammar::new()
+    ///     .rule("args", Expr::rule("expr").separated_by(Expr::terminal(",")))
+    ///     .rule("expr", Expr::choice([Expr::rule("int"), Expr::rule("ident")]));
+    /// let new = Grammar::new()
+    ///     .rule("args", Expr::rule("expr").separated_by(Expr::terminal(";")))
+    ///     .rule("expr", Expr::choice([Expr::rule("int"), Expr::rule("ident")]))
+    ///     .rule("block", Expr::seq([Expr::terminal("{"), Expr::rule("args"), Expr::terminal("}")]));
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added_rules[0].name, "block");
+    /// assert_eq!(diff.changed_rules[0].name, "args");
+    /// assert_eq!(diff.added_terminals, vec![";", "{", "}"]);
+    /// assert_eq!(diff.removed_terminals, vec![","]);
+    /// assert_eq!(
+    ///     diff.to_string(),
+    ///     "+ rule block = \"{\" args \"}\"\n\
+    ///      ~ rule args = expr % \",\"\n\
+    ///      \x20       now = expr % \";\"\n\
+    ///      + terminal \";\"\n\
+    ///      + terminal \"{\"\n\
+    ///      + terminal \"}\"\n\
+    ///      - terminal \",\"\n",
+    /// );
+    /// ```
+    pub fn diff(&self, new: &Grammar) -> GrammarDiff {
+        let mut diff = GrammarDiff::default();
+        for rule in &new.rules {
+            match self.get(&rule.name) {
+                None => diff.added_rules.push(rule.clone()),
+                Some(old) if old.expr != rule.expr => diff.changed_rules.push(RuleChange {
+                    name: rule.name.clone(),
+                    old: old.expr.clone(),
+                    new: rule.expr.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for rule in &self.rules {
+            if new.get(&rule.name).is_none() {
+                diff.removed_rules.push(rule.clone());
+            }
+        }
+
+        let (old_terminals, new_terminals) = (self.terminals(), new.terminals());
+        diff.added_terminals = new_terminals
+            .difference(&old_terminals)
+            .map(|t| t.to_string())
+            .collect();
+        diff.removed_terminals = old_terminals
+            .difference(&new_terminals)
+            .map(|t| t.to_string())
+            .collect();
+        diff
+    }
+}
+
+// Converts the description of a parser into the rules of a grammar
+#[derive(Default)]
+struct Builder {
+    rules: Vec<Rule>,
+    // Recursive parsers that are referred to from within themselves, and the names of their rules once known
+    recursive: Vec<(usize, Option<String>)>,
+    // Whether the structure of the rule being converted is known completely
+    complete: bool,
+}
+
+impl Builder {
+    fn count_references(&mut self, node: &Node) {
+        if let (NodeKind::Recursive(id), true) = (&node.kind, node.children.is_empty()) {
+            if !self.recursive.iter().any(|(other, _)| other == id) {
+                self.recursive.push((*id, None));
+            }
+        }
+        node.children
+            .iter()
+            .for_each(|child| self.count_references(child));
+    }
+
+    // Define a rule, unless it has already been defined
+    fn rule(&mut self, name: &str, mut node: &Node) {
+        if self.get(name).is_some() {
+            return;
+        }
+        // Recursive parsers and named parsers directly within the rule are part of it
+        loop {
+            match (&node.kind, node.children.first()) {
+                (NodeKind::Rule(inner), Some(body)) if inner == name => node = body,
+                (NodeKind::Recursive(id), Some(body)) => {
+                    self.name_recursive(*id, name);
+                    node = body;
+                }
+                _ => break,
+            }
+        }
+
+        let idx = self.rules.len();
+        self.rules.push(Rule {
+            name: name.to_string(),
+            expr: Expr::Empty,
+        });
+        let complete = core::mem::replace(&mut self.complete, true);
+        let expr = self.expr(node);
+        if self.complete {
+            self.rules[idx].expr = expr;
+        } else {
+            self.rules.remove(idx);
+        }
+        self.complete = complete;
+    }
+
+    fn get(&self, name: &str) -> Option<&Rule> {
+        self.rules.iter().find(|rule| rule.name == name)
+    }
+
+    fn name_recursive(&mut self, id: usize, name: &str) {
+        if let Some((_, slot @ None)) = self.recursive.iter_mut().find(|(other, _)| *other == id) {
+            *slot = Some(name.to_string());
+        }
+    }
+
+    fn expr(&mut self, node: &Node) -> Expr {
+        let mut children = node.children.iter();
+        match &node.kind {
+            NodeKind::Rule(name) => {
+                if let Some(body) = children.next() {
+                    self.rule(name, body);
+                }
+                Expr::rule(name)
+            }
+            NodeKind::Recursive(id) => {
+                let Some(body) = children.next() else {
+                    let name = self
+                        .recursive
+                        .iter()
+                        .find(|(other, _)| other == id)
+                        .and_then(|(_, name)| name.clone())
+                        .unwrap_or_default();
+                    return Expr::Rule(name);
+                };
+                match &body.kind {
+                    NodeKind::Rule(name) => {
+                        self.name_recursive(*id, name);
+                        self.expr(body)
+                    }
+                    _ if self.recursive.iter().any(|(other, _)| other == id) => {
+                        let name = format!("recursive{}", self.rules.len());
+                        self.rule(&name, node);
+                        Expr::Rule(name)
+                    }
+                    _ => self.expr(body),
+                }
+            }
+            NodeKind::Opaque => {
+                self.complete = false;
+                Expr::rule(node.name)
+            }
+            NodeKind::Any => {
+                self.complete = false;
+                Expr::rule("any")
+            }
+            NodeKind::Empty | NodeKind::End => Expr::Empty,
+            NodeKind::Tokens(spellings) => Expr::terminal(spellings.concat()),
+            NodeKind::OneOf(spellings) => {
+                Expr::choice(spellings.iter().cloned().map(Expr::Terminal))
+            }
+            NodeKind::Seq => {
+                let mut exprs = Vec::new();
+                for child in children {
+                    match self.expr(child) {
+                        Expr::Empty => {}
+                        Expr::Seq(inner) => exprs.extend(inner),
+                        expr => exprs.push(expr),
+                    }
+                }
+                match exprs.len() {
+                    0 => Expr::Empty,
+                    1 => exprs.remove(0),
+                    _ => Expr::Seq(exprs),
+                }
+            }
+            NodeKind::Choice => {
+                let mut exprs = Vec::new();
+                for child in children {
+                    match self.expr(child) {
+                        Expr::Choice(inner) => exprs.extend(inner),
+                        expr => exprs.push(expr),
+                    }
+                }
+                Expr::Choice(exprs)
+            }
+            NodeKind::Optional => match children.next() {
+                Some(child) => self.expr(child).or_not(),
+                None => Expr::Empty,
+            },
+            NodeKind::Repeated { at_least } => {
+                let Some(item) = children.next() else {
+                    return Expr::Empty;
+                };
+                let item = Box::new(self.expr(item));
+                let separator = children.next().map(|sep| Box::new(self.expr(sep)));
+                let expr = Expr::Repeated(item, separator);
+                if *at_least == 0 {
+                    expr.or_not()
+                } else {
+                    expr
+                }
+            }
+            // Wrappers, boxed parsers, and anything else that might be added later are described by their child
+            _ => children
+                .next()
+                .map_or(Expr::Empty, |child| self.expr(child)),
+        }
+    }
+}
+
+/// The differences between two versions of a [`Grammar`]. See [`Grammar::diff`].
+///
+/// The [`Display`](fmt::Display) implementation produces a line-based summary, suitable for a changelog or for
+/// failing a test when a grammar changes unexpectedly.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GrammarDiff {
+    /// Rules that only appear in the new grammar, in the order they appear there.
+    pub added_rules: Vec<Rule>,
+    /// Rules that only appear in the old grammar, in the order they appear there.
+    pub removed_rules: Vec<Rule>,
+    /// Rules that appear in both grammars with different bodies, in the order they appear in the new grammar.
+    pub changed_rules: Vec<RuleChange>,
+    /// The spellings of terminals that only appear in the new grammar, in sorted order.
+    pub added_terminals: Vec<String>,
+    /// The spellings of terminals that only appear in the old grammar, in sorted order.
+    pub removed_terminals: Vec<String>,
+}
+
+/// A rule whose body differs between two versions of a [`Grammar`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleChange {
+    /// The name of the rule.
+    pub name: String,
+    /// The body of the rule in the old grammar.
+    pub old: Expr,
+    /// The body of the rule in the new grammar.
+    pub new: Expr,
+}
+
+impl GrammarDiff {
+    /// Returns true if the two grammars were identical (ignoring the order of their rules).
+    pub fn is_empty(&self) -> bool {
+        self.added_rules.is_empty()
+            && self.removed_rules.is_empty()
+            && self.changed_rules.is_empty()
+            && self.added_terminals.is_empty()
+            && self.removed_terminals.is_empty()
+    }
+}
+
+impl fmt::Display for GrammarDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rule in &self.added_rules {
+            writeln!(f, "+ rule {} = {}", rule.name, rule.expr)?;
+        }
+        for rule in &self.removed_rules {
+            writeln!(f, "- rule {} = {}", rule.name, rule.expr)?;
+        }
+        for change in &self.changed_rules {
+            writeln!(f, "~ rule {} = {}", change.name, change.old)?;
+            writeln!(
+                f,
+                "{:width$} now = {}",
+                "",
+                change.new,
+                width = change.name.len() + 3
+            )?;
+        }
+        for terminal in &self.added_terminals {
+            writeln!(f, "+ terminal {:?}", terminal)?;
+        }
+        for terminal in &self.removed_terminals {
+            writeln!(f, "- terminal {:?}", terminal)?;
+        }
+        Ok(())
+    }
+}
+
+/// Expressions are displayed in an EBNF-like notation: terminals are quoted, `a | b` is a choice, `a?` is optional,
+/// `a+` is repeated, and `a % b` is `a` repeated and separated by `b`.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Sequences, choices and separated repetitions are parenthesised when they appear within a tighter-binding
+        // expression
+        fn write(expr: &Expr, f: &mut fmt::Formatter<'_>, prec: u8) -> fmt::Result {
+            let (own, open, close) = match expr {
+                Expr::Choice(exprs) if exprs.len() > 1 && prec > 0 => (0, "(", ")"),
+                Expr::Seq(exprs) if exprs.len() > 1 && prec > 1 => (1, "(", ")"),
+                Expr::Repeated(_, Some(_)) if prec > 2 => (2, "(", ")"),
+                _ => (prec, "", ""),
+            };
+            f.write_str(open)?;
+            match expr {
+                Expr::Empty => f.write_str("()")?,
+                Expr::Terminal(spelling) => write!(f, "{:?}", spelling)?,
+                Expr::Rule(name) => f.write_str(name)?,
+                Expr::Seq(exprs) | Expr::Choice(exprs) if exprs.is_empty() => f.write_str("()")?,
+                Expr::Seq(exprs) => {
+                    for (i, expr) in exprs.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(" ")?;
+                        }
+                        write(expr, f, own.max(2))?;
+                    }
+                }
+                Expr::Choice(exprs) => {
+                    for (i, expr) in exprs.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(" | ")?;
+                        }
+                        write(expr, f, own.max(1))?;
+                    }
+                }
+                Expr::Optional(expr) => {
+                    write(expr, f, 3)?;
+                    f.write_str("?")?;
+                }
+                Expr::Repeated(expr, None) => {
+                    write(expr, f, 3)?;
+                    f.write_str("+")?;
+                }
+                Expr::Repeated(expr, Some(sep)) => {
+                    write(expr, f, 3)?;
+                    f.write_str(" % ")?;
+                    write(sep, f, 3)?;
+                }
+            }
+            f.write_str(close)
+        }
+
+        write(self, f, 0)
+    }
+}
+
+/// Layout and drawing of railroad diagrams.
+///
+/// Each expression is laid out in a box with a horizontal line running through it, which its neighbours connect to.
+/// The box is described by its width, and its extent above (`up`) and below (`down`) the line.
+mod railroad {
+    use super::*;
+    use core::fmt::Write;
+
+    /// The horizontal space between adjacent items.
+    pub(super) const GAP: i32 = 10;
+    /// Half the height of the bar marking the start and end of a diagram.
+    pub(super) const BAR: i32 = 8;
+    /// The horizontal space either side of a branch or loop, taken up by its curves.
+    const SIDE: i32 = 20;
+    /// The vertical space between stacked branches.
+    const STACK: i32 = 10;
+    /// Half the height of a box.
+    const HALF: i32 = 11;
+
+    fn box_width(label: &str) -> i32 {
+        label.chars().count() as i32 * 9 + 2 * GAP
+    }
+
+    pub(super) fn measure(expr: &Expr) -> (i32, i32, i32) {
+        match expr {
+            Expr::Empty => (0, 0, 0),
+            Expr::Terminal(label) | Expr::Rule(label) => (box_width(label), HALF, HALF),
+            Expr::Seq(exprs) => exprs.iter().map(measure).enumerate().fold(
+                (0, 0, 0),
+                |(w, up, down), (i, (iw, iup, idown))| {
+                    (
+                        w + iw + if i > 0 { GAP } else { 0 },
+                        up.max(iup),
+                        down.max(idown),
+                    )
+                },
+            ),
+            Expr::Choice(exprs) => {
+                let mut sizes = exprs.iter().map(measure);
+                let Some((w, up, mut down)) = sizes.next() else {
+                    return (0, 0, 0);
+                };
+                let mut width = w;
+                for (w, iup, idown) in sizes {
+                    width = width.max(w);
+                    down += STACK + iup + idown;
+                }
+                (width + 2 * SIDE, up, down)
+            }
+            Expr::Optional(expr) => measure(&Expr::Choice(vec![Expr::Empty, (**expr).clone()])),
+            Expr::Repeated(expr, sep) => {
+                let (w, up, down) = measure(expr);
+                let (sw, sup, sdown) = sep.as_deref().map_or((0, 0, 0), measure);
+                (w.max(sw) + 2 * SIDE, up, down + STACK + sup + sdown)
+            }
+        }
+    }
+
+    pub(super) fn draw(out: &mut String, expr: &Expr, x: i32, y: i32) {
+        match expr {
+            Expr::Empty => {}
+            Expr::Terminal(label) => draw_box(out, x, y, label, true),
+            Expr::Rule(label) => draw_box(out, x, y, label, false),
+            Expr::Seq(exprs) => {
+                let mut x = x;
+                for (i, expr) in exprs.iter().enumerate() {
+                    if i > 0 {
+                        path(out, &format!("M{} {}h{}", x, y, GAP));
+                        x += GAP;
+                    }
+                    draw(out, expr, x, y);
+                    x += measure(expr).0;
+                }
+            }
+            Expr::Choice(exprs) => {
+                let (width, _, _) = measure(expr);
+                let inner = width - 2 * SIDE;
+                let mut branch_y = y;
+                for (i, expr) in exprs.iter().enumerate() {
+                    let (w, up, down) = measure(expr);
+                    if i > 0 {
+                        branch_y += up;
+                        // Curve down from the main line on the left, and back up to it on the right
+                        path(
+                            out,
+                            &format!(
+                                "M{} {}c{} 0 0 {} {} {}M{} {}c{} 0 0 {} {} {}",
+                                x,
+                                y,
+                                SIDE,
+                                branch_y - y,
+                                SIDE,
+                                branch_y - y,
+                                x + width - SIDE,
+                                branch_y,
+                                SIDE,
+                                y - branch_y,
+                                SIDE,
+                                y - branch_y,
+                            ),
+                        );
+                    } else {
+                        path(out, &format!("M{} {}h{}", x, y, SIDE));
+                        path(out, &format!("M{} {}h{}", x + width - SIDE, y, SIDE));
+                    }
+                    draw(out, expr, x + SIDE, branch_y);
+                    if w < inner {
+                        path(
+                            out,
+                            &format!("M{} {}h{}", x + SIDE + w, branch_y, inner - w),
+                        );
+                    }
+                    branch_y += down + STACK;
+                }
+            }
+            Expr::Optional(expr) => draw(
+                out,
+                &Expr::Choice(vec![Expr::Empty, (**expr).clone()]),
+                x,
+                y,
+            ),
+            Expr::Repeated(expr, sep) => {
+                let (w, _, down) = measure(expr);
+                let (sw, sup, _) = sep.as_deref().map_or((0, 0, 0), measure);
+                let inner = w.max(sw);
+                let width = inner + 2 * SIDE;
+                let back_y = y + down + STACK + sup;
+
+                path(out, &format!("M{} {}h{}", x, y, SIDE));
+                draw(out, expr, x + SIDE, y);
+                path(
+                    out,
+                    &format!("M{} {}h{}", x + SIDE + w, y, inner - w + SIDE),
+                );
+                // The loop runs back from the right of the expression to its left, via the separator
+                path(
+                    out,
+                    &format!(
+                        "M{} {}c{} 0 {} {} 0 {}M{} {}c{} 0 {} {} 0 {}",
+                        x + width - SIDE,
+                        y,
+                        SIDE,
+                        SIDE,
+                        back_y - y,
+                        back_y - y,
+                        x + SIDE,
+                        back_y,
+                        -SIDE,
+                        -SIDE,
+                        y - back_y,
+                        y - back_y,
+                    ),
+                );
+                match sep {
+                    Some(sep) => {
+                        let sx = x + SIDE + (inner - sw) / 2;
+                        path(out, &format!("M{} {}h{}", x + SIDE, back_y, sx - x - SIDE));
+                        draw(out, sep, sx, back_y);
+                        path(
+                            out,
+                            &format!("M{} {}h{}", sx + sw, back_y, x + SIDE + inner - sx - sw),
+                        );
+                    }
+                    None => path(out, &format!("M{} {}h{}", x + SIDE, back_y, inner)),
+                }
+            }
+        }
+    }
+
+    fn draw_box(out: &mut String, x: i32, y: i32, label: &str, rounded: bool) {
+        let _ = write!(
+            out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"/>",
+            x,
+            y - HALF,
+            box_width(label),
+            2 * HALF,
+            if rounded { HALF } else { 0 },
+        );
+        text(
+            out,
+            if rounded { "terminal" } else { "rule" },
+            x + box_width(label) / 2,
+            y + 5,
+            label,
+        );
+    }
+
+    pub(super) fn text(out: &mut String, class: &str, x: i32, y: i32, label: &str) {
+        let _ = write!(out, "<text class=\"{}\" x=\"{}\" y=\"{}\">", class, x, y);
+        for c in label.chars() {
+            match c {
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '&' => out.push_str("&amp;"),
+                '"' => out.push_str("&quot;"),
+                c => out.push(c),
+            }
+        }
+        out.push_str("</text>");
+    }
+
+    pub(super) fn path(out: &mut String, d: &str) {
+        let _ = write!(out, "<path d=\"{}\"/>", d);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{named, Expr, Grammar};
+    use crate::prelude::*;
+
+    #[test]
+    fn grammar_from_parser() {
+        let expr = recursive(|expr| {
+            let atom = named("int", text::int::<&str, extra::Default>(10))
+                .ignored()
+                .or(expr.delimited_by(just('('), just(')')))
+                .boxed();
+            atom.clone()
+                .foldl(one_of("+-").then(atom).repeated(), |_, _| ())
+        });
+        let grammar = Grammar::from_parser("file", &expr.then_ignore(end()));
+
+        let rules = grammar
+            .rules
+            .iter()
+            .map(|rule| format!("{} = {}", rule.name, rule.expr))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            [
+                "file = recursive1",
+                r#"recursive1 = (int | "(" recursive1 ")") (("+" | "-") (int | "(" recursive1 ")"))+?"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn railroad_svg() {
+        let grammar = Grammar::new()
+            .rule(
+                "cmp",
+                Expr::seq([
+                    Expr::rule("sum"),
+                    Expr::seq([
+                        Expr::choice([Expr::terminal("<"), Expr::terminal("&&")]),
+                        Expr::rule("sum"),
+                    ])
+                    .repeated(),
+                ]),
+            )
+            .rule("sum", Expr::Empty);
+
+        let svg = grammar.to_svg();
+        assert!(svg.contains(">&lt;<") && svg.contains(">&amp;&amp;<"));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert_eq!(svg.matches("<text").count(), 6);
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn grammar_diff() {
+        let expr = Expr::seq([
+            Expr::choice([Expr::terminal("+"), Expr::terminal("-")]).or_not(),
+            Expr::seq([Expr::rule("digit"), Expr::terminal("_").or_not()]).repeated(),
+        ]);
+        assert_eq!(expr.to_string(), r#"("+" | "-")? (digit "_"?)+"#);
+
+        let old = Grammar::new()
+            .rule("a", expr.clone())
+            .rule("b", Expr::Empty);
+        let new = Grammar::new().rule("b", Expr::Empty).rule("a", expr);
+        assert!(old.diff(&new).is_empty());
+
+        let diff = old.diff(&Grammar::new().rule("a", Expr::Empty));
+        assert_eq!(diff.removed_rules[0].name, "b");
+        assert_eq!(diff.changed_rules[0].name, "a");
+        assert_eq!(diff.removed_terminals, vec!["+", "-", "_"]);
+    }
+}
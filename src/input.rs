@@ -51,7 +51,10 @@ pub trait Input<'src>: 'src {
     /// Create an initial cursor and cache at the start of the input.
     fn begin(self) -> (Self::Cursor, Self::Cache);
 
-    /// TODO
+    /// Get an opaque, monotonically increasing location for the given cursor.
+    ///
+    /// This is used to order cursors relative to one-another (for example, to prioritise errors), and has no
+    /// relation to the offsets of the input's spans. To turn a cursor into a span offset, see [`InputRef::offset_of`].
     fn cursor_location(cursor: &Self::Cursor) -> usize;
 
     /// Pull the next token, if any, from the input.
@@ -1064,6 +1067,246 @@ impl<'src, R: Read + Seek + 'src> ValueInput<'src> for IoInput<R> {
     }
 }
 
+/// Input type for readers that can't seek, like standard input or a socket.
+///
+/// Everything read is kept in memory so that the parser can backtrack. When parsing a stream of records with
+/// [`parse_stream`], records are discarded once they have been parsed, so only one record needs to be held in memory
+/// at a time.
+///
+/// Only available with the `std` feature
+#[cfg(feature = "std")]
+pub struct ReaderInput<R> {
+    reader: R,
+    pub(crate) buf: Vec<u8>,
+    // The offset of the first byte in `buf`
+    offset: usize,
+    eof: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> ReaderInput<R> {
+    /// Create a new `ReaderInput` from a reader.
+    pub fn new(reader: R) -> ReaderInput<R> {
+        ReaderInput {
+            reader,
+            buf: Vec::new(),
+            offset: 0,
+            eof: false,
+        }
+    }
+
+    // Forget everything before the given cursor, which must not be revisited.
+    fn discard_before(&mut self, cursor: usize) {
+        self.buf.drain(..cursor - self.offset);
+        self.offset = cursor;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'src, R: Read + 'src> Input<'src> for ReaderInput<R> {
+    type Cursor = usize;
+    type Span = SimpleSpan;
+
+    type Token = u8;
+    type MaybeToken = u8;
+
+    type Cache = Self;
+
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (0, self)
+    }
+
+    #[inline(always)]
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        *cursor
+    }
+
+    #[inline(always)]
+    unsafe fn next_maybe(
+        this: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Self::next(this, cursor)
+    }
+
+    #[inline]
+    unsafe fn span(_this: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        (*range.start..*range.end).into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'src, R: Read + 'src> ValueInput<'src> for ReaderInput<R> {
+    unsafe fn next(this: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        let idx = *cursor - this.offset;
+        while idx >= this.buf.len() && !this.eof {
+            let mut chunk = [0; 4096];
+            match this.reader.read(&mut chunk) {
+                Ok(0) => this.eof = true,
+                Ok(n) => this.buf.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(_) => this.eof = true,
+            }
+        }
+
+        let tok = this.buf.get(idx).copied()?;
+        *cursor += 1;
+        Some(tok)
+    }
+}
+
+/// Parse a stream of newline-delimited records (such as [JSON lines](https://jsonlines.org/) or log files) from a
+/// reader, yielding the result of parsing each record as soon as it has been read.
+///
+/// Each line is parsed with `parser`, which must consume the whole line (not including the line terminator, which may
+/// be `\n` or `\r\n`). Empty lines are skipped. Records that fail to parse don't prevent the following records from
+/// being parsed: the errors are produced for that record, and parsing continues with the next line. Spans in errors
+/// are byte offsets from the start of the stream.
+///
+/// Parsed records are discarded as parsing proceeds, so arbitrarily long streams can be processed in constant memory.
+///
+/// Only available with the `std` feature
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::{parse_stream, ReaderInput}};
+/// #[derive(Debug, PartialEq)]
+/// struct Entry {
+///     level: String,
+///     code: u32,
+/// }
+///
+/// let log = "info 200\nwarn oops\n\nerror 500\n";
+///
+/// let word = any()
+///     .filter(u8::is_ascii_alphabetic)
+///     .repeated()
+///     .at_least(1)
+///     .collect::<Vec<u8>>()
+///     .map(|w| String::from_utf8(w).unwrap());
+/// let code = any()
+///     .filter(u8::is_ascii_digit)
+///     .repeated()
+///     .at_least(1)
+///     .collect::<Vec<u8>>()
+///     .map(|d| String::from_utf8(d).unwrap().parse().unwrap());
+/// let entry = word
+///     .then_ignore(just(b' '))
+///     .then(code)
+///     .map(|(level, code)| Entry { level, code });
+///
+/// let mut entries = parse_stream::<_, _, _, extra::Err<Rich<u8>>>(log.as_bytes(), entry)
+///     .map(|res| res.into_result());
+///
+/// assert_eq!(entries.next(), Some(Ok(Entry { level: "info".to_string(), code: 200 })));
+/// assert_eq!(entries.next().unwrap().unwrap_err()[0].span(), &SimpleSpan::from(14..15));
+/// assert_eq!(entries.next(), Some(Ok(Entry { level: "error".to_string(), code: 500 })));
+/// assert_eq!(entries.next(), None);
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_stream<'src, R, P, O, E>(reader: R, parser: P) -> ParseStream<'src, R, P, O, E>
+where
+    R: Read + 'src,
+    P: Parser<'src, ReaderInput<R>, O, E>,
+    E: ParserExtra<'src, ReaderInput<R>>,
+    E::State: Default,
+    E::Context: Default,
+{
+    ParseStream {
+        parser,
+        own: InputOwn::new(ReaderInput::new(reader)),
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+/// An iterator over the records parsed from a reader. See [`parse_stream`].
+#[cfg(feature = "std")]
+pub struct ParseStream<'src, R, P, O, E>
+where
+    R: Read + 'src,
+    E: ParserExtra<'src, ReaderInput<R>>,
+{
+    parser: P,
+    pub(crate) own: InputOwn<'src, 'src, ReaderInput<R>, E>,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<O>,
+}
+
+#[cfg(feature = "std")]
+impl<'src, R, P, O, E> Iterator for ParseStream<'src, R, P, O, E>
+where
+    R: Read + 'src,
+    P: Parser<'src, ReaderInput<R>, O, E>,
+    E: ParserExtra<'src, ReaderInput<R>>,
+{
+    type Item = ParseResult<O, E::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.own.start;
+        self.own.cache.discard_before(start);
+        #[cfg(feature = "memoization")]
+        self.own.memos.clear();
+
+        let mut inp = self.own.as_ref_start();
+
+        // Skip empty lines
+        loop {
+            let before = inp.save();
+            match inp.next_inner() {
+                Some(b'\n') => {}
+                Some(b'\r') if inp.peek() == Some(b'\n') => inp.skip(),
+                Some(_) => {
+                    inp.rewind(before);
+                    break;
+                }
+                None => return None,
+            }
+        }
+
+        let record = inp.save();
+        let out = self.parser.go::<Emit>(&mut inp).ok().and_then(|out| {
+            let before = inp.save();
+            match inp.next_inner() {
+                Some(b'\n') | None => Some(out),
+                Some(b'\r') if inp.peek() == Some(b'\n') => {
+                    inp.skip();
+                    Some(out)
+                }
+                found => {
+                    let span = inp.span_since(before.cursor());
+                    inp.add_alt(
+                        [
+                            DefaultExpected::Token(MaybeRef::Val(b'\n')),
+                            DefaultExpected::EndOfInput,
+                        ],
+                        found.map(MaybeRef::Val),
+                        span,
+                    );
+                    None
+                }
+            }
+        });
+
+        let mut errs = inp
+            .errors
+            .secondary
+            .drain(..)
+            .map(|err| err.err)
+            .collect::<Vec<_>>();
+        if out.is_none() {
+            errs.extend(inp.take_alt().map(|alt| alt.err));
+            // Recover by skipping the rest of the line
+            inp.rewind(record);
+            while !matches!(inp.next_inner(), Some(b'\n') | None) {}
+        }
+        inp.errors.alt = None;
+        self.own.start = inp.cursor().inner;
+
+        Some(ParseResult::new(out, errs))
+    }
+}
+
 /// Represents a location in an input that can be rewound to.
 ///
 /// Checkpoints can be created with [`InputRef::save`] and rewound to with [`InputRef::rewind`].
@@ -1084,6 +1327,18 @@ impl<'src, 'parse, I: Input<'src>, C> Checkpoint<'src, 'parse, I, C> {
     pub fn inspector(&self) -> &C {
         &self.inspector
     }
+
+    /// Create a checkpoint for the same location, but with a different inspector checkpoint.
+    ///
+    /// This is useful when implementing [`Inspector`] for a state type that forwards its hooks to inner state types.
+    pub fn map_inspector<D>(&self, f: impl FnOnce(&C) -> D) -> Checkpoint<'src, 'parse, I, D> {
+        Checkpoint {
+            cursor: self.cursor.clone(),
+            err_count: self.err_count,
+            inspector: f(&self.inspector),
+            phantom: PhantomData,
+        }
+    }
 }
 
 impl<'src, I: Input<'src>, C: Clone> Clone for Checkpoint<'src, '_, I, C> {
@@ -1135,7 +1390,7 @@ impl<'src, I: Input<'src>> PartialEq for Cursor<'src, '_, I> {
 
 impl<'src, I: Input<'src>> PartialOrd for Cursor<'src, '_, I> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(I::cursor_location(&self.inner).cmp(&I::cursor_location(&other.inner)))
+        Some(self.cmp(other))
     }
 }
 
@@ -1177,6 +1432,7 @@ pub(crate) struct InputOwn<'src, 's, I: Input<'src>, E: ParserExtra<'src, I>> {
     pub(crate) ctx: E::Context,
     #[cfg(feature = "memoization")]
     pub(crate) memos: HashMap<(usize, usize), Option<Located<I::Cursor, E::Error>>>,
+    pub(crate) safe_point: Option<&'s mut dyn SafePoint>,
 }
 
 impl<'src, 's, I, E> InputOwn<'src, 's, I, E>
@@ -1199,6 +1455,7 @@ where
             ctx: E::Context::default(),
             #[cfg(feature = "memoization")]
             memos: HashMap::default(),
+            safe_point: None,
         }
     }
 
@@ -1215,6 +1472,14 @@ where
             ctx: E::Context::default(),
             #[cfg(feature = "memoization")]
             memos: HashMap::default(),
+            safe_point: None,
+        }
+    }
+
+    pub(crate) fn with_safe_point(self, safe_point: &'s mut dyn SafePoint) -> Self {
+        InputOwn {
+            safe_point: Some(safe_point),
+            ..self
         }
     }
 
@@ -1227,6 +1492,8 @@ where
             ctx: &self.ctx,
             #[cfg(feature = "memoization")]
             memos: &mut self.memos,
+            safe_point: reborrow_safe_point(&mut self.safe_point),
+            base_offset: 0,
         }
     }
 
@@ -1239,6 +1506,213 @@ where
     }
 }
 
+/// A hook invoked at safe points during parsing, such as each iteration of a repetition or each recursive call.
+///
+/// See [`Parser::parse_with_progress`] and [`Parser::parse_with_limits`].
+pub(crate) trait SafePoint {
+    /// Called with the position of the input at the safe point. Returns `false` if parsing should be abandoned.
+    fn check(&mut self, offset: usize) -> bool;
+
+    /// Called when entering a recursive parser. Every successful call is paired with a call to
+    /// [`SafePoint::exit_recursion`].
+    fn enter_recursion(&mut self, offset: usize) -> bool {
+        self.check(offset)
+    }
+
+    /// Called when leaving a recursive parser.
+    fn exit_recursion(&mut self) {}
+
+    /// Called after successfully recovering from an error. Returns `false` if parsing should be abandoned.
+    fn recovered(&mut self) -> bool {
+        true
+    }
+
+    /// Called when the input is rewound to an earlier position.
+    fn backtracked(&mut self) {}
+
+    /// Returns `true` if parsing has been abandoned, in which case recovery should not be attempted.
+    fn abandoned(&self) -> bool {
+        false
+    }
+
+    /// The furthest position that the input may extend to, if limited. This is checked before parsing begins.
+    fn max_position(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// A [`SafePoint`] that periodically calls a progress callback, abandoning the parse if it asks to stop.
+pub(crate) struct Progress<F> {
+    on_progress: F,
+    countdown: usize,
+    pub(crate) cancelled: bool,
+}
+
+impl<F> Progress<F> {
+    /// The number of safe points between calls to the callback.
+    const INTERVAL: usize = 256;
+
+    pub(crate) fn new(on_progress: F) -> Self {
+        Progress {
+            on_progress,
+            countdown: Self::INTERVAL,
+            cancelled: false,
+        }
+    }
+}
+
+impl<F: FnMut(usize) -> ControlFlow<()>> SafePoint for Progress<F> {
+    #[inline]
+    fn check(&mut self, offset: usize) -> bool {
+        if !self.cancelled {
+            self.countdown -= 1;
+            if self.countdown == 0 {
+                self.countdown = Self::INTERVAL;
+                self.cancelled = (self.on_progress)(offset).is_break();
+            }
+        }
+        !self.cancelled
+    }
+
+    #[inline]
+    fn abandoned(&self) -> bool {
+        self.cancelled
+    }
+}
+
+/// A [`SafePoint`] that abandons the parse when it exceeds any of the given [`Limits`].
+pub(crate) struct Bounded {
+    limits: Limits,
+    depth: usize,
+    recoveries: usize,
+    pub(crate) exceeded: Option<LimitExceeded>,
+}
+
+impl Bounded {
+    pub(crate) fn new(limits: Limits) -> Self {
+        Bounded {
+            limits,
+            depth: 0,
+            recoveries: 0,
+            exceeded: None,
+        }
+    }
+
+    fn exceed(&mut self, limit: LimitExceeded) -> bool {
+        self.exceeded = Some(limit);
+        false
+    }
+}
+
+impl SafePoint for Bounded {
+    #[inline]
+    fn check(&mut self, offset: usize) -> bool {
+        if self.exceeded.is_some() {
+            return false;
+        }
+        if let Some(fuel) = &mut self.limits.fuel {
+            if *fuel == 0 {
+                return self.exceed(LimitExceeded::Fuel);
+            }
+            *fuel -= 1;
+        }
+        if self.limits.max_input_len.map_or(false, |max| offset > max) {
+            return self.exceed(LimitExceeded::InputLen);
+        }
+        true
+    }
+
+    #[inline]
+    fn enter_recursion(&mut self, offset: usize) -> bool {
+        if !self.check(offset) {
+            false
+        } else if self.limits.max_depth.map_or(false, |max| self.depth >= max) {
+            self.exceed(LimitExceeded::Depth)
+        } else {
+            self.depth += 1;
+            true
+        }
+    }
+
+    #[inline]
+    fn exit_recursion(&mut self) {
+        self.depth -= 1;
+    }
+
+    #[inline]
+    fn recovered(&mut self) -> bool {
+        if self.exceeded.is_some() {
+            false
+        } else if self
+            .limits
+            .max_recoveries
+            .map_or(false, |max| self.recoveries >= max)
+        {
+            self.exceed(LimitExceeded::Recoveries)
+        } else {
+            self.recoveries += 1;
+            true
+        }
+    }
+
+    #[inline]
+    fn abandoned(&self) -> bool {
+        self.exceeded.is_some()
+    }
+
+    fn max_position(&self) -> Option<usize> {
+        self.limits.max_input_len
+    }
+}
+
+/// A [`SafePoint`] that gathers statistics about a parse.
+#[derive(Default)]
+pub(crate) struct Stats {
+    pub(crate) stats: ParseStats,
+    depth: usize,
+}
+
+impl SafePoint for Stats {
+    #[inline]
+    fn check(&mut self, offset: usize) -> bool {
+        self.stats.consumed = offset;
+        true
+    }
+
+    #[inline]
+    fn enter_recursion(&mut self, _offset: usize) -> bool {
+        self.depth += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+        true
+    }
+
+    #[inline]
+    fn exit_recursion(&mut self) {
+        self.depth -= 1;
+    }
+
+    #[inline]
+    fn recovered(&mut self) -> bool {
+        self.stats.recovered += 1;
+        true
+    }
+
+    #[inline]
+    fn backtracked(&mut self) {
+        self.stats.backtracks += 1;
+    }
+}
+
+#[inline(always)]
+fn reborrow_safe_point<'a>(
+    safe_point: &'a mut Option<&mut dyn SafePoint>,
+) -> Option<&'a mut dyn SafePoint> {
+    match safe_point {
+        Some(safe_point) => Some(&mut **safe_point),
+        None => None,
+    }
+}
+
 /// Internal type representing an input as well as all the necessary context for parsing.
 pub struct InputRef<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> {
     cursor: I::Cursor,
@@ -1248,6 +1722,10 @@ pub struct InputRef<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> {
     pub(crate) ctx: &'parse E::Context,
     #[cfg(feature = "memoization")]
     pub(crate) memos: &'parse mut HashMap<(usize, usize), Option<Located<I::Cursor, E::Error>>>,
+    pub(crate) safe_point: Option<&'parse mut dyn SafePoint>,
+    // The offset of the start of this input within the outermost input, for inputs that are frames of another (see
+    // `length_prefixed`)
+    pub(crate) base_offset: usize,
 }
 
 impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'parse, I, E> {
@@ -1269,6 +1747,8 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             errors: self.errors,
             #[cfg(feature = "memoization")]
             memos: self.memos,
+            safe_point: reborrow_safe_point(&mut self.safe_point),
+            base_offset: self.base_offset,
         };
         let res = f(&mut new_inp);
         self.cursor = new_inp.cursor;
@@ -1293,6 +1773,8 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             errors: self.errors,
             #[cfg(feature = "memoization")]
             memos: self.memos,
+            safe_point: reborrow_safe_point(&mut self.safe_point),
+            base_offset: self.base_offset,
         };
         let res = f(&mut new_inp);
         self.cursor = new_inp.cursor;
@@ -1315,6 +1797,37 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         'parse: 'sub_parse,
         J: Input<'src>,
         F: ParserExtra<'src, J, State = E::State, Context = E::Context, Error = E::Error>,
+    {
+        self.with_input_map_err(
+            start,
+            cache,
+            new_errors,
+            f,
+            |err| err,
+            #[cfg(feature = "memoization")]
+            memos,
+        )
+    }
+
+    /// Like [`InputRef::with_input`], but the sub-parse may have a different error type, its errors being converted
+    /// with the given function.
+    #[inline]
+    pub(crate) fn with_input_map_err<'sub_parse, J, F, O>(
+        &'sub_parse mut self,
+        start: J::Cursor,
+        cache: &'sub_parse mut J::Cache,
+        new_errors: &'sub_parse mut Errors<J::Cursor, F::Error>,
+        f: impl FnOnce(&mut InputRef<'src, 'sub_parse, J, F>) -> O,
+        mut map_err: impl FnMut(F::Error) -> E::Error,
+        #[cfg(feature = "memoization")] memos: &'sub_parse mut HashMap<
+            (usize, usize),
+            Option<Located<J::Cursor, F::Error>>,
+        >,
+    ) -> O
+    where
+        'parse: 'sub_parse,
+        J: Input<'src>,
+        F: ParserExtra<'src, J, State = E::State, Context = E::Context>,
     {
         let mut new_inp = InputRef {
             cursor: start,
@@ -1324,6 +1837,8 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
             errors: new_errors,
             #[cfg(feature = "memoization")]
             memos,
+            safe_point: reborrow_safe_point(&mut self.safe_point),
+            base_offset: 0,
         };
         let out = f(&mut new_inp);
         self.errors.secondary.extend(
@@ -1331,14 +1846,113 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
                 .errors
                 .secondary
                 .drain(..)
-                .map(|err| Located::at(self.cursor.clone(), err.err)),
+                .map(|err| Located::at(self.cursor.clone(), map_err(err.err))),
         );
         if let Some(alt) = new_inp.errors.alt.take() {
-            self.errors.alt = Some(Located::at(self.cursor.clone(), alt.err));
+            self.errors.alt = Some(Located::at(self.cursor.clone(), map_err(alt.err)));
         }
         out
     }
 
+    /// Give the safe point hook of the parse, if any, a chance to run. Fails if parsing should be abandoned.
+    ///
+    /// This should be called by combinators that might run for an unbounded length of time, such as repetitions.
+    #[inline(always)]
+    pub(crate) fn safe_point(&mut self) -> Result<(), ()> {
+        match &mut self.safe_point {
+            Some(safe_point) => {
+                if safe_point.check(I::cursor_location(&self.cursor)) {
+                    Ok(())
+                } else {
+                    self.abandon()
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Fail because the safe point hook abandoned the parse, leaving an error behind like any other failing parser.
+    #[cold]
+    fn abandon(&mut self) -> Result<(), ()> {
+        let before = self.cursor();
+        let span = self.span_since(&before);
+        self.add_alt([], None, span);
+        Err(())
+    }
+
+    /// Check that the input does not extend beyond the furthest position permitted by the safe point hook of the
+    /// parse, if any, without walking more of the input than that. Fails if parsing should be abandoned.
+    pub(crate) fn check_input_len(&mut self) -> Result<(), ()> {
+        let Some(max) = self.safe_point.as_ref().and_then(|sp| sp.max_position()) else {
+            return Ok(());
+        };
+        let mut cursor = self.cursor.clone();
+        while I::cursor_location(&cursor) <= max {
+            // SAFETY: cursor was generated by previous call to `Input::next`
+            if unsafe { I::next_maybe(self.cache, &mut cursor) }.is_none() {
+                break;
+            }
+        }
+        let end = I::cursor_location(&cursor);
+        if self
+            .safe_point
+            .as_mut()
+            .map_or(true, |safe_point| safe_point.check(end))
+        {
+            Ok(())
+        } else {
+            self.abandon()
+        }
+    }
+
+    /// Returns `true` if the safe point hook of the parse, if any, has abandoned the parse.
+    #[inline(always)]
+    pub(crate) fn abandoned(&self) -> bool {
+        self.safe_point
+            .as_ref()
+            .map_or(false, |safe_point| safe_point.abandoned())
+    }
+
+    /// Notify the safe point hook of the parse, if any, that a recursive parser is being entered. Fails if parsing
+    /// should be abandoned: otherwise, [`InputRef::exit_recursion`] must be called when the recursive parser is left.
+    #[inline(always)]
+    pub(crate) fn enter_recursion(&mut self) -> Result<(), ()> {
+        match &mut self.safe_point {
+            Some(safe_point) => {
+                if safe_point.enter_recursion(I::cursor_location(&self.cursor)) {
+                    Ok(())
+                } else {
+                    self.abandon()
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Notify the safe point hook of the parse, if any, that a recursive parser is being left.
+    #[inline(always)]
+    pub(crate) fn exit_recursion(&mut self) {
+        if let Some(safe_point) = &mut self.safe_point {
+            safe_point.exit_recursion();
+        }
+    }
+
+    /// Notify the safe point hook of the parse, if any, that an error was recovered from. Fails if parsing should be
+    /// abandoned.
+    #[inline(always)]
+    pub(crate) fn recovered(&mut self) -> Result<(), ()> {
+        match &mut self.safe_point {
+            Some(safe_point) => {
+                if safe_point.recovered() {
+                    Ok(())
+                } else {
+                    self.abandon()
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
     /// Get the internal cursor of the input at this moment in time.
     ///
     /// Can be used for generating spans or slices. See [`InputRef::span_from`] and [`InputRef::slice`].
@@ -1368,6 +1982,25 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         }
     }
 
+    /// Discard a [`Checkpoint`] that will never be rewound to, letting the parser state free anything it kept for it.
+    #[inline(always)]
+    pub(crate) fn commit(
+        &mut self,
+        checkpoint: Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+    ) {
+        self.state.on_commit(&checkpoint);
+    }
+
+    /// Reset the parse state to that represented by the given [`Checkpoint`], which will never be rewound to again.
+    #[inline(always)]
+    pub(crate) fn rewind_commit(
+        &mut self,
+        checkpoint: Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+    ) {
+        self.rewind(checkpoint.clone());
+        self.commit(checkpoint);
+    }
+
     /// Reset the parse state to that represented by the given [`Checkpoint`].
     ///
     /// You can create a checkpoint with which to perform rewinding using [`InputRef::save`].
@@ -1378,6 +2011,11 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
     ) {
         self.errors.secondary.truncate(checkpoint.err_count);
         self.state.on_rewind(&checkpoint);
+        if let Some(safe_point) = &mut self.safe_point {
+            if I::cursor_location(&checkpoint.cursor.inner) < I::cursor_location(&self.cursor) {
+                safe_point.backtracked();
+            }
+        }
         self.cursor = checkpoint.cursor.inner;
     }
 
@@ -1640,6 +2278,36 @@ impl<'src, 'parse, I: Input<'src>, E: ParserExtra<'src, I>> InputRef<'src, 'pars
         unsafe { I::span(self.cache, &before.inner..&self.cursor) }
     }
 
+    /// Generate a span that extends from the provided raw input cursor to the current input position.
+    ///
+    /// # Safety
+    ///
+    /// The cursor must have been taken from this input.
+    #[inline(always)]
+    pub(crate) unsafe fn span_since_raw(&mut self, before: &I::Cursor) -> I::Span {
+        I::span(self.cache, before..&self.cursor)
+    }
+
+    /// Get the offset (in the units of the input's span type) that the given [`Cursor`] corresponds to.
+    ///
+    /// This is the offset that a span starting at the cursor would begin with, making it suitable for recording exact
+    /// resume points (for example, when handing control to a different lexer mode).
+    #[inline(always)]
+    pub fn offset_of(&mut self, cursor: &Cursor<'src, 'parse, I>) -> <I::Span as Span>::Offset {
+        // SAFETY: `Cursor` is invariant over 'parse, so we know that this cursor came from the same input
+        // See `https://plv.mpi-sws.org/rustbelt/ghostcell/`
+        unsafe { I::span(self.cache, &cursor.inner..&cursor.inner) }.start()
+    }
+
+    /// Get the offset (in the units of the input's span type) of the current input position.
+    ///
+    /// This is equivalent to calling [`InputRef::offset_of`] with [`InputRef::cursor`].
+    #[inline(always)]
+    pub fn offset(&mut self) -> <I::Span as Span>::Offset {
+        // SAFETY: The cursor is the current cursor of this input
+        unsafe { I::span(self.cache, &self.cursor..&self.cursor) }.start()
+    }
+
     /// SAFETY: Previous cursor + skip must not exceed length
     #[inline(always)]
     #[cfg(any(feature = "regex", feature = "lexical-numbers"))]
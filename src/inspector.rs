@@ -8,7 +8,14 @@ use crate::{
     input::{Checkpoint, Cursor},
     Input,
 };
-use core::ops::{Deref, DerefMut};
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    cell::RefCell,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+};
+use hashbrown::{HashMap, HashSet};
 
 #[allow(unused)] // for intra-doc links
 use crate::Parser;
@@ -32,6 +39,17 @@ pub trait Inspector<'src, I: Input<'src>> {
     /// You can use [`Checkpoint::inspector`] to get back the [`Checkpoint`][Self::Checkpoint]
     /// you originally created in [`on_save`][Self::on_save].
     fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>);
+    /// This function is called when a combinator has succeeded and will never rewind to a state it saved earlier.
+    ///
+    /// Not every combinator reports this, so it is only useful for freeing resources kept for the sake of a
+    /// [`Checkpoint`][Self::Checkpoint] early: implementations must still be prepared to keep them until the end of the
+    /// parse.
+    fn on_commit<'parse>(&mut self, _marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {}
+    /// This function is called when a parser marked with [`cover`](crate::trace::cover) is about to be tried.
+    fn on_cover_enter(&mut self, _name: &'static str) {}
+    /// This function is called when a parser marked with [`cover`](crate::trace::cover) has been tried, along with
+    /// whether it matched.
+    fn on_cover_exit(&mut self, _name: &'static str, _matched: bool) {}
 }
 
 impl<'src, I: Input<'src>> Inspector<'src, I> for () {
@@ -44,6 +62,76 @@ impl<'src, I: Input<'src>> Inspector<'src, I> for () {
     fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, Self>) {}
 }
 
+/// A state type that can be saved and restored, so that changes made to it are undone when the parser backtracks.
+///
+/// Any type implementing this trait automatically implements [`Inspector`]: whenever a combinator saves the input
+/// position (as [`Parser::or`], [`Parser::rewind`], [`Parser::repeated`] and others do) the state is saved too, and
+/// it is restored whenever the input is rewound. This allows parsers that build up data in their state, such as
+/// symbol tables, to behave correctly when branches fail.
+///
+/// Because checkpoints are taken often, they should be cheap to create: the length of an append-only list or a
+/// version number are typical choices. For state that can only be snapshotted by cloning it, see
+/// [`Transactional`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, inspector::StateCheckpoint};
+/// #[derive(Default)]
+/// struct Symbols(Vec<String>);
+///
+/// impl StateCheckpoint for Symbols {
+///     type Checkpoint = usize;
+///
+///     fn save(&self) -> usize {
+///         self.0.len()
+///     }
+///
+///     fn restore(&mut self, len: usize) {
+///         self.0.truncate(len);
+///     }
+/// }
+///
+/// let decl = text::ascii::keyword::<_, _, extra::State<Symbols>>("let")
+///     .padded()
+///     .ignore_then(text::ascii::ident())
+///     .map_with(|name: &str, e| e.state().0.push(name.to_string()));
+/// let stmt = decl
+///     .clone()
+///     .then_ignore(just(';'))
+///     .or(decl.then_ignore(just("=1;")));
+///
+/// let mut symbols = Symbols::default();
+/// let parser = stmt.padded().repeated().collect::<Vec<_>>();
+/// assert!(!parser.parse_with_state("let x; let y=1;", &mut symbols).has_errors());
+/// // `y` was declared by the first branch before it failed, but that declaration was undone
+/// assert_eq!(symbols.0, vec!["x", "y"]);
+/// ```
+pub trait StateCheckpoint {
+    /// A cheap, copyable representation of the state at some point during parsing.
+    type Checkpoint: Copy + Clone;
+
+    /// Save the current state.
+    fn save(&self) -> Self::Checkpoint;
+
+    /// Restore the state to how it was when the given checkpoint was saved.
+    fn restore(&mut self, checkpoint: Self::Checkpoint);
+}
+
+impl<'src, I: Input<'src>, S: StateCheckpoint> Inspector<'src, I> for S {
+    type Checkpoint = S::Checkpoint;
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+        self.save()
+    }
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        self.restore(*marker.inspector());
+    }
+}
+
 /// A state type that should be accessible directly from `parser.state()` and has no special behavior.
 ///
 /// This wrapper implements the [`Inspector`] trait for you so you don't have to.
@@ -77,3 +165,786 @@ impl<T> From<T> for SimpleState<T> {
         Self(value)
     }
 }
+
+/// A state type that pairs read-mostly data shared between many parses (such as an interner or a table of keywords)
+/// with mutable state that belongs to a single parse.
+///
+/// Multi-file frontends often parse many files at once, on several threads. Each parse needs its own mutable state,
+/// since `parse_with_state` takes it by mutable reference, but data like an interner should be shared by all of them.
+/// `Shared` holds a shared reference to the common data, so it can be sent to another thread whenever the data is
+/// [`Sync`] and the per-parse state is [`Send`]: no `unsafe` or global variables are needed.
+///
+/// The per-parse state is available through [`Deref`] and [`DerefMut`], and the shared data through
+/// [`Shared::shared`]. Parser events are forwarded to the per-parse state, so it may be any [`Inspector`], such as a
+/// [`SymbolTable`]. If the shared data is a [`SharedInterner`], the state is an [`Interner`] too, so it can be used with
+/// [`Parser::interned`].
+///
+/// Parser extra types (such as [`extra::Full`](crate::extra::Full)) are [`Send`] and [`Sync`] whatever their state
+/// type, so the same parser may be used with `Shared` on every thread. Note that parsers containing
+/// [`Boxed`](crate::Boxed) or [`Recursive`](crate::recursive::Recursive) parsers are not [`Send`]: build the parser on
+/// each thread instead, which is cheap.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::{HashConsTable, Shared, SimpleState}};
+/// use std::{sync::{Arc, RwLock}, thread};
+///
+/// // Identifiers are interned into a table shared by every thread
+/// type Interner = RwLock<HashConsTable<Arc<str>>>;
+/// // Each parse also counts the identifiers in its own file
+/// type Extra<'a> = extra::State<Shared<'a, Interner, SimpleState<usize>>>;
+///
+/// fn idents<'src, 'a: 'src>() -> impl Parser<'src, &'src str, Vec<Arc<str>>, Extra<'a>> {
+///     text::ascii::ident::<&'src str, Extra<'a>>()
+///         .map(Arc::<str>::from)
+///         .interned()
+///         .map_with(|name, e| {
+///             e.state().0 += 1;
+///             name
+///         })
+///         .padded()
+///         .repeated()
+///         .collect()
+/// }
+///
+/// let interner = Interner::default();
+/// let files = ["foo bar", "bar baz", "foo foo"];
+///
+/// let results = thread::scope(|s| {
+///     let handles = files.map(|src| {
+///         let interner = &interner;
+///         s.spawn(move || {
+///             let mut state = Shared::new(interner, SimpleState(0));
+///             let names = idents().parse_with_state(src, &mut state).unwrap();
+///             (names, state.0)
+///         })
+///     });
+///     handles.map(|h| h.join().unwrap())
+/// });
+///
+/// assert_eq!(results[2].1, 2);
+/// // Every `bar` shares the same allocation, wherever it was parsed
+/// assert!(Arc::ptr_eq(&results[0].0[1], &results[1].0[0]));
+/// assert_eq!(interner.read().unwrap().len(), 3);
+/// ```
+pub struct Shared<'a, T: ?Sized, S = ()> {
+    shared: &'a T,
+    local: S,
+}
+
+impl<'a, T: ?Sized, S> Shared<'a, T, S> {
+    /// Create a new state from the given shared data and per-parse state.
+    pub fn new(shared: &'a T, local: S) -> Self {
+        Self { shared, local }
+    }
+
+    /// Get the data shared with other parses.
+    pub fn shared(&self) -> &'a T {
+        self.shared
+    }
+
+    /// Take the per-parse state, ending the borrow of the shared data.
+    pub fn into_local(self) -> S {
+        self.local
+    }
+}
+
+impl<'src, T: ?Sized, S: Inspector<'src, I>, I: Input<'src>> Inspector<'src, I>
+    for Shared<'_, T, S>
+{
+    type Checkpoint = S::Checkpoint;
+    #[inline(always)]
+    fn on_token(&mut self, token: &<I as Input<'src>>::Token) {
+        self.local.on_token(token);
+    }
+    #[inline(always)]
+    fn on_save<'parse>(&self, cursor: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+        self.local.on_save(cursor)
+    }
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        self.local.on_rewind(marker);
+    }
+    #[inline(always)]
+    fn on_commit<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        self.local.on_commit(marker);
+    }
+    #[inline(always)]
+    fn on_cover_enter(&mut self, name: &'static str) {
+        self.local.on_cover_enter(name);
+    }
+    #[inline(always)]
+    fn on_cover_exit(&mut self, name: &'static str, matched: bool) {
+        self.local.on_cover_exit(name, matched);
+    }
+}
+
+impl<T: ?Sized, S> Deref for Shared<'_, T, S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.local
+    }
+}
+
+impl<T: ?Sized, S> DerefMut for Shared<'_, T, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.local
+    }
+}
+
+/// A state type whose changes are rolled back when the parser backtracks.
+///
+/// Without this wrapper, changes made to the parser state (for example, in [`Parser::map_with`]) persist even if the
+/// branch of the parser that made them later fails and another branch is tried instead. `Transactional` snapshots the
+/// inner value whenever it is about to be mutated after a checkpoint was taken, and restores the snapshot when the
+/// parser rewinds to that checkpoint.
+///
+/// Snapshots are taken by cloning the inner value, so this is best suited to small values or to values that are cheap
+/// to clone (such as persistent data structures or `Rc`s). A snapshot is dropped once no checkpoint can rewind to it:
+/// either because the parser rewound past it, or because the combinators that took those checkpoints (such as
+/// [`choice`](crate::primitive::choice), [`Parser::or_not`], and repetitions) succeeded.
+///
+/// For state that should survive failed branches, see [`Persistent`]. The two can be combined by using a tuple of
+/// states.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::Transactional};
+/// type Extra = extra::State<Transactional<Vec<char>>>;
+///
+/// let bang = any::<_, Extra>()
+///     .map_with(|c, e| e.state().push(c))
+///     .then_ignore(just('!'));
+/// let question = any::<_, Extra>()
+///     .then(any())
+///     .map_with(|_, e| e.state().push('*'))
+///     .then_ignore(just('?'));
+/// let parser = bang.or(question).repeated().collect::<Vec<_>>();
+///
+/// let mut state = Transactional::new(Vec::new());
+/// parser.parse_with_state("a!ab?", &mut state).into_result().unwrap();
+/// // The `a` pushed by the first branch before `b` failed to match `!` was rolled back
+/// assert_eq!(*state, vec!['a', '*']);
+/// ```
+pub struct Transactional<T> {
+    value: T,
+    // Earlier versions of the value, indexed by version
+    snapshots: Vec<T>,
+    // The versions referred to by the checkpoints that may still be rewound to, in the order they were taken
+    live: RefCell<Vec<usize>>,
+}
+
+impl<T> Transactional<T> {
+    /// Create a new transactional state with the given initial value.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            snapshots: Vec::new(),
+            live: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Take the inner value out of this state.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Default> Default for Transactional<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for Transactional<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> Deref for Transactional<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: Clone> DerefMut for Transactional<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // The value is about to change: if a checkpoint refers to the current version, keep a copy of it
+        if self.live.get_mut().last() == Some(&self.snapshots.len()) {
+            self.snapshots.push(self.value.clone());
+        }
+        &mut self.value
+    }
+}
+
+impl<'src, T, I: Input<'src>> Inspector<'src, I> for Transactional<T> {
+    // The version of the value, and the position of the checkpoint among those that are live
+    type Checkpoint = (usize, usize);
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+        let mut live = self.live.borrow_mut();
+        live.push(self.snapshots.len());
+        (self.snapshots.len(), live.len() - 1)
+    }
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        let (version, index) = *marker.inspector();
+        if version < self.snapshots.len() {
+            self.snapshots.truncate(version + 1);
+            self.value = self.snapshots.pop().unwrap();
+        }
+        // Checkpoints taken after this one can no longer be rewound to, but this one may be rewound to again
+        self.live.get_mut().truncate(index + 1);
+    }
+    #[inline(always)]
+    fn on_commit<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        let (_, index) = *marker.inspector();
+        // Checkpoints are committed in the reverse order that they were taken, so any taken after this one that were
+        // never committed can no longer be rewound to either
+        let live = self.live.get_mut();
+        live.truncate(index);
+        // Keep only the snapshots that the remaining checkpoints could rewind to
+        self.snapshots
+            .truncate(live.last().map_or(0, |version| version + 1));
+    }
+}
+
+/// A state type whose changes persist even when the parser backtracks.
+///
+/// This is the behaviour of all state that does not explicitly roll itself back on rewind, like [`SimpleState`].
+/// `Persistent` exists to make that intent explicit, and is typically combined with [`Transactional`] in a tuple of
+/// states, for things like lists of warnings that should be reported even if produced by a branch that later failed.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::{Persistent, Transactional}};
+/// type State = (Transactional<Vec<char>>, Persistent<Vec<&'static str>>);
+///
+/// let bang = any::<_, extra::State<State>>()
+///     .map_with(|c, e| {
+///         e.state().0.push(c);
+///         e.state().1.push("saw a char");
+///         c
+///     })
+///     .then_ignore(just('!'));
+/// let parser = bang.or(any().then_ignore(just('?')));
+///
+/// let mut state = (Transactional::new(Vec::new()), Persistent(Vec::new()));
+/// parser.parse_with_state("a?", &mut state).into_result().unwrap();
+/// assert!(state.0.is_empty());
+/// assert_eq!(*state.1, vec!["saw a char"]);
+/// ```
+pub struct Persistent<T>(pub T);
+
+impl<'src, T, I: Input<'src>> Inspector<'src, I> for Persistent<T> {
+    type Checkpoint = ();
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {}
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {}
+}
+
+impl<T> Deref for Persistent<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Persistent<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Persistent<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+macro_rules! impl_inspector_for_tuple {
+    () => {};
+    ($head:ident $head_cp:ident $($X:ident $Cp:ident)*) => {
+        impl_inspector_for_tuple!($($X $Cp)*);
+        impl_inspector_for_tuple!(~ $head $head_cp $($X $Cp)*);
+    };
+    (~ $($X:ident $Cp:ident)+) => {
+        #[allow(non_snake_case)]
+        impl<'src, I: Input<'src>, $($X: Inspector<'src, I>),*> Inspector<'src, I> for ($($X,)*) {
+            type Checkpoint = ($($X::Checkpoint,)*);
+            #[inline(always)]
+            fn on_token(&mut self, token: &<I as Input<'src>>::Token) {
+                let ($($X,)*) = self;
+                $($X.on_token(token);)*
+            }
+            #[inline(always)]
+            fn on_save<'parse>(&self, cursor: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+                let ($($X,)*) = self;
+                ($($X.on_save(cursor),)*)
+            }
+            #[inline(always)]
+            fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+                let ($($X,)*) = self;
+                let ($($Cp,)*) = *marker.inspector();
+                $($X.on_rewind(&marker.map_inspector(|_| $Cp));)*
+            }
+            #[inline(always)]
+            fn on_commit<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+                let ($($X,)*) = self;
+                let ($($Cp,)*) = *marker.inspector();
+                $($X.on_commit(&marker.map_inspector(|_| $Cp));)*
+            }
+            #[inline(always)]
+            fn on_cover_enter(&mut self, name: &'static str) {
+                let ($($X,)*) = self;
+                $($X.on_cover_enter(name);)*
+            }
+            #[inline(always)]
+            fn on_cover_exit(&mut self, name: &'static str, matched: bool) {
+                let ($($X,)*) = self;
+                $($X.on_cover_exit(name, matched);)*
+            }
+        }
+    };
+}
+
+impl_inspector_for_tuple!(A_ a_ B_ b_ C_ c_ D_ d_ E_ e_ F_ f_ G_ g_ H_ h_);
+
+/// A state type that tracks a stack of lexer modes, for use with [`Parser::in_mode`] and [`Parser::when_mode`].
+///
+/// Many real-world lexers are *moded*: the rules that apply inside a string literal, a template literal, or a regex
+/// literal are different from those that apply elsewhere. `ModeStack` keeps track of which mode is currently active,
+/// and is rewound automatically when the parser backtracks.
+///
+/// The stack always contains at least one mode, the base mode it was created with. Modes should only be pushed and
+/// popped via [`Parser::in_mode`], which guarantees that every push is balanced by a pop, even if parsing fails.
+///
+/// If your parser needs other state too, embed a `ModeStack` in your own state type and implement
+/// [`BorrowMut<ModeStack<M>>`](core::borrow::BorrowMut) for it, forwarding the [`Inspector`] hooks.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::ModeStack};
+/// #[derive(Clone, PartialEq)]
+/// enum Mode { Code, Str }
+///
+/// let token = recursive::<_, _, extra::State<ModeStack<Mode>>, _, _>(|token| {
+///     let word = text::ascii::ident().when_mode(Mode::Code);
+///     // Inside a string, whitespace is significant and quotes are not the start of a nested string
+///     let chars = none_of('"').repeated().at_least(1).to_slice().when_mode(Mode::Str);
+///     let string = token
+///         .repeated()
+///         .in_mode(Mode::Str)
+///         .delimited_by(just('"'), just('"'))
+///         .to_slice()
+///         .when_mode(Mode::Code);
+///     choice((string, word, chars))
+/// });
+///
+/// let lexer = token.padded().repeated().collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     lexer
+///         .parse_with_state(r#"let x "hello world" y"#, &mut ModeStack::new(Mode::Code))
+///         .into_result(),
+///     Ok::<_, Vec<EmptyErr>>(vec!["let", "x", r#""hello world""#, "y"]),
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModeStack<M> {
+    modes: Vec<M>,
+}
+
+impl<M> ModeStack<M> {
+    /// Create a new mode stack with the given base mode.
+    pub fn new(base: M) -> Self {
+        Self {
+            modes: alloc::vec![base],
+        }
+    }
+
+    /// Get the currently active mode.
+    pub fn current(&self) -> &M {
+        self.modes
+            .last()
+            .expect("mode stack should always contain a base mode")
+    }
+
+    /// Get the number of modes on the stack, including the base mode.
+    pub fn depth(&self) -> usize {
+        self.modes.len()
+    }
+
+    /// Push a new mode onto the stack, making it the current mode.
+    ///
+    /// Prefer [`Parser::in_mode`], which pops the mode again when the parser it wraps finishes.
+    pub fn push(&mut self, mode: M) {
+        self.modes.push(mode);
+    }
+
+    /// Pop the current mode off the stack, returning it.
+    ///
+    /// The base mode can never be popped: if it is the only mode on the stack, `None` is returned.
+    pub fn pop(&mut self) -> Option<M> {
+        if self.modes.len() > 1 {
+            self.modes.pop()
+        } else {
+            None
+        }
+    }
+}
+
+impl<M: Default> Default for ModeStack<M> {
+    fn default() -> Self {
+        Self::new(M::default())
+    }
+}
+
+impl<'src, M, I: Input<'src>> Inspector<'src, I> for ModeStack<M> {
+    type Checkpoint = usize;
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {
+        self.modes.len()
+    }
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {
+        self.modes.truncate((*marker.inspector()).max(1));
+    }
+}
+
+/// A state type that supports nested scopes, for use with [`Parser::with_scope`].
+pub trait ScopedState {
+    /// Enter a new, innermost scope.
+    fn push_scope(&mut self);
+
+    /// Leave the innermost scope, discarding anything that was declared within it.
+    fn pop_scope(&mut self);
+}
+
+/// A state type that maps names to values within nested scopes, for grammars that need to resolve names while
+/// parsing.
+///
+/// The classic example is C, in which `(a) * b` is either a cast or a multiplication depending on whether `a` has
+/// been declared as a type. Names are declared with [`SymbolTable::declare`], and scopes are entered and left with
+/// [`Parser::with_scope`]. Lookups see the innermost declaration of a name.
+///
+/// `SymbolTable` implements [`StateCheckpoint`], so declarations made by a branch of the parser that later fails are
+/// undone automatically when the parser backtracks.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::SymbolTable};
+/// type Extra<'src> = extra::State<SymbolTable<&'src str, u32>>;
+///
+/// let ident = text::ascii::ident::<_, Extra>().padded();
+/// let decl = text::ascii::keyword("let")
+///     .ignore_then(ident)
+///     .then_ignore(just('='))
+///     .then(text::int(10).from_str().unwrapped().padded())
+///     .map_with(|(name, value), e| e.state().declare(name, value));
+/// // Look up the value of a name, which must have been declared
+/// let use_ = ident.try_map_with(|name, e| {
+///     e.state()
+///         .get(&name)
+///         .copied()
+///         .ok_or_else(|| EmptyErr::default())
+/// });
+///
+/// let block = recursive(|block| {
+///     let stmt = decl.map(|_| None).or(use_.map(Some)).or(block.map(|_| None));
+///     stmt.then_ignore(just(';').padded())
+///         .repeated()
+///         .collect::<Vec<_>>()
+///         .with_scope()
+///         .delimited_by(just('{').padded(), just('}'))
+/// });
+///
+/// let mut table = SymbolTable::new();
+/// assert_eq!(
+///     block.parse_with_state("{ let x = 1; { let x = 2; x; }; x; }", &mut table).into_result(),
+///     Ok(vec![None, None, Some(1)]),
+/// );
+/// // Names that go out of scope can no longer be used
+/// assert!(block.parse_with_state("{ { let y = 1; }; y; }", &mut table).has_errors());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SymbolTable<K, V> {
+    symbols: HashMap<K, Vec<V>>,
+    // Every declaration, in order, so that they can be undone
+    journal: Vec<K>,
+    // The length of the journal when each scope was entered
+    scopes: Vec<usize>,
+}
+
+impl<K: Hash + Eq + Clone, V> SymbolTable<K, V> {
+    /// Create a new, empty symbol table with a single (global) scope.
+    pub fn new() -> Self {
+        Self {
+            symbols: HashMap::default(),
+            journal: Vec::new(),
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Declare a name in the innermost scope, shadowing any existing declaration of it.
+    pub fn declare(&mut self, name: K, value: V) {
+        self.symbols.entry(name.clone()).or_default().push(value);
+        self.journal.push(name);
+    }
+
+    /// Get the value of the innermost declaration of a name, if it has been declared.
+    pub fn get<Q>(&self, name: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.symbols.get(name).and_then(|values| values.last())
+    }
+
+    /// Returns `true` if the name has been declared in any enclosing scope.
+    pub fn contains<Q>(&self, name: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(name).is_some()
+    }
+
+    /// Get the number of scopes that have been entered, not counting the global scope.
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    // Undo declarations until only `len` remain
+    fn unwind(&mut self, len: usize) {
+        while self.journal.len() > len {
+            let name = self.journal.pop().unwrap();
+            if let Some(values) = self.symbols.get_mut(&name) {
+                values.pop();
+                if values.is_empty() {
+                    self.symbols.remove(&name);
+                }
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for SymbolTable<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> ScopedState for SymbolTable<K, V> {
+    fn push_scope(&mut self) {
+        self.scopes.push(self.journal.len());
+    }
+
+    fn pop_scope(&mut self) {
+        if let Some(len) = self.scopes.pop() {
+            self.unwind(len);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> StateCheckpoint for SymbolTable<K, V> {
+    type Checkpoint = (usize, usize);
+
+    fn save(&self) -> Self::Checkpoint {
+        (self.journal.len(), self.scopes.len())
+    }
+
+    fn restore(&mut self, (len, depth): Self::Checkpoint) {
+        // Checkpoints are only rewound to while the parser that saved them is still running, so declarations made in
+        // scopes that have been left since the checkpoint was saved never need to be brought back
+        self.unwind(len);
+        self.scopes.truncate(depth);
+    }
+}
+
+/// A state type that can intern values, for use with [`Parser::interned`].
+///
+/// Interning replaces a value with an equal value that has been seen before, such that identical values share their
+/// storage. See [`HashConsTable`] for an implementation.
+pub trait Interner<T> {
+    /// Intern a value, returning an equal value that may share storage with values that were interned previously.
+    fn intern(&mut self, value: T) -> T;
+}
+
+/// An interner that can be used through a shared reference, such that it can be shared by many parses at once (see
+/// [`Shared`]).
+///
+/// This is implemented for [`RwLock`](std::sync::RwLock)s and [`Mutex`](std::sync::Mutex)es of any [`Interner`].
+/// Interning through a [`RwLock`](std::sync::RwLock) of a [`HashConsTable`] only takes the write lock when a value
+/// is seen for the first time, which suits the read-mostly nature of most interners.
+pub trait SharedInterner<T> {
+    /// Intern a value, returning an equal value that may share storage with values that were interned previously.
+    fn intern_shared(&self, value: T) -> T;
+}
+
+#[cfg(feature = "std")]
+impl<T, N: Interner<T>> SharedInterner<T> for std::sync::Mutex<N> {
+    fn intern_shared(&self, value: T) -> T {
+        // Interning never leaves the table in an inconsistent state, so poisoning can be ignored
+        let mut interner = self.lock().unwrap_or_else(|err| err.into_inner());
+        interner.intern(value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash + Eq + Clone> SharedInterner<T> for std::sync::RwLock<HashConsTable<T>> {
+    fn intern_shared(&self, value: T) -> T {
+        let table = self.read().unwrap_or_else(|err| err.into_inner());
+        if let Some(value) = table.values.get(&value) {
+            return value.clone();
+        }
+        drop(table);
+        let mut table = self.write().unwrap_or_else(|err| err.into_inner());
+        table.intern(value)
+    }
+}
+
+impl<T: SharedInterner<U> + ?Sized, S, U> Interner<U> for Shared<'_, T, S> {
+    fn intern(&mut self, value: U) -> U {
+        self.shared.intern_shared(value)
+    }
+}
+
+/// A state type that hash-conses values: it remembers every distinct value that it interns, and replaces values that it
+/// has seen before with a clone of the remembered value.
+///
+/// This is most useful when values are cheap to clone and share their contents, such as [`Rc`](alloc::rc::Rc)s of AST
+/// nodes. When parsing highly repetitive input (machine-generated code, for example), identical subtrees then share a
+/// single allocation, keeping memory bounded by the number of *distinct* subtrees rather than the size of the input.
+///
+/// Values interned by branches of the parser that later fail remain in the table. This is harmless, because they can
+/// only ever be shared with equal values.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::HashConsTable};
+/// use std::rc::Rc;
+///
+/// #[derive(Debug, PartialEq, Eq, Hash)]
+/// enum Tree {
+///     Leaf(u32),
+///     Node(Vec<Rc<Tree>>),
+/// }
+///
+/// type Extra = extra::State<HashConsTable<Rc<Tree>>>;
+///
+/// let tree = recursive::<_, _, Extra, _, _>(|tree| {
+///     let leaf = text::int(10).from_str().unwrapped().map(Tree::Leaf);
+///     let node = tree
+///         .separated_by(just(','))
+///         .collect()
+///         .delimited_by(just('['), just(']'))
+///         .map(Tree::Node);
+///     leaf.or(node).map(Rc::new).interned()
+/// });
+///
+/// let mut table = HashConsTable::new();
+/// let root = tree.parse_with_state("[[1,2],[1,2],3]", &mut table).unwrap();
+/// let Tree::Node(children) = &*root else { panic!() };
+/// // Both copies of `[1,2]` share the same allocation
+/// assert!(Rc::ptr_eq(&children[0], &children[1]));
+/// // 1, 2, 3, [1,2], and the root
+/// assert_eq!(table.len(), 5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashConsTable<T> {
+    values: HashSet<T>,
+}
+
+impl<T: Hash + Eq + Clone> HashConsTable<T> {
+    /// Create a new, empty table.
+    pub fn new() -> Self {
+        Self {
+            values: HashSet::default(),
+        }
+    }
+
+    /// Get the number of distinct values in the table.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no values have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Forget every value in the table.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+impl<T: Hash + Eq + Clone> Default for HashConsTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq + Clone> Interner<T> for HashConsTable<T> {
+    fn intern(&mut self, value: T) -> T {
+        self.values.get_or_insert(value).clone()
+    }
+}
+
+impl<'src, T, I: Input<'src>> Inspector<'src, I> for HashConsTable<T> {
+    type Checkpoint = ();
+    #[inline(always)]
+    fn on_token(&mut self, _: &<I as Input<'src>>::Token) {}
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> Self::Checkpoint {}
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, Self::Checkpoint>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn transactional_drops_committed_snapshots() {
+        let attempt = |expected: char| {
+            any::<_, extra::State<Transactional<Vec<char>>>>()
+                .map_with(|c, e| e.state().push(c))
+                .then_ignore(just(expected))
+        };
+        let parser = choice((attempt('!'), attempt('?')))
+            .or_not()
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .then_ignore(just(';'))
+            .repeated()
+            .collect::<Vec<_>>();
+
+        let mut state = Transactional::new(Vec::new());
+        let input = "a?,b!,c?;".repeat(100);
+        assert!(!parser.parse_with_state(&*input, &mut state).has_errors());
+        assert_eq!(state.len(), 300);
+        // Only the snapshots that a checkpoint could still rewind to are kept
+        assert!(state.snapshots.is_empty());
+    }
+}
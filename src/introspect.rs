@@ -0,0 +1,203 @@
+//! Describing the structure of parsers.
+//!
+//! *“I think you ought to know I'm feeling very depressed.”*
+//!
+//! Parsers are ordinary Rust values, but most of chumsky's parsers can describe the combinators that they are built
+//! from. [`describe`] produces a tree of [`Node`]s that mirrors the structure of a parser, which is used to derive a
+//! [`Grammar`](crate::grammar::Grammar) from a parser (see [`Grammar::from_parser`](crate::grammar::Grammar::from_parser))
+//! and to profile the complexity of its type.
+//!
+//! Parsers whose structure can't be known, such as those created with [`custom`] or [`select!`], are described as
+//! [`NodeKind::Opaque`].
+//!
+//! # Examples
+//!
+//! ```
+//! use chumsky::{prelude::*, introspect::{describe, NodeKind}};
+//!
+//! let list = just::<_, &str, extra::Default>('a')
+//!     .separated_by(just(','))
+//!     .collect::<Vec<_>>()
+//!     .delimited_by(just('['), just(']'));
+//!
+//! let node = describe(&list, &|c: &char| c.to_string());
+//! assert_eq!(node.name, "DelimitedBy");
+//! assert_eq!(node.kind, NodeKind::Seq);
+//! assert_eq!(node.children[0].kind, NodeKind::Tokens(vec!["[".to_string()]));
+//! ```
+
+use super::*;
+
+/// The state used while describing a parser. See [`Parser::introspect`].
+pub struct Context<'a, 'src, I: Input<'src>> {
+    spell: &'a dyn Fn(&I::Token) -> String,
+    // The recursive parsers that have already been described, so that cycles are only described once
+    seen: Vec<usize>,
+}
+
+impl<'a, 'src, I: Input<'src>> Context<'a, 'src, I> {
+    /// Create a context that spells tokens with the given function.
+    pub fn new(spell: &'a dyn Fn(&I::Token) -> String) -> Self {
+        Self {
+            spell,
+            seen: Vec::new(),
+        }
+    }
+
+    /// Spell a token, as it should be displayed.
+    pub fn spell(&self, token: &I::Token) -> String {
+        (self.spell)(token)
+    }
+
+    // Returns true the first time that the recursive parser with the given identity is seen
+    pub(crate) fn first_visit(&mut self, id: usize) -> bool {
+        if self.seen.contains(&id) {
+            false
+        } else {
+            self.seen.push(id);
+            true
+        }
+    }
+}
+
+/// A description of a parser, as produced by [`describe`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    /// The name of the parser's type, without its module path or generic parameters (such as `"Then"`).
+    pub name: &'static str,
+    /// What the parser does.
+    pub kind: NodeKind,
+    /// The parsers that this parser is built from, in the order that they appear in the input.
+    pub children: Vec<Node>,
+}
+
+/// What a parser does, as described by a [`Node`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NodeKind {
+    /// A parser whose structure is not known. It has no children.
+    Opaque,
+    /// A parser that behaves like its only child, perhaps transforming its output or errors (such as
+    /// [`Parser::map`]).
+    Wrapper,
+    /// Matches nothing (see [`empty`]).
+    Empty,
+    /// Matches the end of the input (see [`end`]).
+    End,
+    /// Matches any single token (see [`any`]).
+    Any,
+    /// Matches a sequence of tokens, spelled one by one (see [`just`]).
+    Tokens(Vec<String>),
+    /// Matches any one of the tokens, spelled one by one (see [`one_of`]).
+    OneOf(Vec<String>),
+    /// Matches each child, one after another (see [`Parser::then`]).
+    Seq,
+    /// Matches any one of the children (see [`choice`]).
+    Choice,
+    /// Matches its only child, or nothing (see [`Parser::or_not`]).
+    Optional,
+    /// Matches its first child repeatedly, separated by its second child if it has one (see [`Parser::repeated`] and
+    /// [`Parser::separated_by`]).
+    Repeated {
+        /// The minimum number of repetitions.
+        at_least: usize,
+    },
+    /// A named rule of a grammar (see [`grammar::named`]).
+    Rule(String),
+    /// A parser with an erased type (see [`Parser::boxed`]).
+    Boxed,
+    /// A recursive parser (see [`recursive()`]), with an identity shared by all handles to the same parser. Only the
+    /// first handle to be described has a child: the others are references back to it.
+    Recursive(usize),
+}
+
+impl Node {
+    /// Create a node.
+    pub fn new(name: &'static str, kind: NodeKind, children: Vec<Node>) -> Self {
+        Self {
+            name,
+            kind,
+            children,
+        }
+    }
+
+    /// Create an [opaque](NodeKind::Opaque) node for a parser of the given type.
+    pub fn opaque(type_name: &'static str) -> Self {
+        // Strip generic parameters and module paths, leaving `Custom` from `chumsky::primitive::Custom<...>`
+        let path = type_name.split('<').next().unwrap_or(type_name);
+        let name = path.rsplit("::").next().unwrap_or(path);
+        Self::new(name, NodeKind::Opaque, Vec::new())
+    }
+
+    /// The number of nodes that make up this node, including itself.
+    pub fn size(&self) -> usize {
+        1 + self.children.iter().map(Node::size).sum::<usize>()
+    }
+}
+
+/// Describe the structure of a parser, spelling the tokens that it matches with the given function.
+pub fn describe<'src, P, I, O, E>(parser: &P, spell: &dyn Fn(&I::Token) -> String) -> Node
+where
+    P: Parser<'src, I, O, E> + ?Sized,
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    parser.introspect(&mut Context::new(spell))
+}
+
+// Describe the tokens of a sequence
+pub(crate) fn spell_seq<'src, 'p, I, T>(cx: &Context<'_, 'src, I>, seq: &T) -> Vec<String>
+where
+    I: Input<'src>,
+    T: Seq<'p, I::Token> + ?Sized,
+{
+    seq.seq_iter().map(|tok| cx.spell(tok.borrow())).collect()
+}
+
+// Describe a parser that behaves like the given parser
+pub(crate) fn wrapper(name: &'static str, child: Node) -> Node {
+    Node::new(name, NodeKind::Wrapper, vec![child])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spell(c: &char) -> String {
+        c.to_string()
+    }
+
+    #[test]
+    fn recursive_cycles_described_once() {
+        let parser = recursive::<&str, (), extra::Default, _, _>(|item| {
+            item.delimited_by(just('('), just(')'))
+                .or(just('x').ignored())
+        });
+        let node = describe(&parser, &spell);
+
+        let NodeKind::Recursive(id) = node.kind else {
+            panic!("expected a recursive node, found {:?}", node.kind);
+        };
+        let choice = &node.children[0];
+        assert_eq!(choice.kind, NodeKind::Choice);
+        let inner = &choice.children[0].children[1];
+        assert_eq!(inner.kind, NodeKind::Recursive(id));
+        assert!(inner.children.is_empty());
+    }
+
+    #[test]
+    fn unknown_parsers_are_opaque() {
+        let parser = custom::<_, &str, (), extra::Default>(|_| Ok(())).then(just("ab"));
+        let node = describe(&parser, &spell);
+
+        assert_eq!(
+            node.children[0],
+            Node::new("Custom", NodeKind::Opaque, Vec::new())
+        );
+        assert_eq!(
+            node.children[1].kind,
+            NodeKind::Tokens(vec!["a".into(), "b".into()])
+        );
+        assert_eq!(node.size(), 3);
+    }
+}
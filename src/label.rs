@@ -120,5 +120,9 @@ where
         res
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Labelled", self.parser.introspect(cx))
+    }
+
     go_extra!(O);
 }
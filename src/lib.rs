@@ -33,9 +33,13 @@ macro_rules! go_extra {
     };
 }
 
+#[cfg(feature = "bench")]
+pub mod bench;
 mod blanket;
 #[cfg(feature = "unstable")]
 pub mod cache;
+#[cfg(feature = "codespan-reporting")]
+mod codespan;
 pub mod combinator;
 pub mod container;
 #[cfg(feature = "either")]
@@ -44,13 +48,19 @@ pub mod error;
 #[cfg(feature = "extension")]
 pub mod extension;
 pub mod extra;
+pub mod grammar;
 #[cfg(docsrs)]
 pub mod guide;
 pub mod input;
 pub mod inspector;
+pub mod introspect;
 pub mod label;
+#[cfg(feature = "lsp-types")]
+mod lsp;
 #[cfg(feature = "lexical-numbers")]
 pub mod number;
+#[cfg(feature = "ops")]
+pub mod ops;
 #[cfg(feature = "pratt")]
 pub mod pratt;
 pub mod primitive;
@@ -64,6 +74,9 @@ mod stream;
 pub mod text;
 #[cfg(feature = "bytes")]
 mod tokio;
+pub mod trace;
+#[cfg(feature = "unstable")]
+pub mod unparse;
 pub mod util;
 
 /// Commonly used functions, traits and types.
@@ -80,18 +93,21 @@ pub mod prelude {
         extra,
         input::Input,
         primitive::{
-            any, any_ref, choice, custom, empty, end, group, just, map_ctx, none_of, one_of, todo,
+            any, any_ref, balanced, choice, custom, disambiguate, empty, end, group, just,
+            just_seq, length_prefixed, map_ctx, none_of, none_of_seq, one_of, postfix_chain,
+            switch_on, take_until, todo,
         },
         recovery::{nested_delimiters, skip_then_retry_until, skip_until, via_parser},
-        recursive::{recursive, Recursive},
+        recursive::{lazy, recursive, Recursive},
         span::{SimpleSpan, Span as _},
-        text, Boxed, ConfigIterParser, ConfigParser, IterParser, ParseResult, Parser,
+        text, Boxed, ConfigIterParser, ConfigParser, Erased, IterParser, ParseResult, Parser,
     };
-    pub use crate::{select, select_ref};
+    pub use crate::{dispatch, recursive_group, select, select_ref, sequence, terminals};
 }
 
 use crate::input::InputOwn;
 use alloc::{
+    borrow::Cow,
     boxed::Box,
     rc::{self, Rc},
     string::String,
@@ -101,30 +117,31 @@ use alloc::{
 #[cfg(feature = "nightly")]
 use core::marker::Tuple;
 use core::{
-    borrow::Borrow,
+    borrow::{Borrow, BorrowMut},
     cell::{Cell, RefCell},
     cmp::{Eq, Ord, Ordering},
     fmt,
     hash::Hash,
     marker::PhantomData,
     mem::MaybeUninit,
-    ops::{Range, RangeFrom},
+    ops::{ControlFlow, Range, RangeFrom},
     panic::Location,
     str::FromStr,
 };
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 #[cfg(feature = "serde")]
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "pratt")]
+use self::inspector::Inspector;
 use self::{
     combinator::*,
     container::*,
-    error::Error,
+    error::{CodeError, Error, ErrorIdentity},
     extra::ParserExtra,
     input::{
         BorrowInput, Emitter, ExactSizeInput, InputRef, MapExtra, SliceInput, StrInput, ValueInput,
     },
-    inspector::Inspector,
     label::{LabelError, Labelled},
     prelude::*,
     primitive::Any,
@@ -171,12 +188,27 @@ pub(crate) type DynOperator<'src, 'b, I, O, E> = dyn pratt::Operator<'src, I, O,
 pub enum DefaultExpected<'a, T> {
     /// A specific token was expected.
     Token(MaybeRef<'a, T>),
+    /// A specific sequence of tokens was expected.
+    ///
+    /// See [`just_seq`](primitive::just_seq).
+    Tokens(Vec<MaybeRef<'a, T>>),
     /// Anything other than the end of input was expected.
     Any,
     /// Something other than the provided input was expected.
     SomethingElse,
     /// The end of input was expected.
     EndOfInput,
+    /// A different number of repeated items was expected.
+    ///
+    /// See [`SeparatedBy::strict_count`](combinator::SeparatedBy::strict_count) and [`IterParser::collect_exactly`].
+    Count {
+        /// The minimum number of items expected.
+        at_least: usize,
+        /// The maximum number of items expected, if any.
+        at_most: Option<usize>,
+        /// The number of items that were found.
+        found: usize,
+    },
 }
 
 impl<T> DefaultExpected<'_, T> {
@@ -188,9 +220,21 @@ impl<T> DefaultExpected<'_, T> {
     {
         match self {
             Self::Token(tok) => DefaultExpected::Token(tok.into_owned()),
+            Self::Tokens(toks) => {
+                DefaultExpected::Tokens(toks.into_iter().map(MaybeRef::into_owned).collect())
+            }
             Self::Any => DefaultExpected::Any,
             Self::SomethingElse => DefaultExpected::SomethingElse,
             Self::EndOfInput => DefaultExpected::EndOfInput,
+            Self::Count {
+                at_least,
+                at_most,
+                found,
+            } => DefaultExpected::Count {
+                at_least,
+                at_most,
+                found,
+            },
         }
     }
 }
@@ -252,6 +296,66 @@ impl<T, E> ParseResult<T, E> {
         (self.output, self.errs)
     }
 
+    /// Merge duplicate errors, as identified by [`ErrorIdentity`], keeping the position of the first of each.
+    ///
+    /// Duplicate errors can arise when the same region of input is parsed more than once, such as by an embedded
+    /// parser (see [`Parser::embedded_in`]) or by several layers of validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, input::{Emitter, MapExtra}};
+    /// fn check<'src>(
+    ///     c: char,
+    ///     e: &mut MapExtra<'src, '_, &'src str, extra::Err<Rich<'src, char>>>,
+    ///     emitter: &mut Emitter<Rich<'src, char>>,
+    /// ) -> char {
+    ///     if c == '0' {
+    ///         emitter.emit(Rich::custom(e.span(), "zero is not allowed"));
+    ///     }
+    ///     c
+    /// }
+    ///
+    /// // Both layers of validation report the same problem
+    /// let digit = one_of::<_, _, extra::Err<Rich<char>>>('0'..='9').validate(check).validate(check);
+    ///
+    /// assert_eq!(digit.parse("0").into_errors().len(), 2);
+    /// assert_eq!(digit.parse("0").dedup_errors().into_errors().len(), 1);
+    /// ```
+    pub fn dedup_errors(mut self) -> Self
+    where
+        E: ErrorIdentity,
+    {
+        let mut seen = HashMap::<E::Id, usize>::new();
+        let mut errs = Vec::<E>::with_capacity(self.errs.len());
+        for err in self.errs.drain(..) {
+            match seen.entry(err.identity()) {
+                hashbrown::hash_map::Entry::Occupied(entry) => {
+                    errs[*entry.get()].merge_duplicate(err)
+                }
+                hashbrown::hash_map::Entry::Vacant(slot) => {
+                    slot.insert(errs.len());
+                    errs.push(err);
+                }
+            }
+        }
+        self.errs = errs;
+        self
+    }
+
+    /// Remove duplicate errors, as identified by the given key function, keeping the first of each.
+    ///
+    /// This is like [`ParseResult::dedup_errors`], but allows the identity of errors to be chosen for each parse.
+    pub fn dedup_errors_by_key<K, F>(mut self, mut key: F) -> Self
+    where
+        K: Eq + Hash,
+        F: FnMut(&E) -> K,
+    {
+        let mut seen = HashSet::new();
+        self.errs.retain(|err| seen.insert(key(err)));
+        self
+    }
+
     /// Convert this `ParseResult` into a standard `Result`. This discards output if parsing generated any errors,
     /// matching the old behavior of [`Parser::parse`].
     pub fn into_result(self) -> Result<T, Vec<E>> {
@@ -282,6 +386,227 @@ impl<T, E> ParseResult<T, E> {
     }
 }
 
+/// Returned when a parse was abandoned before it completed, such as by [`Parser::parse_with_progress`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "parsing was cancelled")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Cancelled {}
+
+/// The sending half of a channel, into which [`IterParser::parse_into_channel`] can send outputs.
+///
+/// This is implemented for [`std::sync::mpsc::SyncSender`] and [`std::sync::mpsc::Sender`] and, with the
+/// `crossbeam-channel` feature, for [`crossbeam_channel::Sender`].
+#[cfg(feature = "std")]
+pub trait ChannelSender<T> {
+    /// Send a value, blocking until there is room for it in the channel.
+    ///
+    /// If the receiving half of the channel has hung up, the value is returned as an error.
+    fn send(&self, value: T) -> Result<(), T>;
+}
+
+#[cfg(feature = "std")]
+impl<T> ChannelSender<T> for std::sync::mpsc::SyncSender<T> {
+    fn send(&self, value: T) -> Result<(), T> {
+        std::sync::mpsc::SyncSender::send(self, value).map_err(|err| err.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ChannelSender<T> for std::sync::mpsc::Sender<T> {
+    fn send(&self, value: T) -> Result<(), T> {
+        std::sync::mpsc::Sender::send(self, value).map_err(|err| err.0)
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl<T> ChannelSender<T> for crossbeam_channel::Sender<T> {
+    fn send(&self, value: T) -> Result<(), T> {
+        crossbeam_channel::Sender::send(self, value).map_err(|err| err.0)
+    }
+}
+
+/// Limits on the resources that a parse may use. See [`Parser::parse_with_limits`].
+///
+/// By default, no limits are imposed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Limits {
+    pub(crate) fuel: Option<usize>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) max_recoveries: Option<usize>,
+    pub(crate) max_input_len: Option<usize>,
+}
+
+impl Limits {
+    /// Limit the amount of work that a parse may perform.
+    ///
+    /// One unit of fuel is consumed by each iteration of a repetition (like [`Parser::repeated`]), by each recursive
+    /// call (see [`recursive()`]) or operand of a pratt parser, and by each token skipped by error recovery.
+    pub fn fuel(self, fuel: usize) -> Self {
+        Self {
+            fuel: Some(fuel),
+            ..self
+        }
+    }
+
+    /// Limit how deeply recursive parsers (see [`recursive()`]) and the operands of pratt parsers may nest.
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        Self {
+            max_depth: Some(max_depth),
+            ..self
+        }
+    }
+
+    /// Limit the number of errors that may be recovered from (see [`Parser::recover_with`]).
+    pub fn max_recoveries(self, max_recoveries: usize) -> Self {
+        Self {
+            max_recoveries: Some(max_recoveries),
+            ..self
+        }
+    }
+
+    /// Limit how long the input may be, measured in the cursor locations of the input (see
+    /// [`Input::cursor_location`]).
+    ///
+    /// Cursor locations are opaque: they increase as the input is consumed, but need not correspond to the offsets of
+    /// the input's spans, so the limit should be chosen with the input type in mind. The length is checked before
+    /// parsing begins, looking no further into the input than the limit.
+    pub fn max_input_len(self, max_input_len: usize) -> Self {
+        Self {
+            max_input_len: Some(max_input_len),
+            ..self
+        }
+    }
+}
+
+/// Returned when a parse exceeded one of its [`Limits`], reporting which limit was exceeded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LimitExceeded {
+    /// The parse ran out of fuel (see [`Limits::fuel`]).
+    Fuel,
+    /// Recursive parsers nested too deeply (see [`Limits::max_depth`]).
+    Depth,
+    /// Too many errors were recovered from (see [`Limits::max_recoveries`]).
+    Recoveries,
+    /// The input was too long (see [`Limits::max_input_len`]).
+    InputLen,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Fuel => write!(f, "parsing ran out of fuel"),
+            Self::Depth => write!(f, "maximum recursion depth exceeded"),
+            Self::Recoveries => write!(f, "maximum number of recovered errors exceeded"),
+            Self::InputLen => write!(f, "maximum input length exceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LimitExceeded {}
+
+/// Statistics about a parse, gathered by [`Parser::parse_with_stats`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseStats {
+    /// How far into the input the parse got, as an opaque cursor location (see [`Input::cursor_location`]).
+    ///
+    /// This increases as the input is consumed, but need not correspond to the offsets of the input's spans, so it is
+    /// only meaningful when compared with the `consumed` of other parses of the same kind of input.
+    pub consumed: usize,
+    /// The deepest that recursive parsers (see [`recursive()`]) and the operands of pratt parsers were nested.
+    pub max_depth: usize,
+    /// The number of times that the parser backtracked to an earlier position in the input.
+    pub backtracks: usize,
+    /// The number of errors that were recovered from (see [`Parser::recover_with`]).
+    pub recovered: usize,
+    /// How long the parse took.
+    #[cfg(feature = "std")]
+    pub duration: std::time::Duration,
+}
+
+impl fmt::Display for ParseStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "consumed {}, max depth {}, {} backtracks, {} recovered errors",
+            self.consumed, self.max_depth, self.backtracks, self.recovered,
+        )?;
+        #[cfg(feature = "std")]
+        write!(f, " in {:?}", self.duration)?;
+        Ok(())
+    }
+}
+
+/// Run a parser over an input, invoking the given hook at safe points (see [`Parser::parse_with_progress`]).
+fn parse_with_safe_point<'src, P, I, O, E>(
+    parser: &P,
+    input: I,
+    safe_point: &mut dyn input::SafePoint,
+) -> ParseResult<O, E::Error>
+where
+    P: Parser<'src, I, O, E>,
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::State: Default,
+    E::Context: Default,
+{
+    let mut state = E::State::default();
+    let mut own = InputOwn::new_state(input, &mut state).with_safe_point(safe_point);
+    let mut inp = own.as_ref_start();
+    let res = inp
+        .check_input_len()
+        .and_then(|()| parser.then_ignore(end()).go::<Emit>(&mut inp));
+    // Give the hook a chance to see how far the parse got, even if it contained no safe points
+    let _ = inp.safe_point();
+    let alt = inp.take_alt().map(|alt| alt.err).unwrap_or_else(|| {
+        let fake_span = inp.span_since(&inp.cursor());
+        E::Error::expected_found([], None, fake_span)
+    });
+    let mut errs = own.into_errs();
+    let out = match res {
+        Ok(out) => Some(out),
+        Err(()) => {
+            errs.push(alt);
+            None
+        }
+    };
+    ParseResult::new(out, errs)
+}
+
+/// A token that can be used to cancel a parse from elsewhere, such as another thread.
+///
+/// Clones of a token share the same cancellation flag: cancelling any of them cancels parses using the others. See
+/// [`Parser::parse_with_cancellation`].
+#[cfg(target_has_atomic = "ptr")]
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(alloc::sync::Arc<core::sync::atomic::AtomicBool>);
+
+#[cfg(target_has_atomic = "ptr")]
+impl CancellationToken {
+    /// Create a new token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel any parses using this token, or any of its clones.
+    pub fn cancel(&self) {
+        self.0.store(true, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Whether this token, or any of its clones, has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(core::sync::atomic::Ordering::Relaxed)
+    }
+}
+
 /// A trait implemented by parsers.
 ///
 /// Parsers take inputs of type `I`, which will implement [`Input`]. Refer to the documentation on [`Input`] for examples
@@ -334,6 +659,13 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
     #[doc(hidden)]
     fn go_check(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<Check, O>;
 
+    /// Describe the structure of this parser. See [`introspect::describe`].
+    #[doc(hidden)]
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        let _ = cx;
+        introspect::Node::opaque(core::any::type_name::<Self>())
+    }
+
     /// Parse a stream of tokens, yielding an output if possible, and any errors encountered along the way.
     ///
     /// If `None` is returned (i.e: parsing failed) then there will *always* be at least one item in the error `Vec`.
@@ -382,6 +714,199 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         ParseResult::new(out, errs)
     }
 
+    /// Parse a stream of tokens like [`Parser::parse`], periodically reporting progress to a callback that may choose
+    /// to abandon the parse.
+    ///
+    /// `on_progress` is called every few hundred *safe points*: iterations of repetitions (like [`Parser::repeated`]
+    /// and [`Parser::separated_by`]) and recursive calls (see [`recursive()`]). If it returns [`ControlFlow::Break`],
+    /// parsing stops as quickly as possible and [`Cancelled`] is returned.
+    ///
+    /// `on_progress` is given the current position in the input as an opaque cursor location (see
+    /// [`Input::cursor_location`]). This increases as the input is consumed, but need not correspond to the offsets of
+    /// the input's spans, so it is only meaningful when compared with other positions passed to the callback.
+    ///
+    /// This is useful for hosts that need to stay responsive while parsing very large inputs, such as GUIs or
+    /// browsers (via WASM): the callback can update a progress indicator, check whether the user has asked for the
+    /// parse to be cancelled, or check whether a time budget has been exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, Cancelled};
+    /// # use core::ops::ControlFlow;
+    /// let digits = one_of::<_, _, extra::Default>('0'..='9').repeated().collect::<String>();
+    /// let input = "7".repeat(100_000);
+    ///
+    /// // Track how far through the input we are
+    /// let mut positions = Vec::new();
+    /// let out = digits.parse_with_progress(input.as_str(), |pos| {
+    ///     positions.push(pos);
+    ///     ControlFlow::Continue(())
+    /// });
+    /// assert_eq!(out.map(|res| res.into_result().map(|s| s.len())), Ok(Ok(100_000)));
+    /// assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    ///
+    /// // Give up after a few progress reports
+    /// let mut reports = 0;
+    /// let out = digits.parse_with_progress(input.as_str(), |_| {
+    ///     reports += 1;
+    ///     if reports > 10 {
+    ///         ControlFlow::Break(())
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(out, Err(Cancelled));
+    /// ```
+    fn parse_with_progress<F>(
+        &self,
+        input: I,
+        on_progress: F,
+    ) -> Result<ParseResult<O, E::Error>, Cancelled>
+    where
+        Self: Sized,
+        I: Input<'src>,
+        E::State: Default,
+        E::Context: Default,
+        F: FnMut(usize) -> ControlFlow<()>,
+    {
+        let mut progress = input::Progress::new(on_progress);
+        let res = parse_with_safe_point(self, input, &mut progress);
+        if progress.cancelled {
+            Err(Cancelled)
+        } else {
+            Ok(res)
+        }
+    }
+
+    /// Parse a stream of tokens like [`Parser::parse`], abandoning the parse if the given token is cancelled.
+    ///
+    /// The token is checked periodically at the same safe points as [`Parser::parse_with_progress`]. If it has been
+    /// cancelled, parsing stops as quickly as possible and [`Cancelled`] is returned.
+    ///
+    /// This is useful for services like language servers, which may want to abandon a parse of a stale version of a
+    /// file when a new edit arrives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, Cancelled, CancellationToken};
+    /// let digits = one_of::<_, _, extra::Default>('0'..='9').repeated().collect::<String>();
+    /// let input = "7".repeat(100_000);
+    ///
+    /// let token = CancellationToken::new();
+    /// assert!(digits.parse_with_cancellation(input.as_str(), &token).is_ok());
+    ///
+    /// // Usually the token would be cancelled from another thread while the parse is ongoing
+    /// token.clone().cancel();
+    /// assert_eq!(digits.parse_with_cancellation(input.as_str(), &token), Err(Cancelled));
+    /// ```
+    #[cfg(target_has_atomic = "ptr")]
+    fn parse_with_cancellation(
+        &self,
+        input: I,
+        token: &CancellationToken,
+    ) -> Result<ParseResult<O, E::Error>, Cancelled>
+    where
+        Self: Sized,
+        I: Input<'src>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        self.parse_with_progress(input, |_| {
+            if token.is_cancelled() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+    }
+
+    /// Parse a stream of tokens like [`Parser::parse`], abandoning the parse if it exceeds any of the given
+    /// [`Limits`].
+    ///
+    /// This is intended for parsers exposed to untrusted input, where a malicious input might otherwise cause a parse
+    /// to consume an unreasonable amount of time or memory (or to overflow the stack). All limits are deterministic:
+    /// the same input and limits will always produce the same result, regardless of the machine that the parse runs
+    /// on.
+    ///
+    /// If a limit is exceeded, parsing stops as quickly as possible and [`LimitExceeded`] is returned, reporting which
+    /// limit was exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, Limits, LimitExceeded};
+    /// let evil = "[".repeat(1_000) + &"]".repeat(1_000);
+    ///
+    /// let nested = recursive::<_, _, extra::Default, _, _>(|nested| nested
+    ///     .delimited_by(just('['), just(']'))
+    ///     .or_not()
+    ///     .ignored());
+    ///
+    /// let limits = Limits::default().max_depth(64);
+    ///
+    /// assert!(nested.parse_with_limits("[[[]]]", limits).is_ok());
+    /// assert_eq!(nested.parse_with_limits(evil.as_str(), limits), Err(LimitExceeded::Depth));
+    /// ```
+    fn parse_with_limits(
+        &self,
+        input: I,
+        limits: Limits,
+    ) -> Result<ParseResult<O, E::Error>, LimitExceeded>
+    where
+        Self: Sized,
+        I: Input<'src>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut bounded = input::Bounded::new(limits);
+        let res = parse_with_safe_point(self, input, &mut bounded);
+        match bounded.exceeded {
+            Some(exceeded) => Err(exceeded),
+            None => Ok(res),
+        }
+    }
+
+    /// Parse a stream of tokens like [`Parser::parse`], also returning [`ParseStats`] describing the parse.
+    ///
+    /// This allows services to export metrics about the parses they perform (such as how much backtracking was
+    /// necessary) without needing to instrument their grammar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let nested = recursive::<_, _, extra::Default, _, _>(|nested| nested
+    ///     .delimited_by(just('('), just(')'))
+    ///     .repeated());
+    ///
+    /// let (res, stats) = nested.parse_with_stats("(())()");
+    /// assert!(!res.has_errors());
+    /// assert!(stats.consumed > 0);
+    /// // One level for the whole input, and one for each level of parentheses
+    /// assert_eq!(stats.max_depth, 3);
+    /// assert_eq!(stats.recovered, 0);
+    /// println!("{stats}");
+    /// ```
+    fn parse_with_stats(&self, input: I) -> (ParseResult<O, E::Error>, ParseStats)
+    where
+        Self: Sized,
+        I: Input<'src>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut stats = input::Stats::default();
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
+        let res = parse_with_safe_point(self, input, &mut stats);
+        #[cfg(feature = "std")]
+        {
+            stats.stats.duration = start.elapsed();
+        }
+        (res, stats.stats)
+    }
+
     /// Parse a stream of tokens, ignoring any output, and returning any errors encountered along the way.
     ///
     /// If parsing failed, then there will *always* be at least one item in the returned `Vec`.
@@ -789,6 +1314,53 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         }
     }
 
+    /// Parse a trailing field (such as a checksum or CRC) after this parser, then validate the raw input consumed by
+    /// this parser against it.
+    ///
+    /// The validation function is given the slice of the input that this parser consumed, the output of `trailer`, and
+    /// the span of the trailer. If it produces an error, parsing fails with that error, which is reported at the start
+    /// of the trailer, so that corrupted frames produce a targeted error rather than an obscure failure later on.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // Three bytes of data, followed by a checksum byte
+    /// let frame = any::<&[u8], extra::Err<Rich<u8>>>()
+    ///     .repeated()
+    ///     .exactly(3)
+    ///     .collect::<Vec<_>>()
+    ///     .checksummed(any(), |data: &[u8], sum, span| {
+    ///         if data.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) == sum {
+    ///             Ok(())
+    ///         } else {
+    ///             Err(Rich::custom(span, "checksum mismatch"))
+    ///         }
+    ///     });
+    ///
+    /// assert_eq!(frame.parse(&[1, 2, 3, 6]).into_result(), Ok(vec![1, 2, 3]));
+    ///
+    /// let errs = frame.parse(&[1, 2, 3, 7]).into_errors();
+    /// assert_eq!(errs[0].to_string(), "checksum mismatch");
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(3..4));
+    /// ```
+    fn checksummed<T, OT, F>(self, trailer: T, check: F) -> Checksummed<Self, T, OT, F>
+    where
+        Self: Sized,
+        I: SliceInput<'src>,
+        T: Parser<'src, I, OT, E>,
+        F: Fn(I::Slice, OT, I::Span) -> Result<(), E::Error>,
+    {
+        Checksummed {
+            parser: self,
+            trailer,
+            check,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Ignore the output of this parser, yielding `()` as an output instead.
     ///
     /// This can be used to reduce the cost of parsing by avoiding unnecessary allocations (most collections containing
@@ -888,6 +1460,36 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         }
     }
 
+    /// Give the errors generated by this parser a stable code, like `E0012`.
+    ///
+    /// Codes allow projects to document their diagnostics and users to search for or suppress particular kinds of
+    /// error. When parsers with codes are nested, the innermost code is used. Errors that already existed before this
+    /// parser was invoked are not affected. See [`Rich::code`] and [`Rich::code_by_label`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let digits = text::digits::<_, extra::Err<Rich<char>>>(10).to_slice();
+    /// let version = digits
+    ///     .separated_by(just('.').with_code("E0101"))
+    ///     .exactly(3)
+    ///     .to_slice()
+    ///     .with_code("E0100")
+    ///     .then_ignore(end());
+    ///
+    /// assert_eq!(version.parse("1.2.3").into_result(), Ok("1.2.3"));
+    /// assert_eq!(version.parse("1.x").into_errors()[0].code(), Some("E0100"));
+    /// assert_eq!(version.parse("1.2-3").into_errors()[0].code(), Some("E0101"));
+    /// ```
+    fn with_code(self, code: &'static str) -> WithCode<Self>
+    where
+        Self: Sized,
+        E::Error: CodeError,
+    {
+        WithCode { parser: self, code }
+    }
+
     /// Parse one thing and then another thing, yielding a tuple of the two outputs.
     ///
     /// The output type of this parser is `(O, U)`, a combination of the outputs of both parsers.
@@ -895,6 +1497,9 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
     /// If you instead only need the output of __one__ of the parsers, use [`ignore_then`](Self::ignore_then)
     /// or [`then_ignore`](Self::then_ignore).
     ///
+    /// Chaining many calls to `then` produces nested tuples like `(((A, B), C), D)`. If you want a flat tuple like
+    /// `(A, B, C, D)` instead, use [`group`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -1067,6 +1672,76 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         }
     }
 
+    /// Parse an embedded language, like SQL within a string literal or CSS within an HTML `<style>` tag, with this
+    /// parser.
+    ///
+    /// `region` is run first, and outputs the input of the embedded language (usually the slice of the outer input that
+    /// it matched, obtained with [`Parser::to_slice`]). This parser is then run on that input, and must consume all of
+    /// it. Unlike [`Parser::nested_in`], this parser may have a different error type to the outer parser: its errors
+    /// are converted with `map_err`, and then annotated with `label` as their context (see
+    /// [`LabelError::in_context`]), so that they are reported as occurring in the embedded language. The parser state
+    /// and context are shared between the two parsers.
+    ///
+    /// `map_err` is given each error of the embedded parser, along with the span of `region` within the outer input.
+    /// Spans within the embedded input are relative to the start of the embedded input, so when `region` outputs the
+    /// slice that it matched they can be moved into the outer input by offsetting them by the start of the region
+    /// (see [`Rich::map_span`]).
+    ///
+    /// The output type of this parser is `O`, the output of the embedded parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // An embedded language, with its own error type
+    /// let select = text::ascii::keyword::<_, _, extra::Err<Rich<char>>>("select")
+    ///     .ignore_then(text::ascii::ident().padded())
+    ///     .map(str::to_string);
+    ///
+    /// let query = select
+    ///     .embedded_in(
+    ///         none_of('"').repeated().to_slice(),
+    ///         "embedded SQL",
+    ///         |err: Rich<char>, region: SimpleSpan| {
+    ///             err.map_span(|span| (region.start + span.start..region.start + span.end).into())
+    ///         },
+    ///     )
+    ///     .delimited_by(just('"'), just('"'));
+    ///
+    /// let stmt = just::<_, _, extra::Err<Rich<char>>>("query ").ignore_then(query);
+    ///
+    /// assert_eq!(stmt.parse(r#"query "select users""#).into_result(), Ok("users".to_string()));
+    ///
+    /// let errs = stmt.parse(r#"query "select 42""#).into_errors();
+    /// // The error is in the outer input's coordinates, with the embedded language as its context
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(14..15));
+    /// assert_eq!(errs[0].contexts().next().unwrap().0.to_string(), "embedded SQL");
+    /// ```
+    fn embedded_in<B, J, F, L, G>(
+        self,
+        region: B,
+        label: L,
+        map_err: G,
+    ) -> EmbeddedIn<Self, B, L, G, I, E, O>
+    where
+        Self: Sized,
+        B: Parser<'src, J, I, F>,
+        J: Input<'src>,
+        F: ParserExtra<'src, J, State = E::State, Context = E::Context>,
+        L: Clone,
+        G: Fn(E::Error, J::Span) -> F::Error,
+        J::Span: Clone,
+        F::Error: LabelError<'src, J, L>,
+    {
+        EmbeddedIn {
+            parser: self,
+            region,
+            label,
+            map_err,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Parse one thing and then another thing, creating the second parser from the result of
     /// the first. If you do need the context in the output, use [`Parser::then_with_ctx`].
     ///
@@ -1156,18 +1831,203 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         WithCtx { parser: self, ctx }
     }
 
-    /// TODO
-    fn with_state<State>(self, state: State) -> WithState<Self, State>
-    where
-        Self: Sized,
-        State: 'src + Clone,
-    {
-        WithState {
-            parser: self,
-            state,
-        }
-    }
-
+    /// Run this parser with a context derived from the enclosing one by the given function.
+    ///
+    /// This is the method form of [`map_ctx`](primitive::map_ctx): where [`Parser::with_ctx`] replaces the context
+    /// entirely with a fixed value, this computes the context of this parser from whatever context it is invoked
+    /// with, which is how inherited attributes (the current indentation level, the operator fixities in scope, etc.)
+    /// are threaded through a grammar. The new context may have a different type to the enclosing one.
+    ///
+    /// # Context and backtracking
+    ///
+    /// Contexts are lexically scoped: the derived context only exists while this parser runs and the enclosing
+    /// context is back in place as soon as it returns, whether it succeeded or failed. Contexts are never part of the
+    /// parser state, so rewinding the input (for example, when [`Parser::or`] tries another alternative) does not
+    /// need to undo anything, and the mapping function is simply called again each time this parser is entered.
+    /// Mutable data that must be unwound on backtracking belongs in the parser state instead (see
+    /// [`Transactional`](extra::Transactional)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // Parses a run of `x`s whose length is given by the context
+    /// let xs = just::<_, &str, extra::Context<usize>>('x')
+    ///     .repeated()
+    ///     .configure(|cfg, n: &usize| cfg.exactly(*n))
+    ///     .to_slice();
+    ///
+    /// // A digit followed by that many `x`s: the digit is passed down as a `char` and converted to a count
+    /// let run = one_of::<_, _, extra::Default>('0'..='9')
+    ///     .ignore_with_ctx(xs.map_ctx(|c: &char| c.to_digit(10).unwrap() as usize))
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(run.parse("2xx,0,3xxx").into_result(), Ok(vec!["xx", "", "xxx"]));
+    /// assert!(run.parse("2xxx").has_errors());
+    /// ```
+    fn map_ctx<EO, F>(self, f: F) -> primitive::MapCtx<Self, E, F, EO>
+    where
+        Self: Sized,
+        EO: ParserExtra<'src, I, Error = E::Error, State = E::State>,
+        F: Fn(&EO::Context) -> E::Context,
+        E::Context: 'src,
+    {
+        primitive::map_ctx(f, self)
+    }
+
+    /// TODO
+    fn with_state<State>(self, state: State) -> WithState<Self, State>
+    where
+        Self: Sized,
+        State: 'src + Clone,
+    {
+        WithState {
+            parser: self,
+            state,
+        }
+    }
+
+    /// Run this parser with the given lexer mode pushed onto the [`ModeStack`](extra::ModeStack) in the parser's
+    /// state, popping it again afterwards (whether or not the parser succeeded).
+    ///
+    /// This is usually used around delimiters: the body of a string literal, for example, might be parsed with a
+    /// 'string' mode active so that parsers guarded by [`Parser::when_mode`] behave accordingly.
+    ///
+    /// See [`ModeStack`](extra::ModeStack) for an example.
+    fn in_mode<Md>(self, mode: Md) -> InMode<Self, Md>
+    where
+        Self: Sized,
+        Md: Clone,
+        E::State: BorrowMut<extra::ModeStack<Md>>,
+    {
+        InMode { parser: self, mode }
+    }
+
+    /// Run this parser inside a new scope of the parser's state, leaving the scope again afterwards (whether or not
+    /// the parser succeeded).
+    ///
+    /// This is usually used for blocks, function bodies, and other constructs that introduce names that should not be
+    /// visible outside them. See [`SymbolTable`](extra::SymbolTable) for an example.
+    fn with_scope(self) -> WithScope<Self>
+    where
+        Self: Sized,
+        E::State: inspector::ScopedState,
+    {
+        WithScope { parser: self }
+    }
+
+    /// Pass the output of this parser through the [`Interner`](inspector::Interner) in the parser's state.
+    ///
+    /// This is usually applied to the parsers that build AST nodes, so that identical subtrees are hash-consed into a
+    /// single shared value. Interning is skipped when the output of the parser is not needed. See
+    /// [`HashConsTable`](extra::HashConsTable) for an example.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    fn interned(self) -> Interned<Self>
+    where
+        Self: Sized,
+        E::State: inspector::Interner<O>,
+    {
+        Interned { parser: self }
+    }
+
+    /// Only attempt this parser if the currently active lexer mode in the parser's
+    /// [`ModeStack`](extra::ModeStack) state is equal to the given mode, failing otherwise.
+    ///
+    /// When the mode does not match, the parser fails without consuming input or generating any expected patterns,
+    /// so it does not pollute the errors produced by other branches of a [`choice`].
+    ///
+    /// See [`ModeStack`](extra::ModeStack) for an example.
+    fn when_mode<Md>(self, mode: Md) -> WhenMode<Self, Md>
+    where
+        Self: Sized,
+        Md: PartialEq,
+        E::State: BorrowMut<extra::ModeStack<Md>>,
+    {
+        WhenMode { parser: self, mode }
+    }
+
+    /// Run this parser with a context derived from the current one by the given function.
+    ///
+    /// Unlike [`map_ctx`], the type of the context stays the same, so this is convenient for adjusting flags in the
+    /// context for part of a grammar, to be queried with [`Parser::when_ctx`]. A common example is Swift- or
+    /// Kotlin-style trailing block arguments: `f { ... }` passes a block to `f`, but in `if f { ... }` the block is
+    /// the body of the `if`, so block arguments must be disabled while parsing the condition (and re-enabled inside
+    /// any delimiters within it).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Expr<'src> {
+    ///     Var(&'src str),
+    ///     Call(Box<Self>, Vec<Self>),
+    ///     If(Box<Self>, Vec<Self>),
+    /// }
+    ///
+    /// // The context is `true` when trailing blocks are not allowed
+    /// let expr = recursive(|expr| {
+    ///     let block = expr
+    ///         .clone()
+    ///         .repeated()
+    ///         .collect::<Vec<_>>()
+    ///         .delimited_by(just('{').padded(), just('}').padded())
+    ///         // Trailing blocks are always allowed inside braces
+    ///         .update_ctx(|_| false);
+    ///
+    ///     let var = text::ascii::ident::<_, extra::Context<bool>>().padded();
+    ///     let call = var
+    ///         .map(Expr::Var)
+    ///         .then(block.clone().when_ctx(|no_block: &bool| !*no_block).or_not())
+    ///         .map(|(f, block)| match block {
+    ///             Some(block) => Expr::Call(Box::new(f), block),
+    ///             None => f,
+    ///         });
+    ///     let if_ = text::ascii::keyword("if")
+    ///         .padded()
+    ///         .ignore_then(expr.update_ctx(|_| true))
+    ///         .then(block)
+    ///         .map(|(cond, body)| Expr::If(Box::new(cond), body));
+    ///
+    ///     if_.or(call)
+    /// });
+    ///
+    /// assert_eq!(
+    ///     expr.parse("f { x }").into_result(),
+    ///     Ok(Expr::Call(Box::new(Expr::Var("f")), vec![Expr::Var("x")])),
+    /// );
+    /// assert_eq!(
+    ///     expr.parse("if f { g { x } }").into_result(),
+    ///     Ok(Expr::If(
+    ///         Box::new(Expr::Var("f")),
+    ///         vec![Expr::Call(Box::new(Expr::Var("g")), vec![Expr::Var("x")])],
+    ///     )),
+    /// );
+    /// ```
+    fn update_ctx<F>(self, f: F) -> UpdateCtx<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&E::Context) -> E::Context,
+    {
+        UpdateCtx { parser: self, f }
+    }
+
+    /// Only attempt this parser if the given predicate holds for the current context, failing otherwise.
+    ///
+    /// When the predicate does not hold, the parser fails without consuming input or generating any expected
+    /// patterns, so it does not pollute the errors produced by other branches of a [`choice`].
+    ///
+    /// See [`Parser::update_ctx`] for an example.
+    fn when_ctx<F>(self, pred: F) -> WhenCtx<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&E::Context) -> bool,
+    {
+        WhenCtx { parser: self, pred }
+    }
+
     /// Applies both parsers to the same position in the input, succeeding
     /// only if both succeed. The returned value will be that of the first parser,
     /// and the input will be at the end of the first parser if `and_is` succeeds.
@@ -1344,6 +2204,41 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         }
     }
 
+    /// Parse one thing or, on failure, another thing that may have a different output type.
+    ///
+    /// This behaves like [`Parser::or`], but instead of requiring both parsers to have the same output type, the
+    /// output of the first parser is wrapped in [`Either::Left`](::either::Either::Left) and the output of the second
+    /// in [`Either::Right`](::either::Either::Right). This avoids the need to invent an enum just to combine two
+    /// alternatives.
+    ///
+    /// The output type of this parser is `Either<O, U>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use either::Either;
+    ///
+    /// let int = text::int::<_, extra::Err<Simple<char>>>(10).from_str::<u64>().unwrapped();
+    /// let ident = text::ascii::ident();
+    /// let arg = int.or_different(ident);
+    ///
+    /// assert_eq!(arg.parse("42").into_result(), Ok(Either::Left(42)));
+    /// assert_eq!(arg.parse("foo").into_result(), Ok(Either::Right("foo")));
+    /// assert!(arg.parse("!").has_errors());
+    /// ```
+    #[cfg(feature = "either")]
+    fn or_different<U, B>(self, other: B) -> OrDifferent<Self, B>
+    where
+        Self: Sized,
+        B: Parser<'src, I, U, E>,
+    {
+        OrDifferent {
+            parser_a: self,
+            parser_b: other,
+        }
+    }
+
     /// Attempt to parse something, but only if it exists.
     ///
     /// If parsing of the pattern is successful, the output is `Some(_)`. Otherwise, the output is `None`.
@@ -1372,6 +2267,56 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         OrNot { parser: self }
     }
 
+    /// Attempt to parse something, falling back to a value derived from the context if it does not exist.
+    ///
+    /// This is like [`Parser::or_not`], except that a missing pattern produces the output of `default` (which is given
+    /// the current context) rather than `None`. This is useful for optional elements whose value is otherwise
+    /// inherited from an enclosing construct, avoiding the need to thread [`Option`]s into later passes.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // An item's visibility is inherited from its enclosing module unless specified
+    /// let vis = choice((
+    ///     text::ascii::keyword::<_, _, extra::Context<&str>>("pub").to("public"),
+    ///     text::ascii::keyword("priv").to("private"),
+    /// ))
+    ///     .padded()
+    ///     .or_default_ctx(|inherited: &&str| *inherited);
+    ///
+    /// let item = vis.then(text::ascii::ident().padded());
+    ///
+    /// let module = choice((
+    ///     text::ascii::keyword::<_, _, extra::Default>("pub").to("public"),
+    ///     text::ascii::keyword("mod").to("private").rewind(),
+    /// ))
+    ///     .padded()
+    ///     .then_ignore(text::ascii::keyword("mod").padded())
+    ///     .ignore_with_ctx(item.repeated().collect::<Vec<_>>());
+    ///
+    /// assert_eq!(
+    ///     module.parse("pub mod priv a b").into_result(),
+    ///     Ok(vec![("private", "a"), ("public", "b")]),
+    /// );
+    /// assert_eq!(
+    ///     module.parse("mod a pub b").into_result(),
+    ///     Ok(vec![("private", "a"), ("public", "b")]),
+    /// );
+    /// ```
+    fn or_default_ctx<F>(self, default: F) -> OrDefaultCtx<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&E::Context) -> O,
+    {
+        OrDefaultCtx {
+            parser: self,
+            default,
+        }
+    }
+
     /// Invert the result of the contained parser, failing if it succeeds and succeeding if it fails.
     /// The output of this parser is always `()`, the unit type.
     ///
@@ -1441,6 +2386,46 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         }
     }
 
+    /// Require that this parser consumes input whenever it succeeds.
+    ///
+    /// Parsers that can succeed without consuming input (such as [`text::whitespace`] or anything made optional
+    /// with [`Parser::or_not`]) are a common source of infinite loops when placed inside [`Parser::repeated`] or
+    /// [`Parser::separated_by`]. This combinator guards against that: if the inner parser succeeds without consuming
+    /// any input, a panic (when debug assertions are enabled) or a parse failure (otherwise) occurs at the point of
+    /// the mistake, rather than somewhere further up in the grammar.
+    ///
+    /// The resulting parser implements [`NonEmptyParser`], allowing it to be used with [`Repeated::strict`] and
+    /// [`SeparatedBy::strict`].
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let word = text::ascii::ident::<_, extra::Err<Simple<char>>>().non_empty();
+    ///
+    /// let words = word
+    ///     .padded()
+    ///     .non_empty()
+    ///     .repeated()
+    ///     .strict()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(words.parse("hello world").into_result(), Ok(vec!["hello", "world"]));
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn non_empty(self) -> NonEmpty<Self>
+    where
+        Self: Sized,
+    {
+        NonEmpty {
+            parser: self,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+        }
+    }
+
     /// Parse a pattern zero or more times (analog to Regex's `<PAT>*`).
     ///
     /// Input is eagerly parsed. Be aware that the parser will accept no occurrences of the pattern too. Consider using
@@ -1515,6 +2500,7 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
             at_most: !0,
             allow_leading: false,
             allow_trailing: false,
+            strict_count: false,
             #[cfg(debug_assertions)]
             location: *Location::caller(),
             phantom: EmptyPhantom::new(),
@@ -1726,6 +2712,43 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         Padded { parser: self }
     }
 
+    /// Parse a pattern, ignoring whitespace before and after it only if the [`Padding`] policy `P`
+    /// says so.
+    ///
+    /// With [`text::FreeForm`], this behaves like [`Parser::padded`]. With [`text::Strict`], this behaves like the
+    /// original parser. Making a grammar generic over the policy allows the same rules to be used for both a strict,
+    /// whitespace-significant format and a lenient, human-friendly one without duplicating them.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::text::{Padding, FreeForm, Strict};
+    ///
+    /// fn assignment<'src, P: Padding>() -> impl Parser<'src, &'src str, (&'src str, &'src str)> {
+    ///     let ident = text::ascii::ident().maybe_padded::<P>();
+    ///     ident.then_ignore(just('=')).then(ident)
+    /// }
+    ///
+    /// assert_eq!(assignment::<FreeForm>().parse(" a = b ").into_result(), Ok(("a", "b")));
+    /// assert_eq!(assignment::<Strict>().parse("a=b").into_result(), Ok(("a", "b")));
+    /// assert!(assignment::<Strict>().parse(" a = b ").has_errors());
+    /// ```
+    fn maybe_padded<P>(self) -> text::MaybePadded<Self, P>
+    where
+        Self: Sized,
+        I: Input<'src>,
+        I::Token: Char,
+        P: text::Padding,
+    {
+        text::MaybePadded {
+            parser: self,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     // /// Flatten a nested collection.
     // ///
     // /// This use-cases of this method are broadly similar to those of [`Iterator::flatten`].
@@ -1983,6 +3006,9 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
     /// The output type of this parser is `Result<U, U::Err>`, the result of attempting to parse the output, `O`, into
     /// the value `U`.
     ///
+    /// This is usually followed by [`Parser::or_fail`], which reports a failed conversion as a parsing error, or by
+    /// [`Parser::unwrapped`] if the conversion cannot fail.
+    ///
     /// # Examples
     ///
     /// ```
@@ -2008,7 +3034,8 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
     /// encountered).
     ///
     /// In general, this method should be avoided except in cases where all possible that the parser might produce can
-    /// by parsed using [`FromStr`] without producing an error.
+    /// by parsed using [`FromStr`] without producing an error. Otherwise, use [`Parser::or_fail`] to turn the error
+    /// into a parsing error.
     ///
     /// This combinator is not named `unwrap` to avoid confusion: it unwraps *during parsing*, not immediately.
     ///
@@ -2040,6 +3067,43 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         }
     }
 
+    /// For parsers that produce a [`Result`] as their output, treat an [`Err`] as a parsing error generated by the
+    /// given function, instead of panicking like [`Parser::unwrapped`].
+    ///
+    /// The function is given the error and the span of the input that produced it, so conversion errors (like an
+    /// integer literal that is out of range) become diagnostics pointing at the offending input. This should generally
+    /// be preferred over [`Parser::unwrapped`] unless the conversion cannot fail.
+    ///
+    /// The output type of this parser is `U`, the [`Ok`] value of the [`Result`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let byte = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .from_str::<u8>()
+    ///     .or_fail(|err, span| Rich::custom(span, format!("invalid byte: {err}")))
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(byte.parse("1 2 255").into_result(), Ok(vec![1, 2, 255]));
+    ///
+    /// let errs = byte.parse("1 256 3").into_errors();
+    /// assert_eq!(errs[0].to_string(), "invalid byte: number too large to fit in target type");
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(2..5));
+    /// ```
+    fn or_fail<F>(self, f: F) -> OrFail<Self, O, F>
+    where
+        Self: Sized,
+    {
+        OrFail {
+            parser: self,
+            fail: f,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Turn this [`Parser`] into an [`IterParser`] if its output type implements [`IntoIterator`].
     ///
     /// The resulting iterable parser will emit each element of the output type in turn.
@@ -2171,6 +3235,45 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
         }
     }
 
+    /// Erase the type of the parser by borrowing it, yielding a parser that performs parsing through dynamic dispatch.
+    ///
+    /// This is a lighter alternative to [`Parser::boxed`] for cutting down compile times in very large grammars. No
+    /// allocation takes place and the resulting [`Erased`] parser is [`Copy`], but it borrows the original parser and so
+    /// cannot outlive it.
+    ///
+    /// Most of the compile time of a large grammar is spent on the deeply nested types produced by combinators, since
+    /// every rule that uses another rule embeds its type. Placing `erased()` at rule boundaries stops this growth: rules
+    /// that accept an [`Erased`] parser are compiled once rather than once per type of the rule passed to them. See the
+    /// technical notes in the guide for more details.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // This rule is compiled once, no matter how many different atom parsers it is used with
+    /// fn list<'src, 'b>(
+    ///     atom: Erased<'src, 'b, &'src str, u32>,
+    /// ) -> impl Parser<'src, &'src str, Vec<u32>> + 'b {
+    ///     atom.padded()
+    ///         .separated_by(just(','))
+    ///         .collect()
+    ///         .delimited_by(just('['), just(']'))
+    /// }
+    ///
+    /// let num = text::int(10).from_str().unwrapped();
+    /// let parser = list(num.erased());
+    ///
+    /// assert_eq!(parser.parse("[1, 2, 3]").into_result(), Ok(vec![1, 2, 3]));
+    /// ```
+    fn erased(&self) -> Erased<'src, '_, I, O, E>
+    where
+        Self: Sized,
+    {
+        Erased { inner: self }
+    }
+
     /// Simplify the type of the parser using Rust's `impl Trait` syntax.
     ///
     /// The only reason for using this function is to make Rust's compiler errors easier to debug: it does not change
@@ -2223,7 +3326,53 @@ pub trait Parser<'src, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Defau
     where
         Self: Sized,
     {
-        pratt::Pratt { atom: self, ops }
+        pratt::Pratt {
+            atom: self,
+            ops,
+            recovery: (),
+        }
+    }
+
+    /// Create a [Pratt parser](Parser::pratt) by adding operators one at a time with a builder.
+    ///
+    /// This is an alternative to passing a tuple of operators to [`Parser::pratt`] that scales better to grammars with
+    /// many operators. Each operator is [boxed](pratt::Operator::boxed), which keeps the type of the parser (and the
+    /// compiler errors that mention it) small, and the types of the fold functions' arguments can be inferred from the
+    /// atom.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::pratt::*;
+    ///
+    /// let int = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .from_str::<i64>()
+    ///     .unwrapped()
+    ///     .padded();
+    ///
+    /// let op = |c| just(c).padded();
+    ///
+    /// let expr = int
+    ///     .pratt_builder()
+    ///     .prefix(3, op('-'), |_, x, _| -x)
+    ///     .postfix(4, op('!'), |x, _, _| (1..=x).product())
+    ///     .infix(right(2), op('^'), |x, _, y, _| x.pow(y as u32))
+    ///     .infix(left(1), op('*'), |x, _, y, _| x * y)
+    ///     .infix(left(0), op('+'), |x, _, y, _| x + y)
+    ///     .op(infix(left(0), op('-'), |x, _, y, _| x - y).labelled("operator"))
+    ///     .build();
+    ///
+    /// assert_eq!(expr.parse("2 + 3 * 2 ^ 2 - -1").into_result(), Ok(15));
+    /// assert_eq!(expr.parse("3! - 1").into_result(), Ok(5));
+    /// ```
+    #[cfg(feature = "pratt")]
+    fn pratt_builder<'a>(self) -> pratt::PrattBuilder<'src, 'a, Self, I, O, E>
+    where
+        Self: Sized,
+        O: 'src,
+    {
+        pratt::PrattBuilder::new(self)
     }
 }
 
@@ -2240,15 +3389,30 @@ where
     go_extra!(O);
 }
 
-/// A [`Parser`] that can be configured with runtime context.
+/// A [`Parser`] that is guaranteed to consume input whenever it succeeds.
 ///
-/// This allows for context-sensitive parsing
-/// of input. Note that chumsky only supports 'left'-sensitive parsing, where the context for a parser
-/// is derived from earlier in the input.
+/// Repeating such a parser can never result in an infinite loop. [`Repeated::strict`] and [`SeparatedBy::strict`]
+/// use this trait to check this property at compile time.
 ///
-/// Chumsky distinguishes 'state' from 'context'. State is not able to change what input a parser
-/// accepts, but may be used to change the contents of the type it emits. In this way state is expected
-/// to be idempotent - combinators such as [`Parser::map_with`] are allowed to not call the
+/// This trait is implemented by parsers that always consume a token, like [`any`] and
+/// [`one_of`], and by [`Parser::non_empty`], which can be used to mark any other parser as
+/// non-empty.
+pub trait NonEmptyParser<'src, I, O, E>: Parser<'src, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+}
+
+/// A [`Parser`] that can be configured with runtime context.
+///
+/// This allows for context-sensitive parsing
+/// of input. Note that chumsky only supports 'left'-sensitive parsing, where the context for a parser
+/// is derived from earlier in the input.
+///
+/// Chumsky distinguishes 'state' from 'context'. State is not able to change what input a parser
+/// accepts, but may be used to change the contents of the type it emits. In this way state is expected
+/// to be idempotent - combinators such as [`Parser::map_with`] are allowed to not call the
 /// provided closure at all if they don't emit any output. Context and configuration, on the other hand,
 /// is used to change what kind of input a parser may accept, and thus must always be evaluated. Context
 /// isn't usable in any map combinator however - while it may affect accepted input, it is not expected
@@ -2406,11 +3570,23 @@ where
         state: &mut Self::IterState<M>,
     ) -> IPResult<M, O>;
 
+    /// Describe the structure of this parser. See [`introspect::describe`].
+    #[doc(hidden)]
+    fn introspect_iter(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        let _ = cx;
+        introspect::Node::opaque(core::any::type_name::<Self>())
+    }
+
     /// Collect this iterable parser into a [`Container`].
     ///
     /// This is commonly useful for collecting parsers that output many values into containers of various kinds:
     /// [`Vec`]s, [`String`]s, or even [`HashMap`]s. This method is analogous to [`Iterator::collect`].
     ///
+    /// [`String`]s can be collected from `char`s, or from string slices without copying them character by character.
+    /// Collecting slices into a [`Cow<str>`](alloc::borrow::Cow) only allocates when there is more than one non-empty
+    /// slice, which avoids allocating for string literals that contain no escape sequences (see
+    /// [`IterParser::fold_slices`]).
+    ///
     /// The output type of this iterable parser is `C`, the type being collected into.
     ///
     /// # Examples
@@ -2430,17 +3606,89 @@ where
     {
         Collect {
             parser: self,
+            capacity: 0,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Collect this iterable parser into a [`Container`], pre-allocating space for `capacity` items.
+    ///
+    /// This behaves exactly like [`IterParser::collect`], but avoids the container being repeatedly grown when the
+    /// number of items is large and can be estimated in advance. The capacity is only a hint: more items may be
+    /// collected, and containers that do not support pre-allocation ignore it.
+    ///
+    /// The output type of this iterable parser is `C`, the type being collected into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let tokens = text::ascii::ident::<_, extra::Default>()
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect_with_capacity::<Vec<_>>(64);
+    ///
+    /// let out = tokens.parse("a b c").into_result().unwrap();
+    /// assert_eq!(out, vec!["a", "b", "c"]);
+    /// assert!(out.capacity() >= 64);
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn collect_with_capacity<C: Container<O>>(self, capacity: usize) -> Collect<Self, O, C>
+    where
+        Self: Sized,
+    {
+        Collect {
+            parser: self,
+            capacity,
             #[cfg(debug_assertions)]
             location: *Location::caller(),
             phantom: EmptyPhantom::new(),
         }
     }
 
+    /// Collect this iterable parser into a [`SmallVec`](smallvec::SmallVec) that can hold up to `N` items without
+    /// allocating.
+    ///
+    /// This is useful for lists that are usually very short, such as function arguments or generic parameters, where
+    /// allocating a [`Vec`] for every list is a significant cost.
+    ///
+    /// This is sugar for [`.collect::<SmallVec<[O; N]>>()`](Self::collect).
+    ///
+    /// The output type of this iterable parser is `SmallVec<[O; N]>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let args = text::ascii::ident::<_, extra::Default>()
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .collect_smallvec::<3>()
+    ///     .delimited_by(just('('), just(')'));
+    ///
+    /// let out = args.parse("(a, b)").into_result().unwrap();
+    /// assert_eq!(out.as_slice(), ["a", "b"]);
+    /// assert!(!out.spilled());
+    /// ```
+    #[cfg(feature = "smallvec")]
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn collect_smallvec<const N: usize>(self) -> Collect<Self, O, smallvec::SmallVec<[O; N]>>
+    where
+        Self: Sized,
+    {
+        self.collect()
+    }
+
     /// Collect this iterable parser into a [`ContainerExactly`].
     ///
     /// This is useful for situations where the number of items to consume is statically known.
     /// A common use-case is collecting into an array.
     ///
+    /// When there are too few items, the error for the next item is reported alongside the number of items that were
+    /// expected (see [`DefaultExpected::Count`]). Any items after the last are left for the next parser.
+    ///
     /// The output type of this iterable parser if `C`, the type being collected into.
     ///
     /// # Examples
@@ -2488,6 +3736,102 @@ where
         self.collect()
     }
 
+    /// Fold the outputs of this iterable parser into an accumulator, starting with `init`.
+    ///
+    /// Unlike [`IterParser::collect`] followed by [`Parser::map`], no intermediate container is ever built: each output
+    /// is passed to `f` as soon as it is parsed. This suits accumulator-style outputs like sums, string builders, or
+    /// inserting symbols into a table, and saves both the allocation and the second pass over the items.
+    ///
+    /// A clone of `init` is taken each time the parser runs. This method is analogous to [`Iterator::fold`].
+    ///
+    /// The output type of this parser is `B`, the type of the accumulator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let sum = text::int::<_, extra::Default>(10)
+    ///     .from_str::<u32>()
+    ///     .unwrapped()
+    ///     .separated_by(just('+').padded())
+    ///     .fold_repeated(0, |acc, x| acc + x);
+    ///
+    /// assert_eq!(sum.parse("1 + 2 + 39").into_result(), Ok(42));
+    /// assert_eq!(sum.parse("").into_result(), Ok(0));
+    ///
+    /// let shout = text::ascii::ident::<_, extra::Default>()
+    ///     .padded()
+    ///     .repeated()
+    ///     .fold_repeated(String::new(), |mut acc, word: &str| {
+    ///         acc.push_str(&word.to_uppercase());
+    ///         acc
+    ///     });
+    ///
+    /// assert_eq!(shout.parse("hello world").into_result(), Ok("HELLOWORLD".to_string()));
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn fold_repeated<B, F>(self, init: B, f: F) -> FoldRepeated<Self, O, B, F>
+    where
+        Self: Sized,
+        B: Clone,
+        F: Fn(B, O) -> B,
+    {
+        FoldRepeated {
+            parser: self,
+            init,
+            f,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Fold the pieces of a string output by this iterable parser, such as slices of the input and the text of escape
+    /// sequences, into a single [`Cow<str>`](alloc::borrow::Cow).
+    ///
+    /// This is a shorthand for `.collect::<Cow<str>>()`. The pieces are appended to one another without an intermediate
+    /// [`Vec`], and no allocation is made at all when there is only one non-empty piece, so string literals that contain
+    /// no escape sequences are borrowed from the input.
+    ///
+    /// The output type of this iterable parser is `Cow<'a, str>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use std::borrow::Cow;
+    ///
+    /// let escape = just::<_, _, extra::Default>('\\').ignore_then(choice((
+    ///     just('n').to("\n"),
+    ///     just('"').to("\""),
+    ///     just('\\').to("\\"),
+    /// )));
+    /// let string = none_of("\\\"")
+    ///     .repeated()
+    ///     .at_least(1)
+    ///     .to_slice()
+    ///     .or(escape)
+    ///     .repeated()
+    ///     .fold_slices()
+    ///     .delimited_by(just('"'), just('"'));
+    ///
+    /// // Borrowed from the input
+    /// assert!(matches!(string.parse(r#""hello""#).into_result(), Ok(Cow::Borrowed("hello"))));
+    /// // Built from the pieces `hello`, `\n`, and `world`
+    /// assert!(matches!(
+    ///     string.parse(r#""hello\nworld""#).into_result(),
+    ///     Ok(Cow::Owned(s)) if s == "hello\nworld",
+    /// ));
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn fold_slices<'a>(self) -> Collect<Self, O, Cow<'a, str>>
+    where
+        Self: Sized,
+        Cow<'a, str>: Container<O>,
+    {
+        self.collect()
+    }
+
     /// Enumerate outputs of this iterable parser.
     ///
     /// This function behaves in a similar way to [`Iterator::enumerate`].
@@ -2605,8 +3949,28 @@ where
         }
     }
 
-    /// TODO
-    #[cfg(feature = "nightly")]
+    /// Flatten the outputs of this iterable parser, which must themselves be iterable, into a single sequence of
+    /// items.
+    ///
+    /// This is useful for concatenating collections produced by each repetition of a parser, like the pieces of a
+    /// string literal split across several lines. See [`IterParser::flatten_with_spans`] if the span of each
+    /// collection is also needed.
+    ///
+    /// The output type of this iterable parser is `O::Item`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::Simple};
+    /// let words = text::ascii::ident::<_, extra::Err<Simple<char>>>()
+    ///     .map(|word: &str| word.chars())
+    ///     .padded()
+    ///     .repeated()
+    ///     .flatten()
+    ///     .collect::<String>();
+    ///
+    /// assert_eq!(words.parse("hello big world").into_result(), Ok("hellobigworld".to_string()));
+    /// ```
     fn flatten(self) -> Flatten<Self, O>
     where
         O: IntoIterator,
@@ -2618,6 +3982,137 @@ where
         }
     }
 
+    /// Flatten the outputs of this iterable parser, which must themselves be iterable, into a container, while also
+    /// collecting the span of each output into a second container.
+    ///
+    /// This is useful when several adjacent pieces of syntax form a single value, but the location of each piece
+    /// must still be known (for example, to point at an invalid escape sequence within one of several concatenated
+    /// string literals). The span of the whole value can be obtained with [`Parser::map_with`] as usual.
+    ///
+    /// The output type of this parser is `(C, S)`: the flattened items, and the span of each output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::Simple};
+    /// #[derive(Debug, PartialEq)]
+    /// struct Str {
+    ///     value: String,
+    ///     span: SimpleSpan,
+    ///     pieces: Vec<SimpleSpan>,
+    /// }
+    ///
+    /// // Adjacent string literals, like `"a" "b"`, are concatenated into a single string
+    /// let string = none_of::<_, _, extra::Err<Simple<char>>>('"')
+    ///     .repeated()
+    ///     .to_slice()
+    ///     .map(|s: &str| s.chars())
+    ///     .delimited_by(just('"'), just('"'))
+    ///     .padded()
+    ///     .repeated()
+    ///     .at_least(1)
+    ///     .flatten_with_spans::<String, Vec<_>>()
+    ///     .map_with(|(value, pieces), e| Str { value, span: e.span(), pieces });
+    ///
+    /// assert_eq!(
+    ///     string.parse(r#""hello, " "world""#).into_result(),
+    ///     Ok(Str {
+    ///         value: "hello, world".to_string(),
+    ///         span: (0..17).into(),
+    ///         pieces: vec![(0..10).into(), (10..17).into()],
+    ///     }),
+    /// );
+    /// ```
+    fn flatten_with_spans<C, S>(self) -> FlattenWithSpans<Self, O, C, S>
+    where
+        O: IntoIterator,
+        C: Container<O::Item>,
+        S: Container<I::Span>,
+        Self: Sized,
+    {
+        FlattenWithSpans {
+            parser: self,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Parse a stream of tokens, sending each output of this iterable parser into a channel as soon as it has been
+    /// parsed, and yielding any errors encountered along the way.
+    ///
+    /// This is useful for pipelines where parsing is one stage among many, and later stages can begin working on
+    /// outputs before the whole input has been parsed. With a bounded channel (such as
+    /// [`std::sync::mpsc::sync_channel`]), parsing pauses whenever the channel is full, so a slow consumer never causes
+    /// outputs to pile up in memory.
+    ///
+    /// Outputs are sent as soon as each iteration completes, so an output may have been sent even if the input as a
+    /// whole fails to parse. If the receiving half of the channel hangs up, parsing stops and [`Cancelled`] is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use std::{sync::mpsc, thread};
+    ///
+    /// let record = text::int::<_, extra::Err<Simple<char>>>(10)
+    ///     .from_str::<u32>()
+    ///     .unwrapped()
+    ///     .then_ignore(just(';'));
+    ///
+    /// // Parsing waits for the consumer whenever there are two records waiting to be processed
+    /// let (tx, rx) = mpsc::sync_channel(2);
+    /// let consumer = thread::spawn(move || rx.iter().sum::<u32>());
+    ///
+    /// let input = "1;2;3;4;5;6;7;8;9;10;";
+    /// let res = record.repeated().parse_into_channel(input, tx).unwrap();
+    ///
+    /// assert!(!res.has_errors());
+    /// assert_eq!(consumer.join().unwrap(), 55);
+    /// ```
+    #[cfg(feature = "std")]
+    fn parse_into_channel<S>(
+        &self,
+        input: I,
+        sender: S,
+    ) -> Result<ParseResult<(), E::Error>, Cancelled>
+    where
+        Self: Sized,
+        I: Input<'src>,
+        E::State: Default,
+        E::Context: Default,
+        S: ChannelSender<O>,
+    {
+        let mut own = InputOwn::new(input);
+        let mut inp = own.as_ref_start();
+        let res = match self.make_iter::<Emit>(&mut inp) {
+            Ok(mut state) => loop {
+                match self.next::<Emit>(&mut inp, &mut state) {
+                    Ok(Some(out)) => {
+                        if sender.send(out).is_err() {
+                            return Err(Cancelled);
+                        }
+                    }
+                    Ok(None) => break end().go::<Emit>(&mut inp),
+                    Err(()) => break Err(()),
+                }
+            },
+            Err(()) => Err(()),
+        };
+        let alt = inp.take_alt().map(|alt| alt.err).unwrap_or_else(|| {
+            let fake_span = inp.span_since(&inp.cursor());
+            E::Error::expected_found([], None, fake_span)
+        });
+        let mut errs = own.into_errs();
+        let out = match res {
+            Ok(()) => Some(()),
+            Err(()) => {
+                errs.push(alt);
+                None
+            }
+        };
+        Ok(ParseResult::new(out, errs))
+    }
+
     /// Create an iterator over the outputs generated by an iterable parser.
     ///
     /// Warning: Trailing errors will be ignored
@@ -2748,6 +4243,48 @@ where
         self
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Boxed",
+            introspect::NodeKind::Boxed,
+            vec![self.inner.introspect(cx)],
+        )
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::erased`].
+pub struct Erased<'src, 'b, I: Input<'src>, O, E: ParserExtra<'src, I> = extra::Default> {
+    inner: &'b DynParser<'src, 'b, I, O, E>,
+}
+
+impl<'src, I: Input<'src>, O, E: ParserExtra<'src, I>> Copy for Erased<'src, '_, I, O, E> {}
+
+impl<'src, I: Input<'src>, O, E: ParserExtra<'src, I>> Clone for Erased<'src, '_, I, O, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'src, I, O, E> Parser<'src, I, O, E> for Erased<'src, '_, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        M::invoke(self.inner, inp)
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Erased",
+            introspect::NodeKind::Boxed,
+            vec![self.inner.introspect(cx)],
+        )
+    }
+
     go_extra!(O);
 }
 
@@ -2765,6 +4302,10 @@ where
         T::go::<M>(self, inp)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        T::introspect(self, cx)
+    }
+
     go_extra!(O);
 }
 
@@ -2782,6 +4323,10 @@ where
         T::go::<M>(self, inp)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        T::introspect(self, cx)
+    }
+
     go_extra!(O);
 }
 
@@ -2799,6 +4344,10 @@ where
         T::go::<M>(self, inp)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        T::introspect(self, cx)
+    }
+
     go_extra!(O);
 }
 
@@ -2938,9 +4487,399 @@ macro_rules! select_ref {
     });
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::prelude::*;
+/// Create a parser that chooses between several parsers by looking at the next token, without backtracking.
+///
+/// Each arm consists of a pattern and a parser. The next token is matched against the patterns and the parser of
+/// the first matching arm is run (the token is *not* consumed by the pattern: the parser will see it too). If no
+/// pattern matches, the parser fails. The error then expects whatever each of the arms expected, just like the error
+/// of a [`choice`](primitive::choice).
+///
+/// This is equivalent to a [`choice`](primitive::choice) in which every branch starts with a distinct token, but
+/// instead of trying each branch in turn (and rewinding after each failure), the branch to run is found with a single
+/// `match`. For statement-heavy grammars, where a large number of alternatives must be checked at the start of every
+/// statement, this can make a substantial difference to performance. However, note that this means that a failing
+/// branch does not fall back to the arms after it.
+///
+/// Patterns are matched against a reference to the token, so this works with any input. Up to 26 arms are
+/// supported.
+///
+/// The output type of this parser is the output type of the inner parsers.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Token {
+///     Let,
+///     Print,
+///     If,
+///     While,
+///     Ident(char),
+///     Num(u64),
+///     Eq,
+///     Semi,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Stmt {
+///     Let(char, u64),
+///     Print(char),
+///     Loop(&'static str, char),
+/// }
+///
+/// let ident = select! { Token::Ident(c) => c };
+/// let num = select! { Token::Num(n) => n };
+///
+/// let stmt = dispatch! {
+///     Token::Let => just::<_, _, extra::Err<Simple<Token>>>(Token::Let)
+///         .ignore_then(ident)
+///         .then_ignore(just(Token::Eq))
+///         .then(num)
+///         .map(|(name, val)| Stmt::Let(name, val)),
+///     Token::Print => just(Token::Print).ignore_then(ident).map(Stmt::Print),
+///     Token::If | Token::While => select! { Token::If => "if", Token::While => "while" }
+///         .then(ident)
+///         .map(|(kw, cond)| Stmt::Loop(kw, cond)),
+/// };
+///
+/// let stmts = stmt.clone().then_ignore(just(Token::Semi)).repeated().collect::<Vec<_>>();
+///
+/// use Token::*;
+/// assert_eq!(
+///     stmts.parse([Let, Ident('x'), Eq, Num(5), Semi, While, Ident('x'), Semi, Print, Ident('x'), Semi].as_slice()).into_result(),
+///     Ok(vec![Stmt::Let('x', 5), Stmt::Loop("while", 'x'), Stmt::Print('x')]),
+/// );
+///
+/// // No arm starts with a number
+/// assert!(stmt.parse([Num(5)].as_slice()).has_errors());
+/// ```
+#[macro_export]
+macro_rules! dispatch {
+    ($($p:pat => $parser:expr),+ $(,)?) => ({
+        $crate::primitive::dispatch(
+            $crate::__dispatch_select!(
+                []
+                [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25]
+                $($p,)+
+            ),
+            ($($parser,)+),
+        )
+    });
+}
+
+/// Parse a sequence of named elements, then combine them into an output.
+///
+/// Each element of the sequence is written as `name: parser`. Elements whose output is not needed may be named `_`.
+/// After all elements have been parsed, the expression after `=>` is evaluated with each name bound to the output of
+/// its parser. A trailing comma after the last element is permitted.
+///
+/// This is useful for large, record-like rules where the positional tuples produced by [`Parser::then`] or
+/// [`group`](primitive::group) become hard to read. The macro simply expands to a [`group`](primitive::group) followed
+/// by a [`Parser::map`], so it behaves identically to writing those by hand (including not evaluating the output
+/// expression when the output is not needed). Up to 26 elements are supported.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// struct Field<'src> {
+///     name: &'src str,
+///     ty: &'src str,
+///     optional: bool,
+/// }
+///
+/// let ident = text::ascii::ident::<_, extra::Err<Simple<char>>>().padded();
+///
+/// let field = sequence! {
+///     name: ident,
+///     optional: just('?').or_not().map(|q| q.is_some()),
+///     _: just(':'),
+///     ty: ident
+///     => Field { name, ty, optional }
+/// };
+///
+/// assert_eq!(
+///     field.parse("age?: u32").into_result(),
+///     Ok(Field { name: "age", ty: "u32", optional: true }),
+/// );
+///
+/// let pair = sequence! {
+///     key: ident,
+///     value: ident,
+///     => (key, value)
+/// };
+///
+/// assert_eq!(pair.parse("a b").into_result(), Ok(("a", "b")));
+/// ```
+#[macro_export]
+macro_rules! sequence {
+    ($($elems:tt)+) => {
+        $crate::__sequence!([] [] $($elems)+)
+    };
+}
+
+// Collects the elements of `sequence!` one at a time, so that a trailing comma before `=>` is not ambiguous
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sequence {
+    ([$($name:tt)+] [$($parser:expr),+] => $out:expr $(,)?) => ({
+        $crate::Parser::map(
+            $crate::primitive::group(($($parser,)+)),
+            |($($name,)+)| $out,
+        )
+    });
+    ([$($names:tt)*] [$($parsers:expr),*] $name:tt : $parser:expr => $($rest:tt)+) => {
+        $crate::__sequence!([$($names)* $name] [$($parsers,)* $parser] => $($rest)+)
+    };
+    ([$($names:tt)*] [$($parsers:expr),*] $name:tt : $parser:expr, $($rest:tt)+) => {
+        $crate::__sequence!([$($names)* $name] [$($parsers,)* $parser] $($rest)+)
+    };
+}
+
+/// Name a [`ParserExtra`](extra::ParserExtra) type by specifying only the parts that differ from the defaults.
+///
+/// Each part is given as `key = Type`, in any order, with `error`, `state`, and `context` as the keys. Parts that are
+/// not given take their default (an [`EmptyErr`](error::EmptyErr) error, and `()` for state and context). The macro
+/// expands to an [`extra::Full`] type, so it may be used wherever a type is expected, such as in a type alias shared by
+/// the functions of a grammar.
+///
+/// This avoids spelling out every part of [`extra::Full`], and is clearer than it when only some parts are given.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// use chumsky::extra::SimpleState;
+///
+/// type Extra<'src> = extra!(error = Rich<'src, char>, context = usize);
+///
+/// fn digits<'src>() -> impl Parser<'src, &'src str, &'src str, Extra<'src>> {
+///     text::digits(10).to_slice()
+/// }
+///
+/// assert_eq!(digits().parse("123").into_result(), Ok("123"));
+///
+/// // Keys may be given in any order
+/// type Counting<'src> = extra!(state = SimpleState<usize>, error = Rich<'src, char>);
+///
+/// let words = text::ascii::ident::<_, Counting>()
+///     .map_with(|_, e| e.state().0 += 1)
+///     .padded()
+///     .repeated()
+///     .collect::<Vec<_>>();
+///
+/// let mut count = SimpleState(0);
+/// assert!(!words.parse_with_state("a b c", &mut count).has_errors());
+/// assert_eq!(count.0, 3);
+/// ```
+#[macro_export]
+macro_rules! extra {
+    ($($parts:tt)*) => {
+        $crate::__extra!([$crate::error::EmptyErr] [()] [()] $($parts)*)
+    };
+}
+
+// Fills in the parts of `extra!` one key at a time
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __extra {
+    ([$err:ty] [$state:ty] [$ctx:ty] $(,)?) => {
+        $crate::extra::Full<$err, $state, $ctx>
+    };
+    ([$err:ty] [$state:ty] [$ctx:ty] error = $ty:ty $(, $($rest:tt)*)?) => {
+        $crate::__extra!([$ty] [$state] [$ctx] $($($rest)*)?)
+    };
+    ([$err:ty] [$state:ty] [$ctx:ty] state = $ty:ty $(, $($rest:tt)*)?) => {
+        $crate::__extra!([$err] [$ty] [$ctx] $($($rest)*)?)
+    };
+    ([$err:ty] [$state:ty] [$ctx:ty] context = $ty:ty $(, $($rest:tt)*)?) => {
+        $crate::__extra!([$err] [$state] [$ty] $($($rest)*)?)
+    };
+}
+
+/// Declare the terminal vocabulary of a grammar once, as a struct of pre-built parsers.
+///
+/// Very large grammars often repeat the same [`just`](primitive::just) and keyword parsers across many rules. Each
+/// repetition is constructed separately and, because every combinator has its own type, contributes to
+/// monomorphization. This macro instead generates a struct with a [`Boxed`] field for each terminal. Calling the
+/// generated `new` function (or [`Default::default`]) builds every terminal once, after which rules may share them by
+/// reference (`&terms.plus`) or by cheaply cloning them, without constructing them again.
+///
+/// The struct is written as `struct Name<'src>(Input, Extra) { field: Output = parser, ... }`. The input and extra
+/// types are shared by every terminal, so the parsers on the right hand side usually need no type annotations.
+/// Attributes and visibility may be given for the struct and for each field.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// terminals! {
+///     /// The terminals of a tiny language.
+///     struct Terms<'src>(&'src str, extra::Err<Simple<'src, char>>) {
+///         kw_let: &'src str = text::ascii::keyword("let").padded(),
+///         eq: char = just('=').padded(),
+///         semi: char = just(';').padded(),
+///         ident: &'src str = text::ascii::ident().padded(),
+///     }
+/// }
+///
+/// let t = Terms::new();
+///
+/// let stmt = (&t.kw_let)
+///     .ignore_then(&t.ident)
+///     .then_ignore(&t.eq)
+///     .then(&t.ident)
+///     .then_ignore(&t.semi);
+///
+/// assert_eq!(stmt.parse("let x = y;").into_result(), Ok(("x", "y")));
+/// ```
+#[macro_export]
+macro_rules! terminals {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident<$src:lifetime>($input:ty, $extra:ty) {
+            $($(#[$fmeta:meta])* $fvis:vis $field:ident : $out:ty = $parser:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name<$src> {
+            $($(#[$fmeta])* $fvis $field: $crate::Boxed<$src, $src, $input, $out, $extra>,)*
+        }
+
+        impl<$src> $name<$src> {
+            /// Build every terminal parser.
+            $vis fn new() -> Self {
+                Self {
+                    $($field: $crate::Parser::boxed($parser),)*
+                }
+            }
+        }
+
+        impl<$src> ::core::default::Default for $name<$src> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+/// Declare a group of mutually recursive parsers at once.
+///
+/// The macro takes a closure-like expression: `|a, b, c| body`. Each name is bound to a declared
+/// [`Recursive`](recursive::Recursive) parser that may be used within the body, and the body must evaluate to a tuple
+/// with the definition of each parser, in the same order as the names. The macro then defines every parser and
+/// evaluates to a tuple of them, again in the same order.
+///
+/// This is a shorthand for calling [`Recursive::declare`](recursive::Recursive::declare) for each parser, then
+/// [`Recursive::define`](recursive::Recursive::define) for each once all of the definitions have been built. Because
+/// every parser is declared before any definition is built, rules may refer to each other in any order. Up to 26
+/// parsers are supported.
+///
+/// Names that are not referred to by any definition in the group are permitted, which is common for the top-level rule
+/// of a grammar.
+///
+/// Like the parser passed to the closure of [`recursive()`], the parsers used within the body only refer weakly to the
+/// parsers of the group, so that they don't own each other. Each parser returned by the macro must therefore be kept
+/// alive for as long as any parser that refers to it is used: bind the parsers that you don't need to names starting
+/// with an underscore, rather than to `_`, which would drop them.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Num(u64),
+///     Block(Vec<Stmt>),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Stmt {
+///     Expr(Expr),
+///     While(Expr, Vec<Stmt>),
+/// }
+///
+/// let (expr, _stmt, block) = recursive_group!(|expr, stmt, block| {
+///     let expr_def = text::int::<_, extra::Err<Simple<char>>>(10)
+///         .from_str()
+///         .unwrapped()
+///         .map(Expr::Num)
+///         .or(block.clone().map(Expr::Block))
+///         .padded();
+///     let stmt_def = text::ascii::keyword("while")
+///         .ignore_then(expr.clone())
+///         .then(block.clone())
+///         .map(|(cond, body)| Stmt::While(cond, body))
+///         .or(expr.clone().map(Stmt::Expr))
+///         .padded();
+///     let block_def = stmt
+///         .separated_by(just(';'))
+///         .collect()
+///         .delimited_by(just('{'), just('}'))
+///         .padded();
+///     (expr_def, stmt_def, block_def)
+/// });
+///
+/// assert_eq!(
+///     expr.parse("{ while 1 { 2; {} }; 3 }").into_result(),
+///     Ok(Expr::Block(vec![
+///         Stmt::While(Expr::Num(1), vec![Stmt::Expr(Expr::Num(2)), Stmt::Expr(Expr::Block(vec![]))]),
+///         Stmt::Expr(Expr::Num(3)),
+///     ])),
+/// );
+/// assert_eq!(block.parse("{}").into_result(), Ok(vec![]));
+/// ```
+#[macro_export]
+macro_rules! recursive_group {
+    (|$($name:ident),+ $(,)?| $body:expr) => ({
+        $(let mut $name = $crate::recursive::Recursive::declare();)+
+        let defs = {
+            $(
+                #[allow(unused_variables)]
+                let $name = $crate::recursive::Recursive::downgrade(&$name);
+            )+
+            $body
+        };
+        $crate::__recursive_group_define!(
+            defs
+            [$($name)+]
+            [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25]
+        );
+        ($($name,)+)
+    });
+}
+
+// Defines each parser of `recursive_group!` with the element of the definitions tuple at its index
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __recursive_group_define {
+    ($defs:ident [$name:ident $($names:ident)*] [$idx:tt $($idxs:tt)*]) => {
+        $crate::recursive::Recursive::define(&mut $name, $defs.$idx);
+        $crate::__recursive_group_define!($defs [$($names)*] [$($idxs)*]);
+    };
+    ($defs:ident [] [$($idxs:tt)*]) => {};
+}
+
+// Builds the selector of `dispatch!` by pairing each pattern with its index
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dispatch_select {
+    ([$($arms:tt)*] [$idx:tt $($idxs:tt)*] $p:pat, $($rest:tt)*) => {
+        $crate::__dispatch_select!([$($arms)* $p => ::core::option::Option::Some($idx),] [$($idxs)*] $($rest)*)
+    };
+    ([$($arms:tt)*] [$($idxs:tt)*]) => {
+        |tok| match tok {
+            $($arms)*
+            _ => ::core::option::Option::None,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
 
     #[test]
     fn zero_copy() {
@@ -3377,6 +5316,37 @@ mod tests {
         expr.then_ignore(end()).parse("a+b+c");
     }
 
+    #[test]
+    fn recursive_group_is_dropped() {
+        use alloc::rc::Rc;
+
+        let alive = Rc::new(());
+        let (list, _item) = recursive_group!(|list, item| {
+            // Owned by the definition of `list`, so it lives exactly as long as the group
+            let marker = alive.clone();
+            let list_def = item
+                .separated_by(just(','))
+                .collect::<Vec<_>>()
+                .delimited_by(just('['), just(']'))
+                .map(move |items| {
+                    let _ = &marker;
+                    items
+                });
+            let item_def = just::<_, &str, extra::Default>('x')
+                .to(())
+                .or(list.ignored());
+            (list_def, item_def)
+        });
+
+        assert_eq!(
+            list.parse("[x,[x],[]]").into_result().map(|l| l.len()),
+            Ok(3)
+        );
+        assert_eq!(Rc::strong_count(&alive), 2);
+        drop((list, _item));
+        assert_eq!(Rc::strong_count(&alive), 1);
+    }
+
     #[test]
     #[should_panic]
     fn todo_err() {
@@ -3519,7 +5489,6 @@ mod tests {
         assert_eq!(parser().parse("aaa").into_result(), Ok(()));
     }
 
-    #[cfg(feature = "nightly")]
     #[test]
     fn flatten() {
         fn parser<'src>() -> impl Parser<'src, &'src str, Vec<char>, extra::Err<MyErr>> {
@@ -3595,6 +5564,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cursor_offsets() {
+        let tokens = [('a', SimpleSpan::from(2..3)), ('b', SimpleSpan::from(5..7))];
+        let eoi = SimpleSpan::from(7..7);
+
+        let parser = custom::<_, _, _, extra::Default>(|inp| {
+            let before = inp.cursor();
+            inp.next();
+            Ok((inp.offset_of(&before), inp.offset()))
+        })
+        .then_ignore(any());
+
+        assert_eq!(
+            parser
+                .parse(tokens.as_slice().map(eoi, |(t, s)| (t, s)))
+                .into_result(),
+            Ok((2, 5)),
+        );
+
+        let parser = just::<_, _, extra::Default>('é')
+            .ignore_then(custom(|inp| Ok(inp.offset())))
+            .then_ignore(any());
+        assert_eq!(parser.parse("éx").into_result(), Ok(2));
+    }
+
     #[test]
     fn label() {
         use crate::label::LabelError;
@@ -3766,4 +5760,1123 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn parse_with_limits() {
+        use crate::{LimitExceeded, Limits};
+
+        let digits = one_of::<_, _, extra::Default>('0'..='9').repeated();
+        let input = "1234567890";
+
+        assert!(digits.parse_with_limits(input, Limits::default()).is_ok());
+        assert!(digits
+            .parse_with_limits(input, Limits::default().fuel(100))
+            .is_ok());
+        assert_eq!(
+            digits.parse_with_limits(input, Limits::default().fuel(5)),
+            Err(LimitExceeded::Fuel)
+        );
+        assert!(digits
+            .parse_with_limits(input, Limits::default().max_input_len(10))
+            .is_ok());
+        assert_eq!(
+            digits.parse_with_limits(input, Limits::default().max_input_len(5)),
+            Err(LimitExceeded::InputLen)
+        );
+        // The input length is checked even if the parser contains no safe points
+        assert_eq!(
+            just::<_, _, extra::Default>(input)
+                .parse_with_limits(input, Limits::default().max_input_len(5)),
+            Err(LimitExceeded::InputLen)
+        );
+
+        // The input length is checked before parsing, even if the parse would fail early
+        assert_eq!(
+            just::<_, _, extra::Default>('x')
+                .parse_with_limits(input, Limits::default().max_input_len(5)),
+            Err(LimitExceeded::InputLen)
+        );
+        // Skipping tokens during recovery consumes fuel
+        let skipping = just::<_, _, extra::Default>('x').recover_with(skip_until(
+            any().ignored(),
+            end(),
+            || 'x',
+        ));
+        assert_eq!(
+            skipping.parse_with_limits(input, Limits::default().fuel(5)),
+            Err(LimitExceeded::Fuel)
+        );
+        // Exceeding the maximum depth is not recovered from
+        let nested = recursive(|nested| {
+            nested
+                .delimited_by(just('('), just(')'))
+                .or(just::<_, _, extra::Default>('x').ignored())
+                .recover_with(via_parser(any().ignored()))
+        });
+        assert_eq!(
+            nested.parse_with_limits("((((x))))", Limits::default().max_depth(2)),
+            Err(LimitExceeded::Depth)
+        );
+
+        // Running out of fuel is not recovered from
+        let recovering = digits
+            .collect::<String>()
+            .recover_with(via_parser(empty().to(String::new())));
+        assert_eq!(
+            recovering.parse_with_limits(input, Limits::default().fuel(5)),
+            Err(LimitExceeded::Fuel)
+        );
+
+        let item = just::<_, _, extra::Err<Simple<char>>>('a')
+            .recover_with(via_parser(any().to('?')))
+            .repeated()
+            .collect::<String>();
+        let res = item
+            .parse_with_limits("aaxaxa", Limits::default().max_recoveries(2))
+            .unwrap();
+        assert_eq!(res.output().map(String::as_str), Some("aa?a?a"));
+        assert_eq!(
+            item.parse_with_limits("aaxaxx", Limits::default().max_recoveries(2)),
+            Err(LimitExceeded::Recoveries)
+        );
+    }
+
+    #[test]
+    fn parse_with_stats() {
+        let parser = just::<_, _, extra::Err<Simple<char>>>("ab")
+            .or(just("ac"))
+            .recover_with(via_parser(any().to("?")))
+            .repeated()
+            .collect::<Vec<_>>();
+
+        let (res, stats) = parser.parse_with_stats("abacx");
+        assert_eq!(res.output(), Some(&vec!["ab", "ac", "?"]));
+        assert_eq!(stats.consumed, 5);
+        assert_eq!(stats.max_depth, 0);
+        // At the very least, `just("ab")` consumes the `a` of `ac` before failing
+        assert!(stats.backtracks > 0);
+        assert_eq!(stats.recovered, 1);
+    }
+
+    #[test]
+    fn display_with_source() {
+        use alloc::string::ToString;
+
+        let item = text::int::<_, extra::Err<Rich<char>>>(10).padded();
+        let list = item
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .delimited_by(just('['), just(']'))
+            .labelled("list")
+            .as_context();
+
+        let src = "[1,\n\t2,\r\n3 x\n]\n";
+        let err = &list.parse(src).into_errors()[0];
+        assert_eq!(
+            err.display_with_source(src).to_string(),
+            "\
+error: found 'x' expected ',', or ']'
+ --> 3:3
+  |
+2 |  2,
+3 | 3 x
+  |   ^
+4 | ]
+  = while parsing list at 1:1
+",
+        );
+
+        // Long messages are wrapped, and long lines are shortened around the error
+        let src = "[".to_string() + &"1, ".repeat(40) + "x]";
+        let err = &list.parse(src.as_str()).into_errors()[0];
+        assert_eq!(
+            err.display_with_source(&src).width(40).to_string(),
+            "\
+error: found 'x' expected non-zero digit, or
+       '0'
+ --> 1:122
+  |
+1 | ... 1, 1, 1, 1, 1, 1, 1, 1, 1, x]
+  |                                ^
+  = while parsing list at 1:1
+",
+        );
+
+        // Extremely long lines, with multi-byte characters
+        let item = just::<_, _, extra::Err<Rich<char>>>("\"é\"").padded();
+        let list = item
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .delimited_by(just('['), just(']'));
+        let src = "[".to_string() + &"\"é\", ".repeat(100_000) + "\"é\" x, \"é\", \"é\", \"é\"]";
+        let err = &list.parse(src.as_str()).into_errors()[0];
+        assert_eq!(
+            err.display_with_source(&src).width(40).to_string(),
+            "\
+error: found 'x' expected ',', or ']'
+ --> 1:500006
+  |
+1 | ...é\", \"é\", \"é\" x, \"é\", \"é\", \"é\"]
+  |                 ^
+",
+        );
+    }
+
+    #[test]
+    fn transactional_state() {
+        use extra::{Persistent, Transactional};
+
+        type State = (Transactional<Vec<char>>, Persistent<usize>);
+
+        let attempt = |expected: char| {
+            any::<_, extra::State<State>>()
+                .map_with(|c, e| {
+                    e.state().0.push(c);
+                    *e.state().1 += 1;
+                })
+                .then_ignore(just(expected))
+        };
+        let parser = choice((attempt('!'), attempt('?'), attempt('.')))
+            .repeated()
+            .collect::<Vec<_>>();
+
+        let mut state = (Transactional::new(Vec::new()), Persistent(0));
+        assert!(!parser.parse_with_state("a.b?c.", &mut state).has_errors());
+        assert_eq!(*state.0, vec!['a', 'b', 'c']);
+        // Every attempt was counted, including those that were rolled back
+        assert_eq!(*state.1, 3 + 2 + 3);
+    }
+
+    #[test]
+    fn with_scope() {
+        use extra::SymbolTable;
+
+        let declare = any::<_, extra::State<SymbolTable<char, ()>>>()
+            .filter(char::is_ascii_lowercase)
+            .map_with(|c, e| e.state().declare(c, ()));
+        let scoped = declare
+            .repeated()
+            .collect::<Vec<_>>()
+            .then(just('!'))
+            .with_scope();
+        let parser = scoped
+            .map(|_| ())
+            .or(declare
+                .repeated()
+                .collect::<Vec<_>>()
+                .then(just('?'))
+                .map(|_| ()))
+            .map_with(|_, e| e.state().depth());
+
+        let mut table = SymbolTable::new();
+        assert_eq!(
+            parser.parse_with_state("ab?", &mut table).into_result(),
+            Ok(0)
+        );
+        assert!(table.contains(&'a') && table.contains(&'b'));
+
+        let mut table = SymbolTable::new();
+        assert_eq!(
+            parser.parse_with_state("ab!", &mut table).into_result(),
+            Ok(0)
+        );
+        // Declarations made inside the scope are discarded when it is left
+        assert!(!table.contains(&'a'));
+    }
+
+    #[test]
+    fn disambiguate_commits() {
+        use extra::SimpleState;
+
+        let parser = disambiguate(
+            any::<_, extra::State<SimpleState<bool>>>(),
+            |_, flag: &SimpleState<bool>| **flag,
+            just("ab").to(1),
+            just("a").to(2),
+        )
+        .then_ignore(end());
+
+        assert_eq!(
+            parser
+                .parse_with_state("ab", &mut SimpleState(true))
+                .into_result(),
+            Ok(1)
+        );
+        // `if_false` is chosen and fails on `b`: `if_true` is not tried, even though it would have succeeded
+        assert!(parser
+            .parse_with_state("ab", &mut SimpleState(false))
+            .has_errors());
+        assert_eq!(
+            parser
+                .parse_with_state("a", &mut SimpleState(false))
+                .into_result(),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn postfix_chain_spans() {
+        let segment = just::<_, _, extra::Err<EmptyErr>>('.').ignore_then(text::ascii::ident());
+        let parser = postfix_chain(
+            text::ascii::ident().map(|_| Vec::new()),
+            segment,
+            |mut spans: Vec<_>, _, span: SimpleSpan, e| {
+                let chain: SimpleSpan = e.span();
+                spans.push((span.into_range(), chain.into_range()));
+                spans
+            },
+        );
+
+        assert_eq!(
+            parser.parse("a.bc.d").into_result(),
+            Ok(vec![(1..4, 0..4), (4..6, 0..6)]),
+        );
+        // A trailing segment that fails to parse ends the chain without consuming it
+        assert_eq!(parser.lazy().parse("a.").into_result(), Ok(vec![]),);
+    }
+
+    #[test]
+    fn none_of_seq_overlapping() {
+        let comment = none_of_seq::<_, _, extra::Default>("*/")
+            .repeated()
+            .to_slice()
+            .delimited_by(just("/*"), just("*/"));
+
+        assert_eq!(comment.parse("/* a **/").into_result(), Ok(" a *"));
+        assert_eq!(comment.parse("/**/").into_result(), Ok(""));
+        assert!(comment.parse("/* a *").has_errors());
+    }
+
+    #[test]
+    fn take_until_leave_terminator() {
+        let parser = take_until::<_, _, _, extra::Err<Simple<char>>>(just(';'))
+            .leave_terminator()
+            .then(just(';'))
+            .map(|((body, _), _)| body);
+
+        assert_eq!(parser.parse("abc;").into_result(), Ok("abc"));
+        assert_eq!(parser.parse(";").into_result(), Ok(""));
+        assert!(parser.parse("abc").has_errors());
+    }
+
+    #[test]
+    fn dispatch_miss_expects_every_arm() {
+        use crate::error::RichPattern;
+
+        let parser = dispatch! {
+            'a' => just::<_, _, extra::Err<Rich<char>>>('a').ignore_then(just('1')),
+            'b' | 'c' => one_of("bc").ignore_then(just('2')),
+            'd' => just('d').labelled("letter d").ignore_then(just('3')),
+        };
+
+        let errs = parser.parse("x").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+        let mut expected = errs[0].expected().cloned().collect::<Vec<_>>();
+        expected.sort_by_key(|pat| format!("{pat}"));
+        assert_eq!(
+            expected,
+            vec![
+                RichPattern::Token('a'.into()),
+                RichPattern::Token('b'.into()),
+                RichPattern::Token('c'.into()),
+                RichPattern::Label("letter d".into()),
+            ],
+        );
+
+        // Errors within the selected arm are reported as usual
+        let errs = parser.parse("b1").into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(1..2));
+        assert_eq!(
+            errs[0].expected().cloned().collect::<Vec<_>>(),
+            vec![RichPattern::Token('2'.into())],
+        );
+    }
+
+    #[test]
+    fn balanced_nesting_and_skip() {
+        let comment = just("//").then(none_of('\n').repeated());
+        let block = balanced::<_, extra::Err<Simple<char>>>('(', ')').skip(comment);
+
+        assert_eq!(
+            block.parse("(a (b) // )\n c)").into_result(),
+            Ok("a (b) // )\n c")
+        );
+        assert!(block.parse("(a (b)").has_errors());
+        assert!(block.parse("a").has_errors());
+
+        let pipes = balanced::<_, extra::Err<Simple<char>>>('|', '|');
+        assert_eq!(pipes.parse("|x|").into_result(), Ok("x"));
+    }
+
+    #[test]
+    fn deferred_regions() {
+        use crate::primitive::DeferredInput;
+
+        let body = text::ident::<DeferredInput<&str>, extra::Err<Rich<_>>>()
+            .map_with(|name, e| (name, e.span()))
+            .padded()
+            .repeated()
+            .collect::<Vec<_>>();
+        let items = balanced::<_, extra::Err<Rich<char>>>('{', '}')
+            .defer(body)
+            .padded()
+            .repeated()
+            .collect::<Vec<_>>();
+
+        let regions = items.parse("{ a } { b { c } }").into_result().unwrap();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[1].span(), &SimpleSpan::from(7..16));
+        assert_eq!(
+            regions[0].force().into_result(),
+            Ok(vec![("a", SimpleSpan::from(2..3))])
+        );
+        // The nested braces aren't valid in the body, but that's only discovered when it is forced
+        assert!(regions[1].force().has_errors());
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn unparse_choice_rolls_back() {
+        use crate::unparse::{bi, lit, PrintError};
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Sign {
+            Neg,
+            NegNeg,
+        }
+
+        // The first alternative prints `-` before failing, which must be undone before trying the second
+        let never = bi(empty().to(Sign::Neg), |_: &Sign, _: &mut String| {
+            Err(PrintError)
+        });
+        let sign = lit::<&str, extra::Default>("-")
+            .ignore_then(never)
+            .or(lit("--").map(
+                |()| Sign::NegNeg,
+                |s: &Sign| (*s == Sign::NegNeg).then_some(()),
+            ));
+
+        assert_eq!(sign.print_to_string(&Sign::NegNeg).as_deref(), Ok("--"));
+        assert_eq!(sign.print_to_string(&Sign::Neg), Err(PrintError));
+        assert_eq!(sign.parse("-").into_result(), Ok(Sign::Neg));
+    }
+
+    #[test]
+    fn rich_display_token_names() {
+        use crate::error::TokenDisplay;
+        use core::fmt;
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Tok {
+            Let,
+            Eq,
+        }
+
+        impl TokenDisplay for Tok {
+            fn fmt_token(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    Tok::Let => write!(f, "keyword `let`"),
+                    Tok::Eq => write!(f, "`=`"),
+                }
+            }
+        }
+
+        let parser = just::<_, _, extra::Err<Rich<Tok>>>([Tok::Let, Tok::Eq])
+            .labelled("binding")
+            .as_context();
+        let errs = parser.parse(&[Tok::Let, Tok::Let]).into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "found keyword `let` expected `=` in binding at 0..1"
+        );
+        assert_eq!(
+            format!("{:?}", errs[0]),
+            "found 'Let' at 1..2 expected 'Eq' in binding at 0..1"
+        );
+
+        // Tokens with a `Display` implementation are still quoted
+        let errs = just::<_, _, extra::Err<Rich<char>>>("ab")
+            .parse("ac")
+            .into_errors();
+        assert_eq!(errs[0].to_string(), "found 'c' expected 'b'");
+        let pattern = crate::error::RichPattern::Tokens(vec!['a'.into(), 'b'.into()]);
+        assert_eq!(pattern.to_string(), "'a' 'b'");
+    }
+
+    #[test]
+    fn rich_display_found_class() {
+        use crate::error::{TokenClass, TokenDisplay};
+        use core::fmt;
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Tok {
+            Plus,
+            Num(u32),
+        }
+
+        impl TokenDisplay for Tok {
+            fn fmt_token(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    Tok::Plus => write!(f, "`+`"),
+                    Tok::Num(n) => write!(f, "`{n}`"),
+                }
+            }
+
+            fn class(&self) -> Option<TokenClass> {
+                match self {
+                    Tok::Plus => Some(TokenClass::Operator),
+                    Tok::Num(_) => None,
+                }
+            }
+        }
+
+        let num = select! { Tok::Num(n) => n };
+        let parser = num
+            .labelled("number")
+            .or(num.labelled("expression"))
+            .then_ignore(end::<&[Tok], extra::Err<Rich<Tok>>>());
+
+        // The note is only given when a single label was expected
+        let errs = num.labelled("expression").parse(&[Tok::Plus]).into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "found `+`, which cannot be used as an expression"
+        );
+        let errs = parser.parse(&[Tok::Plus]).into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "found `+` expected number, or expression"
+        );
+        // Tokens without a class are reported as usual
+        let errs = parser.parse(&[Tok::Num(1), Tok::Num(2)]).into_errors();
+        assert_eq!(errs[0].to_string(), "found `2` expected end of input");
+    }
+
+    #[test]
+    fn rich_snippet_edges() {
+        let snippet = |src: &str, span: core::ops::Range<usize>, radius| {
+            Rich::<char>::custom(SimpleSpan::from(span), "oops")
+                .with_snippet(src, radius)
+                .snippet()
+                .cloned()
+                .unwrap()
+        };
+
+        // Spans over several lines are cut off at the end of the first line
+        let s = snippet("let x = (\r\n  1", 8..14, 3);
+        assert_eq!((s.text.as_str(), s.span.clone()), (" = (", 3..4));
+        assert!(s.truncated_start && !s.truncated_end);
+        assert_eq!(s.to_string(), "1 | ... = (\n  |       ^\n");
+
+        // Empty spans at the end of input still get a caret
+        let s = snippet("a\nbé", 5..5, 10);
+        assert_eq!((s.line, s.column, s.span.clone()), (1, 2, 3..3));
+        assert_eq!(s.to_string(), "2 | bé\n  |   ^\n");
+    }
+
+    #[test]
+    fn error_codes() {
+        let item = text::int::<_, extra::Err<Rich<char>>>(10)
+            .with_code("E2")
+            .recover_with(via_parser(none_of(",]").repeated().at_least(1).to("?")))
+            .with_code("E1");
+        let list = item
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .delimited_by(just('['), just(']'))
+            .with_code("E0");
+
+        // Recovered errors get codes too, and the innermost code wins
+        let errs = list.parse("[1,x]").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].code(), Some("E2"));
+
+        let errs = list.parse("(1]").into_errors();
+        assert_eq!(errs[0].code(), Some("E0"));
+        assert!(errs[0]
+            .display_with_source("(1]")
+            .to_string()
+            .starts_with("error[E0]: found '(' expected '['"));
+    }
+
+    #[test]
+    fn lazy_constructs_once() {
+        use core::cell::Cell;
+        use std::rc::Rc;
+
+        let built = Rc::new(Cell::new(0));
+        let digits = {
+            let built = built.clone();
+            lazy(move || {
+                built.set(built.get() + 1);
+                text::digits::<_, extra::Err<EmptyErr>>(10).to_slice()
+            })
+        };
+        // Nothing is constructed until the parser is first used
+        assert_eq!(built.get(), 0);
+
+        let pair = digits.clone().then_ignore(just(',')).then(digits);
+        assert_eq!(pair.parse("12,34").into_result(), Ok(("12", "34")));
+        assert_eq!(pair.parse("5,6").into_result(), Ok(("5", "6")));
+        // Clones share the same cache
+        assert_eq!(built.get(), 1);
+    }
+
+    #[test]
+    fn collect_strings() {
+        use crate::container::Container;
+        use alloc::borrow::Cow;
+
+        fn string<'src, C: Container<&'src str>>(
+        ) -> impl Parser<'src, &'src str, C, extra::Err<EmptyErr>> {
+            let escape = just('\\').ignore_then(just('n').to("\n").or(just('"').to("\"")));
+            let piece = none_of("\\\"").repeated().at_least(1).to_slice();
+            piece
+                .or(escape)
+                .repeated()
+                .collect()
+                .delimited_by(just('"'), just('"'))
+        }
+
+        assert_eq!(
+            string::<String>().parse(r#""a\"b\nc""#).into_result(),
+            Ok("a\"b\nc".to_string())
+        );
+
+        let out = string::<Cow<str>>().parse(r#""abc""#).into_result();
+        assert!(matches!(out, Ok(Cow::Borrowed("abc"))));
+        let out = string::<Cow<str>>().parse(r#""a\nb""#).into_result();
+        assert!(matches!(out.as_deref(), Ok("a\nb")));
+        assert!(matches!(out, Ok(Cow::Owned(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn parse_stream_records() {
+        use crate::input::{parse_stream, ReaderInput};
+
+        fn digits<'src>(
+        ) -> impl Parser<'src, ReaderInput<&'src [u8]>, u32, extra::Err<Rich<'src, u8>>> {
+            any()
+                .filter(u8::is_ascii_digit)
+                .repeated()
+                .at_least(1)
+                .collect::<Vec<u8>>()
+                .map(|d| d.iter().fold(0, |n, d| n * 10 + (d - b'0') as u32))
+        }
+
+        let src = b"1\r\n2x\n\r\n(3\n4" as &[u8];
+        let num = digits().or(digits()
+            .delimited_by(just(b'('), just(b')'))
+            .recover_with(via_parser(just(b'(').ignore_then(digits()))));
+        let results = parse_stream(src, num)
+            .map(|res| {
+                let (out, errs) = res.into_output_errors();
+                (out, errs.iter().map(|e| *e.span()).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                (Some(1), vec![]),
+                (None, vec![SimpleSpan::from(4..5)]),
+                (Some(3), vec![SimpleSpan::from(10..11)]),
+                (Some(4), vec![]),
+            ]
+        );
+
+        // Records are discarded once parsed
+        let src = "123\n".repeat(10_000);
+        let mut stream = parse_stream(src.as_bytes(), digits());
+        for _ in 0..5_000 {
+            assert_eq!(stream.next().unwrap().into_result(), Ok(123));
+        }
+        assert!(stream.own.cache.buf.len() <= 4096);
+        assert_eq!(stream.count(), 5_000);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn parse_into_channel() {
+        use std::sync::mpsc;
+
+        let item = any::<&str, extra::Err<Simple<char>>>()
+            .filter(char::is_ascii_digit)
+            .repeated()
+            .exactly(2)
+            .to_slice();
+
+        let (tx, rx) = mpsc::channel();
+        let res = item.repeated().parse_into_channel("1234x", tx).unwrap();
+        let errs = res.into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(4..5));
+        assert_eq!(rx.iter().collect::<Vec<_>>(), vec!["12", "34"]);
+
+        let (tx, rx) = mpsc::sync_channel(0);
+        drop(rx);
+        assert_eq!(
+            item.repeated().parse_into_channel("1234", tx),
+            Err(crate::Cancelled)
+        );
+
+        #[cfg(feature = "crossbeam-channel")]
+        {
+            let (tx, rx) = crossbeam_channel::bounded(1);
+            let consumer = std::thread::spawn(move || rx.iter().collect::<String>());
+            let res = item.repeated().parse_into_channel("123456", tx).unwrap();
+            assert!(!res.has_errors());
+            assert_eq!(consumer.join().unwrap(), "123456");
+        }
+    }
+
+    #[test]
+    fn embedded_in_different_error() {
+        // The embedded language uses cheap errors, and recovers from bad items
+        let item = text::int::<_, extra::Err<Simple<char>>>(10)
+            .recover_with(via_parser(none_of(',').repeated().at_least(1).to_slice()))
+            .to_slice();
+        let list = item.separated_by(just(',')).collect::<Vec<_>>();
+
+        let css = list
+            .embedded_in(
+                none_of('>').repeated().to_slice(),
+                "list",
+                |err: Simple<char>, region: SimpleSpan| {
+                    let span = err.span();
+                    Rich::custom(
+                        (region.start + span.start..region.start + span.end).into(),
+                        "bad list",
+                    )
+                },
+            )
+            .delimited_by(just::<_, _, extra::Err<Rich<char>>>('<'), just('>'));
+
+        assert_eq!(css.parse("<1,2>").into_result(), Ok(vec!["1", "2"]));
+
+        let (out, errs) = css.parse("<1,x,3>").into_output_errors();
+        assert_eq!(out, Some(vec!["1", "x", "3"]));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(3..4));
+        assert_eq!(errs[0].to_string(), "bad list in list at 1..6");
+        assert_eq!(
+            errs[0]
+                .contexts()
+                .map(|(label, span)| (label.to_string(), *span))
+                .collect::<Vec<_>>(),
+            vec![("list".to_string(), SimpleSpan::from(1..6))]
+        );
+    }
+
+    #[test]
+    fn dedup_errors_merges_expected() {
+        use crate::{error::CodeError, label::LabelError};
+
+        let err = |expected: char, span: core::ops::Range<usize>| {
+            <Rich<char> as LabelError<&str, _>>::expected_found(
+                [crate::DefaultExpected::Token(expected.into())],
+                None,
+                span.into(),
+            )
+        };
+        let mut coded = err('d', 0..1);
+        coded.with_code("E0001");
+
+        let res = ParseResult::new(
+            Some(()),
+            vec![err('a', 0..1), err('b', 1..2), coded, err('c', 0..1)],
+        );
+        let errs = res.clone().dedup_errors().into_errors();
+        assert_eq!(errs.len(), 3);
+        assert_eq!(
+            errs[0]
+                .expected()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>(),
+            vec!["'a'", "'c'"]
+        );
+        assert_eq!(errs[2].code(), Some("E0001"));
+
+        let errs = res.dedup_errors_by_key(|e| e.span().start).into_errors();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(errs[0].expected().count(), 1);
+
+        // Custom errors with different messages are kept
+        let custom = |msg: &str| Rich::<char>::custom(SimpleSpan::from(0..1), msg);
+        let res = ParseResult::new(
+            Some(()),
+            vec![custom("x"), custom("y"), custom("x"), err('a', 0..1)],
+        );
+        let errs = res.dedup_errors().into_errors();
+        assert_eq!(
+            errs.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+            ["x", "y", "found end of input expected 'a'"]
+        );
+    }
+
+    #[test]
+    fn snapshot_display_is_order_independent() {
+        use crate::error::DisplayMode;
+
+        fn snapshot<'src>(
+            parser: impl Parser<'src, &'src str, char, extra::Err<Rich<'src, char>>>,
+            src: &'src str,
+        ) -> String {
+            parser.parse(src).into_errors()[0]
+                .display_mode(DisplayMode::Snapshot)
+                .to_string()
+        }
+
+        let ab = choice((just('a'), just('b'), just('a')));
+        let ba = choice((just('b'), just('a')));
+        assert_eq!(snapshot(ab, "c"), snapshot(ba, "c"));
+        assert_eq!(snapshot(ba, "c"), "0..1: expected 'a' | 'b', found 'c'");
+
+        let coded = just('x').with_code("E0001");
+        assert_eq!(
+            snapshot(coded, ""),
+            "0..0: [E0001] expected 'x', found end of input"
+        );
+
+        let custom = any().try_map(|_, span| Err(Rich::custom(span, "nope")));
+        assert_eq!(snapshot(custom, "z"), "0..1: nope");
+    }
+
+    #[test]
+    fn terminals_shared_across_rules() {
+        terminals! {
+            struct Terms<'src>(&'src str, extra::Err<Rich<'src, char>>) {
+                lparen: char = just('('),
+                rparen: char = just(')'),
+                comma: char = just(',').padded(),
+                num: u32 = text::int(10).from_str().unwrapped(),
+            }
+        }
+
+        let t = Terms::default();
+        let tuple = t
+            .num
+            .clone()
+            .separated_by(&t.comma)
+            .collect::<Vec<_>>()
+            .delimited_by(&t.lparen, &t.rparen);
+        let call = text::ascii::ident().then(tuple.clone());
+        let both = call.then_ignore(&t.comma).then(tuple);
+
+        assert_eq!(
+            both.parse("f(1, 2), (3)").into_result(),
+            Ok((("f", vec![1, 2]), vec![3])),
+        );
+        assert!(both.parse("f(1 2), ()").has_errors());
+    }
+
+    #[test]
+    fn erased_rule_boundary() {
+        fn pair<'src, 'b>(
+            item: Erased<'src, 'b, &'src str, &'src str>,
+        ) -> impl Parser<'src, &'src str, (&'src str, &'src str)> + 'b {
+            item.then_ignore(just(':')).then(item)
+        }
+
+        let ident = text::ascii::ident();
+        let digits = text::digits(10).to_slice();
+
+        assert_eq!(
+            pair(ident.erased()).parse("a:b").into_result(),
+            Ok(("a", "b"))
+        );
+        assert_eq!(
+            pair(digits.erased()).parse("1:23").into_result(),
+            Ok(("1", "23"))
+        );
+        assert!(pair(digits.erased()).parse("1:b").has_errors());
+    }
+
+    #[test]
+    fn interned_with_custom_interner() {
+        use crate::{
+            input::{Checkpoint, Cursor},
+            inspector::{Inspector, Interner},
+        };
+
+        // Counts the words it sees, and canonicalises them to lowercase
+        #[derive(Default)]
+        struct Words(Vec<String>);
+
+        impl Interner<String> for Words {
+            fn intern(&mut self, value: String) -> String {
+                let value = value.to_lowercase();
+                self.0.push(value.clone());
+                value
+            }
+        }
+
+        impl<'src, I: Input<'src>> Inspector<'src, I> for Words {
+            type Checkpoint = ();
+            fn on_token(&mut self, _: &I::Token) {}
+            fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) {}
+            fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, ()>) {}
+        }
+
+        let word = text::ascii::ident::<_, extra::State<Words>>()
+            .map(String::from)
+            .interned()
+            .padded();
+        let words = word.repeated().collect::<Vec<_>>();
+
+        let mut state = Words::default();
+        assert_eq!(
+            words.parse_with_state("Foo BAR", &mut state).into_result(),
+            Ok(vec!["foo".to_string(), "bar".to_string()]),
+        );
+        assert_eq!(state.0, ["foo", "bar"]);
+
+        // Interning is skipped when the output is unused
+        let mut state = Words::default();
+        assert!(!word
+            .repeated()
+            .parse_with_state("a b", &mut state)
+            .has_errors());
+        assert!(state.0.is_empty());
+    }
+
+    #[test]
+    fn shared_state_across_threads() {
+        use crate::extra::{Shared, SymbolTable};
+        use std::{rc::Rc, sync::Mutex};
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        // Extra types are markers, so their auto traits don't depend on the state type
+        assert_send_sync::<extra::State<Rc<()>>>();
+
+        type Extra<'a> = extra::State<Shared<'a, Mutex<Vec<String>>, SymbolTable<String, ()>>>;
+
+        fn decls<'src, 'a: 'src>() -> impl Parser<'src, &'src str, (), Extra<'a>> {
+            let decl = text::ascii::keyword::<&'src str, _, Extra<'a>>("let")
+                .padded()
+                .ignore_then(text::ascii::ident())
+                .then_ignore(just(';'))
+                .map_with(|name: &str, e| e.state().declare(name.to_string(), ()));
+            decl.padded()
+                .repeated()
+                .collect::<Vec<_>>()
+                .map_with(|_, e| {
+                    let state = e.state();
+                    let mut names = ["a", "b", "c"]
+                        .into_iter()
+                        .filter(|n| state.contains(*n))
+                        .map(String::from)
+                        .collect::<Vec<_>>();
+                    state.shared().lock().unwrap().append(&mut names);
+                })
+        }
+
+        let log = Mutex::new(Vec::new());
+        std::thread::scope(|s| {
+            for src in ["let a;", "let b; let c;"] {
+                let log = &log;
+                s.spawn(move || {
+                    let mut state = Shared::new(log, SymbolTable::new());
+                    assert!(!decls().parse_with_state(src, &mut state).has_errors());
+                    assert_eq!(state.depth(), 0);
+                });
+            }
+        });
+
+        let mut log = log.into_inner().unwrap();
+        log.sort();
+        assert_eq!(log, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn extra_macro_defaults() {
+        use core::marker::PhantomData;
+
+        fn same<T>(_: PhantomData<T>, _: PhantomData<T>) {}
+
+        same(PhantomData::<extra!()>, PhantomData::<extra::Default>);
+        same(
+            PhantomData::<extra!(error = Simple<'static, char>,)>,
+            PhantomData::<extra::Err<Simple<'static, char>>>,
+        );
+        same(
+            PhantomData::<extra!(context = u8, state = (), error = EmptyErr)>,
+            PhantomData::<extra::Context<u8>>,
+        );
+        // Later parts override earlier ones
+        same(
+            PhantomData::<extra!(state = u8, state = ())>,
+            PhantomData::<extra::Default>,
+        );
+    }
+
+    #[test]
+    fn when_mode_rejection_recovers() {
+        type Extra = extra::Full<Simple<'static, char>, extra::ModeStack<u8>, ()>;
+
+        let parser = just::<_, _, Extra>('a')
+            .when_mode(1)
+            .recover_with(via_parser(just('a').to('?')));
+
+        let (out, errs) = parser
+            .parse_with_state("a", &mut extra::ModeStack::new(0))
+            .into_output_errors();
+        assert_eq!(out, Some('?'));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..0));
+    }
+
+    #[test]
+    fn unless_restricted_recovers() {
+        use crate::extra::{unless_restricted, with_restriction, Restrictions};
+        const R: Restrictions = Restrictions::from_bits(1);
+
+        let parser = with_restriction(
+            R,
+            unless_restricted(
+                R,
+                just::<_, _, extra::Full<Simple<char>, (), Restrictions>>('a'),
+            )
+            .recover_with(via_parser(just('a').to('?'))),
+        );
+
+        let (out, errs) = parser.parse("a").into_output_errors();
+        assert_eq!(out, Some('?'));
+        assert_eq!(errs.len(), 1);
+    }
+
+    #[test]
+    fn length_prefixed_frames() {
+        let frame = length_prefixed(
+            any::<&[u8], extra::Err<Rich<u8>>>().map(usize::from),
+            just::<_, &[u8], extra::Err<Rich<u8>>>(b"ab")
+                .to(0)
+                .or(any().repeated().count()),
+        );
+        let frames = frame.repeated().collect::<Vec<_>>();
+
+        assert_eq!(
+            frames.parse(b"\x02ab\x03xyz\x00").into_result(),
+            Ok(vec![0, 3, 0])
+        );
+        // The frame is truncated
+        let errs = frames.parse(b"\x02ab\x04abc").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(7..7));
+        assert_eq!(errs[0].found(), None);
+    }
+
+    #[test]
+    fn checksummed_frames() {
+        let frame = just::<_, &[u8], extra::Err<Rich<u8>>>(b'[')
+            .ignore_then(none_of(b']').repeated().collect::<Vec<_>>())
+            .then_ignore(just(b']'))
+            .checksummed(any(), |data: &[u8], len, span| {
+                if data.len() == len as usize {
+                    Ok(())
+                } else {
+                    Err(Rich::custom(span, "bad length"))
+                }
+            });
+        let frames = frame.repeated().collect::<Vec<_>>();
+
+        assert_eq!(
+            frames.parse(b"[ab]\x04[]\x02").into_result(),
+            Ok(vec![b"ab".to_vec(), Vec::new()])
+        );
+        let errs = frames.parse(b"[ab]\x04[c]\x04").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(8..9));
+        // A missing trailer is reported as such
+        let errs = frames.parse(b"[ab]").into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(4..4));
+    }
+
+    #[test]
+    fn switch_on_tags() {
+        // A two-byte big-endian tag, selecting a record of that many bytes
+        let tag = any::<&[u8], extra::Err<Rich<u8>>>()
+            .then(any())
+            .map(|(hi, lo)| u16::from_be_bytes([hi, lo]));
+        let short = any().repeated().exactly(1).collect::<Vec<_>>().boxed();
+        let long = any().repeated().exactly(3).collect::<Vec<_>>().boxed();
+        let record = switch_on(tag, move |tag: &u16| match tag {
+            1 => Some(short.clone()),
+            3 => Some(long.clone()),
+            _ => None,
+        });
+        let records = record.repeated().collect::<Vec<_>>();
+
+        assert_eq!(
+            records.parse(&[0, 3, 1, 2, 3, 0, 1, 4]).into_result(),
+            Ok(vec![vec![1, 2, 3], vec![4]])
+        );
+        // The unknown tag spans both bytes
+        let errs = records.parse(&[0, 1, 4, 1, 0, 5]).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(3..5));
+        assert_eq!(errs[0].found(), Some(&1));
+        // A truncated record is reported within it
+        let errs = records.parse(&[0, 3, 1]).into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(3..3));
+    }
+
+    #[test]
+    fn align_and_pad() {
+        use crate::primitive::{align_to, pad_bytes};
+
+        // A length byte, then a frame in which fields are aligned to 2 bytes
+        let field = any::<&[u8], extra::Err<Rich<u8>>>().then_ignore(align_to(2));
+        let frame = length_prefixed(
+            any::<&[u8], extra::Err<Rich<u8>>>().map(usize::from),
+            field.repeated().collect::<Vec<_>>(),
+        );
+        let parser = frame.then_ignore(pad_bytes(2)).then(align_to(4).to_slice());
+
+        // Alignment within the frame is relative to the start of the whole input, so the frame starts aligned
+        assert_eq!(
+            parser.parse(&[3, 1, 2, 0, 9, 9, 0, 0]).into_result(),
+            Ok((vec![1, 2], &[0, 0][..]))
+        );
+        // Already aligned
+        assert_eq!(
+            parser.parse(&[1, 1, 9, 9]).into_result(),
+            Ok((vec![1], &[][..]))
+        );
+        // The input ends within the padding
+        let errs = parser.parse(&[3, 1, 2, 0, 9, 9, 0]).into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(7..7));
+    }
+
+    #[test]
+    fn end_spanning_rest() {
+        let line = text::ascii::ident::<_, extra::Err<Rich<char>>>()
+            .separated_by(just(' '))
+            .collect::<Vec<_>>();
+
+        // By default, only the first unexpected token is reported
+        let errs = line.then_ignore(end()).parse("foo bar;baz").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(7..8));
+
+        let parser = line.then_ignore(end().spanning_rest());
+        let errs = parser.parse("foo bar;baz").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(7..11));
+        assert_eq!(errs[0].found(), Some(&';'));
+        assert_eq!(
+            parser.parse("foo bar").into_result(),
+            Ok(vec!["foo", "bar"])
+        );
+
+        // Errors from further into the input still take priority
+        let errs = line
+            .then_ignore(just(';'))
+            .then(just("end"))
+            .or(line.then_ignore(end().spanning_rest()).map(|l| (l, "")))
+            .parse("foo;en")
+            .into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::from(6..6));
+    }
 }
@@ -0,0 +1,91 @@
+//! A small module that converts [`Rich`] errors into
+//! [`lsp_types`](https://docs.rs/lsp-types/latest/lsp_types/) diagnostics, for use in language servers.
+
+use super::*;
+use alloc::{format, string::ToString};
+use error::TokenDisplay;
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+    Position, Range, Url,
+};
+use span::{LineIndex, PositionEncoding};
+
+fn range<S: Span<Offset = usize>>(
+    index: &LineIndex,
+    encoding: PositionEncoding,
+    span: &S,
+) -> Range {
+    let position = |offset| {
+        let (line, col) = index.position(offset, encoding);
+        Position::new(line as u32, col as u32)
+    };
+    Range::new(position(span.start()), position(span.end()))
+}
+
+impl<T, S> Rich<'_, T, S>
+where
+    T: TokenDisplay,
+    S: Span<Offset = usize>,
+{
+    /// Convert this error into a Language Server Protocol [`Diagnostic`] for the document with the given URI, using
+    /// the given [`LineIndex`] of the document's text to find the positions of spans.
+    ///
+    /// Each of the error's contexts (see [`Parser::labelled`]) becomes an item of related information.
+    ///
+    /// The encoding must be the position encoding negotiated with the client. Unless the client supports something
+    /// else, this is [`PositionEncoding::Utf16`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, span::{LineIndex, PositionEncoding}};
+    /// use lsp_types::{DiagnosticSeverity, Position, Range, Url};
+    ///
+    /// let parser = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('['), just(']'))
+    ///     .labelled("list")
+    ///     .as_context();
+    ///
+    /// let src = "[\n    1,\n    2;\n]";
+    /// let uri = Url::parse("file:///example.txt").unwrap();
+    /// let index = LineIndex::new(src);
+    /// let err = &parser.parse(src).into_errors()[0];
+    /// let diagnostic = err.to_lsp_diagnostic(&uri, &index, PositionEncoding::Utf16);
+    ///
+    /// assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+    /// assert_eq!(diagnostic.range, Range::new(Position::new(2, 5), Position::new(2, 6)));
+    /// assert_eq!(diagnostic.related_information.unwrap()[0].message, "while parsing list");
+    /// ```
+    pub fn to_lsp_diagnostic(
+        &self,
+        uri: &Url,
+        index: &LineIndex,
+        encoding: PositionEncoding,
+    ) -> Diagnostic {
+        let related = self
+            .contexts()
+            .map(|(label, span)| DiagnosticRelatedInformation {
+                location: Location::new(uri.clone(), range(index, encoding, span)),
+                message: format!("while parsing {label}"),
+            })
+            .collect::<Vec<_>>();
+
+        Diagnostic {
+            range: range(index, encoding, self.span()),
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: self
+                .code()
+                .map(|code| NumberOrString::String(code.to_string())),
+            message: self.reason().to_string(),
+            related_information: if related.is_empty() {
+                None
+            } else {
+                Some(related)
+            },
+            ..Diagnostic::default()
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Operator sugar for combining parsers.
+//!
+//! *“Forty-two,” said Deep Thought, with infinite majesty and calm.*
+//!
+//! Some people prefer grammars that read more like EBNF than like Rust. Wrapping parsers with [`op`] allows them to
+//! be combined with operators:
+//!
+//! | Operator | Equivalent to         |
+//! |----------|-----------------------|
+//! | `a >> b` | [`Parser::ignore_then`] |
+//! | `a << b` | [`Parser::then_ignore`] |
+//! | `a \| b` | [`Parser::or`]          |
+//!
+//! Both operands must be wrapped, and the result of an operator is wrapped too, so operators can be chained. The
+//! wrapper is a thin layer over the combinators above: it behaves exactly as they do, and can be used anywhere a
+//! parser is expected.
+//!
+//! # Examples
+//!
+//! ```
+//! use chumsky::{prelude::*, ops::op};
+//!
+//! let digits = op(text::digits::<_, extra::Err<Simple<char>>>(10).to_slice());
+//! let sign = op(just('-').or_not());
+//! let number = (sign >> digits.clone() << op(just(';'))) | (op(just('#')) >> digits);
+//!
+//! assert_eq!(number.parse("-42;").into_result(), Ok("42"));
+//! assert_eq!(number.parse("#7").into_result(), Ok("7"));
+//! ```
+
+use super::*;
+use core::ops::{BitOr, Shl, Shr};
+
+/// A parser that can be combined with other parsers using operators. See the [module docs](self).
+pub struct Op<P, I, O, E> {
+    parser: P,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(I, O, E)>,
+}
+
+impl<P: Copy, I, O, E> Copy for Op<P, I, O, E> {}
+impl<P: Clone, I, O, E> Clone for Op<P, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Wrap a parser so that it can be combined with other parsers using operators. See the [module docs](self).
+pub const fn op<'src, P, I, O, E>(parser: P) -> Op<P, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    P: Parser<'src, I, O, E>,
+{
+    Op {
+        parser,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<P, I, O, E> Op<P, I, O, E> {
+    /// Get the wrapped parser back.
+    pub fn into_inner(self) -> P {
+        self.parser
+    }
+}
+
+impl<'src, P, I, O, E> Parser<'src, I, O, E> for Op<P, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    P: Parser<'src, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        self.parser.go::<M>(inp)
+    }
+
+    go_extra!(O);
+}
+
+/// `a >> b` is equivalent to `a.ignore_then(b)`.
+impl<P, Q, I, O, U, E> Shr<Op<Q, I, U, E>> for Op<P, I, O, E> {
+    type Output = Op<IgnoreThen<P, Q, O, E>, I, U, E>;
+
+    fn shr(self, other: Op<Q, I, U, E>) -> Self::Output {
+        Op {
+            parser: IgnoreThen {
+                parser_a: self.parser,
+                parser_b: other.parser,
+                phantom: EmptyPhantom::new(),
+            },
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// `a << b` is equivalent to `a.then_ignore(b)`.
+impl<P, Q, I, O, U, E> Shl<Op<Q, I, U, E>> for Op<P, I, O, E> {
+    type Output = Op<ThenIgnore<P, Q, U, E>, I, O, E>;
+
+    fn shl(self, other: Op<Q, I, U, E>) -> Self::Output {
+        Op {
+            parser: ThenIgnore {
+                parser_a: self.parser,
+                parser_b: other.parser,
+                phantom: EmptyPhantom::new(),
+            },
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// `a | b` is equivalent to `a.or(b)`.
+impl<P, Q, I, O, E> BitOr<Op<Q, I, O, E>> for Op<P, I, O, E> {
+    type Output = Op<Or<P, Q>, I, O, E>;
+
+    fn bitor(self, other: Op<Q, I, O, E>) -> Self::Output {
+        Op {
+            parser: Or {
+                choice: choice((self.parser, other.parser)),
+            },
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
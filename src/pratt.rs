@@ -10,7 +10,8 @@
 //! ['binding power'](https://matklad.github.io/2020/04/13/simple-but-powerful-pratt-parsing.html#From-Precedence-to-Binding-Power)
 //! that determines how strongly operators should bind to the operands around them.
 //!
-//! Pratt parsers are defined with the [`Parser::pratt`] method.
+//! Pratt parsers are defined with the [`Parser::pratt`] method, or with [`Parser::pratt_builder`] when there are many
+//! operators.
 //!
 //! When writing pratt parsers, it is necessary to first define an 'atomic' operand used by the parser for building up
 //! expressions. In most languages, atoms are simple, self-delimiting patterns such as numeric and string literals,
@@ -21,7 +22,7 @@
 //!
 //! Because operators bind atoms together, pratt parsers require you to specify, for each operator, a function that
 //! combines its operands together into a syntax tree. These functions are given as the last arguments of [`infix`],
-//! [`prefix`], and [`postfix`].
+//! [`prefix`], [`postfix`], [`ternary`], [`mixfix`], [`chained`], [`call`], and [`infix_rhs`].
 //!
 //! # Examples
 //!
@@ -187,6 +188,10 @@ macro_rules! op_check_and_emit {
 }
 
 /// A type implemented by pratt parser operators.
+///
+/// This trait is implemented by [`infix`], [`prefix`], [`postfix`], [`ternary`], [`mixfix`], [`chained`], [`call`], and
+/// [`infix_rhs`] operators, by [`Boxed`] and [`Labelled`] operators, and by tuples and [`Vec`]s of operators. Its
+/// methods are an implementation detail: to define new kinds of operator, implement [`CustomOperator`] instead.
 pub trait Operator<'src, I, O, E>
 where
     I: Input<'src>,
@@ -197,7 +202,47 @@ where
     where
         Self: Sized + 'a,
     {
-        Boxed(Rc::new(self))
+        let info = self.info();
+        Boxed(Rc::new(self), info)
+    }
+
+    /// Label this operator, such that errors produced where the operator was expected mention the label instead of
+    /// the tokens the operator begins with.
+    ///
+    /// This is useful for producing more readable diagnostics when a pratt parser has many operators: rather than
+    /// listing every operator token, an error can say that it expected, for example, a 'binary operator'. Operators
+    /// that share a label are reported just once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, pratt::*};
+    /// let expr = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .from_str::<i64>()
+    ///     .unwrapped()
+    ///     .pratt((
+    ///         infix(left(1), just('+'), |l, _, r, _| l + r).labelled("binary operator"),
+    ///         infix(left(1), just('-'), |l, _, r, _| l - r).labelled("binary operator"),
+    ///         infix(left(2), just('*'), |l, _, r, _| l * r).labelled("binary operator"),
+    ///     ))
+    ///     .then_ignore(end());
+    ///
+    /// assert_eq!(expr.parse("1+2*3").into_result(), Ok(7));
+    ///
+    /// let errs = expr.parse("1+2?3").into_errors();
+    /// assert_eq!(errs[0].to_string(), "found '?' expected digit, binary operator, or end of input");
+    /// ```
+    fn labelled<L>(self, label: L) -> Labelled<Self, L>
+    where
+        Self: Sized,
+    {
+        Labelled { op: self, label }
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo::ANY
     }
 
     #[doc(hidden)]
@@ -306,12 +351,193 @@ where
     ) -> Result<O, O>;
 }
 
+/// Describes which kinds of operator an [`Operator`] may parse, and the binding powers at which they apply.
+///
+/// This allows operator tables to skip operators that cannot possibly apply at a given position without invoking them,
+/// which matters most for [`Boxed`] operators (for which the information is recorded when boxing, so checking it
+/// involves no dynamic dispatch).
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OpInfo {
+    prefix: bool,
+    // The highest `min_power` at which a postfix or infix operator can still apply, if there is one
+    postfix: Option<u32>,
+    infix: Option<u32>,
+}
+
+impl OpInfo {
+    const ANY: Self = Self {
+        prefix: true,
+        postfix: Some(u32::MAX),
+        infix: Some(u32::MAX),
+    };
+
+    const NONE: Self = Self {
+        prefix: false,
+        postfix: None,
+        infix: None,
+    };
+
+    #[inline(always)]
+    fn merge(self, other: Self) -> Self {
+        Self {
+            prefix: self.prefix || other.prefix,
+            postfix: self.postfix.max(other.postfix),
+            infix: self.infix.max(other.infix),
+        }
+    }
+
+    #[inline(always)]
+    fn prefix(&self) -> bool {
+        self.prefix
+    }
+
+    #[inline(always)]
+    fn postfix(&self, min_power: u32) -> bool {
+        self.postfix.map_or(false, |power| power >= min_power)
+    }
+
+    #[inline(always)]
+    fn infix(&self, min_power: u32) -> bool {
+        self.infix.map_or(false, |power| power >= min_power)
+    }
+}
+
 /// A boxed pratt parser operator. See [`Operator`].
-pub struct Boxed<'src, 'a, I, O, E = extra::Default>(Rc<DynOperator<'src, 'a, I, O, E>>);
+pub struct Boxed<'src, 'a, I, O, E = extra::Default>(Rc<DynOperator<'src, 'a, I, O, E>>, OpInfo);
 
 impl<I, O, E> Clone for Boxed<'_, '_, I, O, E> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self(self.0.clone(), self.1)
+    }
+}
+
+// Conversions into boxed operators, for building operator tables at runtime (see `Pratt::with_op`)
+
+impl<'src, 'a, A, F, Op, I, O, E> From<Infix<'src, A, F, O, Op, I, E>> for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Infix<'src, A, F, O, Op, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Infix<'src, A, F, O, Op, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, L, F, Op, I, O, E> From<DynamicInfix<'src, A, L, F, O, Op, I, E>>
+    for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    DynamicInfix<'src, A, L, F, O, Op, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: DynamicInfix<'src, A, L, F, O, Op, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, F, Op, I, O, E> From<Prefix<'src, A, F, O, Op, I, E>> for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Prefix<'src, A, F, O, Op, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Prefix<'src, A, F, O, Op, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, F, Op, I, O, E> From<Postfix<'src, A, F, O, Op, I, E>>
+    for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Postfix<'src, A, F, O, Op, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Postfix<'src, A, F, O, Op, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, B, F, OpA, OpB, I, O, E> From<Ternary<'src, A, B, F, O, OpA, OpB, I, E>>
+    for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Ternary<'src, A, B, F, O, OpA, OpB, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Ternary<'src, A, B, F, O, OpA, OpB, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, F, Op, I, O, E> From<Chained<'src, A, F, O, Op, I, E>>
+    for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Chained<'src, A, F, O, Op, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Chained<'src, A, F, O, Op, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, F, Op, I, O, E> From<Mixfix<'src, A, F, O, Op, I, E>> for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Mixfix<'src, A, F, O, Op, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Mixfix<'src, A, F, O, Op, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, S, C, F, OA, OS, OC, I, O, E> From<Call<'src, A, S, C, F, O, OA, OS, OC, I, E>>
+    for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Call<'src, A, S, C, F, O, OA, OS, OC, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Call<'src, A, S, C, F, O, OA, OS, OC, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, Op, L, I, O, E> From<Labelled<Op, L>> for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Labelled<Op, L>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Labelled<Op, L>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, A, B, F, Op, R, I, O, E> From<InfixRhs<'src, A, B, F, O, Op, R, I, E>>
+    for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    InfixRhs<'src, A, B, F, O, Op, R, I, E>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: InfixRhs<'src, A, B, F, O, Op, R, I, E>) -> Self {
+        op.boxed()
+    }
+}
+
+impl<'src, 'a, Op, I, O, E> From<Custom<Op>> for Boxed<'src, 'a, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Custom<Op>: Operator<'src, I, O, E> + 'a,
+{
+    fn from(op: Custom<Op>) -> Self {
+        op.boxed()
     }
 }
 
@@ -320,6 +546,19 @@ where
     I: Input<'src>,
     E: ParserExtra<'src, I>,
 {
+    fn boxed<'b>(self) -> Boxed<'src, 'b, I, O, E>
+    where
+        Self: Sized + 'b,
+    {
+        // Never double-box operators
+        self
+    }
+
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        self.1
+    }
+
     #[inline(always)]
     fn do_parse_prefix<'parse, M: Mode>(
         &self,
@@ -435,15 +674,18 @@ where
 }
 
 /// Defines the [associativity](https://en.wikipedia.org/wiki/Associative_property) and binding power of an [`infix`]
-/// operator (see [`left`] and [`right`]).
+/// operator (see [`left`], [`right`] and [`non_assoc`]).
 ///
 /// Higher binding powers should be used for higher precedence operators.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum Associativity {
     /// Specifies that the operator should be left-associative, with the given binding power (see [`left`]).
     Left(u16),
     /// Specifies that the operator should be right-associative, with the given binding power (see [`right`]).
     Right(u16),
+    /// Specifies that the operator should be non-associative, with the given binding power (see [`non_assoc`]).
+    NonAssoc(u16),
 }
 
 /// Specifies a left [`Associativity`] with the given binding power.
@@ -462,17 +704,58 @@ pub fn right(binding_power: u16) -> Associativity {
     Associativity::Right(binding_power)
 }
 
+/// Specifies a non-associative [`Associativity`] with the given binding power.
+///
+/// Non-associative operators cannot be chained: in languages like OCaml and Haskell, the expression `a < b < c` is
+/// illegal rather than meaning `(a < b) < c`. When the operator is immediately followed by itself at the same level,
+/// an error is emitted at the second occurrence. Parsing then continues as if the operator were left-associative, so
+/// an output is still produced.
+///
+/// Only the operator itself is checked, so operators that share a non-associative level and must not be mixed (such as
+/// `<` and `>`) should be parsed by a single [`infix`] operator, using [`choice`](crate::primitive::choice) or
+/// [`one_of`](crate::primitive::one_of) for the operator parser.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// let atom = text::int::<_, extra::Err<Rich<char>>>(10).from_str::<i64>().unwrapped().padded();
+///
+/// let expr = atom.pratt((
+///     infix(left(2), just('+').padded(), |l, _, r, _| l + r),
+///     infix(non_assoc(1), one_of("<>").padded(), |l, op, r, _| match op {
+///         '<' => (l < r) as i64,
+///         _ => (l > r) as i64,
+///     }),
+/// ));
+///
+/// assert_eq!(expr.parse("1 + 1 < 3").into_result(), Ok(1));
+/// let errs = expr.parse("1 < 2 > 3").into_errors();
+/// assert_eq!(errs.len(), 1);
+/// // The error points at the second comparison
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(6..7));
+/// ```
+pub fn non_assoc(binding_power: u16) -> Associativity {
+    Associativity::NonAssoc(binding_power)
+}
+
 impl Associativity {
     fn left_power(&self) -> u32 {
         match self {
-            Self::Left(x) => *x as u32 * 2,
+            Self::Left(x) | Self::NonAssoc(x) => *x as u32 * 2,
             Self::Right(x) => *x as u32 * 2 + 1,
         }
     }
 
+    fn binding_power(&self) -> u16 {
+        match self {
+            Self::Left(x) | Self::Right(x) | Self::NonAssoc(x) => *x,
+        }
+    }
+
     fn right_power(&self) -> u32 {
         match self {
-            Self::Left(x) => *x as u32 * 2 + 1,
+            Self::Left(x) | Self::NonAssoc(x) => *x as u32 * 2 + 1,
             Self::Right(x) => *x as u32 * 2,
         }
     }
@@ -529,6 +812,31 @@ where
     }
 }
 
+// Emit an error if a non-associative operator is directly followed by another occurrence of itself
+fn check_non_assoc<'src, 'parse, I, E, Op>(
+    associativity: Associativity,
+    op_parser: &impl Parser<'src, I, Op, E>,
+    inp: &mut InputRef<'src, 'parse, I, E>,
+) where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    if !matches!(associativity, Associativity::NonAssoc(_)) {
+        return;
+    }
+    let before = inp.save();
+    if op_parser.go::<Check>(inp).is_ok() {
+        // Point at the first token of the operator so that the span and the found token agree
+        inp.rewind(before.clone());
+        let found = inp.next_maybe();
+        let span = inp.span_since(before.cursor());
+        inp.rewind(before);
+        inp.emit(None, E::Error::expected_found([], found, span));
+    } else {
+        inp.rewind(before);
+    }
+}
+
 impl<'src, I, O, E, A, F, Op> Operator<'src, I, O, E> for Infix<'src, A, F, O, Op, I, E>
 where
     I: Input<'src>,
@@ -536,6 +844,14 @@ where
     A: Parser<'src, I, Op, E>,
     F: Fn(O, Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
 {
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            infix: Some(self.associativity.left_power()),
+            ..OpInfo::NONE
+        }
+    }
+
     #[inline]
     fn do_parse_infix<'parse, M: Mode>(
         &self,
@@ -552,13 +868,16 @@ where
         if self.associativity.left_power() >= min_power {
             match self.op_parser.go::<M>(inp) {
                 Ok(op) => match f(inp, self.associativity.right_power()) {
-                    Ok(rhs) => Ok(M::combine(
-                        M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
-                        op,
-                        |(lhs, rhs), op| {
-                            (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp))
-                        },
-                    )),
+                    Ok(rhs) => {
+                        check_non_assoc(self.associativity, &self.op_parser, inp);
+                        Ok(M::combine(
+                            M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
+                            op,
+                            |(lhs, rhs), op| {
+                                (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp))
+                            },
+                        ))
+                    }
                     Err(()) => {
                         inp.rewind(pre_op.clone());
                         Err(lhs)
@@ -577,6 +896,189 @@ where
     op_check_and_emit!();
 }
 
+/// See [`dynamic_infix`].
+pub struct DynamicInfix<'src, A, L, F, Atom, Op, I, E> {
+    op_parser: A,
+    fixity: L,
+    fold: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, Op, I, E)>,
+}
+
+impl<A: Copy, L: Copy, F: Copy, Atom, Op, I, E> Copy for DynamicInfix<'_, A, L, F, Atom, Op, I, E> {}
+impl<A: Clone, L: Clone, F: Clone, Atom, Op, I, E> Clone
+    for DynamicInfix<'_, A, L, F, Atom, Op, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            op_parser: self.op_parser.clone(),
+            fixity: self.fixity.clone(),
+            fold: self.fold.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a binary infix operator for a pratt parser whose associativity and binding power are looked up in the
+/// parser state when the operator is parsed, using the given [fold function](crate::pratt#fold-functions).
+///
+/// This allows languages like Haskell, in which fixity declarations such as `infixl 6 +` appear in the source code, to
+/// apply those declarations to the expressions that follow them. The fixity function is given the output of the
+/// operator parser and the parser state, and returns the [`Associativity`] of the operator. If it returns `None`, the
+/// operator is not parsed at all (a default fixity, like Haskell's `infixl 9`, can be returned instead).
+///
+/// Operators with a [`non_assoc`] fixity are checked against the fixity of the following operator: if the two have the
+/// same binding power, an error is emitted at the second operator, so that `a == b < c` is rejected when both
+/// operators are declared with `infix 4`.
+///
+/// The fold function (the last argument) must have the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, Op, Atom, &mut MapExtra<'src, '_, I, E>) -> O
+/// ```
+///
+/// Dynamic infix operators cannot be used with [`Pratt::iterative`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*, extra::SimpleState};
+/// use std::collections::HashMap;
+///
+/// type Fixities<'src> = SimpleState<HashMap<&'src str, Associativity>>;
+/// type Extra<'src> = extra::Full<EmptyErr, Fixities<'src>, ()>;
+///
+/// let op = one_of::<_, _, Extra>("+-*^<=").repeated().at_least(1).to_slice().padded();
+///
+/// // `infixl 6 +`, recording the fixity of `+` in the state
+/// let fixity = choice((
+///     text::keyword("infixl").to(left as fn(u16) -> Associativity),
+///     text::keyword("infixr").to(right as fn(u16) -> Associativity),
+///     text::keyword("infix").to(non_assoc as fn(u16) -> Associativity),
+/// ))
+/// .padded()
+/// .then(text::int(10).from_str::<u16>().unwrapped())
+/// .then(op)
+/// .map_with(|((assoc, power), op), e| {
+///     e.state().insert(op, assoc(power));
+/// });
+///
+/// let expr = text::int(10)
+///     .map(str::to_string)
+///     .padded()
+///     .pratt(dynamic_infix(
+///         op,
+///         |op: &&str, fixities: &Fixities| fixities.get(op).copied(),
+///         |l, op, r, _| format!("({l} {op} {r})"),
+///     ));
+///
+/// // Declarations are collected so that they are run for their side effects
+/// let module = fixity
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .then(expr)
+///     .map(|(_, expr)| expr);
+///
+/// let src = "infixl 6 + infixr 8 ^ 1 + 2 ^ 3 ^ 4 + 5";
+/// let mut state = SimpleState(HashMap::new());
+/// assert_eq!(
+///     module.parse_with_state(src, &mut state).into_result(),
+///     Ok("((1 + (2 ^ (3 ^ 4))) + 5)".to_string()),
+/// );
+///
+/// let src = "infix 4 <= infix 4 == 1 <= 2 == 3";
+/// assert!(module.parse_with_state(src, &mut SimpleState(HashMap::new())).has_errors());
+/// ```
+pub const fn dynamic_infix<'src, A, L, F, Atom, Op, I, E>(
+    op_parser: A,
+    fixity: L,
+    fold: F,
+) -> DynamicInfix<'src, A, L, F, Atom, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    L: Fn(&Op, &E::State) -> Option<Associativity>,
+    F: Fn(Atom, Op, Atom, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    DynamicInfix {
+        op_parser,
+        fixity,
+        fold,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, L, F, Op> Operator<'src, I, O, E>
+    for DynamicInfix<'src, A, L, F, O, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    L: Fn(&Op, &E::State) -> Option<Associativity>,
+    F: Fn(O, Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    // The binding power is not known until the operator is parsed
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            infix: Some(u32::MAX),
+            ..OpInfo::NONE
+        }
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        let op = self.op_parser.go::<Emit>(inp).ok().and_then(|op| {
+            let assoc = (self.fixity)(&op, inp.state())?;
+            Some((op, assoc)).filter(|_| assoc.left_power() >= min_power)
+        });
+        let Some((op, assoc)) = op else {
+            inp.rewind(pre_op.clone());
+            return Err(lhs);
+        };
+        match f(inp, assoc.right_power()) {
+            Ok(rhs) => {
+                if let Associativity::NonAssoc(power) = assoc {
+                    // Reject a following operator at the same level
+                    let before = inp.save();
+                    let found = inp.peek_maybe();
+                    let next = self.op_parser.go::<Emit>(inp).ok();
+                    if next.map_or(false, |next| {
+                        (self.fixity)(&next, inp.state())
+                            .map_or(false, |next| next.binding_power() == power)
+                    }) {
+                        let span = inp.span_since(before.cursor());
+                        inp.rewind(before);
+                        inp.emit(None, E::Error::expected_found([], found, span));
+                    } else {
+                        inp.rewind(before);
+                    }
+                }
+                Ok(M::combine(lhs, rhs, |lhs, rhs| {
+                    (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp))
+                }))
+            }
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
 /// See [`prefix`].
 pub struct Prefix<'src, A, F, Atom, Op, I, E> {
     op_parser: A,
@@ -632,6 +1134,14 @@ where
     A: Parser<'src, I, Op, E>,
     F: Fn(Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
 {
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            prefix: true,
+            ..OpInfo::NONE
+        }
+    }
+
     #[inline]
     fn do_parse_prefix<'parse, M: Mode>(
         &self,
@@ -717,6 +1227,14 @@ where
     A: Parser<'src, I, Op, E>,
     F: Fn(O, Op, &mut MapExtra<'src, '_, I, E>) -> O,
 {
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            postfix: Some(Associativity::Left(self.binding_power).right_power()),
+            ..OpInfo::NONE
+        }
+    }
+
     #[inline]
     fn do_parse_postfix<'parse, M: Mode>(
         &self,
@@ -747,246 +1265,2205 @@ where
     op_check_and_emit!();
 }
 
-/// See [`Parser::pratt`].
-#[derive(Copy, Clone)]
-pub struct Pratt<Atom, Ops> {
-    pub(crate) atom: Atom,
-    pub(crate) ops: Ops,
+/// See [`ternary`].
+pub struct Ternary<'src, A, B, F, Atom, OpA, OpB, I, E> {
+    first: A,
+    second: B,
+    fold: F,
+    associativity: Associativity,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, OpA, OpB, I, E)>,
 }
 
-macro_rules! impl_operator_for_tuple {
-    () => {};
-    ($head:ident $($X:ident)*) => {
-        impl_operator_for_tuple!($($X)*);
-        impl_operator_for_tuple!(~ $head $($X)*);
-    };
-    (~ $($X:ident)+) => {
-        #[allow(unused_variables, non_snake_case)]
-        impl<'src, I, O, E, $($X),*> Operator<'src, I, O, E> for ($($X,)*)
+impl<A: Copy, B: Copy, F: Copy, Atom, OpA, OpB, I, E> Copy
+    for Ternary<'_, A, B, F, Atom, OpA, OpB, I, E>
+{
+}
+impl<A: Clone, B: Clone, F: Clone, Atom, OpA, OpB, I, E> Clone
+    for Ternary<'_, A, B, F, Atom, OpA, OpB, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            first: self.first.clone(),
+            second: self.second.clone(),
+            fold: self.fold.clone(),
+            associativity: self.associativity,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a ternary operator for a pratt parser, such as `cond ? a : b`, with the given associativity, binding power,
+/// and [fold function](crate::pratt#fold-functions).
+///
+/// A ternary operator is made up of two operator parsers (`?` and `:` in the example above) with three operands: one
+/// before, one between, and one after them. The outer operands bind according to the associativity, just like the
+/// operands of an [`infix`] operator. Because the middle operand is delimited by the operator's own tokens, it may be
+/// any expression at all, regardless of binding power. Conditional operators are conventionally right-associative, so
+/// that `a ? b : c ? d : e` is parsed as `a ? b : (c ? d : e)`.
+///
+/// The fold function (the last argument) tells the parser how to combine the operators and operands into a new
+/// expression. It must have the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, OpA, Atom, OpB, Atom, &mut MapExtra<'src, '_, I, E>) -> O
+/// ```
+///
+/// For operators with more than two parts, see [`mixfix`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// let expr = text::int::<_, extra::Err<Simple<char>>>(10)
+///     .from_str::<i64>()
+///     .unwrapped()
+///     .padded()
+///     .pratt((
+///         ternary(right(1), just('?'), just(':'), |c, _, a, _, b, _| if c != 0 { a } else { b }),
+///         infix(left(2), just('+'), |a, _, b, _| a + b),
+///     ));
+///
+/// assert_eq!(expr.parse("1 ? 2 : 3").into_result(), Ok(2));
+/// // The else branch extends as far as possible
+/// assert_eq!(expr.parse("0 ? 2 : 0 ? 3 : 4 + 5").into_result(), Ok(9));
+/// // The middle operand may be any expression, including another ternary
+/// assert_eq!(expr.parse("1 + 1 ? 1 ? 2 : 3 : 4").into_result(), Ok(2));
+/// assert!(expr.parse("1 ? 2").has_errors());
+/// ```
+pub const fn ternary<'src, A, B, F, Atom, OpA, OpB, I, E>(
+    associativity: Associativity,
+    first: A,
+    second: B,
+    fold: F,
+) -> Ternary<'src, A, B, F, Atom, OpA, OpB, I, E>
+where
+    F: Fn(Atom, OpA, Atom, OpB, Atom, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    Ternary {
+        first,
+        second,
+        fold,
+        associativity,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, B, F, OpA, OpB> Operator<'src, I, O, E>
+    for Ternary<'src, A, B, F, O, OpA, OpB, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, OpA, E>,
+    B: Parser<'src, I, OpB, E>,
+    F: Fn(O, OpA, O, OpB, O, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            infix: Some(self.associativity.left_power()),
+            ..OpInfo::NONE
+        }
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        if self.associativity.left_power() < min_power {
+            return Err(lhs);
+        }
+        let parts = self.first.go::<M>(inp).and_then(|op_a| {
+            let mid = f(inp, 0)?;
+            let op_b = self.second.go::<M>(inp)?;
+            let rhs = f(inp, self.associativity.right_power())?;
+            Ok(M::combine(
+                M::combine(op_a, mid, |op_a, mid| (op_a, mid)),
+                M::combine(op_b, rhs, |op_b, rhs| (op_b, rhs)),
+                |a, b| (a, b),
+            ))
+        });
+        match parts {
+            Ok(parts) => Ok(M::combine(lhs, parts, |lhs, ((op_a, mid), (op_b, rhs))| {
+                (self.fold)(lhs, op_a, mid, op_b, rhs, &mut MapExtra::new(pre_expr, inp))
+            })),
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`mixfix`].
+pub struct Mixfix<'src, A, F, Atom, Op, I, E> {
+    ops: Vec<A>,
+    fold: F,
+    associativity: Associativity,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, Op, I, E)>,
+}
+
+impl<A: Clone, F: Clone, Atom, Op, I, E> Clone for Mixfix<'_, A, F, Atom, Op, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            ops: self.ops.clone(),
+            fold: self.fold.clone(),
+            associativity: self.associativity,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a mixfix operator for a pratt parser, made up of any number of interleaved operator parsers and operands,
+/// with the given associativity, binding power, and [fold function](crate::pratt#fold-functions).
+///
+/// A mixfix operator begins with an operand, followed by each of the operator parsers in turn, with an operand after
+/// each of them. For example, the operator parsers `[`, `..`, and `] =` parse `xs[a..b] = ys`. As with
+/// [`ternary`], the first and last operands bind according to the associativity, and the operands between the operator
+/// parsers may be any expression at all.
+///
+/// All of the operator parsers must have the same type. When they do not, [`Parser::boxed`] or [`Parser::to`] can be
+/// used to give them one. There must be at least one operator parser.
+///
+/// The fold function (the last argument) tells the parser how to combine the operators and operands into a new
+/// expression. It is given every operand (one more than the number of operator parsers), followed by the output of each
+/// operator parser, and must have the following signature:
+///
+/// ```ignore
+/// impl Fn(Vec<Atom>, Vec<Op>, &mut MapExtra<'src, '_, I, E>) -> O
+/// ```
+///
+/// # Panics
+///
+/// Panics if no operator parsers are given.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Var(char),
+///     // `xs[a..b] = ys`: splice `ys` into the range `a..b` of `xs`
+///     Splice(Vec<Expr>),
+/// }
+///
+/// let expr = any::<_, extra::Err<Simple<char>>>()
+///     .filter(char::is_ascii_lowercase)
+///     .map(Expr::Var)
+///     .padded()
+///     .pratt(mixfix(
+///         right(1),
+///         [
+///             just('[').padded().boxed(),
+///             just("..").padded().to('.').boxed(),
+///             just(']').padded().then_ignore(just('=').padded()).boxed(),
+///         ],
+///         |operands, _, _| Expr::Splice(operands),
+///     ));
+///
+/// use Expr::*;
+/// assert_eq!(
+///     expr.parse("x[a..b] = y").into_result(),
+///     Ok(Splice(vec![Var('x'), Var('a'), Var('b'), Var('y')])),
+/// );
+/// // The operands between the operator parsers can be any expression
+/// assert!(expr.parse("x[a[b..c] = d..e] = y").into_result().is_ok());
+/// assert!(expr.parse("x[a..b]").has_errors());
+/// ```
+pub fn mixfix<'src, A, F, Atom, Op, I, E>(
+    associativity: Associativity,
+    ops: impl IntoIterator<Item = A>,
+    fold: F,
+) -> Mixfix<'src, A, F, Atom, Op, I, E>
+where
+    F: Fn(Vec<Atom>, Vec<Op>, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    let ops = ops.into_iter().collect::<Vec<_>>();
+    assert!(
+        !ops.is_empty(),
+        "mixfix operators need at least one operator parser"
+    );
+    Mixfix {
+        ops,
+        fold,
+        associativity,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, F, Op> Operator<'src, I, O, E> for Mixfix<'src, A, F, O, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    F: Fn(Vec<O>, Vec<Op>, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            infix: Some(self.associativity.left_power()),
+            ..OpInfo::NONE
+        }
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        if self.associativity.left_power() < min_power {
+            return Err(lhs);
+        }
+        let mut operands = M::bind(|| Vec::with_capacity(self.ops.len()));
+        let mut ops = M::bind(|| Vec::with_capacity(self.ops.len()));
+        for (i, op_parser) in self.ops.iter().enumerate() {
+            let Ok(op) = op_parser.go::<M>(inp) else {
+                inp.rewind(pre_op.clone());
+                return Err(lhs);
+            };
+            M::combine_mut(&mut ops, op, |ops, op| ops.push(op));
+            // Only the last operand is bound by the associativity, the others are delimited by operator parsers
+            let power = if i + 1 == self.ops.len() {
+                self.associativity.right_power()
+            } else {
+                0
+            };
+            let Ok(operand) = f(inp, power) else {
+                inp.rewind(pre_op.clone());
+                return Err(lhs);
+            };
+            M::combine_mut(&mut operands, operand, |operands, operand| {
+                operands.push(operand)
+            });
+        }
+        Ok(M::combine(
+            M::combine(lhs, operands, |lhs, mut operands| {
+                operands.insert(0, lhs);
+                operands
+            }),
+            ops,
+            |operands, ops| (self.fold)(operands, ops, &mut MapExtra::new(pre_expr, inp)),
+        ))
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`chained`].
+pub struct Chained<'src, A, F, Atom, Op, I, E> {
+    op_parser: A,
+    fold: F,
+    associativity: Associativity,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, Op, I, E)>,
+}
+
+impl<A: Copy, F: Copy, Atom, Op, I, E> Copy for Chained<'_, A, F, Atom, Op, I, E> {}
+impl<A: Clone, F: Clone, Atom, Op, I, E> Clone for Chained<'_, A, F, Atom, Op, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            op_parser: self.op_parser.clone(),
+            fold: self.fold.clone(),
+            associativity: self.associativity,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a chained infix operator for a pratt parser, such as Python's comparisons, with the given associativity,
+/// binding power, and [fold function](crate::pratt#fold-functions).
+///
+/// In Python, `a < b <= c` means `a < b and b <= c` rather than `(a < b) <= c`. A chained operator parses every
+/// operand and operator in such a chain before calling the fold function once, so that an n-ary node can be built
+/// instead of a nested binary tree. A chain is made up of the operators parsed by `op_parser`, so operators that may
+/// be chained together (like `<` and `<=`) should be parsed by the same chained operator. An operator without any
+/// others following it is a chain of length one.
+///
+/// The operands of the chain bind according to the binding power, and the associativity only affects how chains are
+/// combined with the operators around them.
+///
+/// The fold function (the last argument) is given every operand of the chain (one more than the number of operators),
+/// followed by the output of each operator, and must have the following signature:
+///
+/// ```ignore
+/// impl Fn(Vec<Atom>, Vec<Op>, &mut MapExtra<'src, '_, I, E>) -> O
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Num(i64),
+///     Add(Box<Expr>, Box<Expr>),
+///     Compare(Vec<Expr>, Vec<&'static str>),
+/// }
+///
+/// let op = |s| just(s).padded();
+///
+/// let expr = text::int::<_, extra::Err<Simple<char>>>(10)
+///     .from_str()
+///     .unwrapped()
+///     .map(Expr::Num)
+///     .padded()
+///     .pratt((
+///         infix(left(2), op("+"), |l, _, r, _| Expr::Add(Box::new(l), Box::new(r))),
+///         chained(left(1), choice((op("<="), op("<"))), |operands, ops, _| {
+///             Expr::Compare(operands, ops)
+///         }),
+///     ));
+///
+/// use Expr::*;
+/// assert_eq!(
+///     expr.parse("1 < 2 + 3 <= 4").into_result(),
+///     Ok(Compare(
+///         vec![Num(1), Add(Box::new(Num(2)), Box::new(Num(3))), Num(4)],
+///         vec!["<", "<="],
+///     )),
+/// );
+/// assert_eq!(
+///     expr.parse("1 < 2").into_result(),
+///     Ok(Compare(vec![Num(1), Num(2)], vec!["<"])),
+/// );
+/// ```
+pub const fn chained<'src, A, F, Atom, Op, I, E>(
+    associativity: Associativity,
+    op_parser: A,
+    fold: F,
+) -> Chained<'src, A, F, Atom, Op, I, E>
+where
+    F: Fn(Vec<Atom>, Vec<Op>, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    Chained {
+        op_parser,
+        fold,
+        associativity,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, F, Op> Operator<'src, I, O, E> for Chained<'src, A, F, O, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    F: Fn(Vec<O>, Vec<Op>, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            infix: Some(self.associativity.left_power()),
+            ..OpInfo::NONE
+        }
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        if self.associativity.left_power() < min_power {
+            return Err(lhs);
+        }
+        // Operands bind more tightly than the chain itself, even for right associative chains, so that the operators of
+        // the chain are never parsed as part of an operand (which would nest chains within each other)
+        let operand_power = self.associativity.left_power() + 1;
+        let (op, rhs) = match self.op_parser.go::<M>(inp) {
+            Ok(op) => match f(inp, operand_power) {
+                Ok(rhs) => (op, rhs),
+                Err(()) => {
+                    inp.rewind(pre_op.clone());
+                    return Err(lhs);
+                }
+            },
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                return Err(lhs);
+            }
+        };
+        let mut operands = M::combine(lhs, rhs, |lhs, rhs| vec![lhs, rhs]);
+        let mut ops = M::map(op, |op| vec![op]);
+        // Continue the chain for as long as there are more operators
+        loop {
+            let before = inp.save();
+            let Ok(op) = self.op_parser.go::<M>(inp) else {
+                inp.rewind(before);
+                break;
+            };
+            let Ok(operand) = f(inp, operand_power) else {
+                inp.rewind(before);
+                break;
+            };
+            M::combine_mut(&mut ops, op, |ops, op| ops.push(op));
+            M::combine_mut(&mut operands, operand, |operands, operand| {
+                operands.push(operand)
+            });
+        }
+        Ok(M::combine(operands, ops, |operands, ops| {
+            (self.fold)(operands, ops, &mut MapExtra::new(pre_expr, inp))
+        }))
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`call`].
+pub struct Call<'src, A, S, C, F, Atom, OA, OS, OC, I, E> {
+    open: A,
+    separator: S,
+    close: C,
+    fold: F,
+    binding_power: u16,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, OA, OS, OC, I, E)>,
+}
+
+impl<A: Copy, S: Copy, C: Copy, F: Copy, Atom, OA, OS, OC, I, E> Copy
+    for Call<'_, A, S, C, F, Atom, OA, OS, OC, I, E>
+{
+}
+impl<A: Clone, S: Clone, C: Clone, F: Clone, Atom, OA, OS, OC, I, E> Clone
+    for Call<'_, A, S, C, F, Atom, OA, OS, OC, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            open: self.open.clone(),
+            separator: self.separator.clone(),
+            close: self.close.clone(),
+            fold: self.fold.clone(),
+            binding_power: self.binding_power,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a postfix operator that carries a list of arguments, such as a function call `f(a, b)` or an index
+/// `m[i, j]`, with the given binding power and [fold function](crate::pratt#fold-functions).
+///
+/// The operator begins with `open`, followed by any number of arguments separated by `separator`, and ends with `close`.
+/// Each argument is parsed by the pratt parser itself, so it may be any expression at all, without needing to define
+/// the operator parser in terms of a [`recursive`](crate::recursive::recursive) expression parser. Like other postfix
+/// operators, the operator binds to its operand according to its binding power.
+///
+/// The fold function (the last argument) tells the parser how to combine the operand and arguments into a new
+/// expression. It must have the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, Vec<Atom>, &mut MapExtra<'src, '_, I, E>) -> O
+/// ```
+///
+/// Call operators cannot be used with [`Pratt::iterative`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Var(char),
+///     Neg(Box<Expr>),
+///     Add(Box<Expr>, Box<Expr>),
+///     Call(Box<Expr>, Vec<Expr>),
+///     Index(Box<Expr>, Vec<Expr>),
+/// }
+///
+/// let op = |c| just(c).padded();
+///
+/// let expr = any::<_, extra::Err<Simple<char>>>()
+///     .filter(char::is_ascii_lowercase)
+///     .map(Expr::Var)
+///     .padded()
+///     .pratt((
+///         call(3, op('('), op(','), op(')'), |f, args, _| Expr::Call(Box::new(f), args)),
+///         call(3, op('['), op(','), op(']'), |x, args, _| Expr::Index(Box::new(x), args)),
+///         prefix(2, op('-'), |_, x, _| Expr::Neg(Box::new(x))),
+///         infix(left(1), op('+'), |l, _, r, _| Expr::Add(Box::new(l), Box::new(r))),
+///     ));
+///
+/// use Expr::*;
+/// assert_eq!(
+///     expr.parse("-f(a + b, m[i, j])()").into_result(),
+///     Ok(Neg(Box::new(Call(
+///         Box::new(Call(
+///             Box::new(Var('f')),
+///             vec![
+///                 Add(Box::new(Var('a')), Box::new(Var('b'))),
+///                 Index(Box::new(Var('m')), vec![Var('i'), Var('j')]),
+///             ],
+///         )),
+///         vec![],
+///     )))),
+/// );
+/// assert!(expr.parse("f(a,)").has_errors());
+/// assert!(expr.parse("f(a").has_errors());
+/// ```
+pub const fn call<'src, A, S, C, F, Atom, OA, OS, OC, I, E>(
+    binding_power: u16,
+    open: A,
+    separator: S,
+    close: C,
+    fold: F,
+) -> Call<'src, A, S, C, F, Atom, OA, OS, OC, I, E>
+where
+    F: Fn(Atom, Vec<Atom>, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    Call {
+        open,
+        separator,
+        close,
+        fold,
+        binding_power,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, S, C, F, OA, OS, OC> Operator<'src, I, O, E>
+    for Call<'src, A, S, C, F, O, OA, OS, OC, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, OA, E>,
+    S: Parser<'src, I, OS, E>,
+    C: Parser<'src, I, OC, E>,
+    F: Fn(O, Vec<O>, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    // Calls are postfix operators, but are parsed as infix operators since they need to parse expressions
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            infix: Some(Associativity::Left(self.binding_power).right_power()),
+            ..OpInfo::NONE
+        }
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        if Associativity::Left(self.binding_power).right_power() < min_power
+            || self.open.go::<Check>(inp).is_err()
+        {
+            inp.rewind(pre_op.clone());
+            return Err(lhs);
+        }
+        let mut args = M::bind(Vec::new);
+        // Check for an empty argument list first, so that operand recovery does not insert an argument
+        let before_close = inp.save();
+        if self.close.go::<Check>(inp).is_err() {
+            inp.rewind(before_close);
+            loop {
+                let Ok(arg) = f(inp, 0) else {
+                    inp.rewind(pre_op.clone());
+                    return Err(lhs);
+                };
+                M::combine_mut(&mut args, arg, |args, arg| args.push(arg));
+                let before_sep = inp.save();
+                if self.separator.go::<Check>(inp).is_err() {
+                    inp.rewind(before_sep);
+                    break;
+                }
+            }
+            if self.close.go::<Check>(inp).is_err() {
+                inp.rewind(pre_op.clone());
+                return Err(lhs);
+            }
+        }
+        Ok(M::combine(lhs, args, |lhs, args| {
+            (self.fold)(lhs, args, &mut MapExtra::new(pre_expr, inp))
+        }))
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`infix_rhs`].
+pub struct InfixRhs<'src, A, B, F, Atom, Op, R, I, E> {
+    op_parser: A,
+    rhs_parser: B,
+    fold: F,
+    associativity: Associativity,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<&'src (Atom, Op, R, I, E)>,
+}
+
+impl<A: Copy, B: Copy, F: Copy, Atom, Op, R, I, E> Copy
+    for InfixRhs<'_, A, B, F, Atom, Op, R, I, E>
+{
+}
+impl<A: Clone, B: Clone, F: Clone, Atom, Op, R, I, E> Clone
+    for InfixRhs<'_, A, B, F, Atom, Op, R, I, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            op_parser: self.op_parser.clone(),
+            rhs_parser: self.rhs_parser.clone(),
+            fold: self.fold.clone(),
+            associativity: self.associativity,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Specify a binary infix operator whose right operand is parsed by `rhs_parser` rather than being an expression, with
+/// the given associativity and [fold function](crate::pratt#fold-functions).
+///
+/// Operators like type ascription (`x : T`) and casts (`x as T`) look like infix operators, but their right operand is
+/// something else entirely, such as a type.
+///
+/// Since the right operand is not an expression, the operator always applies to the expression to its left, and may be
+/// chained (`x as T as U` is `(x as T) as U`). The associativity decides how the operator interacts with infix
+/// operators of the same binding power: with [`left`], `a + b as T` means `(a + b) as T`, while with [`right`] it
+/// means `a + (b as T)`. With [`non_assoc`], the operator cannot be chained.
+///
+/// The fold function (the last argument) tells the parser how to combine the operator and operands into a new
+/// expression. It must have the following signature:
+///
+/// ```ignore
+/// impl Fn(Atom, Op, R, &mut MapExtra<'src, '_, I, E>) -> O
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr<'src> {
+///     Num(i64),
+///     Add(Box<Self>, Box<Self>),
+///     Cast(Box<Self>, &'src str),
+/// }
+///
+/// let ty = text::ascii::ident().padded();
+///
+/// let expr = text::int::<_, extra::Err<Simple<char>>>(10)
+///     .from_str()
+///     .unwrapped()
+///     .map(Expr::Num)
+///     .padded()
+///     .pratt((
+///         infix(left(1), just('+'), |l, _, r, _| Expr::Add(Box::new(l), Box::new(r))),
+///         infix_rhs(left(2), text::keyword("as"), ty, |x, _, ty, _| Expr::Cast(Box::new(x), ty)),
+///     ));
+///
+/// use Expr::*;
+/// assert_eq!(
+///     expr.parse("1 + 2 as u8 as i32").into_result(),
+///     Ok(Add(
+///         Box::new(Num(1)),
+///         Box::new(Cast(Box::new(Cast(Box::new(Num(2)), "u8")), "i32")),
+///     )),
+/// );
+/// ```
+pub const fn infix_rhs<'src, A, B, F, Atom, Op, R, I, E>(
+    associativity: Associativity,
+    op_parser: A,
+    rhs_parser: B,
+    fold: F,
+) -> InfixRhs<'src, A, B, F, Atom, Op, R, I, E>
+where
+    F: Fn(Atom, Op, R, &mut MapExtra<'src, '_, I, E>) -> Atom,
+{
+    InfixRhs {
+        op_parser,
+        rhs_parser,
+        fold,
+        associativity,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, B, F, Op, R> Operator<'src, I, O, E>
+    for InfixRhs<'src, A, B, F, O, Op, R, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    B: Parser<'src, I, R, E>,
+    F: Fn(O, Op, R, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    // The right operand is not an expression, so these operators are parsed as postfix operators
+    #[inline(always)]
+    fn info(&self) -> OpInfo {
+        OpInfo {
+            postfix: Some(self.associativity.left_power()),
+            ..OpInfo::NONE
+        }
+    }
+
+    #[inline]
+    fn do_parse_postfix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        if self.associativity.left_power() < min_power {
+            return Err(lhs);
+        }
+        let Ok(op) = self.op_parser.go::<M>(inp) else {
+            inp.rewind(pre_op.clone());
+            return Err(lhs);
+        };
+        let Ok(rhs) = self.rhs_parser.go::<M>(inp) else {
+            inp.rewind(pre_op.clone());
+            return Err(lhs);
+        };
+        check_non_assoc(self.associativity, &self.op_parser, inp);
+        Ok(M::combine(
+            M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
+            op,
+            |(lhs, rhs), op| (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp)),
+        ))
+    }
+
+    op_check_and_emit!();
+}
+
+/// The output of part of an expression, as seen by a [`CustomOperator`].
+///
+/// Chumsky sometimes only checks whether an expression is valid without producing its output, so an operand does not
+/// necessarily hold a value. Operands can still be combined with [`Operand::map`] and [`Operand::zip`], which are
+/// only evaluated when the output is needed.
+pub struct Operand<T>(Option<T>);
+
+impl<T> Operand<T> {
+    /// Transform the value of the operand, if it has one.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Operand<U> {
+        Operand(self.0.map(f))
+    }
+
+    /// Combine the operand with another, producing an operand of both values.
+    pub fn zip<U>(self, other: Operand<U>) -> Operand<(T, U)> {
+        Operand(self.0.zip(other.0))
+    }
+}
+
+#[inline(always)]
+fn to_operand<M: Mode, T>(out: M::Output<T>) -> Operand<T> {
+    Operand(M::get_or(M::map(out, Some), || None))
+}
+
+#[inline(always)]
+fn from_operand<M: Mode, T>(operand: Operand<T>) -> M::Output<T> {
+    M::bind(|| {
+        operand
+            .0
+            .expect("custom operator produced an operand without a value")
+    })
+}
+
+/// The state available to a [`CustomOperator`] while it is parsing.
+pub struct OperatorContext<'src, 'parse, 'a, I: Input<'src>, O, E: ParserExtra<'src, I>> {
+    inp: &'a mut InputRef<'src, 'parse, I, E>,
+    pre_expr: &'a input::Cursor<'src, 'parse, I>,
+    min_power: u32,
+    #[allow(clippy::type_complexity)]
+    expr: &'a dyn Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> Option<Operand<O>>,
+}
+
+impl<'src, I: Input<'src>, O, E: ParserExtra<'src, I>> OperatorContext<'src, '_, '_, I, O, E> {
+    /// Whether an operator with the given associativity may bind to the operand on its left at this point in the
+    /// expression.
+    ///
+    /// Operators that follow an operand should return it without parsing anything if this is `false`, just as
+    /// [`infix`] and [`postfix`] operators do.
+    pub fn binds(&self, associativity: Associativity) -> bool {
+        associativity.left_power() >= self.min_power
+    }
+
+    /// Parse the given parser, such as the tokens of the operator, returning `None` if it fails.
+    pub fn parse<T, P: Parser<'src, I, T, E>>(&mut self, parser: P) -> Option<T> {
+        parser.go::<Emit>(self.inp).ok()
+    }
+
+    /// Parse the operand on the right of an operator with the given associativity, returning `None` if there isn't
+    /// one.
+    ///
+    /// A prefix operator with binding power `n` (see [`prefix`]) should parse its operand with [`right(n)`](right).
+    pub fn expr(&mut self, associativity: Associativity) -> Option<Operand<O>> {
+        (self.expr)(self.inp, associativity.right_power())
+    }
+
+    /// Parse a complete expression, regardless of the operators around it, returning `None` if there isn't one.
+    ///
+    /// This is useful for parts of an operator that are delimited by its own tokens, like the middle operand of a
+    /// ternary operator or the arguments of a function call.
+    pub fn nested_expr(&mut self) -> Option<Operand<O>> {
+        (self.expr)(self.inp, 0)
+    }
+
+    /// Get the span of the expression that the operator is part of, from its start to the current position.
+    pub fn span(&mut self) -> I::Span {
+        self.inp.span_since(self.pre_expr)
+    }
+}
+
+/// A kind of operator that can be used in a pratt parser by calling [`operator`], for operators that do not fit
+/// [`infix`], [`prefix`], [`postfix`], [`ternary`], or [`mixfix`] (such as chained comparisons).
+///
+/// Custom operators are given an [`OperatorContext`] with which to parse their tokens and operands, and combine
+/// operands into a new [`Operand`]. When an operator does not match (returning `None` or `Err`), the input is rewound
+/// to where the operator started, so implementations do not need to do this themselves.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// // A right-associative ternary operator, `cond ? a : b`
+/// struct Ternary;
+///
+/// impl<'src> CustomOperator<'src, &'src str, i64, extra::Default> for Ternary {
+///     fn parse_infix(
+///         &self,
+///         cond: Operand<i64>,
+///         ctx: &mut OperatorContext<'src, '_, '_, &'src str, i64, extra::Default>,
+///     ) -> Result<Operand<i64>, Operand<i64>> {
+///         if !ctx.binds(right(1)) || ctx.parse(just('?').padded()).is_none() {
+///             return Err(cond);
+///         }
+///         let Some(a) = ctx.nested_expr() else { return Err(cond) };
+///         if ctx.parse(just(':').padded()).is_none() {
+///             return Err(cond);
+///         }
+///         let Some(b) = ctx.expr(right(1)) else { return Err(cond) };
+///         Ok(cond.zip(a).zip(b).map(|((cond, a), b)| if cond != 0 { a } else { b }))
+///     }
+/// }
+///
+/// let expr = text::int(10)
+///     .from_str()
+///     .unwrapped()
+///     .pratt((
+///         infix(left(2), just('+').padded(), |a, _, b, _| a + b),
+///         operator(Ternary),
+///     ));
+///
+/// assert_eq!(expr.parse("1 ? 2 : 3").into_result(), Ok(2));
+/// assert_eq!(expr.parse("0 ? 2 : 0 ? 3 : 4 + 5").into_result(), Ok(9));
+/// assert_eq!(expr.parse("1 + 1 ? 1 ? 2 : 3 : 4").into_result(), Ok(2));
+/// assert!(expr.parse("1 ? 2").has_errors());
+/// ```
+pub trait CustomOperator<'src, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    /// Parse the operator where it appears before an operand, returning the resulting operand, or `None` if it does
+    /// not match.
+    fn parse_prefix(&self, ctx: &mut OperatorContext<'src, '_, '_, I, O, E>) -> Option<Operand<O>> {
+        let _ = ctx;
+        None
+    }
+
+    /// Parse the operator where it follows the operand `lhs`, returning the resulting operand, or `lhs` as an error if
+    /// it does not match.
+    fn parse_infix(
+        &self,
+        lhs: Operand<O>,
+        ctx: &mut OperatorContext<'src, '_, '_, I, O, E>,
+    ) -> Result<Operand<O>, Operand<O>> {
+        let _ = ctx;
+        Err(lhs)
+    }
+}
+
+/// See [`operator`].
+#[derive(Copy, Clone)]
+pub struct Custom<Op>(Op);
+
+/// Use a [`CustomOperator`] as an operator of a pratt parser.
+///
+/// Custom operators cannot be used with [`Pratt::iterative`].
+pub const fn operator<Op>(op: Op) -> Custom<Op> {
+    Custom(op)
+}
+
+impl<'src, I, O, E, Op> Operator<'src, I, O, E> for Custom<Op>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Op: CustomOperator<'src, I, O, E>,
+{
+    #[inline]
+    fn do_parse_prefix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        let mut ctx = OperatorContext {
+            inp,
+            pre_expr: pre_expr.cursor(),
+            min_power: 0,
+            expr: &|inp, power| f(inp, power).ok().map(to_operand::<M, O>),
+        };
+        match self.0.parse_prefix(&mut ctx) {
+            Some(out) => Ok(from_operand::<M, O>(out)),
+            None => {
+                inp.rewind(pre_expr.clone());
+                Err(())
+            }
+        }
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        let mut ctx = OperatorContext {
+            inp,
+            pre_expr,
+            min_power,
+            expr: &|inp, power| f(inp, power).ok().map(to_operand::<M, O>),
+        };
+        match self.0.parse_infix(to_operand::<M, O>(lhs), &mut ctx) {
+            Ok(out) => Ok(from_operand::<M, O>(out)),
+            Err(lhs) => {
+                inp.rewind(pre_op.clone());
+                Err(from_operand::<M, O>(lhs))
+            }
+        }
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`Operator::labelled`].
+#[derive(Copy, Clone)]
+pub struct Labelled<Op, L> {
+    op: Op,
+    label: L,
+}
+
+impl<Op, L> Labelled<Op, L> {
+    /// Run `f`, replacing the expected patterns of any error it produces at `before` with this operator's label.
+    #[inline(always)]
+    fn relabel<'src, 'parse, I, E, R>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        before: &input::Cursor<'src, 'parse, I>,
+        f: impl FnOnce(&mut InputRef<'src, 'parse, I, E>) -> R,
+    ) -> R
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        E::Error: LabelError<'src, I, L>,
+        L: Clone,
+    {
+        let old_alt = inp.errors.alt.take();
+        let res = f(inp);
+        let new_alt = inp.errors.alt.take();
+        inp.errors.alt = old_alt;
+
+        if let Some(mut new_alt) = new_alt {
+            if I::cursor_location(&new_alt.pos) == I::cursor_location(&before.inner) {
+                new_alt.err.label_with(self.label.clone());
+            }
+            inp.add_alt_err(&new_alt.pos, new_alt.err);
+        }
+
+        res
+    }
+}
+
+impl<'src, I, O, E, Op, L> Operator<'src, I, O, E> for Labelled<Op, L>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, L>,
+    Op: Operator<'src, I, O, E>,
+    L: Clone,
+{
+    #[inline]
+    fn info(&self) -> OpInfo {
+        self.op.info()
+    }
+
+    #[inline]
+    fn do_parse_prefix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        self.relabel(inp, pre_expr.cursor(), |inp| {
+            self.op.do_parse_prefix::<M>(inp, pre_expr, f)
+        })
+    }
+
+    #[inline]
+    fn do_parse_postfix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        self.relabel(inp, pre_op.cursor(), |inp| {
+            self.op
+                .do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power)
+        })
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        self.relabel(inp, pre_op.cursor(), |inp| {
+            self.op
+                .do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f)
+        })
+    }
+
+    op_check_and_emit!();
+}
+
+/// See [`Parser::pratt`].
+#[derive(Copy, Clone)]
+pub struct Pratt<Atom, Ops, Rec = ()> {
+    pub(crate) atom: Atom,
+    pub(crate) ops: Ops,
+    pub(crate) recovery: Rec,
+}
+
+impl<Atom, Ops> Pratt<Atom, Ops> {
+    /// Recover from operands that are missing after an operator (as in `1 + * 2`) by inserting an expression
+    /// generated by the given function, then continuing to parse the rest of the expression.
+    ///
+    /// The function is given the (zero-width) span at which the operand was expected. The error that would otherwise
+    /// have caused the expression to end early is emitted as a secondary error.
+    ///
+    /// This is particularly useful for IDE-like applications, since expressions are where users make most of their
+    /// transient errors while typing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, pratt::*};
+    /// #[derive(Debug, PartialEq)]
+    /// enum Expr {
+    ///     Num(i64),
+    ///     Error,
+    ///     Add(Box<Expr>, Box<Expr>),
+    ///     Mul(Box<Expr>, Box<Expr>),
+    /// }
+    ///
+    /// let atom = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .from_str()
+    ///     .unwrapped()
+    ///     .map(Expr::Num)
+    ///     .padded();
+    /// let op = |c| just(c).padded();
+    ///
+    /// let expr = atom
+    ///     .pratt((
+    ///         infix(left(2), op('*'), |l, _, r, _| Expr::Mul(Box::new(l), Box::new(r))),
+    ///         infix(left(1), op('+'), |l, _, r, _| Expr::Add(Box::new(l), Box::new(r))),
+    ///     ))
+    ///     .recover_missing_operand(|_| Expr::Error);
+    ///
+    /// let (out, errs) = expr.parse("1 + * 2").into_output_errors();
+    /// assert_eq!(
+    ///     out,
+    ///     Some(Expr::Add(
+    ///         Box::new(Expr::Num(1)),
+    ///         Box::new(Expr::Mul(Box::new(Expr::Error), Box::new(Expr::Num(2)))),
+    ///     )),
+    /// );
+    /// assert_eq!(errs.len(), 1);
+    /// // The error points at the unexpected `*`
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(4..5));
+    /// ```
+    pub fn recover_missing_operand<F>(self, fallback: F) -> Pratt<Atom, Ops, MissingOperand<F>> {
+        Pratt {
+            atom: self.atom,
+            ops: self.ops,
+            recovery: MissingOperand(fallback),
+        }
+    }
+}
+
+impl<Atom, Ops, Rec> Pratt<Atom, Ops, Rec> {
+    /// Parse expressions using an explicit, heap-allocated stack of pending operators instead of recursion.
+    ///
+    /// The default pratt parser recurses once for every operand that follows an operator, so very long operator
+    /// chains (like `a + b + c + ...` with right associativity, or `- - - ... x`) use stack space in proportion to
+    /// their length. The iterative parser produces exactly the same output and errors, but its own stack usage is
+    /// constant: only the atom and operator parsers themselves may recurse.
+    ///
+    /// Operators must implement [`IterativeOperator`], which excludes [`Boxed`] operators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, pratt::*};
+    /// let atom = text::int::<_, extra::Err<Simple<char>>>(10).from_str::<i64>().unwrapped();
+    ///
+    /// let expr = atom
+    ///     .pratt((
+    ///         prefix(2, just('-'), |_, x: i64, _| -x),
+    ///         infix(right(1), just('+'), |l, _, r, _| l + r),
+    ///     ))
+    ///     .iterative();
+    ///
+    /// let src = "1+".repeat(100_000) + &"-".repeat(100_000) + "1";
+    /// assert_eq!(expr.parse(src.as_str()).into_result(), Ok(100_001));
+    /// ```
+    pub fn iterative(self) -> IterativePratt<Atom, Ops, Rec> {
+        IterativePratt { pratt: self }
+    }
+}
+
+impl<Atom, Op, Rec> Pratt<Atom, Vec<Op>, Rec> {
+    /// Add an operator to the end of this parser's operator table.
+    ///
+    /// Operator tables that are [`Vec`]s can be built at runtime (for example, from user-configurable operators loaded
+    /// from a file). They usually contain [`Boxed`] operators, which every kind of operator can be converted into with
+    /// [`Into`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, pratt::{self, *}};
+    /// // Operators and their binding powers, as they might be loaded from a configuration file
+    /// let config = [('+', 1), ('*', 2), ('^', 3)];
+    ///
+    /// let atom = text::int::<_, extra::Default>(10).from_str::<i64>().unwrapped().padded();
+    ///
+    /// let ops = config
+    ///     .iter()
+    ///     .map(|&(c, power)| {
+    ///         infix(left(power), just(c), move |l: i64, _, r, _| match c {
+    ///             '+' => l + r,
+    ///             '*' => l * r,
+    ///             _ => l.pow(r as u32),
+    ///         })
+    ///         .into()
+    ///     })
+    ///     .collect::<Vec<pratt::Boxed<_, _, _>>>();
+    ///
+    /// let expr = atom
+    ///     .pratt(ops)
+    ///     .with_op(prefix(4, just('-'), |_, x: i64, _| -x))
+    ///     .with_ops([postfix(5, just('!').padded(), |x: i64, _, _| (1..=x).product())]);
+    ///
+    /// assert_eq!(expr.parse("1 + 2 * 3 ^ 2").into_result(), Ok(19));
+    /// assert_eq!(expr.parse("-3! + 1").into_result(), Ok(-5));
+    /// ```
+    pub fn with_op(mut self, op: impl Into<Op>) -> Self {
+        self.ops.push(op.into());
+        self
+    }
+
+    /// Add several operators to the end of this parser's operator table. See [`Pratt::with_op`].
+    pub fn with_ops<T: Into<Op>>(mut self, ops: impl IntoIterator<Item = T>) -> Self {
+        self.ops.extend(ops.into_iter().map(Into::into));
+        self
+    }
+}
+
+/// A builder for pratt parsers with many operators. See [`Parser::pratt_builder`].
+///
+/// Each operator added to the builder is [boxed](Operator::boxed), so the type of the resulting parser does not grow
+/// with the number of operators.
+pub struct PrattBuilder<'src, 'a, Atom, I, O, E = extra::Default> {
+    atom: Atom,
+    ops: Vec<Boxed<'src, 'a, I, O, E>>,
+}
+
+impl<Atom: Clone, I, O, E> Clone for PrattBuilder<'_, '_, Atom, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            atom: self.atom.clone(),
+            ops: self.ops.clone(),
+        }
+    }
+}
+
+impl<'src, 'a, Atom, I, O, E> PrattBuilder<'src, 'a, Atom, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    O: 'src,
+{
+    pub(crate) fn new(atom: Atom) -> Self {
+        Self {
+            atom,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Add an [`infix`] operator.
+    pub fn infix<A, F, Op: 'src>(self, associativity: Associativity, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'src, I, Op, E>,
+        F: Fn(O, Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
+        Infix<'src, A, F, O, Op, I, E>: 'a,
+    {
+        self.op(infix(associativity, op_parser, fold))
+    }
+
+    /// Add a [`prefix`] operator.
+    pub fn prefix<A, F, Op: 'src>(self, binding_power: u16, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'src, I, Op, E>,
+        F: Fn(Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
+        Prefix<'src, A, F, O, Op, I, E>: 'a,
+    {
+        self.op(prefix(binding_power, op_parser, fold))
+    }
+
+    /// Add a [`postfix`] operator.
+    pub fn postfix<A, F, Op: 'src>(self, binding_power: u16, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'src, I, Op, E>,
+        F: Fn(O, Op, &mut MapExtra<'src, '_, I, E>) -> O,
+        Postfix<'src, A, F, O, Op, I, E>: 'a,
+    {
+        self.op(postfix(binding_power, op_parser, fold))
+    }
+
+    /// Add any other kind of operator, such as a [`ternary`], [`call`], or [`Labelled`] operator.
+    pub fn op(mut self, op: impl Operator<'src, I, O, E> + 'a) -> Self {
+        self.ops.push(op.boxed());
+        self
+    }
+
+    /// Finish building the pratt parser.
+    ///
+    /// The resulting parser's operator table is a [`Vec`] of [`Boxed`] operators, so more operators can still be added
+    /// with [`Pratt::with_op`].
+    pub fn build(self) -> Pratt<Atom, Vec<Boxed<'src, 'a, I, O, E>>> {
+        Pratt {
+            atom: self.atom,
+            ops: self.ops,
+            recovery: (),
+        }
+    }
+}
+
+/// See [`Pratt::recover_missing_operand`].
+#[derive(Copy, Clone)]
+pub struct MissingOperand<F>(F);
+
+/// Produces a fallback operand when an operand is missing after an operator.
+pub trait OperandRecovery<'src, I: Input<'src>, O>: Sealed {
+    #[doc(hidden)]
+    fn recover(&self, span: I::Span) -> Option<O>;
+}
+
+impl Sealed for () {}
+impl<'src, I: Input<'src>, O> OperandRecovery<'src, I, O> for () {
+    #[inline(always)]
+    fn recover(&self, _span: I::Span) -> Option<O> {
+        None
+    }
+}
+
+impl<F> Sealed for MissingOperand<F> {}
+impl<'src, I: Input<'src>, O, F: Fn(I::Span) -> O> OperandRecovery<'src, I, O>
+    for MissingOperand<F>
+{
+    #[inline(always)]
+    fn recover(&self, span: I::Span) -> Option<O> {
+        Some((self.0)(span))
+    }
+}
+
+macro_rules! impl_operator_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_operator_for_tuple!($($X)*);
+        impl_operator_for_tuple!(~ $head $($X)*);
+    };
+    (~ $($X:ident)+) => {
+        #[allow(unused_variables, non_snake_case)]
+        impl<'src, I, O, E, $($X),*> Operator<'src, I, O, E> for ($($X,)*)
+            where
+                I: Input<'src>,
+                E: ParserExtra<'src, I>,
+                $($X: Operator<'src, I, O, E>),*
+        {
+            #[inline]
+            fn info(&self) -> OpInfo {
+                let ($($X,)*) = self;
+                OpInfo::NONE $(.merge($X.info()))*
+            }
+
+            #[inline]
+            fn do_parse_prefix<'parse, M: Mode>(
+                &self,
+                inp: &mut InputRef<'src, 'parse, I, E>,
+                pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+            ) -> PResult<M, O>
+            where
+                Self: Sized,
+            {
+                let ($($X,)*) = self;
+                $(
+                    match $X.do_parse_prefix::<M>(inp, pre_expr, f) {
+                        Ok(out) => return Ok(out),
+                        Err(()) => {},
+                    }
+                )*
+                Err(())
+            }
+
+            #[inline]
+            fn do_parse_postfix<'parse, M: Mode>(
+                &self,
+                inp: &mut InputRef<'src, 'parse, I, E>,
+                pre_expr: &input::Cursor<'src, 'parse, I>,
+                pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+                mut lhs: M::Output<O>,
+                min_power: u32,
+            ) -> Result<M::Output<O>, M::Output<O>>
+            where
+                Self: Sized,
+            {
+                let ($($X,)*) = self;
+                $(
+                    match $X.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
+                        Ok(out) => return Ok(out),
+                        Err(out) => lhs = out,
+                    }
+                )*
+                Err(lhs)
+            }
+
+            #[inline]
+            fn do_parse_infix<'parse, M: Mode>(
+                &self,
+                inp: &mut InputRef<'src, 'parse, I, E>,
+                pre_expr: &input::Cursor<'src, 'parse, I>,
+                pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+                mut lhs: M::Output<O>,
+                min_power: u32,
+                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+            ) -> Result<M::Output<O>, M::Output<O>>
+            where
+                Self: Sized,
+            {
+                let ($($X,)*) = self;
+                $(
+                    match $X.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
+                        Ok(out) => return Ok(out),
+                        Err(out) => lhs = out,
+                    }
+                )*
+                Err(lhs)
+            }
+
+            op_check_and_emit!();
+        }
+    };
+}
+
+impl_operator_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+#[allow(unused_variables, non_snake_case)]
+impl<'src, I, O, E, Op> Operator<'src, I, O, E> for Vec<Op>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Op: Operator<'src, I, O, E>,
+{
+    #[inline]
+    fn info(&self) -> OpInfo {
+        self.iter()
+            .fold(OpInfo::NONE, |info, op| info.merge(op.info()))
+    }
+
+    #[inline]
+    fn do_parse_prefix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        // Operators that cannot apply are skipped without invoking them, which avoids dynamic dispatch for `Boxed`
+        // operators
+        for op in self.iter().filter(|op| op.info().prefix()) {
+            if let Ok(out) = op.do_parse_prefix::<M>(inp, pre_expr, f) {
+                return Ok(out);
+            }
+        }
+        Err(())
+    }
+
+    #[inline]
+    fn do_parse_postfix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        mut lhs: M::Output<O>,
+        min_power: u32,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        for op in self.iter().filter(|op| op.info().postfix(min_power)) {
+            match op.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
+                Ok(out) => return Ok(out),
+                Err(out) => lhs = out,
+            }
+        }
+        Err(lhs)
+    }
+
+    #[inline]
+    fn do_parse_infix<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Cursor<'src, 'parse, I>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        mut lhs: M::Output<O>,
+        min_power: u32,
+        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
+    ) -> Result<M::Output<O>, M::Output<O>>
+    where
+        Self: Sized,
+    {
+        for op in self.iter().filter(|op| op.info().infix(min_power)) {
+            match op.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
+                Ok(out) => return Ok(out),
+                Err(out) => lhs = out,
+            }
+        }
+        Err(lhs)
+    }
+
+    op_check_and_emit!();
+}
+
+#[allow(unused_variables, non_snake_case)]
+impl<'src, Atom, Ops, Rec> Pratt<Atom, Ops, Rec> {
+    // Parse the operand of an operator, which counts as a recursive call for the purposes of parse limits and stats
+    #[inline]
+    fn pratt_operand<M: Mode, I, O, E>(
+        &self,
+        inp: &mut InputRef<'src, '_, I, E>,
+        min_power: u32,
+    ) -> PResult<M, O>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        Atom: Parser<'src, I, O, E>,
+        Ops: Operator<'src, I, O, E>,
+        Rec: OperandRecovery<'src, I, O>,
+    {
+        inp.enter_recursion()?;
+        let res = recursive::recurse(|| self.pratt_go::<M, _, _, _>(inp, min_power, true));
+        inp.exit_recursion();
+        res
+    }
+
+    #[inline]
+    fn pratt_go<M: Mode, I, O, E>(
+        &self,
+        inp: &mut InputRef<'src, '_, I, E>,
+        min_power: u32,
+        after_op: bool,
+    ) -> PResult<M, O>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        Atom: Parser<'src, I, O, E>,
+        Ops: Operator<'src, I, O, E>,
+        Rec: OperandRecovery<'src, I, O>,
+    {
+        let pre_expr = inp.save();
+        // Prefix unary operators
+        let mut lhs = match self
+            .ops
+            .do_parse_prefix::<M>(inp, &pre_expr, &|inp, min_power| {
+                self.pratt_operand::<M, _, _, _>(inp, min_power)
+            }) {
+            Ok(out) => out,
+            Err(()) => match self.atom.go::<M>(inp) {
+                Ok(out) => out,
+                Err(()) if after_op => {
+                    // The operand following an operator is missing: try to recover by inserting a fallback
+                    inp.rewind(pre_expr.clone());
+                    let span = inp.span_since(pre_expr.cursor());
+                    let out = self.recovery.recover(span).ok_or(())?;
+                    let err = inp.take_alt().map(|alt| alt.err).unwrap_or_else(|| {
+                        E::Error::expected_found([], None, inp.span_since(pre_expr.cursor()))
+                    });
+                    inp.emit(None, err);
+                    M::bind(|| out)
+                }
+                Err(()) => return Err(()),
+            },
+        };
+
+        loop {
+            let pre_op = inp.save();
+
+            // Postfix unary operators
+            match self
+                .ops
+                .do_parse_postfix::<M>(inp, pre_expr.cursor(), &pre_op, lhs, min_power)
+            {
+                Ok(out) => {
+                    lhs = out;
+                    continue;
+                }
+                Err(out) => lhs = out,
+            }
+
+            // Infix binary operators
+            match self.ops.do_parse_infix::<M>(
+                inp,
+                pre_expr.cursor(),
+                &pre_op,
+                lhs,
+                min_power,
+                &|inp, min_power| self.pratt_operand::<M, _, _, _>(inp, min_power),
+            ) {
+                Ok(out) => {
+                    lhs = out;
+                    continue;
+                }
+                Err(out) => lhs = out,
+            }
+
+            inp.rewind(pre_op);
+            break;
+        }
+
+        Ok(lhs)
+    }
+}
+
+#[allow(unused_variables, non_snake_case)]
+impl<'src, I, O, E, Atom, Ops, Rec> Parser<'src, I, O, E> for Pratt<Atom, Ops, Rec>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Atom: Parser<'src, I, O, E>,
+    Ops: Operator<'src, I, O, E>,
+    Rec: OperandRecovery<'src, I, O>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        self.pratt_go::<M, _, _, _>(inp, 0, false)
+    }
+
+    go_extra!(O);
+}
+
+/// A pending operator produced by [`IterativeOperator`]: the operator has been parsed, but its right-hand operand
+/// has not.
+#[doc(hidden)]
+pub struct Deferred<'b, 'src, 'parse, I, O, E, M>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    M: Mode,
+{
+    index: usize,
+    power: u32,
+    lhs: Option<M::Output<O>>,
+    #[allow(clippy::type_complexity)]
+    fold: Box<
+        dyn FnOnce(
+                Option<M::Output<O>>,
+                M::Output<O>,
+                &input::Cursor<'src, 'parse, I>,
+                &mut InputRef<'src, 'parse, I, E>,
+            ) -> M::Output<O>
+            + 'b,
+    >,
+}
+
+/// A type implemented by operators that can be used with [`Pratt::iterative`].
+///
+/// This is implemented by [`Infix`], [`Prefix`], [`Postfix`], [`InfixRhs`], [`Labelled`] wrappers and tuples or
+/// [`Vec`]s of them, but not by [`Boxed`].
+pub trait IterativeOperator<'src, I, O, E>: Operator<'src, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    #[doc(hidden)]
+    #[inline(always)]
+    fn op_count(&self) -> usize {
+        1
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    fn do_parse_prefix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
+        _inp: &mut InputRef<'src, 'parse, I, E>,
+        _pre_expr: &input::Checkpoint<
+            'src,
+            'parse,
+            I,
+            <E::State as Inspector<'src, I>>::Checkpoint,
+        >,
+        _skip: usize,
+    ) -> Option<Deferred<'b, 'src, 'parse, I, O, E, M>> {
+        None
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    fn do_parse_infix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
+        _inp: &mut InputRef<'src, 'parse, I, E>,
+        _pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        _min_power: u32,
+        _skip: usize,
+    ) -> Result<Deferred<'b, 'src, 'parse, I, O, E, M>, M::Output<O>> {
+        Err(lhs)
+    }
+}
+
+impl<'src, I, O, E, A, F, Op> IterativeOperator<'src, I, O, E> for Infix<'src, A, F, O, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    F: Fn(O, Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline]
+    fn do_parse_infix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        skip: usize,
+    ) -> Result<Deferred<'b, 'src, 'parse, I, O, E, M>, M::Output<O>> {
+        if skip > 0 || self.associativity.left_power() < min_power {
+            return Err(lhs);
+        }
+        match self.op_parser.go::<M>(inp) {
+            Ok(op) => Ok(Deferred {
+                index: 0,
+                power: self.associativity.right_power(),
+                lhs: Some(lhs),
+                fold: Box::new(move |lhs, rhs, pre_expr, inp| {
+                    check_non_assoc(self.associativity, &self.op_parser, inp);
+                    M::combine(
+                        M::combine(lhs.unwrap(), rhs, |lhs, rhs| (lhs, rhs)),
+                        op,
+                        |(lhs, rhs), op| {
+                            (self.fold)(lhs, op, rhs, &mut MapExtra::new(pre_expr, inp))
+                        },
+                    )
+                }),
+            }),
+            Err(()) => {
+                inp.rewind(pre_op.clone());
+                Err(lhs)
+            }
+        }
+    }
+}
+
+impl<'src, I, O, E, A, F, Op> IterativeOperator<'src, I, O, E> for Prefix<'src, A, F, O, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    F: Fn(Op, O, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline]
+    fn do_parse_prefix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        skip: usize,
+    ) -> Option<Deferred<'b, 'src, 'parse, I, O, E, M>> {
+        if skip > 0 {
+            return None;
+        }
+        match self.op_parser.go::<M>(inp) {
+            Ok(op) => Some(Deferred {
+                index: 0,
+                power: Associativity::Left(self.binding_power).left_power(),
+                lhs: None,
+                fold: Box::new(move |_, rhs, pre_expr, inp| {
+                    M::combine(op, rhs, |op, rhs| {
+                        (self.fold)(op, rhs, &mut MapExtra::new(pre_expr, inp))
+                    })
+                }),
+            }),
+            Err(()) => {
+                inp.rewind(pre_expr.clone());
+                None
+            }
+        }
+    }
+}
+
+impl<'src, I, O, E, A, F, Op> IterativeOperator<'src, I, O, E> for Postfix<'src, A, F, O, Op, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    F: Fn(O, Op, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+}
+
+impl<'src, I, O, E, Op, L> IterativeOperator<'src, I, O, E> for Labelled<Op, L>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, L>,
+    Op: IterativeOperator<'src, I, O, E>,
+    L: Clone,
+{
+    #[inline]
+    fn op_count(&self) -> usize {
+        self.op.op_count()
+    }
+
+    #[inline]
+    fn do_parse_prefix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        skip: usize,
+    ) -> Option<Deferred<'b, 'src, 'parse, I, O, E, M>> {
+        self.relabel(inp, pre_expr.cursor(), |inp| {
+            self.op.do_parse_prefix_deferred::<M>(inp, pre_expr, skip)
+        })
+    }
+
+    #[inline]
+    fn do_parse_infix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        skip: usize,
+    ) -> Result<Deferred<'b, 'src, 'parse, I, O, E, M>, M::Output<O>> {
+        self.relabel(inp, pre_op.cursor(), |inp| {
+            self.op
+                .do_parse_infix_deferred::<M>(inp, pre_op, lhs, min_power, skip)
+        })
+    }
+}
+
+impl<'src, I, O, E, A, B, F, Op, R> IterativeOperator<'src, I, O, E>
+    for InfixRhs<'src, A, B, F, O, Op, R, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, Op, E>,
+    B: Parser<'src, I, R, E>,
+    F: Fn(O, Op, R, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+}
+
+macro_rules! impl_iterative_operator_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_iterative_operator_for_tuple!($($X)*);
+        impl_iterative_operator_for_tuple!(~ $head $($X)*);
+    };
+    (~ $($X:ident)+) => {
+        #[allow(unused_variables, unused_assignments, non_snake_case)]
+        impl<'src, I, O, E, $($X),*> IterativeOperator<'src, I, O, E> for ($($X,)*)
             where
                 I: Input<'src>,
                 E: ParserExtra<'src, I>,
-                $($X: Operator<'src, I, O, E>),*
+                $($X: IterativeOperator<'src, I, O, E>),*
         {
             #[inline]
-            fn do_parse_prefix<'parse, M: Mode>(
-                &self,
-                inp: &mut InputRef<'src, 'parse, I, E>,
-                pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
-            ) -> PResult<M, O>
-            where
-                Self: Sized,
-            {
+            fn op_count(&self) -> usize {
                 let ($($X,)*) = self;
-                $(
-                    match $X.do_parse_prefix::<M>(inp, pre_expr, f) {
-                        Ok(out) => return Ok(out),
-                        Err(()) => {},
-                    }
-                )*
-                Err(())
+                0 $(+ $X.op_count())*
             }
 
             #[inline]
-            fn do_parse_postfix<'parse, M: Mode>(
-                &self,
+            fn do_parse_prefix_deferred<'b, 'parse, M: Mode + 'b>(
+                &'b self,
                 inp: &mut InputRef<'src, 'parse, I, E>,
-                pre_expr: &input::Cursor<'src, 'parse, I>,
-                pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-                mut lhs: M::Output<O>,
-                min_power: u32,
-            ) -> Result<M::Output<O>, M::Output<O>>
-            where
-                Self: Sized,
-            {
+                pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+                skip: usize,
+            ) -> Option<Deferred<'b, 'src, 'parse, I, O, E, M>> {
                 let ($($X,)*) = self;
+                let mut base = 0;
                 $(
-                    match $X.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
-                        Ok(out) => return Ok(out),
-                        Err(out) => lhs = out,
+                    let count = $X.op_count();
+                    if skip < base + count {
+                        if let Some(mut deferred) = $X.do_parse_prefix_deferred::<M>(inp, pre_expr, skip.saturating_sub(base)) {
+                            deferred.index += base;
+                            return Some(deferred);
+                        }
                     }
+                    base += count;
                 )*
-                Err(lhs)
+                None
             }
 
             #[inline]
-            fn do_parse_infix<'parse, M: Mode>(
-                &self,
+            fn do_parse_infix_deferred<'b, 'parse, M: Mode + 'b>(
+                &'b self,
                 inp: &mut InputRef<'src, 'parse, I, E>,
-                pre_expr: &input::Cursor<'src, 'parse, I>,
                 pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
                 mut lhs: M::Output<O>,
                 min_power: u32,
-                f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
-            ) -> Result<M::Output<O>, M::Output<O>>
-            where
-                Self: Sized,
-            {
+                skip: usize,
+            ) -> Result<Deferred<'b, 'src, 'parse, I, O, E, M>, M::Output<O>> {
                 let ($($X,)*) = self;
+                let mut base = 0;
                 $(
-                    match $X.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
-                        Ok(out) => return Ok(out),
-                        Err(out) => lhs = out,
+                    let count = $X.op_count();
+                    if skip < base + count {
+                        match $X.do_parse_infix_deferred::<M>(inp, pre_op, lhs, min_power, skip.saturating_sub(base)) {
+                            Ok(mut deferred) => {
+                                deferred.index += base;
+                                return Ok(deferred);
+                            }
+                            Err(out) => lhs = out,
+                        }
                     }
+                    base += count;
                 )*
                 Err(lhs)
             }
-
-            op_check_and_emit!();
         }
     };
 }
-
-impl_operator_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
-
-#[allow(unused_variables, non_snake_case)]
-impl<'src, I, O, E, Op> Operator<'src, I, O, E> for Vec<Op>
-where
-    I: Input<'src>,
-    E: ParserExtra<'src, I>,
-    Op: Operator<'src, I, O, E>,
-{
-    #[inline]
-    fn do_parse_prefix<'parse, M: Mode>(
-        &self,
-        inp: &mut InputRef<'src, 'parse, I, E>,
-        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
-    ) -> PResult<M, O>
-    where
-        Self: Sized,
-    {
-        for op in self {
-            if let Ok(out) = op.do_parse_prefix::<M>(inp, pre_expr, f) {
-                return Ok(out);
-            }
-        }
-        Err(())
+
+impl_iterative_operator_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+impl<'src, I, O, E, Op> IterativeOperator<'src, I, O, E> for Vec<Op>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    Op: IterativeOperator<'src, I, O, E>,
+{
+    #[inline]
+    fn op_count(&self) -> usize {
+        self.iter().map(|op| op.op_count()).sum()
     }
 
     #[inline]
-    fn do_parse_postfix<'parse, M: Mode>(
-        &self,
+    fn do_parse_prefix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
         inp: &mut InputRef<'src, 'parse, I, E>,
-        pre_expr: &input::Cursor<'src, 'parse, I>,
-        pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
-        mut lhs: M::Output<O>,
-        min_power: u32,
-    ) -> Result<M::Output<O>, M::Output<O>>
-    where
-        Self: Sized,
-    {
+        pre_expr: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+        skip: usize,
+    ) -> Option<Deferred<'b, 'src, 'parse, I, O, E, M>> {
+        let mut base = 0;
         for op in self {
-            match op.do_parse_postfix::<M>(inp, pre_expr, pre_op, lhs, min_power) {
-                Ok(out) => return Ok(out),
-                Err(out) => lhs = out,
+            let count = op.op_count();
+            if skip < base + count {
+                if let Some(mut deferred) =
+                    op.do_parse_prefix_deferred::<M>(inp, pre_expr, skip.saturating_sub(base))
+                {
+                    deferred.index += base;
+                    return Some(deferred);
+                }
             }
+            base += count;
         }
-        Err(lhs)
+        None
     }
 
     #[inline]
-    fn do_parse_infix<'parse, M: Mode>(
-        &self,
+    fn do_parse_infix_deferred<'b, 'parse, M: Mode + 'b>(
+        &'b self,
         inp: &mut InputRef<'src, 'parse, I, E>,
-        pre_expr: &input::Cursor<'src, 'parse, I>,
         pre_op: &input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
         mut lhs: M::Output<O>,
         min_power: u32,
-        f: &impl Fn(&mut InputRef<'src, 'parse, I, E>, u32) -> PResult<M, O>,
-    ) -> Result<M::Output<O>, M::Output<O>>
-    where
-        Self: Sized,
-    {
+        skip: usize,
+    ) -> Result<Deferred<'b, 'src, 'parse, I, O, E, M>, M::Output<O>> {
+        let mut base = 0;
         for op in self {
-            match op.do_parse_infix::<M>(inp, pre_expr, pre_op, lhs, min_power, f) {
-                Ok(out) => return Ok(out),
-                Err(out) => lhs = out,
+            let count = op.op_count();
+            if skip < base + count {
+                match op.do_parse_infix_deferred::<M>(
+                    inp,
+                    pre_op,
+                    lhs,
+                    min_power,
+                    skip.saturating_sub(base),
+                ) {
+                    Ok(mut deferred) => {
+                        deferred.index += base;
+                        return Ok(deferred);
+                    }
+                    Err(out) => lhs = out,
+                }
             }
+            base += count;
         }
         Err(lhs)
     }
-
-    op_check_and_emit!();
 }
 
-#[allow(unused_variables, non_snake_case)]
-impl<'src, Atom, Ops> Pratt<Atom, Ops> {
-    #[inline]
-    fn pratt_go<M: Mode, I, O, E>(
-        &self,
-        inp: &mut InputRef<'src, '_, I, E>,
-        min_power: u32,
-    ) -> PResult<M, O>
-    where
-        I: Input<'src>,
-        E: ParserExtra<'src, I>,
-        Atom: Parser<'src, I, O, E>,
-        Ops: Operator<'src, I, O, E>,
-    {
-        let pre_expr = inp.save();
-        // Prefix unary operators
-        let mut lhs = match self
-            .ops
-            .do_parse_prefix::<M>(inp, &pre_expr, &|inp, min_power| {
-                recursive::recurse(|| self.pratt_go::<M, _, _, _>(inp, min_power))
-            }) {
-            Ok(out) => out,
-            Err(()) => self.atom.go::<M>(inp)?,
-        };
-
-        loop {
-            let pre_op = inp.save();
-
-            // Postfix unary operators
-            match self
-                .ops
-                .do_parse_postfix::<M>(inp, pre_expr.cursor(), &pre_op, lhs, min_power)
-            {
-                Ok(out) => {
-                    lhs = out;
-                    continue;
-                }
-                Err(out) => lhs = out,
-            }
-
-            // Infix binary operators
-            match self.ops.do_parse_infix::<M>(
-                inp,
-                pre_expr.cursor(),
-                &pre_op,
-                lhs,
-                min_power,
-                &|inp, min_power| {
-                    recursive::recurse(|| self.pratt_go::<M, _, _, _>(inp, min_power))
-                },
-            ) {
-                Ok(out) => {
-                    lhs = out;
-                    continue;
-                }
-                Err(out) => lhs = out,
-            }
+/// See [`Pratt::iterative`].
+#[derive(Copy, Clone)]
+pub struct IterativePratt<Atom, Ops, Rec = ()> {
+    pratt: Pratt<Atom, Ops, Rec>,
+}
 
-            inp.rewind(pre_op);
-            break;
-        }
+// An operator on the explicit expression stack of an `IterativePratt`, waiting for its right-hand operand.
+struct Frame<'b, 'src, 'parse, I, O, E, M>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    M: Mode,
+{
+    // The start of the expression that the operator belongs to
+    pre_expr: input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>,
+    // Where to rewind to if the operand fails (`None` for prefix operators, which rewind to `pre_expr`)
+    pre_op:
+        Option<input::Checkpoint<'src, 'parse, I, <E::State as Inspector<'src, I>>::Checkpoint>>,
+    min_power: u32,
+    after_op: bool,
+    deferred: Deferred<'b, 'src, 'parse, I, O, E, M>,
+}
 
-        Ok(lhs)
-    }
+enum Step<T> {
+    // Parse an operand, trying prefix operators from the given index onwards
+    Operand(usize),
+    // An operand failed to parse: abandon the innermost pending operator
+    Abandon,
+    // Parse operators following an operand, trying infix operators from the given index onwards (or postfix
+    // operators first, if `None`)
+    Operators(T, Option<usize>),
 }
 
 #[allow(unused_variables, non_snake_case)]
-impl<'src, I, O, E, Atom, Ops> Parser<'src, I, O, E> for Pratt<Atom, Ops>
+impl<'src, I, O, E, Atom, Ops, Rec> Parser<'src, I, O, E> for IterativePratt<Atom, Ops, Rec>
 where
     I: Input<'src>,
     E: ParserExtra<'src, I>,
     Atom: Parser<'src, I, O, E>,
-    Ops: Operator<'src, I, O, E>,
+    Ops: IterativeOperator<'src, I, O, E>,
+    Rec: OperandRecovery<'src, I, O>,
 {
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
-        self.pratt_go::<M, _, _, _>(inp, 0)
+        let Pratt {
+            atom,
+            ops,
+            recovery,
+        } = &self.pratt;
+
+        let mut stack = Vec::<Frame<'_, 'src, '_, I, O, E, M>>::new();
+        let mut pre_expr = inp.save();
+        let mut min_power = 0;
+        let mut after_op = false;
+        let mut step = Step::Operand(0);
+
+        loop {
+            step = match step {
+                Step::Operand(skip) => {
+                    // Prefix unary operators
+                    if let Some(deferred) = ops.do_parse_prefix_deferred::<M>(inp, &pre_expr, skip)
+                    {
+                        if inp.enter_recursion().is_err() {
+                            stack.iter().for_each(|_| inp.exit_recursion());
+                            return Err(());
+                        }
+                        let power = deferred.power;
+                        stack.push(Frame {
+                            pre_expr: core::mem::replace(&mut pre_expr, inp.save()),
+                            pre_op: None,
+                            min_power: core::mem::replace(&mut min_power, power),
+                            after_op: core::mem::replace(&mut after_op, true),
+                            deferred,
+                        });
+                        Step::Operand(0)
+                    } else {
+                        match atom.go::<M>(inp) {
+                            Ok(out) => Step::Operators(out, None),
+                            Err(()) if after_op => {
+                                // The operand following an operator is missing: try to recover by inserting a
+                                // fallback
+                                inp.rewind(pre_expr.clone());
+                                let span = inp.span_since(pre_expr.cursor());
+                                match recovery.recover(span) {
+                                    Some(out) => {
+                                        let err = inp.take_alt().map(|alt| alt.err).unwrap_or_else(
+                                            || {
+                                                E::Error::expected_found(
+                                                    [],
+                                                    None,
+                                                    inp.span_since(pre_expr.cursor()),
+                                                )
+                                            },
+                                        );
+                                        inp.emit(None, err);
+                                        Step::Operators(M::bind(|| out), None)
+                                    }
+                                    None => Step::Abandon,
+                                }
+                            }
+                            Err(()) => Step::Abandon,
+                        }
+                    }
+                }
+                Step::Abandon => {
+                    let frame = stack.pop().ok_or(())?;
+                    inp.exit_recursion();
+                    pre_expr = frame.pre_expr;
+                    min_power = frame.min_power;
+                    after_op = frame.after_op;
+                    // Try the operators that come after the abandoned one
+                    let skip = frame.deferred.index + 1;
+                    match (frame.pre_op, frame.deferred.lhs) {
+                        (Some(pre_op), Some(lhs)) => {
+                            inp.rewind(pre_op);
+                            Step::Operators(lhs, Some(skip))
+                        }
+                        _ => {
+                            inp.rewind(pre_expr.clone());
+                            Step::Operand(skip)
+                        }
+                    }
+                }
+                Step::Operators(mut lhs, skip) => 'ops: {
+                    let pre_op = inp.save();
+
+                    // Postfix unary operators
+                    if skip.is_none() {
+                        match ops.do_parse_postfix::<M>(
+                            inp,
+                            pre_expr.cursor(),
+                            &pre_op,
+                            lhs,
+                            min_power,
+                        ) {
+                            Ok(out) => break 'ops Step::Operators(out, None),
+                            Err(out) => lhs = out,
+                        }
+                    }
+
+                    // Infix binary operators
+                    match ops.do_parse_infix_deferred::<M>(
+                        inp,
+                        &pre_op,
+                        lhs,
+                        min_power,
+                        skip.unwrap_or(0),
+                    ) {
+                        Ok(deferred) => {
+                            if inp.enter_recursion().is_err() {
+                                stack.iter().for_each(|_| inp.exit_recursion());
+                                return Err(());
+                            }
+                            let power = deferred.power;
+                            stack.push(Frame {
+                                pre_expr: core::mem::replace(&mut pre_expr, inp.save()),
+                                pre_op: Some(pre_op),
+                                min_power: core::mem::replace(&mut min_power, power),
+                                after_op: core::mem::replace(&mut after_op, true),
+                                deferred,
+                            });
+                            Step::Operand(0)
+                        }
+                        Err(lhs) => {
+                            inp.rewind(pre_op);
+                            // This expression is complete: it becomes the operand of the innermost pending operator
+                            let Some(frame) = stack.pop() else {
+                                return Ok(lhs);
+                            };
+                            inp.exit_recursion();
+                            pre_expr = frame.pre_expr;
+                            min_power = frame.min_power;
+                            after_op = frame.after_op;
+                            let out = (frame.deferred.fold)(
+                                frame.deferred.lhs,
+                                lhs,
+                                pre_expr.cursor(),
+                                inp,
+                            );
+                            Step::Operators(out, None)
+                        }
+                    }
+                }
+            };
+        }
     }
 
     go_extra!(O);
@@ -1032,18 +3509,32 @@ mod tests {
         assert_eq!(parser().parse("-2 + 2").into_result(), Ok(0));
     }
 
-    #[allow(dead_code)]
     fn parser_dynamic<'src>() -> impl Parser<'src, &'src str, i64> {
         let atom = text::int(10).padded().from_str::<i64>().unwrapped();
 
         atom.pratt(vec![
             prefix(2, just('-'), |_, x: i64, _| -x).boxed(),
             postfix(2, just('!'), |x, _, _| factorial(x)).boxed(),
-            infix(left(0), just('+'), |l, _, r, _| l + r).boxed(),
-            infix(left(0), just('-'), |l, _, r, _| l - r).boxed(),
-            infix(left(1), just('*'), |l, _, r, _| l * r).boxed(),
-            infix(left(1), just('/'), |l, _, r, _| l / r).boxed(),
         ])
+        .with_ops(['+', '-'].map(|c| {
+            infix(
+                left(0),
+                just(c),
+                move |l, _, r, _| if c == '+' { l + r } else { l - r },
+            )
+        }))
+        .with_op(infix(left(1), just('*'), |l, _, r, _| l * r))
+        .with_op(infix(left(1), just('/'), |l, _, r, _| l / r))
+    }
+
+    #[test]
+    fn dynamic_operators() {
+        assert_eq!(parser_dynamic().parse("2 + 3 * 4").into_result(), Ok(14));
+        assert_eq!(
+            parser_dynamic().parse("-2 + 8 / 2 + 3!").into_result(),
+            Ok(8)
+        );
+        assert_eq!(parser_dynamic().parse("2 - 3 - 4").into_result(), Ok(-5));
     }
 
     enum Expr {
@@ -1099,11 +3590,11 @@ mod tests {
         expr_parser().then_ignore(end())
     }
 
-    fn parse(input: &str) -> ParseResult<String, Simple<char>> {
+    fn parse(input: &str) -> ParseResult<String, Simple<'_, char>> {
         complete_parser().parse(input)
     }
 
-    fn parse_partial(input: &str) -> ParseResult<String, Simple<char>> {
+    fn parse_partial(input: &str) -> ParseResult<String, Simple<'_, char>> {
         expr_parser().lazy().parse(input)
     }
 
@@ -1254,4 +3745,473 @@ mod tests {
             Ok("(((§(1 + (-(~(2!)))))$) * 3)".to_string()),
         )
     }
+
+    #[test]
+    fn missing_operand_recovery() {
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str()
+            .unwrapped()
+            .map(Expr::Literal);
+
+        let parser = atom
+            .pratt((
+                prefix(4, just('-'), |_, r, _| u(Expr::Negate, r)),
+                infix(left(1), just('+'), |l, _, r, _| i(Expr::Add, l, r)),
+                infix(left(2), just('*'), |l, _, r, _| i(Expr::Mul, l, r)),
+            ))
+            .recover_missing_operand(|_| Expr::Literal(0))
+            .map(|x| x.to_string());
+
+        assert_eq!(
+            parser.parse("1+*2").into_output_errors(),
+            (
+                Some("(1 + (0 * 2))".to_string()),
+                vec![unexpected(Some('*'.into()), 2..3)]
+            ),
+        );
+        assert_eq!(
+            parser.parse("1+-").into_output_errors(),
+            (Some("(1 + (-0))".to_string()), vec![unexpected(None, 3..3)]),
+        );
+        // Expressions that are missing entirely are not recovered
+        assert!(parser.parse("").into_output().is_none());
+    }
+
+    fn backtracking_parser<'src>() -> Pratt<
+        impl Parser<'src, &'src str, Expr, Err<Simple<'src, char>>>,
+        impl IterativeOperator<'src, &'src str, Expr, Err<Simple<'src, char>>>,
+    > {
+        let atom = text::int(10).from_str().unwrapped().map(Expr::Literal);
+
+        atom.pratt((
+            prefix(4, just("--"), |_, r, _| u(Expr::Confusion, r)),
+            prefix(4, just('-'), |_, r, _| u(Expr::Negate, r)),
+            prefix(1, just('~'), |_, r, _| u(Expr::Not, r)),
+            postfix(5, just('!'), |l, _, _| u(Expr::Factorial, l)),
+            infix(left(1), just("++"), |l, _, r, _| i(Expr::Mul, l, r)),
+            infix(left(1), just('+'), |l, _, r, _| i(Expr::Add, l, r)),
+            infix(right(2), just('-'), |l, _, r, _| i(Expr::Sub, l, r)),
+        ))
+    }
+
+    #[test]
+    fn iterative_matches_recursive() {
+        let recursive = backtracking_parser().map(|x| x.to_string());
+        let iterative = backtracking_parser().iterative().map(|x| x.to_string());
+        let recursive_recovery = backtracking_parser()
+            .recover_missing_operand(|_| Expr::Literal(0))
+            .map(|x| x.to_string());
+        let iterative_recovery = backtracking_parser()
+            .recover_missing_operand(|_| Expr::Literal(0))
+            .iterative()
+            .map(|x| x.to_string());
+
+        for src in [
+            "", "1", "-1", "--1", "---1", "~-1+2", "1++2", "1+++2", "1++-2!", "1-2-3+4", "1---2",
+            "1+", "1++", "1--", "-", "--", "1!+~2-3!", "~1++~2", "1+?", "?", "1+~", "--~",
+        ] {
+            assert_eq!(
+                iterative.parse(src).into_output_errors(),
+                recursive.parse(src).into_output_errors(),
+                "{src:?}",
+            );
+            assert_eq!(
+                iterative_recovery.parse(src).into_output_errors(),
+                recursive_recovery.parse(src).into_output_errors(),
+                "{src:?}",
+            );
+        }
+    }
+
+    #[test]
+    fn iterative_deep_nesting() {
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped();
+        let parser = atom
+            .pratt((
+                prefix(2, just('-'), |_, x: i64, _| -x),
+                postfix(3, just('!'), |x, _, _| x + 1),
+                infix(right(1), just('+'), |l, _, r, _| l + r),
+            ))
+            .iterative();
+
+        let depth = 200_000;
+        let src = "1+".repeat(depth) + &"-".repeat(depth) + "1" + &"!".repeat(depth);
+        assert_eq!(
+            parser.parse(src.as_str()).into_result(),
+            Ok(depth as i64 * 2 + 1)
+        );
+        let src = "1+".repeat(depth);
+        assert!(parser.parse(src.as_str()).has_errors());
+    }
+
+    #[test]
+    fn operand_depth_limited() {
+        use crate::{LimitExceeded, Limits};
+
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped();
+        let parser = atom.pratt((
+            prefix(2, just('-'), |_, x: i64, _| -x),
+            infix(right(1), just('+'), |l, _, r, _| l + r),
+        ));
+
+        let src = "-".repeat(100) + "1";
+        let rhs = "1+".repeat(100) + "1";
+        for parser in [parser.boxed(), parser.iterative().boxed()] {
+            for src in [&src, &rhs] {
+                assert!(parser
+                    .parse_with_limits(src.as_str(), Limits::default().max_depth(100))
+                    .is_ok());
+                assert_eq!(
+                    parser.parse_with_limits(src.as_str(), Limits::default().max_depth(64)),
+                    Err(LimitExceeded::Depth)
+                );
+            }
+            assert_eq!(parser.parse_with_stats(src.as_str()).1.max_depth, 100);
+        }
+    }
+
+    #[test]
+    fn custom_prefix_operator() {
+        // `max a b`, a prefix operator with two operands
+        struct Max;
+
+        impl<'src> CustomOperator<'src, &'src str, i64, Err<Simple<'src, char>>> for Max {
+            fn parse_prefix(
+                &self,
+                ctx: &mut OperatorContext<'src, '_, '_, &'src str, i64, Err<Simple<'src, char>>>,
+            ) -> Option<Operand<i64>> {
+                ctx.parse(text::ascii::keyword("max").padded())?;
+                let a = ctx.expr(right(3))?;
+                let b = ctx.expr(right(3))?;
+                Some(a.zip(b).map(|(a, b)| a.max(b)))
+            }
+        }
+
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let parser = atom.pratt((
+            operator(Max),
+            prefix(2, just('-'), |_, x: i64, _| -x),
+            infix(left(1), just('+'), |l, _, r, _| l + r),
+        ));
+
+        assert_eq!(parser.parse("max 1 2").into_result(), Ok(2));
+        assert_eq!(parser.parse("max -1 -2 + 3").into_result(), Ok(2));
+        assert_eq!(parser.parse("max max 1 5 2").into_result(), Ok(5));
+        assert!(parser.parse("max 1").has_errors());
+        // Without outputs
+        assert_eq!(
+            parser.to_slice().parse("max 1 2 + 1").into_result(),
+            Ok("max 1 2 + 1")
+        );
+    }
+
+    #[test]
+    fn ternary_and_mixfix() {
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let parser = atom.pratt((
+            infix(left(3), just('+'), |l, _, r, _| l + r),
+            ternary(
+                right(1),
+                just('?'),
+                just(':'),
+                |c, _, a, _, b, _| {
+                    if c != 0 {
+                        a
+                    } else {
+                        b
+                    }
+                },
+            ),
+            // `lo <= x <= hi`, clamping `x` into the range
+            mixfix(left(2), [just("<="), just("<=")], |xs: Vec<i64>, _, _| {
+                xs[1].clamp(xs[0], xs[2])
+            }),
+        ));
+
+        // The outer operands bind according to the binding power
+        assert_eq!(parser.parse("0 ? 1 : 2 + 3").into_result(), Ok(5));
+        assert_eq!(parser.parse("1 + 1 ? 7 : 8").into_result(), Ok(7));
+        assert_eq!(parser.parse("1 <= 5 + 5 <= 3").into_result(), Ok(3));
+        // The middle operands may be any expression
+        assert_eq!(parser.parse("1 ? 0 ? 1 : 2 : 3").into_result(), Ok(2));
+        assert_eq!(
+            parser.parse("1 <= 0 ? 4 : 0 <= 3 ? 2 : 9").into_result(),
+            Ok(2)
+        );
+        // Incomplete operators
+        assert!(parser.parse("1 ? 2").has_errors());
+        assert!(parser.parse("1 <= 2").has_errors());
+        // Without outputs
+        assert_eq!(
+            parser.to_slice().parse("1 ? 2 : 3 <= 4 <= 5").into_result(),
+            Ok("1 ? 2 : 3 <= 4 <= 5")
+        );
+    }
+
+    #[test]
+    fn non_assoc_chains() {
+        let atom = text::int::<_, Err<Rich<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let parser = atom.pratt((
+            infix(left(2), just('+'), |l, _, r, _| l + r),
+            infix(non_assoc(1), just('<'), |l, _, r, _| (l < r) as i64),
+            infix(left(0), just('&'), |l, _, r, _| l & r),
+        ));
+
+        assert_eq!(parser.parse("1 < 2").into_result(), Ok(1));
+        assert_eq!(parser.parse("1 + 1 < 2 & 0 < 1").into_result(), Ok(0));
+        for parser in [parser.boxed(), parser.iterative().boxed()] {
+            let (out, errs) = parser.parse("1 < 2 < 0 < 3").into_output_errors();
+            // Recovered as if left-associative
+            assert_eq!(out, Some(1));
+            assert_eq!(
+                errs.iter().map(|e| *e.span()).collect::<Vec<_>>(),
+                [SimpleSpan::from(6..7), SimpleSpan::from(10..11)],
+            );
+        }
+    }
+
+    #[test]
+    fn call_operators() {
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let op = |c| just(c).padded();
+        let parser = atom
+            .pratt((
+                // Sum of the arguments, added to the operand
+                call(3, op('('), op(','), op(')'), |x, args: Vec<i64>, _| {
+                    x + args.iter().sum::<i64>()
+                }),
+                prefix(2, op('-'), |_, x: i64, _| -x),
+                infix(left(1), op('*'), |l, _, r, _| l * r),
+            ))
+            .recover_missing_operand(|_| 0);
+
+        assert_eq!(parser.parse("1()").into_result(), Ok(1));
+        assert_eq!(parser.parse("-1(2, 3 * 4)(5)").into_result(), Ok(-20));
+        assert_eq!(parser.parse("2 * 1(-1(1))").into_result(), Ok(-2));
+        // Missing arguments are recovered
+        let (out, errs) = parser.parse("1(2, )").into_output_errors();
+        assert_eq!((out, errs.len()), (Some(3), 1));
+        // Without outputs
+        assert_eq!(
+            parser.to_slice().parse("1(2, 3(4))").into_result(),
+            Ok("1(2, 3(4))")
+        );
+    }
+
+    #[test]
+    fn dynamic_fixity() {
+        use crate::extra::SimpleState;
+        use std::collections::HashMap;
+
+        type State = SimpleState<HashMap<char, Associativity>>;
+        let op = one_of::<_, _, extra::Full<EmptyErr, State, ()>>("+*").padded();
+        let expr = text::int(10)
+            .map(str::to_string)
+            .padded()
+            .pratt(dynamic_infix(
+                op,
+                |op: &char, state: &State| state.get(op).copied(),
+                |l, op, r, _| format!("({l}{op}{r})"),
+            ));
+        let decl = op
+            .then(text::int(10).from_str::<u16>().unwrapped())
+            .map_with(|(op, power), e| {
+                e.state().insert(op, right(power));
+            });
+        let stmts = decl
+            .map(|()| None)
+            .or(expr.map(Some))
+            .separated_by(just(';'))
+            .collect::<Vec<_>>();
+
+        let mut state = SimpleState(HashMap::new());
+        state.insert('+', left(1));
+        state.insert('*', left(2));
+        assert_eq!(
+            stmts
+                .parse_with_state("1+2*3+4; * 0; 1*2*3+4", &mut state)
+                .into_result(),
+            Ok(vec![
+                Some("((1+(2*3))+4)".to_string()),
+                None,
+                // `*` now binds more loosely than `+`, and to the right
+                Some("(1*(2*(3+4)))".to_string()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn chained_comparisons() {
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .padded();
+        let parser = atom.pratt((
+            infix(left(3), just('+'), |l, _, r, _| l + r),
+            // Evaluates like Python, producing 1 or 0
+            chained(left(2), one_of("<="), |xs: Vec<i64>, ops: Vec<char>, _| {
+                xs.windows(2).zip(ops).all(|(w, op)| match op {
+                    '<' => w[0] < w[1],
+                    _ => w[0] == w[1],
+                }) as i64
+            }),
+            infix(left(1), just('&'), |l, _, r, _| l & r),
+        ));
+
+        assert_eq!(parser.parse("1 < 2 < 3").into_result(), Ok(1));
+        assert_eq!(parser.parse("1 < 3 < 2").into_result(), Ok(0));
+        assert_eq!(parser.parse("1 < 1 + 1 = 2 & 3 < 4").into_result(), Ok(1));
+        assert_eq!(parser.parse("2 = 2 = 3").into_result(), Ok(0));
+        // An incomplete operator at the end of a chain is an error
+        assert!(parser.parse("1 < 2 <").has_errors());
+        // Without outputs
+        assert_eq!(
+            parser.to_slice().parse("1 < 2 = 2 & 3").into_result(),
+            Ok("1 < 2 = 2 & 3")
+        );
+
+        // Associativity doesn't split up chains
+        let chain = |assoc| {
+            atom.pratt(chained(assoc, one_of("<="), |xs: Vec<i64>, _, _| {
+                xs.len() as i64
+            }))
+        };
+        assert_eq!(chain(left(1)).parse("1 < 2 < 3 = 4").into_result(), Ok(4));
+        assert_eq!(chain(right(1)).parse("1 < 2 < 3 = 4").into_result(), Ok(4));
+    }
+
+    #[test]
+    fn labelled_operators() {
+        let atom = text::int::<_, Err<Rich<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped();
+        let ops = (
+            prefix(3, just('-'), |_, x: i64, _| -x).labelled("unary operator"),
+            infix(left(1), just('+'), |l, _, r, _| l + r).labelled("binary operator"),
+            infix(left(2), just('*'), |l, _, r, _| l * r).labelled("binary operator"),
+        );
+        let recursive = atom.pratt(ops).then_ignore(end());
+        let iterative = atom.pratt(ops).iterative().then_ignore(end());
+
+        for parser in [recursive.boxed(), iterative.boxed()] {
+            assert_eq!(parser.parse("-1+2*3").into_result(), Ok(5));
+
+            // Operator tokens are replaced by labels, and shared labels are only reported once
+            let errs = parser.parse("1*2/3").into_errors();
+            assert_eq!(
+                errs[0].to_string(),
+                "found '/' expected digit, binary operator, or end of input"
+            );
+            let errs = parser.parse("1+x").into_errors();
+            assert_eq!(
+                errs[0].to_string(),
+                "found 'x' expected unary operator, non-zero digit, or '0'"
+            );
+        }
+    }
+
+    #[test]
+    fn builder_matches_tuple() {
+        let atom = text::int::<_, Err<Simple<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped();
+        let tuple = atom.pratt((
+            prefix(2, just('-'), |_, x: i64, _| -x),
+            postfix(3, just('!'), |x: i64, _, _| factorial(x)),
+            infix(left(0), just('+'), |l, _, r, _| l + r),
+            infix(right(1), just('*'), |l, _, r, _| l * r),
+            ternary(
+                left(0),
+                just('?'),
+                just(':'),
+                |c: i64, _, a, _, b, _| if c != 0 { a } else { b },
+            ),
+        ));
+        let builder = atom
+            .pratt_builder()
+            .prefix(2, just('-'), |_, x, _| -x)
+            .postfix(3, just('!'), |x, _, _| factorial(x))
+            .infix(left(0), just('+'), |l, _, r, _| l + r)
+            .build()
+            .with_op(infix(right(1), just('*'), |l, _, r, _| l * r))
+            .with_op(ternary(
+                left(0),
+                just('?'),
+                just(':'),
+                |c: i64, _, a, _, b, _| {
+                    if c != 0 {
+                        a
+                    } else {
+                        b
+                    }
+                },
+            ));
+
+        for input in ["1+2*3!", "-3!*2+1", "0?1:2+3", "1?-2:3", "4*"] {
+            assert_eq!(
+                builder.parse(input).into_result(),
+                tuple.parse(input).into_result(),
+                "{input}",
+            );
+        }
+    }
+
+    #[test]
+    fn infix_rhs_operators() {
+        let atom = text::ascii::ident::<_, Err<Simple<char>>>()
+            .map(|x: &str| x.to_string())
+            .padded();
+        let ty = text::ascii::ident().padded();
+        let ops = (
+            infix(left(1), just('+'), |l, _, r, _| format!("({l} + {r})")),
+            infix_rhs(left(1), just(':'), ty, |x, _, ty, _| format!("({x}: {ty})")),
+            infix_rhs(right(1), text::keyword("as"), ty, |x, _, ty, _| {
+                format!("({x} as {ty})")
+            }),
+            infix_rhs(non_assoc(2), just('?'), ty, |x, _, ty, _| {
+                format!("({x} is {ty})")
+            }),
+        );
+        let recursive = atom.pratt(ops.clone());
+        let iterative = atom.pratt(ops).iterative();
+
+        for parser in [recursive.boxed(), iterative.boxed()] {
+            assert_eq!(
+                parser.parse("a + b : T").into_result(),
+                Ok("((a + b): T)".to_string()),
+            );
+            assert_eq!(
+                parser.parse("a + b as T as U : V").into_result(),
+                Ok("((a + ((b as T) as U)): V)".to_string()),
+            );
+            assert_eq!(
+                parser.clone().to_slice().parse("a : T + b").into_result(),
+                Ok("a : T + b"),
+            );
+            // Non-associative operators cannot be chained
+            assert_eq!(
+                parser.parse("a ? T").into_result(),
+                Ok("(a is T)".to_string())
+            );
+            assert!(parser.parse("a ? T ? U").has_errors());
+            // The right operand must be present
+            assert!(parser.parse("a : ").has_errors());
+        }
+    }
 }
@@ -16,15 +16,23 @@
 //! - [`end`]: parses the end of input (i.e: if there any more inputs, this parse fails)
 
 use super::*;
+use crate::input::{Cursor, MappedSpan};
 
 /// See [`end`].
-pub struct End<I, E>(EmptyPhantom<(E, I)>);
+pub struct End<I, E> {
+    spanning_rest: bool,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
 
 /// A parser that accepts only the end of input.
 ///
 /// The output type of this parser is `()`.
 pub const fn end<'src, I: Input<'src>, E: ParserExtra<'src, I>>() -> End<I, E> {
-    End(EmptyPhantom::new())
+    End {
+        spanning_rest: false,
+        phantom: EmptyPhantom::new(),
+    }
 }
 
 impl<I, E> Copy for End<I, E> {}
@@ -34,6 +42,36 @@ impl<I, E> Clone for End<I, E> {
     }
 }
 
+impl<I, E> End<I, E> {
+    /// When there is trailing input, make the error span the whole of it rather than just its first token.
+    ///
+    /// By default, the error produced by [`end`] points at the first unexpected token. This is precise, but for
+    /// tools that validate data files, an error that covers all of the trailing input (from the first unexpected
+    /// token to the end of the input) gives a much better picture of what went wrong.
+    ///
+    /// Finding the end of the trailing input means walking over it, so avoid this where [`end`] fails often, such as
+    /// on one side of an [`or`](Parser::or) that is tried at many positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let items = text::int::<_, extra::Err<Rich<char>>>(10)
+    ///     .separated_by(just(','))
+    ///     .then_ignore(end().spanning_rest());
+    ///
+    /// let errs = items.parse("1,2,3 oops").into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(5..10));
+    /// assert_eq!(errs[0].to_string(), "found ' ' expected digit, ',', or end of input");
+    /// ```
+    pub const fn spanning_rest(self) -> Self {
+        Self {
+            spanning_rest: true,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
 impl<'src, I, E> Parser<'src, I, (), E> for End<I, E>
 where
     I: Input<'src>,
@@ -44,6 +82,26 @@ where
         let before = inp.save();
         match inp.next_maybe_inner() {
             None => Ok(M::bind(|| ())),
+            Some(tok) if self.spanning_rest => {
+                while inp.next_maybe_inner().is_some() {}
+                let span = inp.span_since(before.cursor());
+                inp.rewind(before);
+                let mut err: E::Error = LabelError::<I, _>::expected_found(
+                    [DefaultExpected::EndOfInput],
+                    Some(tok.into()),
+                    span,
+                );
+                // Errors keep the span of the first error they are merged into, so ours must come first
+                let at = inp.cursor().inner;
+                match inp.take_alt() {
+                    Some(alt) if I::cursor_location(&alt.pos) == I::cursor_location(&at) => {
+                        err = err.merge(alt.err);
+                    }
+                    alt => inp.errors.alt = alt,
+                }
+                inp.add_alt_err(&at, err);
+                Err(())
+            }
             Some(tok) => {
                 let span = inp.span_since(before.cursor());
                 inp.rewind(before);
@@ -53,6 +111,10 @@ where
         }
     }
 
+    fn introspect(&self, _cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new("End", introspect::NodeKind::End, Vec::new())
+    }
+
     go_extra!(());
 }
 
@@ -83,6 +145,10 @@ where
         Ok(M::bind(|| ()))
     }
 
+    fn introspect(&self, _cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new("Empty", introspect::NodeKind::Empty, Vec::new())
+    }
+
     go_extra!(());
 }
 
@@ -165,6 +231,14 @@ where
         Self::go_cfg::<M>(self, inp, JustCfg::default())
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Just",
+            introspect::NodeKind::Tokens(introspect::spell_seq(cx, &self.seq)),
+            Vec::new(),
+        )
+    }
+
     go_extra!(T);
 }
 
@@ -205,6 +279,114 @@ where
     }
 }
 
+/// See [`just_seq`].
+pub struct JustSeq<T, I, E = EmptyErr> {
+    seq: T,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<T: Copy, I, E> Copy for JustSeq<T, I, E> {}
+impl<T: Clone, I, E> Clone for JustSeq<T, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// A parser that accepts only the given sequence of tokens, treating it as a single unit.
+///
+/// This is most useful with token inputs, for sequences like `[Token::Fn, Token::LParen]`. [`just`] accepts the same
+/// sequences, but reports a mismatch as an error expecting the single token that didn't match at its position.
+/// `just_seq` instead produces one error, spanning from the start of the sequence to the first token that didn't
+/// match, expecting the whole sequence (see [`DefaultExpected::Tokens`]). This is also cheaper than chaining
+/// [`just`]s together, since the sequence is compared in a single loop with no intermediate backtracking.
+///
+/// The output type of this parser is `T`, the sequence that was provided.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::{Rich, RichPattern}, util::MaybeRef};
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Token { Fn, Ident, LParen, RParen }
+///
+/// let fn_start = just_seq::<_, _, extra::Err<Rich<Token>>>([Token::Fn, Token::Ident, Token::LParen]);
+///
+/// assert!(!fn_start.parse(&[Token::Fn, Token::Ident, Token::LParen]).has_errors());
+///
+/// let errs = fn_start.parse(&[Token::Fn, Token::Ident, Token::RParen]).into_errors();
+/// assert_eq!(errs.len(), 1);
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(0..3));
+/// assert_eq!(
+///     errs[0].expected().collect::<Vec<_>>(),
+///     vec![&RichPattern::Tokens(vec![
+///         MaybeRef::Val(Token::Fn),
+///         MaybeRef::Val(Token::Ident),
+///         MaybeRef::Val(Token::LParen),
+///     ])],
+/// );
+/// ```
+pub const fn just_seq<'src, T, I, E>(seq: T) -> JustSeq<T, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq + Clone,
+    T: OrderedSeq<'src, I::Token> + Clone,
+{
+    JustSeq {
+        seq,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, E, T> Parser<'src, I, T, E> for JustSeq<T, I, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq + Clone,
+    T: OrderedSeq<'src, I::Token> + Clone,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, T> {
+        let before = inp.save();
+        for next in self.seq.seq_iter() {
+            match inp.next_maybe_inner() {
+                Some(tok) if next.borrow() == tok.borrow() => {}
+                found => {
+                    let span = inp.span_since(before.cursor());
+                    inp.rewind(before);
+                    let expected = self
+                        .seq
+                        .seq_iter()
+                        .map(|tok| MaybeRef::Val(tok.borrow().clone()))
+                        .collect();
+                    inp.add_alt(
+                        [DefaultExpected::Tokens(expected)],
+                        found.map(|f| f.into()),
+                        span,
+                    );
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(M::bind(|| self.seq.clone()))
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "JustSeq",
+            introspect::NodeKind::Tokens(introspect::spell_seq(cx, &self.seq)),
+            Vec::new(),
+        )
+    }
+
+    go_extra!(T);
+}
+
 /// See [`one_of`].
 pub struct OneOf<T, I, E> {
     seq: T,
@@ -279,9 +461,26 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "OneOf",
+            introspect::NodeKind::OneOf(introspect::spell_seq(cx, &self.seq)),
+            Vec::new(),
+        )
+    }
+
     go_extra!(I::Token);
 }
 
+impl<'src, I, E, T> NonEmptyParser<'src, I, I::Token, E> for OneOf<T, I, E>
+where
+    I: ValueInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq,
+    T: Seq<'src, I::Token>,
+{
+}
+
 /// See [`none_of`].
 pub struct NoneOf<T, I, E> {
     seq: T,
@@ -357,164 +556,780 @@ where
     go_extra!(I::Token);
 }
 
-/// See [`custom`].
-pub struct Custom<F, I, O, E> {
-    f: F,
+impl<'src, I, E, T> NonEmptyParser<'src, I, I::Token, E> for NoneOf<T, I, E>
+where
+    I: ValueInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq,
+    T: Seq<'src, I::Token>,
+{
+}
+
+/// See [`none_of_seq`].
+pub struct NoneOfSeq<T, I, E> {
+    seq: T,
     #[allow(dead_code)]
-    phantom: EmptyPhantom<(E, O, I)>,
+    phantom: EmptyPhantom<(E, I)>,
 }
 
-impl<F: Copy, I, O, E> Copy for Custom<F, I, O, E> {}
-impl<F: Clone, I, O, E> Clone for Custom<F, I, O, E> {
+impl<T: Copy, I, E> Copy for NoneOfSeq<T, I, E> {}
+impl<T: Clone, I, E> Clone for NoneOfSeq<T, I, E> {
     fn clone(&self) -> Self {
         Self {
-            f: self.f.clone(),
+            seq: self.seq.clone(),
             phantom: EmptyPhantom::new(),
         }
     }
 }
 
-/// TODO
+/// A parser that accepts any single input that does *not* begin the given sequence of inputs.
 ///
-/// # Example
+/// This is useful for the bodies of comments, raw strings, and other regions that end with a multi-token
+/// terminator: `none_of_seq("*/").repeated()` consumes everything up to (but not including) the first `*/`. Only a
+/// single input is consumed, even though the whole sequence is examined.
+///
+/// The output type of this parser is `I`, the input that was found.
+///
+/// # Examples
 ///
 /// ```
 /// # use chumsky::{prelude::*, error::Simple};
+/// let comment = none_of_seq::<_, _, extra::Err<Simple<char>>>("*/")
+///     .repeated()
+///     .to_slice()
+///     .delimited_by(just("/*"), just("*/"));
 ///
-/// let x = custom::<_, &str, _, extra::Err<Simple<char>>>(|inp| {
-///     let _ = inp.next();
-///     Ok(())
-/// });
-///
-/// assert_eq!(x.parse("!").into_result(), Ok(()));
+/// assert_eq!(comment.parse("/* a * b / c */").into_result(), Ok(" a * b / c "));
+/// assert!(comment.parse("/* a */ b */").has_errors());
 /// ```
-pub const fn custom<'src, F, I, O, E>(f: F) -> Custom<F, I, O, E>
+pub const fn none_of_seq<'src, T, I, E>(seq: T) -> NoneOfSeq<T, I, E>
 where
-    I: Input<'src>,
+    I: ValueInput<'src>,
     E: ParserExtra<'src, I>,
-    F: Fn(&mut InputRef<'src, '_, I, E>) -> Result<O, E::Error>,
+    I::Token: PartialEq,
+    T: OrderedSeq<'src, I::Token>,
 {
-    Custom {
-        f,
+    NoneOfSeq {
+        seq,
         phantom: EmptyPhantom::new(),
     }
 }
 
-impl<'src, I, O, E, F> Parser<'src, I, O, E> for Custom<F, I, O, E>
+impl<'src, I, E, T> Parser<'src, I, I::Token, E> for NoneOfSeq<T, I, E>
 where
-    I: Input<'src>,
+    I: ValueInput<'src>,
     E: ParserExtra<'src, I>,
-    F: Fn(&mut InputRef<'src, '_, I, E>) -> Result<O, E::Error>,
+    I::Token: PartialEq,
+    T: OrderedSeq<'src, I::Token>,
 {
     #[inline]
-    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
-        let before = inp.cursor();
-        match (self.f)(inp) {
-            Ok(out) => Ok(M::bind(|| out)),
-            Err(err) => {
-                inp.add_alt_err(&before.inner, err);
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, I::Token> {
+        let before = inp.save();
+        let starts_seq = self
+            .seq
+            .seq_iter()
+            .all(|next| inp.next_inner().map_or(false, |tok| next.borrow() == &tok));
+        inp.rewind(before.clone());
+
+        match inp.next_inner() {
+            Some(tok) if !starts_seq => Ok(M::bind(|| tok)),
+            found => {
+                let err_span = inp.span_since(before.cursor());
+                inp.rewind(before);
+                inp.add_alt(
+                    [DefaultExpected::SomethingElse],
+                    found.map(|f| f.into()),
+                    err_span,
+                );
                 Err(())
             }
         }
     }
 
-    go_extra!(O);
+    go_extra!(I::Token);
 }
 
-/// See [`select!`].
-pub struct Select<F, I, O, E> {
-    filter: F,
+impl<'src, I, E, T> NonEmptyParser<'src, I, I::Token, E> for NoneOfSeq<T, I, E>
+where
+    I: ValueInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq,
+    T: OrderedSeq<'src, I::Token>,
+{
+}
+
+/// See [`take_until`].
+pub struct TakeUntil<P, OP, I, E> {
+    terminator: P,
+    consume_terminator: bool,
     #[allow(dead_code)]
-    phantom: EmptyPhantom<(E, O, I)>,
+    phantom: EmptyPhantom<(OP, I, E)>,
 }
 
-impl<F: Copy, I, O, E> Copy for Select<F, I, O, E> {}
-impl<F: Clone, I, O, E> Clone for Select<F, I, O, E> {
+impl<P: Copy, OP, I, E> Copy for TakeUntil<P, OP, I, E> {}
+impl<P: Clone, OP, I, E> Clone for TakeUntil<P, OP, I, E> {
     fn clone(&self) -> Self {
         Self {
-            filter: self.filter.clone(),
+            terminator: self.terminator.clone(),
+            consume_terminator: self.consume_terminator,
             phantom: EmptyPhantom::new(),
         }
     }
 }
 
-/// See [`select!`].
-pub const fn select<'src, F, I, O, E>(filter: F) -> Select<F, I, O, E>
+impl<P, OP, I, E> TakeUntil<P, OP, I, E> {
+    /// Leave the terminator in the input instead of consuming it.
+    ///
+    /// The terminator must still be found for this parser to succeed, and its output is still produced.
+    pub fn leave_terminator(self) -> Self {
+        Self {
+            consume_terminator: false,
+            ..self
+        }
+    }
+}
+
+/// A parser that consumes inputs until the given terminator parser matches, producing a slice of the inputs that
+/// were skipped along with the output of the terminator.
+///
+/// The terminator is tried at every position, starting with the current one, so the slice may be empty. If the end
+/// of the input is reached without the terminator matching, this parser fails. The terminator is consumed too,
+/// unless [`TakeUntil::leave_terminator`] is used.
+///
+/// This is the natural tool for comments, heredocs, and other regions with an arbitrary body, and is equivalent to
+/// (but cheaper and less fiddly than) `any().and_is(terminator.not()).repeated().to_slice().then(terminator)`.
+///
+/// The output type of this parser is `(I::Slice, OP)`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let comment = just::<_, _, extra::Err<Simple<char>>>("/*").ignore_then(take_until(just("*/")));
+///
+/// assert_eq!(comment.parse("/* hello * world */").into_result(), Ok((" hello * world ", "*/")));
+/// assert!(comment.parse("/* unterminated").has_errors());
+///
+/// // A heredoc whose terminator is either a newline or the end of input, which is left in place
+/// let line = take_until(text::newline().or(end())).leave_terminator();
+/// let lines = line
+///     .map(|(line, _)| line)
+///     .separated_by(text::newline::<_, extra::Err<Simple<char>>>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(lines.parse("a\nb c").into_result(), Ok(vec!["a", "b c"]));
+/// ```
+pub const fn take_until<'src, P, OP, I, E>(terminator: P) -> TakeUntil<P, OP, I, E>
 where
-    I: Input<'src>,
-    I::Token: Clone + 'src,
+    I: SliceInput<'src>,
     E: ParserExtra<'src, I>,
-    F: Fn(I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
+    P: Parser<'src, I, OP, E>,
 {
-    Select {
-        filter,
+    TakeUntil {
+        terminator,
+        consume_terminator: true,
         phantom: EmptyPhantom::new(),
     }
 }
 
-impl<'src, I, O, E, F> Parser<'src, I, O, E> for Select<F, I, O, E>
+impl<'src, P, OP, I, E> Parser<'src, I, (I::Slice, OP), E> for TakeUntil<P, OP, I, E>
 where
-    I: ValueInput<'src>,
-    I::Token: Clone + 'src,
+    I: SliceInput<'src>,
     E: ParserExtra<'src, I>,
-    F: Fn(I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
+    P: Parser<'src, I, OP, E>,
 {
     #[inline]
-    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
-        let before = inp.save();
-        let next = inp.next_inner();
-        let found = match next {
-            Some(tok) => {
-                match (self.filter)(tok.clone(), &mut MapExtra::new(before.cursor(), inp)) {
-                    Some(out) => return Ok(M::bind(|| out)),
-                    None => Some(tok.into()),
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, (I::Slice, OP)> {
+        let start = inp.cursor();
+        loop {
+            inp.safe_point()?;
+            let before = inp.save();
+            match self.terminator.go::<M>(inp) {
+                Ok(out) => {
+                    let slice = inp.slice(&start..before.cursor());
+                    if !self.consume_terminator {
+                        inp.rewind(before);
+                    }
+                    break Ok(M::map(out, |out| (slice, out)));
+                }
+                Err(()) => {
+                    inp.rewind(before);
+                    if inp.next_maybe_inner().is_none() {
+                        break Err(());
+                    }
                 }
             }
-            found => found.map(|f| f.into()),
-        };
-        let err_span = inp.span_since(before.cursor());
-        inp.rewind(before);
-        inp.add_alt([DefaultExpected::SomethingElse], found, err_span);
-        Err(())
+        }
     }
 
-    go_extra!(O);
+    go_extra!((I::Slice, OP));
 }
 
-/// See [`select_ref!`].
-pub struct SelectRef<F, I, O, E> {
-    filter: F,
+/// See [`balanced`].
+pub struct Balanced<T, S, OS, I, E> {
+    open: T,
+    close: T,
+    skip: Option<S>,
     #[allow(dead_code)]
-    phantom: EmptyPhantom<(E, O, I)>,
+    phantom: EmptyPhantom<(OS, I, E)>,
 }
 
-impl<F: Copy, I, O, E> Copy for SelectRef<F, I, O, E> {}
-impl<F: Clone, I, O, E> Clone for SelectRef<F, I, O, E> {
+impl<T: Copy, S: Copy, OS, I, E> Copy for Balanced<T, S, OS, I, E> {}
+impl<T: Clone, S: Clone, OS, I, E> Clone for Balanced<T, S, OS, I, E> {
     fn clone(&self) -> Self {
         Self {
-            filter: self.filter.clone(),
+            open: self.open.clone(),
+            close: self.close.clone(),
+            skip: self.skip.clone(),
             phantom: EmptyPhantom::new(),
         }
     }
 }
 
-/// See [`select_ref!`].
-pub const fn select_ref<'src, F, I, O, E>(filter: F) -> SelectRef<F, I, O, E>
-where
-    I: BorrowInput<'src>,
-    I::Token: 'src,
-    E: ParserExtra<'src, I>,
-    F: Fn(&'src I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
-{
-    SelectRef {
-        filter,
-        phantom: EmptyPhantom::new(),
+impl<T, S, OS, I, E> Balanced<T, S, OS, I, E> {
+    /// Skip over regions of the body matched by the given parser without inspecting the delimiters within them.
+    ///
+    /// The parser is tried before each token of the body. This is how string literals, character literals, and
+    /// comments are made opaque, so that a `}` inside `"..."` does not close the region. To skip several kinds of
+    /// region, combine them with [`Parser::or`] or [`choice`]. A skip parser that succeeds without consuming input is
+    /// treated as if it had failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let string = just('"').then(none_of('"').repeated()).then(just('"'));
+    /// let body = balanced::<_, extra::Err<Simple<char>>>('{', '}').skip(string);
+    ///
+    /// assert_eq!(body.parse(r#"{ "}" }"#).into_result(), Ok(r#" "}" "#));
+    /// ```
+    pub fn skip<S2, OS2>(self, skip: S2) -> Balanced<T, S2, OS2, I, E> {
+        Balanced {
+            open: self.open,
+            close: self.close,
+            skip: Some(skip),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Capture the body without parsing it, producing a [`Deferred`] that can parse it with the given parser later.
+    ///
+    /// This allows a first pass over a large input to skip the bodies of items (functions, say) and parse them only
+    /// when needed. Errors produced by [`Deferred::force`] have spans that point into the original input, not into
+    /// the captured slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, primitive::DeferredInput};
+    /// let body = text::int::<DeferredInput<&str>, extra::Err<Rich<_>>>(10)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>();
+    /// let item = text::ident()
+    ///     .padded()
+    ///     .then(balanced::<_, extra::Err<Rich<char>>>('{', '}').defer(body));
+    ///
+    /// let (name, deferred) = item.parse("foo { 1, 2, x }").into_result().unwrap();
+    /// assert_eq!(name, "foo");
+    /// assert_eq!(*deferred.slice(), " 1, 2, x ");
+    ///
+    /// // The error's span points at the `x` in the original input
+    /// let errs = deferred.force().into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(12..13));
+    /// ```
+    pub fn defer<B>(self, body: B) -> Defer<T, S, OS, B, I, E> {
+        Defer {
+            balanced: self,
+            body,
+        }
     }
 }
 
-impl<'src, I, O, E, F> Parser<'src, I, O, E> for SelectRef<F, I, O, E>
-where
-    I: BorrowInput<'src>,
+/// A parser that accepts an opening delimiter and then everything up to its matching closing delimiter, producing a
+/// slice of the body between them.
+///
+/// Nested delimiter pairs within the body are balanced, so `{ a { b } c }` is captured in full. Nothing else about
+/// the body is inspected, making this useful for macro-like constructs whose bodies are parsed later (or never). Use
+/// [`Balanced::skip`] to stop delimiters inside string literals or comments from being counted.
+///
+/// If the input ends before the matching delimiter is found, an error expecting the closing delimiter is produced at
+/// the end of the input.
+///
+/// The output type of this parser is `I::Slice`, excluding the delimiters themselves.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let body = balanced::<_, extra::Err<Simple<char>>>('{', '}');
+///
+/// assert_eq!(body.parse("{ a { b } c }").into_result(), Ok(" a { b } c "));
+/// assert_eq!(body.parse("{}").into_result(), Ok(""));
+/// // The closing delimiter of the outer pair is missing
+/// assert!(body.parse("{ a { b }").has_errors());
+/// ```
+pub const fn balanced<'src, I, E>(
+    open: I::Token,
+    close: I::Token,
+) -> Balanced<I::Token, Empty<I, E>, (), I, E>
+where
+    I: SliceInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq + Clone,
+{
+    Balanced {
+        open,
+        close,
+        skip: None,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, S, OS, I, E> Balanced<I::Token, S, OS, I, E>
+where
+    I: SliceInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq + Clone,
+    S: Parser<'src, I, OS, E>,
+{
+    /// Parse the opening delimiter and the body, leaving the input just before the matching closing delimiter.
+    /// Returns the cursor at the start of the body.
+    #[inline]
+    fn go_body<'parse>(
+        &self,
+        inp: &mut InputRef<'src, 'parse, I, E>,
+    ) -> Result<Cursor<'src, 'parse, I>, ()> {
+        let before = inp.save();
+        match inp.next_maybe_inner() {
+            Some(tok) if *tok.borrow() == self.open => {}
+            found => {
+                let span = inp.span_since(before.cursor());
+                inp.rewind(before);
+                inp.add_alt(
+                    [DefaultExpected::Token(MaybeRef::Val(self.open.clone()))],
+                    found.map(|f| f.into()),
+                    span,
+                );
+                return Err(());
+            }
+        }
+
+        let start = inp.cursor();
+        let mut depth = 1usize;
+        loop {
+            inp.safe_point()?;
+            let before = inp.save();
+            if let Some(skip) = &self.skip {
+                match skip.go::<Check>(inp) {
+                    Ok(()) if inp.cursor() > *before.cursor() => continue,
+                    _ => inp.rewind(before.clone()),
+                }
+            }
+
+            match inp.next_maybe_inner() {
+                // Checked first, so that identical delimiters like `|...|` don't nest forever
+                Some(tok) if *tok.borrow() == self.close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        inp.rewind(before);
+                        return Ok(start);
+                    }
+                }
+                Some(tok) if *tok.borrow() == self.open => depth += 1,
+                Some(_) => {}
+                None => {
+                    let span = inp.span_since(before.cursor());
+                    inp.add_alt(
+                        [DefaultExpected::Token(MaybeRef::Val(self.close.clone()))],
+                        None,
+                        span,
+                    );
+                    return Err(());
+                }
+            }
+        }
+    }
+}
+
+impl<'src, S, OS, I, E> Parser<'src, I, I::Slice, E> for Balanced<I::Token, S, OS, I, E>
+where
+    I: SliceInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq + Clone,
+    S: Parser<'src, I, OS, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, I::Slice> {
+        let start = self.go_body(inp)?;
+        let out = M::bind(|| inp.slice_since(&start..));
+        inp.next_maybe_inner();
+        Ok(out)
+    }
+
+    go_extra!(I::Slice);
+}
+
+/// See [`Balanced::defer`].
+pub struct Defer<T, S, OS, B, I, E> {
+    balanced: Balanced<T, S, OS, I, E>,
+    body: B,
+}
+
+impl<T: Copy, S: Copy, OS, B: Copy, I, E> Copy for Defer<T, S, OS, B, I, E> {}
+impl<T: Clone, S: Clone, OS, B: Clone, I, E> Clone for Defer<T, S, OS, B, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            balanced: self.balanced.clone(),
+            body: self.body.clone(),
+        }
+    }
+}
+
+impl<'src, S, OS, B, I, E> Parser<'src, I, Deferred<'src, I, B>, E>
+    for Defer<I::Token, S, OS, B, I, E>
+where
+    I: SliceInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: PartialEq + Clone,
+    S: Parser<'src, I, OS, E>,
+    B: Clone,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, Deferred<'src, I, B>> {
+        let start = self.balanced.go_body(inp)?;
+        let out = M::bind(|| Deferred {
+            slice: inp.slice_since(&start..),
+            span: inp.span_since(&start),
+            parser: self.body.clone(),
+        });
+        inp.next_maybe_inner();
+        Ok(out)
+    }
+
+    go_extra!(Deferred<'src, I, B>);
+}
+
+/// The input type that the body parser of a [`Deferred`] region runs against.
+///
+/// This is the captured slice, with its spans translated back into spans of the original input.
+pub type DeferredInput<'src, I> = MappedSpan<
+    <I as Input<'src>>::Span,
+    <I as SliceInput<'src>>::Slice,
+    Box<dyn Fn(SimpleSpan) -> <I as Input<'src>>::Span + 'src>,
+>;
+
+/// A region of input that was captured by [`Balanced::defer`], but not yet parsed.
+///
+/// Call [`Deferred::force`] to parse it.
+pub struct Deferred<'src, I: SliceInput<'src>, P> {
+    slice: I::Slice,
+    span: I::Span,
+    parser: P,
+}
+
+impl<'src, I: SliceInput<'src>, P: Clone> Clone for Deferred<'src, I, P>
+where
+    I::Span: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            slice: self.slice.clone(),
+            span: self.span.clone(),
+            parser: self.parser.clone(),
+        }
+    }
+}
+
+impl<'src, I: SliceInput<'src>, P> fmt::Debug for Deferred<'src, I, P>
+where
+    I::Slice: fmt::Debug,
+    I::Span: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deferred")
+            .field("slice", &self.slice)
+            .field("span", &self.span)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'src, I: SliceInput<'src>, P> Deferred<'src, I, P> {
+    /// The unparsed input of this region.
+    pub fn slice(&self) -> &I::Slice {
+        &self.slice
+    }
+
+    /// The span of this region within the original input.
+    pub fn span(&self) -> &I::Span {
+        &self.span
+    }
+
+    /// Parse this region, producing spans (including those of any errors) relative to the original input.
+    ///
+    /// The region is parsed independently each time this is called, so cache the result if it is needed more than
+    /// once.
+    pub fn force<O, E>(&self) -> ParseResult<O, E::Error>
+    where
+        I::Slice: Input<'src, Span = SimpleSpan>,
+        I::Span: Span<Offset = usize> + Clone + 'src,
+        <I::Span as Span>::Context: Clone + 'src,
+        E: ParserExtra<'src, DeferredInput<'src, I>>,
+        E::State: Default,
+        E::Context: Default,
+        P: Parser<'src, DeferredInput<'src, I>, O, E>,
+    {
+        self.force_with_state(&mut E::State::default())
+    }
+
+    /// Parse this region with the given state. See [`Deferred::force`].
+    pub fn force_with_state<O, E>(&self, state: &mut E::State) -> ParseResult<O, E::Error>
+    where
+        I::Slice: Input<'src, Span = SimpleSpan>,
+        I::Span: Span<Offset = usize> + Clone + 'src,
+        <I::Span as Span>::Context: Clone + 'src,
+        E: ParserExtra<'src, DeferredInput<'src, I>>,
+        E::Context: Default,
+        P: Parser<'src, DeferredInput<'src, I>, O, E>,
+    {
+        let region = self.span.clone();
+        let map_span: Box<dyn Fn(SimpleSpan) -> I::Span + 'src> = Box::new(move |span| {
+            let offset = region.start();
+            I::Span::new(region.context(), offset + span.start..offset + span.end)
+        });
+        self.parser
+            .parse_with_state(self.slice.clone().map_span(map_span), state)
+    }
+
+    /// Parse many regions in parallel, using `rayon`'s global thread pool.
+    ///
+    /// Each region is parsed with its own default state. The outputs are returned in the same order as the regions,
+    /// and errors are merged in region order (and, within a region, in the order they were produced), so the result
+    /// does not depend on how the work was scheduled.
+    ///
+    /// The body parser must be [`Sync`]. Parsers built with [`recursive`] are only `Sync` when the `sync` feature is
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, primitive::{Deferred, DeferredInput}};
+    /// let body = text::int::<DeferredInput<&str>, extra::Err<Rich<_>>>(10)
+    ///     .from_str::<u32>()
+    ///     .unwrapped()
+    ///     .padded();
+    /// let items = balanced::<_, extra::Err<Rich<char>>>('{', '}')
+    ///     .defer(body)
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let regions = items.parse("{ 1 } { x } { 3 } { y }").into_result().unwrap();
+    /// let (outputs, errors) = Deferred::force_all(&regions);
+    ///
+    /// assert_eq!(outputs, vec![Some(1), None, Some(3), None]);
+    /// assert_eq!(
+    ///     errors.iter().map(|e| *e.span()).collect::<Vec<_>>(),
+    ///     vec![SimpleSpan::from(8..9), SimpleSpan::from(20..21)],
+    /// );
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn force_all<O, E>(regions: &[Self]) -> (Vec<Option<O>>, Vec<E::Error>)
+    where
+        Self: Sync,
+        I::Slice: Input<'src, Span = SimpleSpan>,
+        I::Span: Span<Offset = usize> + Clone + 'src,
+        <I::Span as Span>::Context: Clone + 'src,
+        E: ParserExtra<'src, DeferredInput<'src, I>>,
+        E::State: Default,
+        E::Context: Default,
+        E::Error: Send,
+        P: Parser<'src, DeferredInput<'src, I>, O, E>,
+        O: Send,
+    {
+        use rayon::prelude::*;
+
+        let results = regions
+            .par_iter()
+            .map(|region| region.force::<O, E>().into_output_errors())
+            .collect::<Vec<_>>();
+
+        let mut outputs = Vec::with_capacity(results.len());
+        let mut errors = Vec::new();
+        for (output, errs) in results {
+            outputs.push(output);
+            errors.extend(errs);
+        }
+        (outputs, errors)
+    }
+}
+
+/// See [`custom`].
+pub struct Custom<F, I, O, E> {
+    f: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, O, I)>,
+}
+
+impl<F: Copy, I, O, E> Copy for Custom<F, I, O, E> {}
+impl<F: Clone, I, O, E> Clone for Custom<F, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// TODO
+///
+/// # Example
+///
+/// ```
+/// # use chumsky::{prelude::*, error::Simple};
+///
+/// let x = custom::<_, &str, _, extra::Err<Simple<char>>>(|inp| {
+///     let _ = inp.next();
+///     Ok(())
+/// });
+///
+/// assert_eq!(x.parse("!").into_result(), Ok(()));
+/// ```
+pub const fn custom<'src, F, I, O, E>(f: F) -> Custom<F, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    F: Fn(&mut InputRef<'src, '_, I, E>) -> Result<O, E::Error>,
+{
+    Custom {
+        f,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, F> Parser<'src, I, O, E> for Custom<F, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    F: Fn(&mut InputRef<'src, '_, I, E>) -> Result<O, E::Error>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.cursor();
+        match (self.f)(inp) {
+            Ok(out) => Ok(M::bind(|| out)),
+            Err(err) => {
+                inp.add_alt_err(&before.inner, err);
+                Err(())
+            }
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// See [`select!`].
+pub struct Select<F, I, O, E> {
+    filter: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, O, I)>,
+}
+
+impl<F: Copy, I, O, E> Copy for Select<F, I, O, E> {}
+impl<F: Clone, I, O, E> Clone for Select<F, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            filter: self.filter.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// See [`select!`].
+pub const fn select<'src, F, I, O, E>(filter: F) -> Select<F, I, O, E>
+where
+    I: Input<'src>,
+    I::Token: Clone + 'src,
+    E: ParserExtra<'src, I>,
+    F: Fn(I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
+{
+    Select {
+        filter,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, F> Parser<'src, I, O, E> for Select<F, I, O, E>
+where
+    I: ValueInput<'src>,
+    I::Token: Clone + 'src,
+    E: ParserExtra<'src, I>,
+    F: Fn(I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let next = inp.next_inner();
+        let found = match next {
+            Some(tok) => {
+                match (self.filter)(tok.clone(), &mut MapExtra::new(before.cursor(), inp)) {
+                    Some(out) => return Ok(M::bind(|| out)),
+                    None => Some(tok.into()),
+                }
+            }
+            found => found.map(|f| f.into()),
+        };
+        let err_span = inp.span_since(before.cursor());
+        inp.rewind(before);
+        inp.add_alt([DefaultExpected::SomethingElse], found, err_span);
+        Err(())
+    }
+
+    go_extra!(O);
+}
+
+impl<'src, I, O, E, F> NonEmptyParser<'src, I, O, E> for Select<F, I, O, E>
+where
+    I: ValueInput<'src>,
+    I::Token: Clone + 'src,
+    E: ParserExtra<'src, I>,
+    F: Fn(I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
+{
+}
+
+/// See [`select_ref!`].
+pub struct SelectRef<F, I, O, E> {
+    filter: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, O, I)>,
+}
+
+impl<F: Copy, I, O, E> Copy for SelectRef<F, I, O, E> {}
+impl<F: Clone, I, O, E> Clone for SelectRef<F, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            filter: self.filter.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// See [`select_ref!`].
+pub const fn select_ref<'src, F, I, O, E>(filter: F) -> SelectRef<F, I, O, E>
+where
+    I: BorrowInput<'src>,
+    I::Token: 'src,
+    E: ParserExtra<'src, I>,
+    F: Fn(&'src I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
+{
+    SelectRef {
+        filter,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, F> Parser<'src, I, O, E> for SelectRef<F, I, O, E>
+where
+    I: BorrowInput<'src>,
     I::Token: 'src,
     E: ParserExtra<'src, I>,
     F: Fn(&'src I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
@@ -539,6 +1354,15 @@ where
     go_extra!(O);
 }
 
+impl<'src, I, O, E, F> NonEmptyParser<'src, I, O, E> for SelectRef<F, I, O, E>
+where
+    I: BorrowInput<'src>,
+    I::Token: 'src,
+    E: ParserExtra<'src, I>,
+    F: Fn(&'src I::Token, &mut MapExtra<'src, '_, I, E>) -> Option<O>,
+{
+}
+
 /// See [`any`].
 pub struct Any<I, E> {
     #[allow(dead_code)]
@@ -571,9 +1395,20 @@ where
         }
     }
 
+    fn introspect(&self, _cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new("Any", introspect::NodeKind::Any, Vec::new())
+    }
+
     go_extra!(I::Token);
 }
 
+impl<'src, I, E> NonEmptyParser<'src, I, I::Token, E> for Any<I, E>
+where
+    I: ValueInput<'src>,
+    E: ParserExtra<'src, I>,
+{
+}
+
 /// A parser that accepts any input (but not the end of input).
 ///
 /// The output type of this parser is `I::Token`, the input that was found.
@@ -630,6 +1465,13 @@ where
     go_extra!(&'src I::Token);
 }
 
+impl<'src, I, E> NonEmptyParser<'src, I, &'src I::Token, E> for AnyRef<I, E>
+where
+    I: BorrowInput<'src>,
+    E: ParserExtra<'src, I>,
+{
+}
+
 /// A parser that accepts any input (but not the end of input).
 ///
 /// The output type of this parser is `&'src I::Token`, the input that was found.
@@ -692,6 +1534,9 @@ where
 
 /// Apply a mapping function to the context of this parser.
 ///
+/// [`Parser::map_ctx`] is usually more convenient, since it does not need the extra types to be spelled out. See its
+/// documentation for how contexts interact with backtracking.
+///
 /// Note that this combinator will behave differently from all other maps, in terms of which
 /// parsers it effects - while other maps apply to the output of the parser, and thus read left-to-right, this one
 /// applies to the _input_ of the parser, and as such applies right-to-left.
@@ -760,6 +1605,285 @@ where
     }
 }
 
+/// See [`length_prefixed`].
+pub struct LengthPrefixed<L, B, EB> {
+    len: L,
+    body: B,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<EB>,
+}
+
+impl<L: Copy, B: Copy, EB> Copy for LengthPrefixed<L, B, EB> {}
+impl<L: Clone, B: Clone, EB> Clone for LengthPrefixed<L, B, EB> {
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len.clone(),
+            body: self.body.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'src, I, O, E, EB, L, B> Parser<'src, I, O, E> for LengthPrefixed<L, B, EB>
+where
+    I: SliceInput<'src>,
+    I::Slice: Input<'src>,
+    E: ParserExtra<'src, I>,
+    EB: ParserExtra<'src, I::Slice, State = E::State, Context = E::Context, Error = E::Error>,
+    L: Parser<'src, I, usize, E>,
+    B: Parser<'src, I::Slice, O, EB>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let len = self.len.go::<Emit>(inp)?;
+
+        // Find the end of the frame
+        let before = inp.cursor();
+        let base_offset = inp.base_offset + I::cursor_location(&before.inner);
+        skip_tokens(inp, len)?;
+        let frame = inp.slice_since(&before..);
+
+        let alt = inp.errors.alt.take();
+
+        #[cfg(feature = "memoization")]
+        let mut memos = HashMap::default();
+        let (start, mut cache) = frame.begin();
+        let res = inp.with_input(
+            start,
+            &mut cache,
+            &mut Default::default(),
+            |inp| {
+                inp.base_offset = base_offset;
+                (&self.body).then_ignore(end()).go::<M>(inp)
+            },
+            #[cfg(feature = "memoization")]
+            &mut memos,
+        );
+
+        let new_alt = inp.errors.alt.take();
+        inp.errors.alt = alt;
+        if let Some(new_alt) = new_alt {
+            inp.add_alt_err(&inp.cursor().inner, new_alt.err);
+        }
+
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// Parse a length-delimited frame: `len` is parsed first, then the following `len` tokens of the input are parsed by
+/// `body`, as their own input.
+///
+/// This is the backbone of most binary formats, such as type-length-value records and protobuf-like messages. The
+/// body sees the end of the frame as the end of its input, so it may use parsers like [`Parser::repeated`] to consume
+/// the whole frame. It is an error for the input to end before the frame does, for the body to try to read past the
+/// end of the frame, or for the body to leave part of the frame unconsumed.
+///
+/// As with [`Parser::nested_in`], the parser state and context are shared with the body, and spans within the body are
+/// relative to the start of the frame.
+///
+/// The output type of this parser is `O`, the output of the body.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// // A frame is a length byte followed by a big-endian `u16`
+/// let frame = length_prefixed(
+///     any::<&[u8], extra::Default>().map(usize::from),
+///     any::<&[u8], extra::Default>()
+///         .then(any())
+///         .map(|(hi, lo)| u16::from_be_bytes([hi, lo])),
+/// );
+///
+/// assert_eq!(frame.parse(&[2, 1, 0]).into_result(), Ok(256));
+/// // The body does not consume the whole frame
+/// assert!(frame.parse(&[3, 1, 0, 0]).has_errors());
+/// // The body reads past the end of the frame
+/// assert!(frame.parse(&[1, 1, 0]).has_errors());
+/// // The input ends before the frame does
+/// assert!(frame.parse(&[4, 1, 0]).has_errors());
+///
+/// // Bodies of variable length consume the whole frame
+/// let frames = length_prefixed(
+///     any::<&[u8], extra::Default>().map(usize::from),
+///     any::<&[u8], extra::Default>().repeated().collect::<Vec<_>>(),
+/// )
+/// .repeated()
+/// .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     frames.parse(&[2, 7, 8, 0, 1, 9]).into_result(),
+///     Ok(vec![vec![7, 8], vec![], vec![9]]),
+/// );
+/// ```
+pub const fn length_prefixed<'src, L, B, I, O, E, EB>(len: L, body: B) -> LengthPrefixed<L, B, EB>
+where
+    I: SliceInput<'src>,
+    I::Slice: Input<'src>,
+    E: ParserExtra<'src, I>,
+    EB: ParserExtra<'src, I::Slice>,
+    L: Parser<'src, I, usize, E>,
+    B: Parser<'src, I::Slice, O, EB>,
+{
+    LengthPrefixed {
+        len,
+        body,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+// Skip `count` tokens, failing if the input ends first
+#[inline]
+fn skip_tokens<'src, I, E>(inp: &mut InputRef<'src, '_, I, E>, count: usize) -> Result<(), ()>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    for _ in 0..count {
+        let before = inp.cursor();
+        if inp.next_maybe_inner().is_none() {
+            let span = inp.span_since(&before);
+            inp.add_alt([DefaultExpected::Any], None, span);
+            return Err(());
+        }
+    }
+    Ok(())
+}
+
+/// See [`align_to`].
+pub struct AlignTo<I, E> {
+    align: usize,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(I, E)>,
+}
+
+impl<I, E> Copy for AlignTo<I, E> {}
+impl<I, E> Clone for AlignTo<I, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// A parser that skips padding bytes until the absolute offset within the input is a multiple of `align`.
+///
+/// Structured binary formats (such as ELF files or wasm sections) often align their fields. The padding is relative
+/// to the start of the whole input, even within the frames of [`length_prefixed`], not the start of the current
+/// structure, and no bytes are skipped when the input is already aligned. The values of the padding bytes are not
+/// checked. It is an error for the input to end within the padding.
+///
+/// The output type of this parser is `()`.
+///
+/// # Panics
+///
+/// Panics if `align` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, primitive::align_to};
+/// // A tag byte, followed by a little-endian `u32` aligned to 4 bytes
+/// let field = any::<&[u8], extra::Default>().then_ignore(align_to(4)).then(
+///     any()
+///         .repeated()
+///         .collect_exactly()
+///         .map(u32::from_le_bytes),
+/// );
+///
+/// assert_eq!(field.parse(&[7, 0, 0, 0, 1, 0, 0, 0]).into_result(), Ok((7, 1)));
+/// assert!(field.parse(&[7, 0, 0]).has_errors());
+///
+/// // Alignment is relative to the start of the input
+/// let fields = field.repeated().collect::<Vec<_>>();
+/// assert_eq!(
+///     fields.parse(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0]).into_result(),
+///     Ok(vec![(1, 2), (3, 4)]),
+/// );
+/// ```
+pub const fn align_to<'src, I, E>(align: usize) -> AlignTo<I, E>
+where
+    I: Input<'src, Token = u8>,
+    I::Span: Span<Offset = usize>,
+    E: ParserExtra<'src, I>,
+{
+    assert!(align > 0, "alignment must be non-zero");
+    AlignTo {
+        align,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, E> Parser<'src, I, (), E> for AlignTo<I, E>
+where
+    I: Input<'src, Token = u8>,
+    I::Span: Span<Offset = usize>,
+    E: ParserExtra<'src, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, ()> {
+        let misalignment = (inp.base_offset + inp.offset()) % self.align;
+        let count = (self.align - misalignment) % self.align;
+        skip_tokens(inp, count).map(|()| M::bind(|| ()))
+    }
+
+    go_extra!(());
+}
+
+/// See [`pad_bytes`].
+pub struct PadBytes<I, E> {
+    count: usize,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(I, E)>,
+}
+
+impl<I, E> Copy for PadBytes<I, E> {}
+impl<I, E> Clone for PadBytes<I, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// A parser that skips exactly `count` padding bytes, whatever their values.
+///
+/// It is an error for the input to end within the padding. See also [`align_to`].
+///
+/// The output type of this parser is `()`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, primitive::pad_bytes};
+/// // Two bytes, separated by three reserved bytes
+/// let pair = any::<&[u8], extra::Default>().then_ignore(pad_bytes(3)).then(any());
+///
+/// assert_eq!(pair.parse(&[1, 0xff, 0xff, 0xff, 2]).into_result(), Ok((1, 2)));
+/// assert!(pair.parse(&[1, 0xff, 0xff]).has_errors());
+/// ```
+pub const fn pad_bytes<'src, I, E>(count: usize) -> PadBytes<I, E>
+where
+    I: Input<'src, Token = u8>,
+    E: ParserExtra<'src, I>,
+{
+    PadBytes {
+        count,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, E> Parser<'src, I, (), E> for PadBytes<I, E>
+where
+    I: Input<'src, Token = u8>,
+    E: ParserExtra<'src, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, ()> {
+        skip_tokens(inp, self.count).map(|()| M::bind(|| ()))
+    }
+
+    go_extra!(());
+}
+
 /// See [`fn@todo`].
 pub struct Todo<I, O, E> {
     location: Location<'static>,
@@ -901,13 +2025,19 @@ macro_rules! impl_choice_for_tuple {
                 let Choice { parsers: ($Head, $($X,)*), .. } = self;
 
                 match $Head.go::<M>(inp) {
-                    Ok(out) => return Ok(out),
+                    Ok(out) => {
+                        inp.commit(before);
+                        return Ok(out);
+                    }
                     Err(()) => inp.rewind(before.clone()),
                 }
 
                 $(
                     match $X.go::<M>(inp) {
-                        Ok(out) => return Ok(out),
+                        Ok(out) => {
+                            inp.commit(before);
+                            return Ok(out);
+                        }
                         Err(()) => inp.rewind(before.clone()),
                     }
                 )*
@@ -915,6 +2045,15 @@ macro_rules! impl_choice_for_tuple {
                 Err(())
             }
 
+            fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+                let Choice { parsers: ($Head, $($X,)*), .. } = self;
+                introspect::Node::new(
+                    "Choice",
+                    introspect::NodeKind::Choice,
+                    vec![$Head.introspect(cx), $($X.introspect(cx)),*],
+                )
+            }
+
             go_extra!(O);
         }
     };
@@ -930,6 +2069,14 @@ macro_rules! impl_choice_for_tuple {
                 self.parsers.0.go::<M>(inp)
             }
 
+            fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+                introspect::Node::new(
+                    "Choice",
+                    introspect::NodeKind::Choice,
+                    vec![self.parsers.0.introspect(cx)],
+                )
+            }
+
             go_extra!(O);
         }
     };
@@ -955,6 +2102,7 @@ where
             for parser in self.parsers.iter() {
                 inp.rewind(before.clone());
                 if let Ok(out) = parser.go::<M>(inp) {
+                    inp.commit(before);
                     return Ok(out);
                 }
             }
@@ -962,6 +2110,17 @@ where
         }
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Choice",
+            introspect::NodeKind::Choice,
+            self.parsers
+                .iter()
+                .map(|parser| parser.introspect(cx))
+                .collect(),
+        )
+    }
+
     go_extra!(O);
 }
 
@@ -975,6 +2134,17 @@ where
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
         choice(&self.parsers[..]).go::<M>(inp)
     }
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Choice",
+            introspect::NodeKind::Choice,
+            self.parsers
+                .iter()
+                .map(|parser| parser.introspect(cx))
+                .collect(),
+        )
+    }
+
     go_extra!(O);
 }
 
@@ -988,6 +2158,512 @@ where
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
         choice(&self.parsers[..]).go::<M>(inp)
     }
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::Node::new(
+            "Choice",
+            introspect::NodeKind::Choice,
+            self.parsers
+                .iter()
+                .map(|parser| parser.introspect(cx))
+                .collect(),
+        )
+    }
+
+    go_extra!(O);
+}
+
+/// See [`dispatch!`].
+pub struct Dispatch<F, T, I> {
+    select: F,
+    parsers: T,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<I>,
+}
+
+impl<F: Copy, T: Copy, I> Copy for Dispatch<F, T, I> {}
+impl<F: Clone, T: Clone, I> Clone for Dispatch<F, T, I> {
+    fn clone(&self) -> Self {
+        Self {
+            select: self.select.clone(),
+            parsers: self.parsers.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Create a parser that peeks at the next token and uses `select` to pick which of the `parsers` (a tuple) to run.
+///
+/// `select` should return the index of the parser to run, or `None` if no parser can start with the token. You
+/// probably want to use the [`dispatch!`] macro instead of calling this function directly.
+///
+/// # Panics
+///
+/// In debug builds, the parser panics if `select` returns an index that is out of range for `parsers`. In release
+/// builds, such an index is treated like `None`.
+pub const fn dispatch<'src, F, T, I>(select: F, parsers: T) -> Dispatch<F, T, I>
+where
+    I: Input<'src>,
+    F: Fn(&I::Token) -> Option<usize>,
+{
+    Dispatch {
+        select,
+        parsers,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+macro_rules! impl_dispatch_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_dispatch_for_tuple!($($X)*);
+        impl_dispatch_for_tuple!(~ $head $($X)*);
+    };
+    (~ $Head:ident $($X:ident)*) => {
+        #[allow(unused_variables, unused_mut, unused_assignments, non_snake_case)]
+        impl<'src, I, E, F, $Head, $($X,)* O> Parser<'src, I, O, E> for Dispatch<F, ($Head, $($X,)*), I>
+        where
+            I: Input<'src>,
+            E: ParserExtra<'src, I>,
+            F: Fn(&I::Token) -> Option<usize>,
+            $Head: Parser<'src, I, O, E>,
+            $($X: Parser<'src, I, O, E>),*
+        {
+            #[inline]
+            fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+                let before = inp.save();
+                let found = inp.next_maybe();
+                let err_span = inp.span_since(before.cursor());
+                inp.rewind(before.clone());
+
+                let Dispatch { parsers: ($Head, $($X,)*), .. } = self;
+
+                if let Some(mut idx) = found.as_deref().and_then(&self.select) {
+                    if idx == 0 {
+                        return $Head.go::<M>(inp);
+                    }
+                    $(
+                        idx -= 1;
+                        if idx == 0 {
+                            return $X.go::<M>(inp);
+                        }
+                    )*
+                    debug_assert!(false, "dispatch selector returned an out-of-range parser index");
+                }
+
+                // No arm was selected, so find out what each arm expected here to report the same error as `choice`
+                // would. Only the failure path pays for this.
+                let old_alt = inp.take_alt();
+                let _ = $Head.go::<Check>(inp);
+                inp.rewind(before.clone());
+                $(
+                    let _ = $X.go::<Check>(inp);
+                    inp.rewind(before.clone());
+                )*
+                let arms_alt = inp.take_alt().filter(|alt| {
+                    I::cursor_location(&alt.pos) == I::cursor_location(&before.cursor().inner)
+                });
+                inp.errors.alt = old_alt;
+
+                match arms_alt {
+                    Some(alt) => inp.add_alt_err(&alt.pos, alt.err),
+                    None => inp.add_alt([DefaultExpected::SomethingElse], found, err_span),
+                }
+                Err(())
+            }
+
+            go_extra!(O);
+        }
+    };
+}
+
+impl_dispatch_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+/// See [`switch_on`].
+pub struct SwitchOn<A, F, T, P> {
+    tag: A,
+    select: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(T, P)>,
+}
+
+impl<A: Copy, F: Copy, T, P> Copy for SwitchOn<A, F, T, P> {}
+impl<A: Clone, F: Clone, T, P> Clone for SwitchOn<A, F, T, P> {
+    fn clone(&self) -> Self {
+        Self {
+            tag: self.tag.clone(),
+            select: self.select.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Create a parser that parses a tag with `tag`, then uses `select` to pick the parser for the rest of the record.
+///
+/// This is intended for binary formats and bytecode, in which a discriminant (such as an opcode byte) determines the
+/// layout of what follows. Unlike [`choice`], alternatives are never tried in turn: once the tag has been parsed, only
+/// the selected parser is run, and no backtracking occurs if it fails. Unlike [`dispatch!`], the tag is consumed, and
+/// may be produced by any parser (such as a multi-byte integer).
+///
+/// `select` is given the tag, and returns the parser to run, or `None` if the tag is not recognised, in which case an
+/// error is produced at the tag. Since every parser returned by `select` must have the same type, the variant parsers
+/// are usually built ahead of time as [`Boxed`] or [`Erased`] parsers, which are cheap to copy or clone.
+///
+/// The output type of this parser is `O`, the output of the selected parser.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Instr {
+///     Nop,
+///     Push(u8),
+///     Jump(u16),
+/// }
+///
+/// type Extra<'src> = extra::Err<Rich<'src, u8>>;
+///
+/// let nop = empty::<&[u8], Extra>().to(Instr::Nop);
+/// let push = any::<&[u8], Extra>().map(Instr::Push);
+/// let jump = any::<&[u8], Extra>()
+///     .then(any())
+///     .map(|(lo, hi)| Instr::Jump(u16::from_le_bytes([lo, hi])));
+///
+/// let (nop, push, jump) = (nop.erased(), push.erased(), jump.erased());
+/// let instr = switch_on(any(), |opcode: &u8| match opcode {
+///     0 => Some(nop),
+///     1 => Some(push),
+///     2 => Some(jump),
+///     _ => None,
+/// });
+///
+/// let program = instr.repeated().collect::<Vec<_>>();
+/// assert_eq!(
+///     program.parse(&[1, 7, 0, 2, 0, 1]).into_result(),
+///     Ok(vec![Instr::Push(7), Instr::Nop, Instr::Jump(256)]),
+/// );
+///
+/// // Unknown opcodes are reported at the tag
+/// let errs = program.parse(&[0, 9, 1]).into_errors();
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(1..2));
+/// ```
+pub const fn switch_on<'src, A, F, T, P, I, O, E>(tag: A, select: F) -> SwitchOn<A, F, T, P>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, T, E>,
+    F: Fn(&T) -> Option<P>,
+    P: Parser<'src, I, O, E>,
+{
+    SwitchOn {
+        tag,
+        select,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, A, F, T, P> Parser<'src, I, O, E> for SwitchOn<A, F, T, P>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, T, E>,
+    F: Fn(&T) -> Option<P>,
+    P: Parser<'src, I, O, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let tag = self.tag.go::<Emit>(inp)?;
+
+        match (self.select)(&tag) {
+            Some(parser) => parser.go::<M>(inp),
+            None => {
+                let err_span = inp.span_since(before.cursor());
+                inp.rewind(before);
+                let found = inp.peek_maybe();
+                inp.add_alt([DefaultExpected::SomethingElse], found, err_span);
+                Err(())
+            }
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// See [`disambiguate`].
+pub struct Disambiguate<P, F, A, B, OP> {
+    probe: P,
+    decide: F,
+    if_true: A,
+    if_false: B,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<OP>,
+}
+
+impl<P: Copy, F: Copy, A: Copy, B: Copy, OP> Copy for Disambiguate<P, F, A, B, OP> {}
+impl<P: Clone, F: Clone, A: Clone, B: Clone, OP> Clone for Disambiguate<P, F, A, B, OP> {
+    fn clone(&self) -> Self {
+        Self {
+            probe: self.probe.clone(),
+            decide: self.decide.clone(),
+            if_true: self.if_true.clone(),
+            if_false: self.if_false.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Create a parser that decides between two parses of the same input by consulting the parser state.
+///
+/// This solves the class of problems known as the
+/// ['lexer hack'](https://en.wikipedia.org/wiki/Lexer_hack): in C-like languages, `(a) * b` is a cast if `a` names
+/// a type, and a multiplication otherwise, so the grammar alone cannot tell the two apart.
+///
+/// The `probe` parser is first run as lookahead (the input is rewound after it succeeds), and its output is given to
+/// `decide` along with the current state. If `decide` returns `true`, `if_true` is run from the original position;
+/// otherwise, `if_false` is. The other parser is never tried, even if the chosen parser fails, so errors always come
+/// from the parse that the state called for. If `probe` fails, the whole parser fails.
+///
+/// State is usually a [`SymbolTable`](crate::extra::SymbolTable) that is filled in by earlier declarations.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, extra::SymbolTable};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr<'src> {
+///     Var(&'src str),
+///     Cast(&'src str, Box<Self>),
+///     Mul(Box<Self>, Box<Self>),
+/// }
+///
+/// type Extra<'src> = extra::State<SymbolTable<&'src str, ()>>;
+///
+/// let ident = text::ascii::ident::<_, Extra>().padded();
+/// let var = ident.map(Expr::Var);
+/// let parens = ident.delimited_by(just('('), just(')'));
+///
+/// // `(a) * b` is either a cast of a dereference or a multiplication, depending on whether `a` is a type
+/// let cast = parens
+///     .then(just('*').padded().ignore_then(var))
+///     .map(|(ty, e)| Expr::Cast(ty, Box::new(e)));
+/// let mul = parens
+///     .map(Expr::Var)
+///     .then(just('*').padded().ignore_then(var))
+///     .map(|(l, r)| Expr::Mul(Box::new(l), Box::new(r)));
+/// let expr = disambiguate(
+///     parens,
+///     |name, types: &SymbolTable<_, _>| types.contains(name),
+///     cast,
+///     mul,
+/// );
+///
+/// let typedef = text::ascii::keyword("typedef")
+///     .ignore_then(ident)
+///     .then_ignore(just(';').padded())
+///     .map_with(|name, e| e.state().declare(name, ()));
+/// let parser = typedef
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .then(expr)
+///     .map(|(_, expr)| expr);
+///
+/// assert_eq!(
+///     parser.parse_with_state("typedef a; (a) * b", &mut SymbolTable::new()).into_result(),
+///     Ok(Expr::Cast("a", Box::new(Expr::Var("b")))),
+/// );
+/// assert_eq!(
+///     parser.parse_with_state("(a) * b", &mut SymbolTable::new()).into_result(),
+///     Ok(Expr::Mul(Box::new(Expr::Var("a")), Box::new(Expr::Var("b")))),
+/// );
+/// ```
+pub const fn disambiguate<'src, P, F, A, B, OP, I, E>(
+    probe: P,
+    decide: F,
+    if_true: A,
+    if_false: B,
+) -> Disambiguate<P, F, A, B, OP>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    P: Parser<'src, I, OP, E>,
+    F: Fn(&OP, &E::State) -> bool,
+{
+    Disambiguate {
+        probe,
+        decide,
+        if_true,
+        if_false,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, P, F, A, B, OP> Parser<'src, I, O, E> for Disambiguate<P, F, A, B, OP>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    P: Parser<'src, I, OP, E>,
+    F: Fn(&OP, &E::State) -> bool,
+    A: Parser<'src, I, O, E>,
+    B: Parser<'src, I, O, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        // The probe's output is needed to make the decision, whatever mode we're parsing in
+        let probed = self.probe.go::<Emit>(inp)?;
+        inp.rewind(before);
+
+        if (self.decide)(&probed, inp.state()) {
+            self.if_true.go::<M>(inp)
+        } else {
+            self.if_false.go::<M>(inp)
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// See [`postfix_chain`].
+pub struct PostfixChain<B, S, F, OS> {
+    base: B,
+    segment: S,
+    fold: F,
+    #[cfg(debug_assertions)]
+    location: Location<'static>,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<OS>,
+}
+
+impl<B: Copy, S: Copy, F: Copy, OS> Copy for PostfixChain<B, S, F, OS> {}
+impl<B: Clone, S: Clone, F: Clone, OS> Clone for PostfixChain<B, S, F, OS> {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            segment: self.segment.clone(),
+            fold: self.fold.clone(),
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Create a parser that parses a `base` followed by any number of `segment`s, folding each segment into a
+/// left-nested chain.
+///
+/// This is intended for access chains like `a.b[c](d, e).f`, in which each segment (a field access, an index, a
+/// call, etc.) applies to everything before it. Different kinds of segment are usually parsed by a [`choice`] that
+/// produces an enum.
+///
+/// The fold function is given the chain so far, the segment, the span of the segment alone, and a [`MapExtra`]
+/// whose [`span`](MapExtra::span) covers the whole chain up to and including the segment. It must have the following
+/// signature:
+///
+/// ```ignore
+/// impl Fn(O, Segment, I::Span, &mut MapExtra<'src, '_, I, E>) -> O
+/// ```
+///
+/// The chain ends at the first segment that fails to parse, which is not an error. Segments that succeed without
+/// consuming input would cause an infinite loop: in debug builds, this causes a panic.
+///
+/// [`Parser::foldl_with`] is similar, but does not give the fold the span of each segment.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Expr<'src> {
+///     Var(&'src str, SimpleSpan),
+///     Field(Box<Self>, &'src str, SimpleSpan),
+///     Index(Box<Self>, Box<Self>, SimpleSpan),
+/// }
+///
+/// enum Segment<'src> {
+///     Field(&'src str),
+///     Index(Expr<'src>),
+/// }
+///
+/// let expr = recursive(|expr| {
+///     let var = text::ascii::ident::<_, extra::Err<Simple<char>>>().map_with(|name, e| Expr::Var(name, e.span()));
+///     let segment = choice((
+///         just('.').ignore_then(text::ascii::ident()).map(Segment::Field),
+///         expr.delimited_by(just('['), just(']')).map(Segment::Index),
+///     ));
+///     postfix_chain(var, segment, |chain, segment, _, e| match segment {
+///         Segment::Field(name) => Expr::Field(Box::new(chain), name, e.span()),
+///         Segment::Index(index) => Expr::Index(Box::new(chain), Box::new(index), e.span()),
+///     })
+/// });
+///
+/// assert_eq!(
+///     expr.parse("a.b[c]").into_result(),
+///     Ok(Expr::Index(
+///         Box::new(Expr::Field(Box::new(Expr::Var("a", (0..1).into())), "b", (0..3).into())),
+///         Box::new(Expr::Var("c", (4..5).into())),
+///         (0..6).into(),
+///     )),
+/// );
+/// ```
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn postfix_chain<'src, B, S, F, O, OS, I, E>(
+    base: B,
+    segment: S,
+    fold: F,
+) -> PostfixChain<B, S, F, OS>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    B: Parser<'src, I, O, E>,
+    S: Parser<'src, I, OS, E>,
+    F: Fn(O, OS, I::Span, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    PostfixChain {
+        base,
+        segment,
+        fold,
+        #[cfg(debug_assertions)]
+        location: *Location::caller(),
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, I, O, E, B, S, F, OS> Parser<'src, I, O, E> for PostfixChain<B, S, F, OS>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    B: Parser<'src, I, O, E>,
+    S: Parser<'src, I, OS, E>,
+    F: Fn(O, OS, I::Span, &mut MapExtra<'src, '_, I, E>) -> O,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let start = inp.cursor();
+        let mut out = self.base.go::<M>(inp)?;
+        loop {
+            let before = inp.save();
+            match self.segment.go::<M>(inp) {
+                Ok(segment) => {
+                    #[cfg(debug_assertions)]
+                    debug_assert!(
+                        *before.cursor() != inp.cursor(),
+                        "found PostfixChain combinator making no progress at {}",
+                        self.location,
+                    );
+                    let span = inp.span_since(before.cursor());
+                    out = M::combine(out, segment, |out, segment| {
+                        (self.fold)(out, segment, span, &mut MapExtra::new(&start, inp))
+                    });
+                }
+                Err(()) => {
+                    inp.rewind(before);
+                    break Ok(out);
+                }
+            }
+        }
+    }
+
     go_extra!(O);
 }
 
@@ -1000,7 +2676,34 @@ pub struct Group<T> {
 /// Parse using a tuple of many parsers, producing a tuple of outputs if all successfully parse,
 /// otherwise returning an error if any parsers fail.
 ///
-/// This parser is to [`Parser::then`] as [`choice`] is to [`Parser::or`]
+/// This parser is to [`Parser::then`] as [`choice`] is to [`Parser::or`]. Unlike a chain of [`Parser::then`] calls,
+/// which produces nested tuples like `(((A, B), C), D)`, the output is a flat tuple `(A, B, C, D)`. This keeps the
+/// closures passed to [`Parser::map`] readable for long sequences, and inserting a new parser in the middle of the
+/// sequence does not change the nesting of the elements around it.
+///
+/// Tuples of up to 26 parsers and arrays of parsers are supported.
+///
+/// The output type of this parser is a tuple (or array) of the outputs of the inner parsers.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let ident = text::ascii::ident::<_, extra::Err<Simple<char>>>().padded();
+/// let int = text::int(10).padded().from_str::<u32>().unwrapped();
+///
+/// // `let x: u32 = 42;`
+/// let decl = group((
+///     text::ascii::keyword("let").padded(),
+///     ident,
+///     just(':').ignore_then(ident),
+///     just('=').ignore_then(int),
+///     just(';'),
+/// ))
+///     .map(|(_, name, ty, val, _)| (name, ty, val));
+///
+/// assert_eq!(decl.parse("let x: u32 = 42;").into_result(), Ok(("x", "u32", 42)));
+/// ```
 pub const fn group<T>(parsers: T) -> Group<T> {
     Group { parsers }
 }
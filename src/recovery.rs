@@ -72,10 +72,18 @@ where
         let before = inp.save();
         match self.parser.go::<M>(inp) {
             Ok(out) => Ok(out),
+            // Recovering would only be abandoned again, so don't try
+            Err(()) if inp.abandoned() => Err(()),
             Err(()) => {
                 inp.rewind(before.clone());
                 match self.strategy.recover::<M, _>(inp, &self.parser) {
-                    Ok(out) => Ok(out),
+                    Ok(out) => match inp.recovered() {
+                        Ok(()) => Ok(out),
+                        Err(()) => {
+                            inp.rewind(before);
+                            Err(())
+                        }
+                    },
                     Err(()) => {
                         // Reset to before fallback attempt
                         inp.rewind(before);
@@ -112,6 +120,11 @@ where
     ) -> PResult<M, O> {
         let alt = inp.take_alt().unwrap(); // Can't fail!
         loop {
+            if let Err(()) = inp.safe_point() {
+                inp.errors.alt = Some(alt);
+                break Err(());
+            }
+
             let before = inp.save();
             if let Ok(()) = self.until.go::<Check>(inp) {
                 inp.errors.alt = Some(alt);
@@ -172,6 +185,11 @@ where
     ) -> PResult<M, O> {
         let alt = inp.take_alt().unwrap(); // Can't fail!
         loop {
+            if let Err(()) = inp.safe_point() {
+                inp.errors.alt = Some(alt);
+                break Err(());
+            }
+
             let before = inp.save();
             if let Ok(()) = self.until.go::<Check>(inp) {
                 inp.emit(None, alt.err);
@@ -127,7 +127,18 @@ impl<P: ?Sized> Recursive<P> {
             RecursiveInner::Owned(x) => x.clone(),
             RecursiveInner::Unowned(x) => x
                 .upgrade()
-                .expect("Recursive parser used before being defined"),
+                .expect("Recursive parser used before being defined or after being dropped"),
+        }
+    }
+
+    // Used by `recursive_group!` so that the parsers of a group don't own each other, which would leak them
+    #[doc(hidden)]
+    pub fn downgrade(&self) -> Self {
+        Self {
+            inner: match &self.inner {
+                RecursiveInner::Owned(x) => RecursiveInner::Unowned(Rc::downgrade(x)),
+                RecursiveInner::Unowned(x) => RecursiveInner::Unowned(x.clone()),
+            },
         }
     }
 }
@@ -161,7 +172,8 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
-        recurse(move || {
+        inp.enter_recursion()?;
+        let res = recurse(|| {
             M::invoke(
                 self.parser()
                     .inner
@@ -170,7 +182,25 @@ where
                     .as_ref(),
                 inp,
             )
-        })
+        });
+        inp.exit_recursion();
+        res
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        let parser = self.parser();
+        let id = Rc::as_ptr(&parser) as *const () as usize;
+        let children = if cx.first_visit(id) {
+            parser
+                .inner
+                .get()
+                .map(|parser| parser.introspect(cx))
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        introspect::Node::new("Recursive", introspect::NodeKind::Recursive(id), children)
     }
 
     go_extra!(O);
@@ -183,7 +213,21 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
-        recurse(move || M::invoke(&*self.parser(), inp))
+        inp.enter_recursion()?;
+        let res = recurse(|| M::invoke(&*self.parser(), inp));
+        inp.exit_recursion();
+        res
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        let parser = self.parser();
+        let id = Rc::as_ptr(&parser) as *const () as usize;
+        let children = if cx.first_visit(id) {
+            vec![parser.introspect(cx)]
+        } else {
+            Vec::new()
+        };
+        introspect::Node::new("Recursive", introspect::NodeKind::Recursive(id), children)
     }
 
     go_extra!(O);
@@ -259,3 +303,114 @@ where
         inner: RecursiveInner::Owned(rc),
     }
 }
+
+struct LazyInner<'src, 'b, I: Input<'src>, O, E: ParserExtra<'src, I>> {
+    make: Box<dyn Fn() -> Box<DynParser<'src, 'b, I, O, E>> + 'b>,
+    parser: OnceCell<Box<DynParser<'src, 'b, I, O, E>>>,
+}
+
+/// See [`lazy`].
+pub struct Lazy<'src, 'b, I: Input<'src>, O, E: ParserExtra<'src, I>> {
+    inner: Rc<LazyInner<'src, 'b, I, O, E>>,
+}
+
+impl<'src, I: Input<'src>, O, E: ParserExtra<'src, I>> Clone for Lazy<'src, '_, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Refer to a parser that is constructed by a function, calling the function only when the parser is first used.
+///
+/// This allows grammars to be written as ordinary functions that refer to each other, including recursively, without
+/// nesting all of the mutually recursive rules within a single call to [`recursive()`]. Each rule can live in its own
+/// function, or even its own module, and refer to others with `lazy(other_rule)`.
+///
+/// The constructed parser is cached, and shared between clones of the returned parser. Note that each call to `lazy`
+/// has its own cache: a rule that refers to itself through `lazy` constructs a new copy of itself for each level of
+/// nesting that the input reaches, although each copy is only constructed once. For grammars that nest deeply and are
+/// expensive to construct, [`recursive()`] avoids this.
+///
+/// The output type of this parser is `O`, the same as the inner parser.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, recursive::lazy};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Num(u64),
+///     Neg(Box<Expr>),
+///     List(Vec<Expr>),
+/// }
+///
+/// fn expr<'src>() -> impl Parser<'src, &'src str, Expr> + Clone {
+///     choice((
+///         text::int(10).from_str().unwrapped().map(Expr::Num),
+///         neg(),
+///         list(),
+///     ))
+///     .padded()
+/// }
+///
+/// fn neg<'src>() -> impl Parser<'src, &'src str, Expr> + Clone {
+///     just('-').ignore_then(lazy(expr)).map(|e| Expr::Neg(Box::new(e)))
+/// }
+///
+/// fn list<'src>() -> impl Parser<'src, &'src str, Expr> + Clone {
+///     lazy(expr)
+///         .separated_by(just(','))
+///         .collect()
+///         .delimited_by(just('['), just(']'))
+///         .map(Expr::List)
+/// }
+///
+/// assert_eq!(
+///     expr().parse("[1, -[2], --3]").into_result(),
+///     Ok(Expr::List(vec![
+///         Expr::Num(1),
+///         Expr::Neg(Box::new(Expr::List(vec![Expr::Num(2)]))),
+///         Expr::Neg(Box::new(Expr::Neg(Box::new(Expr::Num(3))))),
+///     ])),
+/// );
+/// ```
+pub fn lazy<'src, 'b, I, O, E, A, F>(make: F) -> Lazy<'src, 'b, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E> + 'b,
+    F: Fn() -> A + 'b,
+{
+    Lazy {
+        inner: Rc::new(LazyInner {
+            make: Box::new(move || Box::new(make())),
+            parser: OnceCell::new(),
+        }),
+    }
+}
+
+impl<'src, I, O, E> Parser<'src, I, O, E> for Lazy<'src, '_, I, O, E>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        let parser = match self.inner.parser.get() {
+            Some(parser) => parser,
+            None => {
+                // Constructing the parser cannot reach this cell, since `lazy` never calls the function itself
+                let _ = self.inner.parser.set((self.inner.make)());
+                self.inner.parser.get().unwrap()
+            }
+        };
+        inp.enter_recursion()?;
+        let res = recurse(|| M::invoke(parser.as_ref(), inp));
+        inp.exit_recursion();
+        res
+    }
+
+    go_extra!(O);
+}
@@ -80,6 +80,86 @@ pub trait Span {
             self.start().min(other.start())..self.end().max(other.end()),
         )
     }
+
+    /// Create a span covering the gap between two spans, regardless of the order they are given in.
+    ///
+    /// For example, spans like `3..5` and `7..8` will result in a span of `5..7`. If the spans overlap, the result is a
+    /// zero-width span positioned at the end of the earlier of the two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Self::Context`]s of both spans are not equal.
+    fn between(&self, other: Self) -> Self
+    where
+        Self::Context: PartialEq + fmt::Debug,
+        Self::Offset: Ord,
+        Self: Sized,
+    {
+        assert_eq!(
+            self.context(),
+            other.context(),
+            "tried to find the gap between two spans with different contexts"
+        );
+        let (first, second) = if self.start() <= other.start() {
+            (self.end(), other.start())
+        } else {
+            (other.end(), self.start())
+        };
+        let end = first.clone().max(second);
+        Self::new(self.context(), first..end)
+    }
+
+    /// Split this span into two at the given offset, producing the spans before and after it.
+    ///
+    /// For example, splitting the span `3..8` at `5` will result in the spans `3..5` and `5..8`. Offsets outside the
+    /// span are clamped to its bounds, so both results are always contained within the original span.
+    fn split(&self, at: Self::Offset) -> (Self, Self)
+    where
+        Self::Offset: Ord,
+        Self: Sized,
+    {
+        let at = at.clamp(self.start(), self.end());
+        (
+            Self::new(self.context(), self.start()..at.clone()),
+            Self::new(self.context(), at..self.end()),
+        )
+    }
+
+    /// Determine whether the given offset falls within this span.
+    ///
+    /// As with [`Range`], the start offset is inclusive and the end offset is exclusive.
+    fn contains_offset(&self, offset: &Self::Offset) -> bool
+    where
+        Self::Offset: Ord,
+    {
+        &self.start() <= offset && offset < &self.end()
+    }
+
+    /// Determine whether another span lies entirely within this span.
+    ///
+    /// Spans with differing contexts are never considered to contain one-another. A span always contains itself.
+    fn contains_span(&self, other: &Self) -> bool
+    where
+        Self::Context: PartialEq,
+        Self::Offset: Ord,
+    {
+        self.context() == other.context()
+            && self.start() <= other.start()
+            && other.end() <= self.end()
+    }
+
+    /// Determine whether this span and another span share at least one offset.
+    ///
+    /// Zero-width spans never overlap anything. Spans with differing contexts never overlap.
+    fn overlaps(&self, other: &Self) -> bool
+    where
+        Self::Context: PartialEq,
+        Self::Offset: Ord,
+    {
+        self.context() == other.context()
+            && self.start() < other.end()
+            && other.start() < self.end()
+    }
 }
 
 /// The most basic implementor of `Span` - akin to `Range`, but `Copy` since it's not also
@@ -206,3 +286,458 @@ impl<T: Clone> Span for Range<T> {
         self.end.clone()
     }
 }
+
+/// A span that tracks two different kinds of position at once.
+///
+/// This is useful for pipelines that need more than one notion of 'location'. For example, a parser operating over
+/// the output of a lexer might want token indices (for recovery and lookahead heuristics) while also keeping hold of
+/// the byte offsets of the original source (for diagnostics).
+///
+/// Both components are combined and recombined in lockstep: when the parser creates a new span (such as the one
+/// returned by [`MapExtra::span`](crate::input::MapExtra::span)), the primary and secondary spans are created from
+/// the corresponding components of the start and end offsets.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, span::DualSpan};
+/// // Tokens, each carrying both its index in the token stream and its byte range in the source
+/// let tokens = [('a', 0..1, 0..1), ('+', 1..2, 2..3), ('b', 2..3, 4..5)]
+///     .map(|(tok, idx, bytes)| (tok, DualSpan::new(SimpleSpan::from(idx), SimpleSpan::from(bytes))));
+/// let eoi = DualSpan::new(SimpleSpan::from(3..3), SimpleSpan::from(5..5));
+///
+/// let sum = any::<_, extra::Err<EmptyErr>>()
+///     .then_ignore(just('+'))
+///     .then(any())
+///     .map_with(|_, e| e.span());
+///
+/// let span: DualSpan<SimpleSpan, SimpleSpan> = sum
+///     .parse(tokens.as_slice().map(eoi, |(t, s)| (t, s)))
+///     .unwrap();
+/// assert_eq!(span.primary, SimpleSpan::from(0..3));
+/// assert_eq!(span.secondary, SimpleSpan::from(0..5));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DualSpan<A, B> {
+    /// The primary span, usually measured in the units the parser operates on (such as token indices).
+    pub primary: A,
+    /// The secondary span, usually measured in the units of the original source (such as byte offsets).
+    pub secondary: B,
+}
+
+impl<A, B> DualSpan<A, B> {
+    /// Create a new dual span from its primary and secondary components.
+    pub const fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Split this dual span into its primary and secondary components.
+    pub fn into_parts(self) -> (A, B) {
+        (self.primary, self.secondary)
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for DualSpan<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.primary, self.secondary)
+    }
+}
+
+impl<A: Span, B: Span> Span for DualSpan<A, B> {
+    type Context = (A::Context, B::Context);
+    type Offset = (A::Offset, B::Offset);
+
+    fn new((a_ctx, b_ctx): Self::Context, range: Range<Self::Offset>) -> Self {
+        Self {
+            primary: A::new(a_ctx, range.start.0..range.end.0),
+            secondary: B::new(b_ctx, range.start.1..range.end.1),
+        }
+    }
+    fn context(&self) -> Self::Context {
+        (self.primary.context(), self.secondary.context())
+    }
+    fn start(&self) -> Self::Offset {
+        (self.primary.start(), self.secondary.start())
+    }
+    fn end(&self) -> Self::Offset {
+        (self.primary.end(), self.secondary.end())
+    }
+}
+
+/// The units in which the columns of positions are measured. See [`LineIndex::position`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PositionEncoding {
+    /// Columns are measured in bytes (UTF-8 code units).
+    Utf8,
+    /// Columns are measured in UTF-16 code units. This is the default encoding of the Language Server Protocol, and is
+    /// used by many editors and by JavaScript.
+    Utf16,
+    /// Columns are measured in `char`s (Unicode scalar values, or UTF-32 code units).
+    Utf32,
+}
+
+impl PositionEncoding {
+    fn len(self, c: char) -> usize {
+        match self {
+            Self::Utf8 => c.len_utf8(),
+            Self::Utf16 => c.len_utf16(),
+            Self::Utf32 => 1,
+        }
+    }
+}
+
+/// A window onto a line of source code, produced by [`LineIndex::snippet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snippet<'src> {
+    /// The (zero-indexed) line that the window is on.
+    pub line: usize,
+    /// The text of the window.
+    pub text: &'src str,
+    /// The byte offset in the source at which the window starts.
+    pub offset: usize,
+    /// Whether the line continues before the start of the window.
+    pub truncated_start: bool,
+    /// Whether the line continues after the end of the window.
+    pub truncated_end: bool,
+    /// The range of characters within the window covered by the span. This always covers at least one character,
+    /// even if the span is empty, although it may be just past the end of the window if the span is at the end of the
+    /// line.
+    pub carets: Range<usize>,
+}
+
+pub(crate) fn floor_char_boundary(s: &str, offset: usize) -> usize {
+    let mut offset = offset.min(s.len());
+    while !s.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// A non-ASCII character in the source of a [`LineIndex`].
+#[derive(Copy, Clone, Debug)]
+struct WideChar {
+    // The byte offset of the character
+    offset: usize,
+    // The number of `char`s that precede the character
+    chars_before: usize,
+    c: char,
+}
+
+/// An index of the lines of a source string, allowing byte offsets (such as those in the spans produced when parsing a
+/// `&str`) to be efficiently converted into line and column positions.
+///
+/// Lines are separated by `\n`. Lines and columns are zero-indexed. Columns are measured in bytes by
+/// [`LineIndex::line_col`], or in the units of any [`PositionEncoding`] by [`LineIndex::position`]: the positions of
+/// non-ASCII characters are cached, so that converting positions remains cheap even for long lines.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::span::{LineIndex, PositionEncoding};
+/// let index = LineIndex::new("let x = 1;\nlet y = \"🦀\";\n");
+///
+/// assert_eq!(index.line_count(), 3);
+/// assert_eq!(index.line_col(4), (0, 4));
+/// assert_eq!(index.line(1), Some("let y = \"🦀\";"));
+///
+/// // The `"` after the crab
+/// assert_eq!(index.line_col(24), (1, 13));
+/// assert_eq!(index.position(24, PositionEncoding::Utf16), (1, 11));
+/// assert_eq!(index.position(24, PositionEncoding::Utf32), (1, 10));
+/// assert_eq!(index.offset(1, 11, PositionEncoding::Utf16), Some(24));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LineIndex<'src> {
+    src: &'src str,
+    line_starts: Vec<usize>,
+    wide_chars: Vec<WideChar>,
+}
+
+impl<'src> LineIndex<'src> {
+    /// Index the lines of the given source string.
+    pub fn new(src: &'src str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars = Vec::new();
+        for (chars_before, (offset, c)) in src.char_indices().enumerate() {
+            if c == '\n' {
+                line_starts.push(offset + 1);
+            } else if !c.is_ascii() {
+                wide_chars.push(WideChar {
+                    offset,
+                    chars_before,
+                    c,
+                });
+            }
+        }
+        Self {
+            src,
+            line_starts,
+            wide_chars,
+        }
+    }
+
+    /// Get the source string that this index refers to.
+    pub fn src(&self) -> &'src str {
+        self.src
+    }
+
+    /// Get the number of lines in the source.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Get the text of the given line, excluding its line terminator (`\n` or `\r\n`).
+    pub fn line(&self, line: usize) -> Option<&'src str> {
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.src.len(), |end| end - 1);
+        let text = &self.src[start..end];
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// Get the byte offset at which the given line starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line).copied()
+    }
+
+    /// Get the line that the given byte offset falls on.
+    ///
+    /// Offsets beyond the end of the source are treated as being at the end of the source.
+    pub fn line_of(&self, offset: usize) -> usize {
+        let offset = offset.min(self.src.len());
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+
+    /// Get the line and column (in bytes) of the given byte offset.
+    ///
+    /// Offsets beyond the end of the source are treated as being at the end of the source.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.src.len());
+        let line = self.line_of(offset);
+        (line, offset - self.line_starts[line])
+    }
+
+    /// Get the non-ASCII characters in the given range of bytes.
+    fn wide_chars(&self, range: Range<usize>) -> &[WideChar] {
+        let start = self.wide_chars.partition_point(|c| c.offset < range.start);
+        let end = self.wide_chars.partition_point(|c| c.offset < range.end);
+        &self.wide_chars[start..end]
+    }
+
+    /// Get the line and column of the given byte offset, with the column measured in the units of the given encoding.
+    ///
+    /// Offsets beyond the end of the source are treated as being at the end of the source. Offsets within a character
+    /// are treated as being at the start of that character.
+    pub fn position(&self, offset: usize, encoding: PositionEncoding) -> (usize, usize) {
+        let (line, col) = self.line_col(offset);
+        let line_start = self.line_starts[line];
+        let offset = line_start + col;
+        let col = self
+            .wide_chars(line_start..offset)
+            .iter()
+            .fold(col, |col, wide| {
+                if wide.offset + wide.c.len_utf8() > offset {
+                    // The offset falls within this character
+                    col - (offset - wide.offset)
+                } else {
+                    col - wide.c.len_utf8() + encoding.len(wide.c)
+                }
+            });
+        (line, col)
+    }
+
+    /// Get the byte offset of the given line and column, with the column measured in the units of the given encoding.
+    ///
+    /// Returns `None` if the line does not exist. Columns beyond the end of the line are treated as being at the end of
+    /// the line, and columns within a character are treated as being at the start of that character.
+    pub fn offset(&self, line: usize, col: usize, encoding: PositionEncoding) -> Option<usize> {
+        let line_start = self.line_start(line)?;
+        let line_end = line_start + self.line(line)?.len();
+        let mut offset = line_start;
+        let mut remaining = col;
+        for wide in self.wide_chars(line_start..line_end) {
+            let ascii = wide.offset - offset;
+            if remaining < ascii + encoding.len(wide.c) {
+                return Some((offset + remaining.min(ascii)).min(line_end));
+            }
+            remaining -= ascii + encoding.len(wide.c);
+            offset = wide.offset + wide.c.len_utf8();
+        }
+        Some((offset + remaining).min(line_end))
+    }
+
+    /// Get a window of at most `width` characters of the line containing the start of the given span of bytes,
+    /// positioned so that the start of the span is visible.
+    ///
+    /// This is useful for showing the source of an error, even when the source contains extremely long lines (such as
+    /// minified JSON or JavaScript). Only the characters around the window are examined, so the cost of this method
+    /// does not depend on the length of the line.
+    ///
+    /// Offsets within a character are treated as being at the start of that character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::span::LineIndex;
+    /// let src = format!("[{}true, flase]", "true, ".repeat(100_000));
+    /// let index = LineIndex::new(&src);
+    ///
+    /// let snippet = index.snippet(600_007..600_012, 24);
+    /// assert_eq!(snippet.text, "true, true, true, flase]");
+    /// assert!(snippet.truncated_start && !snippet.truncated_end);
+    /// assert_eq!(snippet.carets, 18..23);
+    /// ```
+    pub fn snippet(&self, span: Range<usize>, width: usize) -> Snippet<'src> {
+        let width = width.max(1);
+        let start = floor_char_boundary(self.src, span.start);
+        let line = self.line_of(start);
+        let line_start = self.line_starts[line];
+        let line_end = line_start + self.line(line).map_or(0, str::len);
+        let start = start.min(line_end);
+        let end = floor_char_boundary(self.src, span.end).clamp(start, line_end);
+
+        // Show some context before the span, and then as much of the line after it as will fit
+        let before = |from: usize, n: usize| {
+            self.src[line_start..from]
+                .char_indices()
+                .rev()
+                .take(n)
+                .last()
+                .map_or(from, |(i, _)| line_start + i)
+        };
+        let mut lo = before(start, width / 3);
+        let mut count = 0;
+        let mut hi = lo;
+        for c in self.src[lo..line_end].chars().take(width) {
+            hi += c.len_utf8();
+            count += 1;
+        }
+        // If the end of the line was reached, use any remaining space to show more of the line before the span
+        if count < width {
+            lo = before(lo, width - count);
+        }
+
+        let col = self.src[lo..start].chars().count();
+        Snippet {
+            line,
+            text: &self.src[lo..hi],
+            offset: lo,
+            truncated_start: lo > line_start,
+            truncated_end: hi < line_end,
+            carets: col..col + self.src[start..end.min(hi)].chars().count().max(1),
+        }
+    }
+
+    /// Convert an offset measured in `char`s (as produced by parsing `&[char]` or a stream of `char`s) into a byte
+    /// offset.
+    ///
+    /// Offsets beyond the end of the source are treated as being at the end of the source.
+    pub fn byte_offset(&self, char_offset: usize) -> usize {
+        let before = self
+            .wide_chars
+            .partition_point(|c| c.chars_before < char_offset);
+        let offset = match before.checked_sub(1) {
+            Some(i) => {
+                let wide = &self.wide_chars[i];
+                wide.offset + wide.c.len_utf8() + (char_offset - wide.chars_before - 1)
+            }
+            None => char_offset,
+        };
+        offset.min(self.src.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_between() {
+        let a = SimpleSpan::from(3..5);
+        let b = SimpleSpan::from(7..8);
+        assert_eq!(a.union(b), SimpleSpan::from(3..8));
+        assert_eq!(a.between(b), SimpleSpan::from(5..7));
+        assert_eq!(b.between(a), SimpleSpan::from(5..7));
+        assert_eq!(a.between(SimpleSpan::from(4..9)), SimpleSpan::from(5..5));
+    }
+
+    #[test]
+    fn split_clamps() {
+        let span = SimpleSpan::from(3..8);
+        assert_eq!(
+            span.split(5),
+            (SimpleSpan::from(3..5), SimpleSpan::from(5..8))
+        );
+        assert_eq!(
+            span.split(1),
+            (SimpleSpan::from(3..3), SimpleSpan::from(3..8))
+        );
+        assert_eq!(
+            span.split(10),
+            (SimpleSpan::from(3..8), SimpleSpan::from(8..8))
+        );
+    }
+
+    #[test]
+    fn containment() {
+        let span = SimpleSpan::from(3..8);
+        assert!(span.contains_offset(&3));
+        assert!(!span.contains_offset(&8));
+        assert!(span.contains_span(&SimpleSpan::from(4..8)));
+        assert!(!span.contains_span(&SimpleSpan::from(2..4)));
+        assert!(span.overlaps(&SimpleSpan::from(7..9)));
+        assert!(!span.overlaps(&SimpleSpan::from(8..9)));
+        assert!(!(("a", 3..8).contains_span(&("b", 4..5))));
+    }
+
+    #[test]
+    fn line_index_encodings() {
+        use PositionEncoding::*;
+
+        let src = "aé𝄞b\r\nxyz\n𝄞";
+        let index = LineIndex::new(src);
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line(0), Some("aé𝄞b"));
+        assert_eq!(index.line(2), Some("𝄞"));
+        assert_eq!(index.line(3), None);
+
+        // Offsets of the characters of the first line, and the end of the line
+        let offsets = [0, 1, 3, 7, 8];
+        let cols = |encoding| {
+            offsets
+                .iter()
+                .map(|offset| index.position(*offset, encoding).1)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(cols(Utf8), offsets);
+        assert_eq!(cols(Utf16), [0, 1, 2, 4, 5]);
+        assert_eq!(cols(Utf32), [0, 1, 2, 3, 4]);
+        for encoding in [Utf8, Utf16, Utf32] {
+            for offset in offsets {
+                let (line, col) = index.position(offset, encoding);
+                assert_eq!(index.offset(line, col, encoding), Some(offset));
+            }
+        }
+
+        // Offsets and columns within characters snap to the start of the character
+        assert_eq!(index.position(5, Utf16), (0, 2));
+        assert_eq!(index.offset(0, 3, Utf16), Some(3));
+        // Columns beyond the end of the line snap to the end of the line
+        assert_eq!(index.offset(0, 100, Utf16), Some(8));
+        assert_eq!(index.offset(3, 0, Utf16), None);
+
+        assert_eq!(index.position(14, Utf16), (2, 0));
+        assert_eq!(index.position(18, Utf16), (2, 2));
+        assert_eq!(index.position(100, Utf16), (2, 2));
+
+        // Conversion from char offsets
+        let char_offsets = src.char_indices().map(|(i, _)| i).collect::<Vec<_>>();
+        for (char_offset, offset) in char_offsets.into_iter().enumerate() {
+            assert_eq!(index.byte_offset(char_offset), offset);
+        }
+        assert_eq!(index.byte_offset(100), src.len());
+    }
+}
@@ -6,7 +6,7 @@
 //! The parsers in this module are generic over both Unicode ([`char`]) and ASCII ([`u8`]) characters. Most parsers take
 //! a type parameter, `C`, that can be either [`u8`] or [`char`] in order to handle either case.
 
-use crate::prelude::*;
+use crate::{error::LiteralError, prelude::*};
 use alloc::string::ToString;
 
 use super::*;
@@ -206,6 +206,75 @@ where
         Ok(out)
     }
 
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Padded", self.parser.introspect(cx))
+    }
+
+    go_extra!(O);
+}
+
+/// A compile-time policy deciding whether [`Parser::maybe_padded`] skips whitespace.
+///
+/// This allows a single grammar definition to be instantiated both for formats in which whitespace is significant and
+/// for free-form formats in which it is not, by making the grammar generic over the policy.
+pub trait Padding {
+    /// Whether whitespace surrounding padded patterns should be skipped.
+    const SKIP_WHITESPACE: bool;
+}
+
+/// A [`Padding`] policy that skips whitespace surrounding padded patterns.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FreeForm;
+
+impl Padding for FreeForm {
+    const SKIP_WHITESPACE: bool = true;
+}
+
+/// A [`Padding`] policy under which whitespace is significant, and so is not skipped.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Strict;
+
+impl Padding for Strict {
+    const SKIP_WHITESPACE: bool = false;
+}
+
+/// See [`Parser::maybe_padded`].
+pub struct MaybePadded<A, P> {
+    pub(crate) parser: A,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<P>,
+}
+
+impl<A: Copy, P> Copy for MaybePadded<A, P> {}
+impl<A: Clone, P> Clone for MaybePadded<A, P> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'src, I, O, E, A, P> Parser<'src, I, O, E> for MaybePadded<A, P>
+where
+    I: ValueInput<'src>,
+    E: ParserExtra<'src, I>,
+    I::Token: Char,
+    A: Parser<'src, I, O, E>,
+    P: Padding,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        if P::SKIP_WHITESPACE {
+            inp.skip_while(|c| c.is_whitespace());
+        }
+        let out = self.parser.go::<M>(inp)?;
+        if P::SKIP_WHITESPACE {
+            inp.skip_while(|c| c.is_whitespace());
+        }
+        Ok(out)
+    }
+
     go_extra!(O);
 }
 
@@ -232,6 +301,8 @@ where
     IdentifierPart,
     /// A specific identifier.
     Identifier(I::Slice),
+    /// A floating-point literal.
+    Float,
 }
 
 /// A parser that accepts (and ignores) any number of whitespace characters.
@@ -502,6 +573,973 @@ where
         .to_slice()
 }
 
+/// An integer type that can be parsed by [`int_checked`].
+///
+/// This is implemented for all of Rust's primitive integer types. With the `num-bigint` feature, it is also implemented
+/// for [`num_bigint::BigInt`] and [`num_bigint::BigUint`], which are never out of range.
+pub trait Integer: Sealed + Sized {
+    #[doc(hidden)]
+    const NAME: &'static str;
+    #[doc(hidden)]
+    fn push_digit(value: Option<Self>, radix: u32, digit: u32) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($T:ident)*) => {$(
+        impl Integer for $T {
+            const NAME: &'static str = stringify!($T);
+            #[inline(always)]
+            fn push_digit(value: Option<Self>, radix: u32, digit: u32) -> Option<Self> {
+                let digit = Self::try_from(digit).ok()?;
+                match value {
+                    Some(value) => value.checked_mul(Self::try_from(radix).ok()?)?.checked_add(digit),
+                    None => Some(digit),
+                }
+            }
+        }
+    )*};
+}
+
+impl Sealed for u16 {}
+impl Sealed for u32 {}
+impl Sealed for u64 {}
+impl Sealed for u128 {}
+impl Sealed for usize {}
+impl Sealed for i8 {}
+impl Sealed for i16 {}
+impl Sealed for i32 {}
+impl Sealed for i64 {}
+impl Sealed for i128 {}
+impl Sealed for isize {}
+impl_integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+#[cfg(feature = "num-bigint")]
+macro_rules! impl_big_integer {
+    ($($T:ident)*) => {$(
+        impl Sealed for num_bigint::$T {}
+        impl Integer for num_bigint::$T {
+            const NAME: &'static str = stringify!($T);
+            #[inline(always)]
+            fn push_digit(value: Option<Self>, radix: u32, digit: u32) -> Option<Self> {
+                Some(match value {
+                    Some(value) => value * radix + digit,
+                    None => Self::from(digit),
+                })
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "num-bigint")]
+impl_big_integer!(BigInt BigUint);
+
+/// A parser that accepts a non-negative integer, like [`int`], and converts it to the integer type `T`.
+///
+/// If the integer is too large for `T`, an error is produced at the span of the whole integer (see
+/// [`LiteralError::out_of_range`]). With [`Rich`] errors, the message is "integer literal out of range for `T`".
+///
+/// Like [`int`], this parser does not accept a sign: negative literals are usually parsed as the negation of a
+/// positive one. Note that this means the minimum value of a signed type cannot be written as a literal.
+///
+/// The output type of this parser is `T`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let byte = text::int_checked::<u8, _, extra::Err<Rich<char>>>(10);
+///
+/// assert_eq!(byte.parse("0").into_result(), Ok(0));
+/// assert_eq!(byte.parse("255").into_result(), Ok(255));
+///
+/// let errs = byte.parse("256").into_errors();
+/// assert_eq!(errs[0].to_string(), "integer literal out of range for u8");
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(0..3));
+///
+/// // Leading zeroes are not permitted, as with `text::int`
+/// assert!(byte.parse("04").has_errors());
+///
+/// let hex = text::int_checked::<i64, _, extra::Err<Rich<char>>>(16);
+/// assert_eq!(hex.parse("7fffffffffffffff").into_result(), Ok(i64::MAX));
+/// assert!(hex.parse("8000000000000000").has_errors());
+/// ```
+#[must_use]
+pub fn int_checked<'src, T, I, E>(radix: u32) -> impl Parser<'src, I, T, E> + Copy
+where
+    T: Integer,
+    I: StrInput<'src>,
+    I::Token: Char + 'src,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, TextExpected<'src, I>>
+        + LabelError<'src, I, MaybeRef<'src, I::Token>>
+        + LiteralError<'src, I>,
+{
+    let digit = move |c: I::Token| {
+        c.to_ascii()
+            .and_then(|c| (c as char).to_digit(radix))
+            .unwrap_or(0)
+    };
+
+    any()
+        .try_map(move |c: I::Token, span| {
+            if c.is_digit(radix) && c != I::Token::digit_zero() {
+                Ok(c)
+            } else {
+                Err(LabelError::expected_found(
+                    [TextExpected::Digit(1..radix)],
+                    Some(MaybeRef::Val(c)),
+                    span,
+                ))
+            }
+        })
+        .map(move |c| (c, T::push_digit(None, radix, digit(c))))
+        .foldl(
+            any()
+                .try_map(move |c: I::Token, span| {
+                    if c.is_digit(radix) {
+                        Ok(c)
+                    } else {
+                        Err(LabelError::expected_found(
+                            [TextExpected::Digit(0..radix)],
+                            Some(MaybeRef::Val(c)),
+                            span,
+                        ))
+                    }
+                })
+                .repeated(),
+            move |(first, value), c| {
+                (
+                    first,
+                    value.and_then(|value| T::push_digit(Some(value), radix, digit(c))),
+                )
+            },
+        )
+        .or(just(I::Token::digit_zero()).map(move |c| (c, T::push_digit(None, radix, 0))))
+        .try_map(|(first, value), span| {
+            value.ok_or_else(|| {
+                LiteralError::out_of_range("integer", T::NAME, Some(MaybeRef::Val(first)), span)
+            })
+        })
+}
+
+/// See [`float`].
+pub struct Float<I, E, O = f64> {
+    trailing_dot: bool,
+    leading_dot: bool,
+    infinity: bool,
+    nan: bool,
+    hex: bool,
+    integer: bool,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(I, E, O)>,
+}
+
+impl<I, E, O> Copy for Float<I, E, O> {}
+impl<I, E, O> Clone for Float<I, E, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, E, O> Float<I, E, O> {
+    /// Accept literals with a trailing dot and no fractional digits, like `1.` (accepted by default, as in Rust).
+    ///
+    /// As in Rust, a dot followed by another dot or by an identifier is never part of the literal, so `1..2` and
+    /// `1.max(2)` are not affected.
+    pub fn trailing_dot(self, accept: bool) -> Self {
+        Self {
+            trailing_dot: accept,
+            ..self
+        }
+    }
+
+    /// Accept literals with a leading dot and no integer digits, like `.5` (rejected by default, as in Rust).
+    pub fn leading_dot(self, accept: bool) -> Self {
+        Self {
+            leading_dot: accept,
+            ..self
+        }
+    }
+
+    /// Accept literals with neither a fractional part nor an exponent, like `42` (rejected by default, since Rust
+    /// considers them to be integer literals).
+    pub fn integer(self, accept: bool) -> Self {
+        Self {
+            integer: accept,
+            ..self
+        }
+    }
+}
+
+impl<I, E> Float<I, E> {
+    /// Accept `inf` and `infinity`, in any case (rejected by default).
+    pub fn infinity(self, accept: bool) -> Self {
+        Self {
+            infinity: accept,
+            ..self
+        }
+    }
+
+    /// Accept `nan`, in any case (rejected by default).
+    pub fn nan(self, accept: bool) -> Self {
+        Self {
+            nan: accept,
+            ..self
+        }
+    }
+
+    /// Accept C-style hexadecimal floats, like `0x1.8p3` (rejected by default).
+    ///
+    /// A hexadecimal float must have a binary exponent, written with `p` or `P`.
+    pub fn hex(self, accept: bool) -> Self {
+        Self {
+            hex: accept,
+            ..self
+        }
+    }
+
+    /// Produce a [`rust_decimal::Decimal`] instead of an [`f64`], so that no precision is lost.
+    ///
+    /// A literal that cannot be represented exactly as a `Decimal` (because it is too large, or has too many
+    /// significant digits) produces an error at the span of the literal (see [`LiteralError::out_of_range`]). With
+    /// [`Rich`] errors, the message is "decimal literal out of range for Decimal".
+    ///
+    /// Hexadecimal floats, infinity, and NaN have no `Decimal` representation, and are never accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let price = text::float::<_, extra::Err<Rich<char>>>().integer(true).decimal();
+    ///
+    /// assert_eq!(price.parse("19.99").into_result(), Ok((Decimal::new(1999, 2), "19.99")));
+    /// assert_eq!(price.parse("1.5e2").into_result(), Ok((Decimal::new(150, 0), "1.5e2")));
+    /// // 0.1 is not exactly representable as an `f64`, but is as a `Decimal`
+    /// assert_eq!(price.parse("0.1").into_result(), Ok((Decimal::new(1, 1), "0.1")));
+    ///
+    /// let errs = price.parse("1e40").into_errors();
+    /// assert_eq!(errs[0].to_string(), "decimal literal out of range for Decimal");
+    /// ```
+    #[cfg(feature = "rust_decimal")]
+    pub fn decimal(self) -> Float<I, E, rust_decimal::Decimal> {
+        Float {
+            trailing_dot: self.trailing_dot,
+            leading_dot: self.leading_dot,
+            infinity: false,
+            nan: false,
+            hex: false,
+            integer: self.integer,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// A parser that accepts a floating-point literal.
+///
+/// By default, this follows the rules for Rust's float literals: the literal must have a fractional part (`1.5`), an
+/// exponent (`1e3`), or both (`1.5e-3`), and may end in a trailing dot (`1.`). Since languages disagree about which
+/// forms are valid, each can be turned on or off with [`Float::trailing_dot`], [`Float::leading_dot`],
+/// [`Float::infinity`], [`Float::nan`], [`Float::hex`], and [`Float::integer`].
+///
+/// Like [`int`], this parser does not accept a sign. Digit separators (such as `_`) are not accepted either.
+///
+/// The output type of this parser is `(f64, I::Slice)`: the value of the literal, and the literal as written. With the
+/// `rust_decimal` feature, [`Float::decimal`] produces a [`rust_decimal::Decimal`] instead.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let rust = text::float::<_, extra::Err<Simple<char>>>();
+///
+/// assert_eq!(rust.parse("1.5").into_result(), Ok((1.5, "1.5")));
+/// assert_eq!(rust.parse("2.").into_result(), Ok((2.0, "2.")));
+/// assert_eq!(rust.parse("1e3").into_result(), Ok((1000.0, "1e3")));
+/// assert!(rust.parse(".5").has_errors());
+/// assert!(rust.parse("42").has_errors());
+///
+/// // A dot followed by another dot is not part of the literal
+/// let range = rust.then_ignore(just("..")).then(text::int(10));
+/// assert!(range.parse("1..2").has_errors());
+///
+/// // JavaScript-style numbers
+/// let js = text::float::<_, extra::Err<Simple<char>>>()
+///     .leading_dot(true)
+///     .integer(true);
+///
+/// assert_eq!(js.parse(".5").into_result(), Ok((0.5, ".5")));
+/// assert_eq!(js.parse("42").into_result(), Ok((42.0, "42")));
+///
+/// // C-style hexadecimal floats
+/// let c = text::float::<_, extra::Err<Simple<char>>>().hex(true);
+/// assert_eq!(c.parse("0x1.8p3").into_result(), Ok((12.0, "0x1.8p3")));
+///
+/// // Special values
+/// let special = text::float::<_, extra::Err<Simple<char>>>()
+///     .infinity(true)
+///     .nan(true);
+///
+/// assert_eq!(special.parse("inf").into_result(), Ok((f64::INFINITY, "inf")));
+/// assert!(special.parse("NaN").into_result().unwrap().0.is_nan());
+/// ```
+#[must_use]
+pub const fn float<'src, I, E>() -> Float<I, E>
+where
+    I: StrInput<'src>,
+    I::Token: Char,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, TextExpected<'src, I>>,
+{
+    Float {
+        trailing_dot: true,
+        leading_dot: false,
+        infinity: false,
+        nan: false,
+        hex: false,
+        integer: false,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+fn float_digits<'src, I, E>(
+    inp: &mut InputRef<'src, '_, I, E>,
+    radix: u32,
+    mut f: impl FnMut(u32),
+) -> usize
+where
+    I: StrInput<'src>,
+    I::Token: Char,
+    E: ParserExtra<'src, I>,
+{
+    let mut count = 0;
+    while let Some(digit) = inp
+        .peek()
+        .and_then(|c| c.to_ascii())
+        .and_then(|c| (c as char).to_digit(radix))
+    {
+        f(digit);
+        inp.skip();
+        count += 1;
+    }
+    count
+}
+
+fn scale_by_pow2(mut value: f64, mut exp: i64) -> f64 {
+    // 2^n, for -1022 <= n <= 1023
+    let pow2 = |n: i64| f64::from_bits(((n + 1023) as u64) << 52);
+    while exp > 0 && value.is_finite() && value != 0.0 {
+        let step = exp.min(1023);
+        value *= pow2(step);
+        exp -= step;
+    }
+    while exp < 0 && value != 0.0 {
+        let step = exp.max(-1022);
+        value *= pow2(step);
+        exp -= step;
+    }
+    value
+}
+
+/// A literal recognised by [`Float`].
+enum ScannedFloat {
+    /// A special value or hexadecimal float, which has already been evaluated.
+    Value(f64),
+    /// A decimal literal, whose ASCII text has been collected for conversion.
+    Decimal,
+}
+
+impl<I, E, O> Float<I, E, O> {
+    fn scan<'src>(
+        &self,
+        inp: &mut InputRef<'src, '_, I, E>,
+        text: &mut String,
+    ) -> Option<ScannedFloat>
+    where
+        I: StrInput<'src>,
+        I::Token: Char,
+        E: ParserExtra<'src, I>,
+    {
+        let ascii = |c: Option<I::Token>| c.and_then(|c| c.to_ascii());
+
+        if inp.peek().map_or(false, |c| c.is_ident_start()) {
+            let before = inp.save();
+            let mut word = String::new();
+            while let Some(c) = inp.peek().filter(|c| c.is_ident_continue()) {
+                word.push(
+                    c.to_ascii()
+                        .map_or('\0', |c| c.to_ascii_lowercase() as char),
+                );
+                inp.skip();
+            }
+            return match word.as_str() {
+                "inf" | "infinity" if self.infinity => Some(ScannedFloat::Value(f64::INFINITY)),
+                "nan" if self.nan => Some(ScannedFloat::Value(f64::NAN)),
+                _ => {
+                    inp.rewind(before);
+                    None
+                }
+            };
+        }
+
+        if self.hex && inp.peek() == Some(I::Token::digit_zero()) {
+            let before = inp.save();
+            inp.skip();
+            if matches!(ascii(inp.peek()), Some(b'x' | b'X')) {
+                inp.skip();
+                return self.scan_hex(inp).map(ScannedFloat::Value);
+            }
+            inp.rewind(before);
+        }
+
+        let int_digits = float_digits(inp, 10, |digit| {
+            text.push(char::from_digit(digit, 10).unwrap())
+        });
+        if int_digits == 0 && !self.leading_dot {
+            return None;
+        }
+
+        let mut is_float = false;
+        if ascii(inp.peek()) == Some(b'.') {
+            let before = inp.save();
+            inp.skip();
+            let next = inp.peek();
+            if next.map_or(false, |c| c.is_digit(10)) {
+                text.push('.');
+                float_digits(inp, 10, |digit| {
+                    text.push(char::from_digit(digit, 10).unwrap())
+                });
+                is_float = true;
+            } else if int_digits > 0
+                && self.trailing_dot
+                && !next.map_or(false, |c| c.is_ident_start() || c.to_ascii() == Some(b'.'))
+            {
+                text.push('.');
+                is_float = true;
+            } else {
+                inp.rewind(before);
+            }
+        }
+        if int_digits == 0 && !is_float {
+            return None;
+        }
+
+        if matches!(ascii(inp.peek()), Some(b'e' | b'E')) {
+            let before = inp.save();
+            let len = text.len();
+            text.push('e');
+            inp.skip();
+            if let Some(sign @ (b'+' | b'-')) = ascii(inp.peek()) {
+                text.push(sign as char);
+                inp.skip();
+            }
+            if float_digits(inp, 10, |digit| {
+                text.push(char::from_digit(digit, 10).unwrap())
+            }) > 0
+            {
+                is_float = true;
+            } else {
+                inp.rewind(before);
+                text.truncate(len);
+            }
+        }
+
+        if is_float || self.integer {
+            Some(ScannedFloat::Decimal)
+        } else {
+            None
+        }
+    }
+
+    fn scan_hex<'src>(&self, inp: &mut InputRef<'src, '_, I, E>) -> Option<f64>
+    where
+        I: StrInput<'src>,
+        I::Token: Char,
+        E: ParserExtra<'src, I>,
+    {
+        let ascii = |c: Option<I::Token>| c.and_then(|c| c.to_ascii());
+
+        // Keep the 60 most significant bits of the mantissa, adjusting the exponent to account for the rest. Any
+        // non-zero bits that are dropped are remembered so that the result is rounded correctly.
+        let mut mantissa = 0u64;
+        let mut exp = 0i64;
+        let mut sticky = false;
+        let mut digits = float_digits(inp, 16, |digit| {
+            if mantissa >> 60 == 0 {
+                mantissa = mantissa * 16 + digit as u64;
+            } else {
+                exp += 4;
+                sticky |= digit != 0;
+            }
+        });
+        if ascii(inp.peek()) == Some(b'.') {
+            inp.skip();
+            digits += float_digits(inp, 16, |digit| {
+                if mantissa >> 60 == 0 {
+                    mantissa = mantissa * 16 + digit as u64;
+                    exp -= 4;
+                } else {
+                    sticky |= digit != 0;
+                }
+            });
+        }
+        if digits == 0 || !matches!(ascii(inp.peek()), Some(b'p' | b'P')) {
+            return None;
+        }
+        inp.skip();
+
+        let negative = match ascii(inp.peek()) {
+            Some(b'-') => {
+                inp.skip();
+                true
+            }
+            Some(b'+') => {
+                inp.skip();
+                false
+            }
+            _ => false,
+        };
+        let mut bin_exp = 0i64;
+        if float_digits(inp, 10, |digit| {
+            bin_exp = bin_exp.saturating_mul(10).saturating_add(digit as i64)
+        }) == 0
+        {
+            return None;
+        }
+        let exp = exp.saturating_add(if negative { -bin_exp } else { bin_exp });
+
+        Some(scale_by_pow2((mantissa | sticky as u64) as f64, exp))
+    }
+}
+
+// Report that a float was expected at `before`, leaving the input there. The scan may have consumed part of a literal
+// before giving up, so the found token is the first one of the literal rather than wherever the scan stopped.
+fn float_expected<'src, 'parse, I, E>(
+    inp: &mut InputRef<'src, 'parse, I, E>,
+    before: input::Checkpoint<
+        'src,
+        'parse,
+        I,
+        <E::State as inspector::Inspector<'src, I>>::Checkpoint,
+    >,
+) where
+    I: StrInput<'src>,
+    I::Token: Char,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, TextExpected<'src, I>>,
+{
+    inp.rewind(before.clone());
+    let found = inp.next_maybe_inner();
+    let span = inp.span_since(before.cursor());
+    inp.rewind(before);
+    inp.add_alt([TextExpected::Float], found.map(|f| f.into()), span);
+}
+
+impl<'src, I, E> Parser<'src, I, (f64, I::Slice), E> for Float<I, E>
+where
+    I: StrInput<'src>,
+    I::Token: Char,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, TextExpected<'src, I>>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, (f64, I::Slice)> {
+        let before = inp.save();
+        let mut text = String::new();
+        let value = match self.scan(inp, &mut text) {
+            Some(ScannedFloat::Value(value)) => Some(value),
+            Some(ScannedFloat::Decimal) => text.parse().ok(),
+            None => None,
+        };
+        match value {
+            Some(value) => Ok(M::bind(|| (value, inp.slice_since(before.cursor()..)))),
+            None => {
+                float_expected(inp, before);
+                Err(())
+            }
+        }
+    }
+
+    go_extra!((f64, I::Slice));
+}
+
+#[cfg(feature = "rust_decimal")]
+impl<'src, I, E> Parser<'src, I, (rust_decimal::Decimal, I::Slice), E>
+    for Float<I, E, rust_decimal::Decimal>
+where
+    I: StrInput<'src>,
+    I::Token: Char,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, TextExpected<'src, I>> + LiteralError<'src, I>,
+{
+    #[inline]
+    fn go<M: Mode>(
+        &self,
+        inp: &mut InputRef<'src, '_, I, E>,
+    ) -> PResult<M, (rust_decimal::Decimal, I::Slice)> {
+        use rust_decimal::Decimal;
+
+        let before = inp.save();
+        let first = inp.peek_maybe();
+        let mut text = String::new();
+        match self.scan(inp, &mut text) {
+            Some(ScannedFloat::Decimal) => {
+                // `Decimal` doesn't accept a leading or trailing dot
+                if text.starts_with('.') {
+                    text.insert(0, '0');
+                }
+                let text = text.strip_suffix('.').unwrap_or(&text);
+                let value = if text.contains('e') {
+                    Decimal::from_scientific(text)
+                } else {
+                    Decimal::from_str_exact(text)
+                };
+                match value {
+                    Ok(value) => Ok(M::bind(|| (value, inp.slice_since(before.cursor()..)))),
+                    Err(_) => {
+                        let span = inp.span_since(before.cursor());
+                        inp.add_alt_err(
+                            &before.cursor().inner,
+                            LiteralError::out_of_range("decimal", "Decimal", first, span),
+                        );
+                        inp.rewind(before);
+                        Err(())
+                    }
+                }
+            }
+            _ => {
+                float_expected(inp, before);
+                Err(())
+            }
+        }
+    }
+
+    go_extra!((rust_decimal::Decimal, I::Slice));
+}
+
+/// A chunk of literal text within a template literal. See [`template`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TemplateText<S, Sp> {
+    /// The text with escape sequences interpreted, or `None` if the text contained an invalid escape sequence.
+    pub cooked: Option<String>,
+    /// The text exactly as it appears in the source, escape sequences and all.
+    pub raw: S,
+    /// The span of the text within the source.
+    pub span: Sp,
+}
+
+/// The output of the [`template`] parser.
+///
+/// Text chunks and interpolated expressions alternate, starting and ending with a text chunk, so there is always
+/// exactly one more text chunk than there are expressions. Text chunks may be empty.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Template<S, Sp, O> {
+    /// The literal text chunks of the template.
+    pub texts: Vec<TemplateText<S, Sp>>,
+    /// The expressions interpolated between the text chunks.
+    pub exprs: Vec<O>,
+}
+
+/// A parser that accepts a JavaScript-style template literal, such as `` `Hello, ${name}!` ``.
+///
+/// Template literals are delimited by backticks and may contain interpolated expressions between `${` and `}`, which
+/// are parsed with the supplied parser. All spans (both those of text chunks and those produced by the expression
+/// parser) point into the original source.
+///
+/// Text chunks support the escape sequences `\n`, `\r`, `\t`, `\0`, `\b`, `\f`, `\v`, `\\`, `` \` ``, `\$`, `\'`,
+/// `\"`, `\xHH`, and `\u{H...}`, as well as line continuations (a backslash followed by a newline). Any other escape
+/// sequence is accepted, but causes the chunk's [`TemplateText::cooked`] field to be `None`, mirroring the semantics
+/// of JavaScript's tagged templates. The raw text is always available.
+///
+/// The output type of this parser is [`Template`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, text::Template};
+/// let expr = text::ascii::ident::<_, extra::Err<Simple<char>>>().padded();
+/// let template = text::template(expr);
+///
+/// let Template { texts, exprs } = template.parse(r"`Hello, ${ name }!\n`").unwrap();
+/// assert_eq!(exprs, vec!["name"]);
+/// assert_eq!(texts[0].cooked.as_deref(), Some("Hello, "));
+/// assert_eq!(texts[0].span, SimpleSpan::from(1..8));
+/// assert_eq!(texts[1].cooked.as_deref(), Some("!\n"));
+/// assert_eq!(texts[1].raw, r"!\n");
+/// ```
+pub fn template<'src, I, O, E, P>(
+    expr: P,
+) -> impl Parser<'src, I, Template<I::Slice, I::Span, O>, E> + Clone
+where
+    I: StrInput<'src, Token = char>,
+    E: ParserExtra<'src, I>,
+    P: Parser<'src, I, O, E> + Clone,
+{
+    let hex = |n: usize| {
+        any()
+            .filter(|c: &char| c.is_ascii_hexdigit())
+            .repeated()
+            .exactly(n)
+            .to_slice()
+    };
+    // Each character is `None` if it is an invalid escape, or `Some(None)` if it contributes no text to the chunk
+    let escape = just('\\').ignore_then(choice((
+        // Line continuation
+        just("\r\n").or(just("\n")).to(Some(None)),
+        just('x')
+            .ignore_then(hex(2))
+            .map(|s: I::Slice| char_from_hex(&I::stringify(s)).map(Some)),
+        just("u{")
+            .ignore_then(
+                any()
+                    .filter(|c: &char| c.is_ascii_hexdigit())
+                    .repeated()
+                    .at_least(1)
+                    .to_slice(),
+            )
+            .then_ignore(just('}'))
+            .map(|s: I::Slice| char_from_hex(&I::stringify(s)).map(Some)),
+        any().map(|c| {
+            Some(Some(match c {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                '0' => '\0',
+                'b' => '\x08',
+                'f' => '\x0C',
+                'v' => '\x0B',
+                '\\' | '`' | '$' | '\'' | '"' => c,
+                _ => return None,
+            }))
+        }),
+    )));
+    let text_char = choice((
+        escape,
+        just('$')
+            .then_ignore(just('{').not().rewind())
+            .to(Some(Some('$'))),
+        none_of("`\\$").map(|c| Some(Some(c))),
+    ));
+    let text = text_char
+        .repeated()
+        .collect::<Vec<_>>()
+        .map_with(|chars, e| TemplateText {
+            // An invalid escape sequence anywhere in the chunk invalidates the whole cooked string
+            cooked: chars.into_iter().try_fold(String::new(), |mut s, c| {
+                s.extend(c?);
+                Some(s)
+            }),
+            raw: e.slice(),
+            span: e.span(),
+        });
+
+    just('`')
+        .ignore_then(
+            text.then(
+                expr.delimited_by(just("${"), just('}'))
+                    .then(text)
+                    .repeated()
+                    .collect::<Vec<_>>(),
+            ),
+        )
+        .then_ignore(just('`'))
+        .map(|(first, rest)| {
+            let mut texts = Vec::with_capacity(rest.len() + 1);
+            let mut exprs = Vec::with_capacity(rest.len());
+            texts.push(first);
+            for (expr, text) in rest {
+                exprs.push(expr);
+                texts.push(text);
+            }
+            Template { texts, exprs }
+        })
+}
+
+fn char_from_hex(s: &str) -> Option<char> {
+    u32::from_str_radix(s, 16).ok().and_then(char::from_u32)
+}
+
+/// A keyword matched by [`ascii::keywords_ignore_case`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeywordMatch<T, S, Sp> {
+    /// The canonical value associated with the keyword.
+    pub value: T,
+    /// The keyword exactly as it appears in the source, with its original case.
+    pub slice: S,
+    /// The span of the keyword within the source.
+    pub span: Sp,
+}
+
+/// See [`phrase`].
+pub struct Phrase<S, T, OT, I, E> {
+    phrase: S,
+    trivia: Option<T>,
+    ignore_case: bool,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(OT, I, E)>,
+}
+
+impl<S: Copy, T: Copy, OT, I, E> Copy for Phrase<S, T, OT, I, E> {}
+impl<S: Clone, T: Clone, OT, I, E> Clone for Phrase<S, T, OT, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            phrase: self.phrase.clone(),
+            trivia: self.trivia.clone(),
+            ignore_case: self.ignore_case,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<S, T, OT, I, E> Phrase<S, T, OT, I, E> {
+    /// Match the words of the phrase regardless of their ASCII case, so `group by` also matches `GROUP By`.
+    pub fn ignore_case(self) -> Self {
+        Self {
+            ignore_case: true,
+            ..self
+        }
+    }
+
+    /// Use the given parser to skip trivia (such as comments) between words, instead of whitespace.
+    ///
+    /// The parser is applied repeatedly between words, and must succeed at least once. It should consume whitespace
+    /// too, if whitespace is allowed.
+    pub fn trivia<T2, OT2>(self, trivia: T2) -> Phrase<S, T2, OT2, I, E> {
+        Phrase {
+            phrase: self.phrase,
+            trivia: Some(trivia),
+            ignore_case: self.ignore_case,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// A parser that accepts a phrase of several keywords, like `group by`, allowing any amount of whitespace between the
+/// words.
+///
+/// The words of the phrase are separated by whitespace. Each word in the input must be separated from the next by at
+/// least one whitespace character, but any more than that is accepted, so `group by` also matches `group   by` and
+/// `group\nby`. Comments or other trivia can be allowed between words with [`Phrase::trivia`]. As with
+/// [`keyword`](unicode::keyword), trailing identifier characters are rejected, so `group by` does not match
+/// `group byte`.
+///
+/// A failure produces one error covering the phrase as far as it matched, expecting the whole phrase.
+///
+/// The output type of this parser is `I::Slice`, the phrase as it was written (including the whitespace between its
+/// words).
+///
+/// # Panics
+///
+/// Panics (in debug builds) if the phrase contains non-ASCII characters.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let group_by = text::phrase::<_, _, extra::Err<Simple<char>>>("group by").ignore_case();
+///
+/// assert_eq!(group_by.parse("GROUP   BY").into_result(), Ok("GROUP   BY"));
+/// assert_eq!(group_by.parse("group\n  by").into_result(), Ok("group\n  by"));
+/// assert!(group_by.parse("groupby").has_errors());
+/// assert!(group_by.parse("group bytes").has_errors());
+///
+/// // Allow comments between the words
+/// let comment = just("/*").then(any().and_is(just("*/").not()).repeated()).then(just("*/"));
+/// let group_by = group_by.trivia(comment.ignored().or(text::whitespace().at_least(1)));
+/// assert_eq!(group_by.parse("group /* ! */ by").into_result(), Ok("group /* ! */ by"));
+/// ```
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn phrase<'src, I, S, E>(phrase: S) -> Phrase<S, primitive::Empty<I, E>, (), I, E>
+where
+    I: StrInput<'src>,
+    I::Token: Char,
+    S: AsRef<str> + Clone,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, S>,
+{
+    debug_assert!(
+        phrase.as_ref().is_ascii(),
+        "Phrases must only contain ASCII characters, not {:?}",
+        phrase.as_ref()
+    );
+    Phrase {
+        phrase,
+        trivia: None,
+        ignore_case: false,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'src, S, T, OT, I, E> Parser<'src, I, I::Slice, E> for Phrase<S, T, OT, I, E>
+where
+    I: StrInput<'src>,
+    I::Token: Char,
+    S: AsRef<str> + Clone,
+    T: Parser<'src, I, OT, E>,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, S>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, I::Slice> {
+        let before = inp.save();
+        let matched = 'matched: {
+            for (i, word) in self.phrase.as_ref().split_whitespace().enumerate() {
+                if i > 0 {
+                    let start = inp.cursor();
+                    match &self.trivia {
+                        Some(trivia) => loop {
+                            let before_trivia = inp.save();
+                            match trivia.go::<Check>(inp) {
+                                Ok(()) if inp.cursor() > *before_trivia.cursor() => {}
+                                _ => {
+                                    inp.rewind(before_trivia);
+                                    break;
+                                }
+                            }
+                        },
+                        None => {
+                            while inp.peek().map_or(false, |c| c.is_whitespace()) {
+                                inp.skip();
+                            }
+                        }
+                    }
+                    if inp.cursor() == start {
+                        break 'matched false;
+                    }
+                }
+
+                for expected in word.bytes() {
+                    let found = inp.peek().and_then(|c| c.to_ascii());
+                    let eq = match found {
+                        Some(found) if self.ignore_case => found.eq_ignore_ascii_case(&expected),
+                        Some(found) => found == expected,
+                        None => false,
+                    };
+                    if !eq {
+                        break 'matched false;
+                    }
+                    inp.skip();
+                }
+            }
+
+            !inp.peek().map_or(false, |c| c.is_ident_continue())
+        };
+
+        if matched {
+            Ok(M::bind(|| inp.slice_since(before.cursor()..)))
+        } else {
+            let found = inp.peek_maybe();
+            let span = inp.span_since(before.cursor());
+            inp.rewind(before);
+            inp.add_alt([self.phrase.clone()], found, span);
+            Err(())
+        }
+    }
+
+    go_extra!(I::Slice);
+}
+
 /// Parsers and utilities for working with ASCII inputs.
 pub mod ascii {
     use super::*;
@@ -617,6 +1655,70 @@ pub mod ascii {
             })
             .to_slice()
     }
+
+    /// Like [`keyword`], but accepts any of several keywords while ignoring ASCII case, producing the value associated
+    /// with the keyword that was found.
+    ///
+    /// This suits languages like SQL, where `SELECT`, `select`, and `Select` are all the same keyword. The original
+    /// slice (in whatever case it was written) and its span are kept alongside the value, so that tools like
+    /// formatters can preserve the source as written.
+    ///
+    /// As with [`keyword`], trailing identifier characters are rejected, so `selection` does not match `select`.
+    ///
+    /// The output type of this parser is [`KeywordMatch`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, text::KeywordMatch};
+    /// #[derive(Copy, Clone, Debug, PartialEq)]
+    /// enum Kw { Select, From }
+    ///
+    /// let kw = text::ascii::keywords_ignore_case::<&str, _, _, extra::Err<Simple<char>>>([
+    ///     ("select", Kw::Select),
+    ///     ("from", Kw::From),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     kw.parse("SeLeCt").into_result(),
+    ///     Ok(KeywordMatch { value: Kw::Select, slice: "SeLeCt", span: SimpleSpan::from(0..6) }),
+    /// );
+    /// assert_eq!(kw.parse("FROM").into_result().map(|m| m.value), Ok(Kw::From));
+    /// assert!(kw.parse("selection").has_errors());
+    /// ```
+    pub fn keywords_ignore_case<'src, I, K, T, E>(
+        keywords: impl IntoIterator<Item = (K, T)>,
+    ) -> impl Parser<'src, I, KeywordMatch<T, I::Slice, I::Span>, E> + Clone + 'src
+    where
+        I: StrInput<'src>,
+        I::Slice: AsRef<[u8]>,
+        I::Token: Char + 'src,
+        K: AsRef<[u8]> + Clone + 'src,
+        T: Clone + 'src,
+        E: ParserExtra<'src, I> + 'src,
+        E::Error: LabelError<'src, I, TextExpected<'src, I>> + LabelError<'src, I, K>,
+    {
+        let keywords = Rc::new(keywords.into_iter().collect::<Vec<_>>());
+        ident()
+            .try_map(move |s: I::Slice, span| {
+                keywords
+                    .iter()
+                    .find(|(kw, _)| kw.as_ref().eq_ignore_ascii_case(s.as_ref()))
+                    .map(|(_, value)| (value.clone(), s))
+                    .ok_or_else(|| {
+                        LabelError::expected_found(
+                            keywords.iter().map(|(kw, _)| kw.clone()),
+                            None,
+                            span,
+                        )
+                    })
+            })
+            .map_with(|(value, slice), e| KeywordMatch {
+                value,
+                slice,
+                span: e.span(),
+            })
+    }
 }
 
 // Unicode is the default
@@ -648,12 +1750,12 @@ pub mod unicode {
         }
 
         /// Gets an iterator over code points.
-        pub fn code_points(&self) -> Chars {
+        pub fn code_points(&self) -> Chars<'_> {
             self.inner.chars()
         }
 
         /// Gets an iterator over bytes.
-        pub fn bytes(&self) -> Bytes {
+        pub fn bytes(&self) -> Bytes<'_> {
             self.inner.bytes()
         }
 
@@ -708,17 +1810,17 @@ pub mod unicode {
         }
 
         /// Gets an iterator over graphemes.
-        pub fn iter(&self) -> GraphemesIter {
+        pub fn iter(&self) -> GraphemesIter<'_> {
             self.into_iter()
         }
 
         /// Gets an iterator over code points.
-        pub fn code_points(&self) -> Chars {
+        pub fn code_points(&self) -> Chars<'_> {
             self.inner.chars()
         }
 
         /// Gets an iterator over bytes.
-        pub fn bytes(&self) -> Bytes {
+        pub fn bytes(&self) -> Bytes<'_> {
             self.inner.bytes()
         }
 
@@ -1039,6 +2141,112 @@ pub mod unicode {
             })
             .to_slice()
     }
+
+    /// Like [`just`], but matches any text that is canonically equivalent to the given text.
+    ///
+    /// Text written by users may use either composed (NFC) or decomposed (NFD) forms of the same characters: `é` may
+    /// be the single character `U+00E9`, or `e` followed by the combining accent `U+0301`. This parser accepts either,
+    /// so long as the input is canonically equivalent to the pattern once both are normalized. A match may not end
+    /// part way through a character's combining marks, so `"e"` will not match the start of a decomposed `é`.
+    ///
+    /// The output type of this parser is `I::Slice`, the input that was matched (in whatever form it was written).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let cafe = text::unicode::just_normalized::<&str, _, extra::Err<Simple<char>>>("caf\u{e9}");
+    ///
+    /// assert_eq!(cafe.parse("caf\u{e9}").into_result(), Ok("caf\u{e9}"));
+    /// assert_eq!(cafe.parse("cafe\u{301}").into_result(), Ok("cafe\u{301}"));
+    /// assert!(cafe.parse("cafe").has_errors());
+    ///
+    /// // A match can't split a character from its combining marks
+    /// let e = text::unicode::just_normalized::<&str, _, extra::Err<Simple<char>>>("e");
+    /// assert!(e.lazy().parse("e\u{301}").has_errors());
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn just_normalized<'src, I, S, E>(
+        pattern: S,
+    ) -> impl Parser<'src, I, <I as SliceInput<'src>>::Slice, E> + Clone
+    where
+        I: StrInput<'src, Token = char>,
+        S: AsRef<str> + Clone,
+        E: ParserExtra<'src, I>,
+        E::Error: LabelError<'src, I, S>,
+    {
+        use unicode_normalization::{char::canonical_combining_class, UnicodeNormalization};
+
+        let target = pattern.as_ref().nfd().collect::<Vec<_>>();
+        custom(move |inp| {
+            let before = inp.save();
+            let mut consumed = String::new();
+            let matched = loop {
+                let Some(c) = inp.next() else {
+                    break false;
+                };
+                consumed.push(c);
+                let len = consumed.nfd().count();
+                if len >= target.len() {
+                    break len == target.len() && consumed.nfd().eq(target.iter().copied());
+                }
+            };
+
+            // Don't split a character from combining marks that follow it
+            if matched
+                && inp
+                    .peek()
+                    .map_or(true, |c| canonical_combining_class(c) == 0)
+            {
+                Ok(inp.slice_since(before.cursor()..))
+            } else {
+                let span = inp.span_since(before.cursor());
+                inp.rewind(before);
+                Err(LabelError::expected_found([pattern.clone()], None, span))
+            }
+        })
+    }
+
+    /// Like [`keyword`], but matches any identifier that is canonically equivalent to the given keyword.
+    ///
+    /// See [`just_normalized`] for more information about canonical equivalence.
+    ///
+    /// The output type of this parser is `I::Slice`, the identifier that was matched (in whatever form it was
+    /// written).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let kw = text::unicode::keyword_normalized::<&str, _, extra::Err<Simple<char>>>("r\u{e9}sum\u{e9}");
+    ///
+    /// assert_eq!(kw.parse("re\u{301}sume\u{301}").into_result(), Ok("re\u{301}sume\u{301}"));
+    /// assert!(kw.parse("r\u{e9}sum\u{e9}s").has_errors());
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn keyword_normalized<'src, I, S, E>(
+        keyword: S,
+    ) -> impl Parser<'src, I, <I as SliceInput<'src>>::Slice, E> + Clone + 'src
+    where
+        I: StrInput<'src, Token = char>,
+        I::Slice: AsRef<str>,
+        S: AsRef<str> + Clone + 'src,
+        E: ParserExtra<'src, I> + 'src,
+        E::Error: LabelError<'src, I, TextExpected<'src, I>> + LabelError<'src, I, S>,
+    {
+        use unicode_normalization::UnicodeNormalization;
+
+        let target = keyword.as_ref().nfc().collect::<String>();
+        ident()
+            .try_map(move |s: I::Slice, span| {
+                if s.as_ref().nfc().eq(target.chars()) {
+                    Ok(())
+                } else {
+                    Err(LabelError::expected_found([keyword.clone()], None, span))
+                }
+            })
+            .to_slice()
+    }
 }
 
 // TODO: Better native form of semantic indentation that uses the context system?
@@ -1137,4 +2345,199 @@ mod tests {
         make_ascii_kw_parser::<&str>("שלום");
     }
     */
+
+    #[test]
+    fn template_nested() {
+        let expr = recursive(|expr| {
+            text::template(expr)
+                .map(|t| t.exprs.len())
+                .or(text::int(10).map(|_| 0))
+                .padded()
+        });
+        let template = text::template::<_, _, extra::Default, _>(expr);
+
+        let t = template.parse("`a${ `b${1}c` }d$e\\q`").unwrap();
+        assert_eq!(t.exprs, vec![1]);
+        assert_eq!(t.texts.len(), 2);
+        assert_eq!(t.texts[0].raw, "a");
+        assert_eq!(t.texts[1].raw, "d$e\\q");
+        assert_eq!(t.texts[1].span, SimpleSpan::from(15..20));
+        assert_eq!(t.texts[1].cooked, None);
+
+        let t = template.parse("`\\u{1F980}\\x41\\\n`").unwrap();
+        assert_eq!(t.texts[0].cooked.as_deref(), Some("\u{1F980}A"));
+
+        assert!(template.parse("`${`").has_errors());
+        assert!(template.parse("`abc").has_errors());
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn just_normalized_forms() {
+        // The pattern itself is decomposed, and the input is composed
+        let angstrom =
+            text::unicode::just_normalized::<&str, _, extra::Err<Simple<char>>>("A\u{30a}")
+                .then(just('!'));
+
+        assert_eq!(angstrom.parse("\u{c5}!").into_result(), Ok(("\u{c5}", '!')));
+        assert_eq!(
+            angstrom.parse("A\u{30a}!").into_result(),
+            Ok(("A\u{30a}", '!'))
+        );
+        assert!(angstrom.parse("A!").has_errors());
+        assert!(angstrom.parse("A\u{30a}\u{301}!").has_errors());
+    }
+
+    #[test]
+    fn keywords_ignore_case_bytes() {
+        let kw = text::ascii::keywords_ignore_case::<&[u8], _, _, extra::Err<Simple<u8>>>([
+            ("and", 0),
+            ("or", 1),
+        ])
+        .map(|m| (m.value, m.slice))
+        .padded()
+        .repeated()
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            kw.parse(b"AND oR" as &[u8]).into_result(),
+            Ok(vec![(0, b"AND" as &[u8]), (1, b"oR" as &[u8])])
+        );
+        assert!(kw.parse(b"andor" as &[u8]).has_errors());
+    }
+
+    #[test]
+    fn phrase_error_span() {
+        let order_by = text::phrase::<&str, _, extra::Err<Rich<char>>>("order by");
+
+        let errs = order_by.parse("order  bx").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..8));
+
+        let order_by = text::phrase::<&[u8], _, extra::Err<Simple<u8>>>("order by").ignore_case();
+        assert_eq!(
+            order_by.parse(b"Order\tBY" as &[u8]).into_result(),
+            Ok(b"Order\tBY" as &[u8])
+        );
+    }
+
+    #[test]
+    fn int_checked_range() {
+        let i8_hex = text::int_checked::<i8, &str, extra::Err<Rich<char>>>(16);
+        assert_eq!(i8_hex.parse("7f").into_result(), Ok(127));
+
+        let errs = i8_hex.parse("80").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..2));
+        assert_eq!(errs[0].to_string(), "integer literal out of range for i8");
+
+        let u16_dec = text::int_checked::<u16, &[u8], extra::Err<Simple<u8>>>(10);
+        assert_eq!(u16_dec.parse(b"65535" as &[u8]).into_result(), Ok(65535));
+        assert!(u16_dec.parse(b"65536" as &[u8]).has_errors());
+    }
+
+    #[test]
+    fn float_literals() {
+        let rust = text::float::<&str, extra::Err<Rich<char>>>();
+        assert_eq!(
+            rust.integer(true)
+                .then(just(".max"))
+                .parse("1.max")
+                .into_result(),
+            Ok(((1.0, "1"), ".max"))
+        );
+        assert_eq!(
+            rust.integer(true)
+                .then(text::ident())
+                .parse("2e")
+                .into_result(),
+            Ok(((2.0, "2"), "e"))
+        );
+        assert_eq!(rust.parse("1.5E+2").into_result(), Ok((150.0, "1.5E+2")));
+
+        // Errors are reported at the start of the literal, however much of it was scanned
+        for (input, found) in [("12", '1'), ("1..", '1'), ("x", 'x')] {
+            let errs = rust.parse(input).into_errors();
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+            assert_eq!(
+                errs[0].to_string(),
+                format!("found {found:?} expected float literal")
+            );
+        }
+
+        let c = text::float::<&[u8], extra::Err<Simple<u8>>>()
+            .hex(true)
+            .trailing_dot(false);
+        assert_eq!(
+            c.parse(b"0x.1p-1070" as &[u8]).into_result(),
+            Ok((f64::from_bits(1), b"0x.1p-1070" as &[u8]))
+        );
+        assert_eq!(
+            c.parse(b"0xffffffffffffffffffp0" as &[u8]).into_result(),
+            Ok((
+                0xffffffffffffffffffu128 as f64,
+                b"0xffffffffffffffffffp0" as &[u8]
+            ))
+        );
+        assert_eq!(
+            c.parse(b"0X1P1000000" as &[u8]).into_result().unwrap().0,
+            f64::INFINITY
+        );
+        assert!(c.parse(b"0x1.8" as &[u8]).has_errors());
+        assert!(c.parse(b"1." as &[u8]).has_errors());
+        assert!(c.parse(b"inf" as &[u8]).has_errors());
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn int_checked_bigint() {
+        use num_bigint::{BigInt, BigUint};
+
+        let big = text::int_checked::<BigUint, &str, extra::Err<Rich<char>>>(16);
+        assert_eq!(
+            big.parse("10000000000000000000000000000000").into_result(),
+            Ok(BigUint::from(1u8) << 124)
+        );
+        let big = text::int_checked::<BigInt, &[u8], extra::Err<Simple<u8>>>(10);
+        assert_eq!(
+            big.parse(b"340282366920938463463374607431768211456" as &[u8])
+                .into_result(),
+            Ok(BigInt::from(u128::MAX) + 1)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn float_decimal() {
+        use rust_decimal::Decimal;
+
+        let decimal = text::float::<&str, extra::Err<Rich<char>>>()
+            .leading_dot(true)
+            .decimal();
+        assert_eq!(
+            decimal.parse(".25").into_result(),
+            Ok((Decimal::new(25, 2), ".25"))
+        );
+        assert_eq!(
+            decimal.parse("3.").into_result(),
+            Ok((Decimal::new(3, 0), "3."))
+        );
+        assert_eq!(
+            decimal.parse("2.5E-3").into_result(),
+            Ok((Decimal::new(25, 4), "2.5E-3"))
+        );
+
+        let errs = decimal
+            .parse("0.00000000000000000000000000001")
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..31));
+        assert!(decimal.parse("inf").has_errors());
+
+        let errs = decimal.parse("1..").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+        assert_eq!(errs[0].to_string(), "found '1' expected float literal");
+    }
 }
@@ -0,0 +1,1087 @@
+//! Recording parses so that they can be replayed later, such as to reproduce a bug.
+//!
+//! *"The History of every major Galactic Civilization tends to pass through three distinct and recognizable phases,
+//! those of Survival, Inquiry and Sophistication."*
+//!
+//! A [`Trace`] holds a copy of the input that was parsed along with the decisions that the parser made while parsing
+//! it: how far it advanced through the input, and where it backtracked to when a branch failed. Traces are compact,
+//! and can be serialized with the `serde` feature, which makes them suitable for attaching to bug reports.
+//!
+//! Replaying a trace parses the recorded input again and checks that the parser makes the same decisions, reporting
+//! the first point at which it does not. Because the replay is deterministic, it can be run under a debugger to step
+//! through the exact parse that went wrong.
+//!
+//! Recording requires the parser's state to be a [`Recorder`], which is an [`Inspector`] that is told about every
+//! token consumed and every rewind made by the parser.
+//!
+//! When the input that triggers a bug is large, [`minimize`] and [`minimize_str`] can shrink it to a small input that
+//! still triggers the bug before it is recorded.
+//!
+//! To find such inputs in the first place, [`Fuzzer`] generates inputs by mutating a corpus of examples, keeping those
+//! that reach parsers marked with [`cover`] in new ways (as recorded by a [`Coverage`] inspector) so that deep parts of
+//! a grammar are explored.
+//! [`measure_coverage`] uses the same marks to report which parts of a grammar a corpus of tests never exercises.
+//! [`check_branch_order`] uses a corpus to find [`choice`] branches that are shadowed by earlier branches.
+//!
+//! # Examples
+//!
+//! ```
+//! use chumsky::{prelude::*, trace::{Recorder, Trace}};
+//!
+//! fn parser<'src>() -> impl Parser<'src, &'src str, Vec<&'src str>, extra::Full<Rich<'src, char>, Recorder, ()>> {
+//!     // `let` followed by a space is a keyword, otherwise the parser backtracks and tries an identifier
+//!     just("let ")
+//!         .to_slice()
+//!         .or(text::ascii::ident())
+//!         .padded()
+//!         .repeated()
+//!         .collect()
+//! }
+//!
+//! let (result, trace) = Trace::record(&parser(), "letter let x");
+//! assert_eq!(result.into_result(), Ok(vec!["letter", "let ", "x"]));
+//!
+//! // Later, perhaps after the trace has been sent along with a bug report...
+//! let replayed = trace.replay(&parser()).unwrap();
+//! assert_eq!(replayed.into_output(), Some(vec!["letter", "let ", "x"]));
+//! ```
+
+use super::*;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use input::{Checkpoint, Cursor};
+use inspector::Inspector;
+
+/// A decision made by a parser, as recorded in a [`Trace`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// The parser consumed the given number of tokens in a row.
+    Advance(usize),
+    /// The parser backtracked to the position after the given number of tokens.
+    Rewind(usize),
+}
+
+/// An [`Inspector`] that records the [`Event`]s of a parse. See the [module-level documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct Recorder {
+    pos: usize,
+    events: Vec<Event>,
+}
+
+impl Recorder {
+    /// Create a new recorder with no events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the events recorded so far.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Take the events recorded so far, leaving the recorder ready to record another parse.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        self.pos = 0;
+        core::mem::take(&mut self.events)
+    }
+}
+
+impl<'src, I: Input<'src>> Inspector<'src, I> for Recorder {
+    type Checkpoint = usize;
+
+    #[inline]
+    fn on_token(&mut self, _: &I::Token) {
+        self.pos += 1;
+        match self.events.last_mut() {
+            Some(Event::Advance(n)) => *n += 1,
+            _ => self.events.push(Event::Advance(1)),
+        }
+    }
+
+    #[inline]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) -> usize {
+        self.pos
+    }
+
+    #[inline]
+    fn on_rewind<'parse>(&mut self, marker: &Checkpoint<'src, 'parse, I, usize>) {
+        let pos = *marker.inspector();
+        if pos != self.pos {
+            self.pos = pos;
+            self.events.push(Event::Rewind(pos));
+        }
+    }
+}
+
+/// The point at which a replayed parse stopped matching its [`Trace`]. See [`Trace::replay`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The index of the first event that differed.
+    pub index: usize,
+    /// The recorded event, or `None` if the replay made more decisions than were recorded.
+    pub expected: Option<Event>,
+    /// The event of the replay, or `None` if the replay made fewer decisions than were recorded.
+    pub found: Option<Event>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "replay diverged from trace at event {}: ", self.index)?;
+        match (self.expected, self.found) {
+            (Some(expected), Some(found)) => write!(f, "expected {expected:?}, found {found:?}"),
+            (Some(expected), None) => write!(f, "expected {expected:?}, found end of parse"),
+            (None, Some(found)) => write!(f, "expected end of parse, found {found:?}"),
+            (None, None) => write!(f, "no difference"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Divergence {}
+
+/// A recorded parse: the input that was parsed, and the decisions that the parser made while parsing it.
+///
+/// The input is owned, such as a [`String`] for parsers of `&str` or a [`Vec`] of tokens for parsers of token
+/// slices. See the [module-level documentation](self).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Trace<X> {
+    /// The input that was parsed.
+    pub input: X,
+    /// The decisions that the parser made.
+    pub events: Vec<Event>,
+}
+
+impl<X> Trace<X> {
+    /// Parse the input with the given parser, recording a copy of the input along with the parser's decisions.
+    pub fn record<'src, Y, O, E, P>(parser: &P, input: &'src Y) -> (ParseResult<O, E::Error>, Self)
+    where
+        Y: ToOwned<Owned = X> + ?Sized,
+        &'src Y: Input<'src>,
+        E: ParserExtra<'src, &'src Y, State = Recorder>,
+        E::Context: Default,
+        P: Parser<'src, &'src Y, O, E>,
+    {
+        let mut recorder = Recorder::new();
+        let result = parser.parse_with_state(input, &mut recorder);
+        let trace = Self {
+            input: input.to_owned(),
+            events: recorder.take_events(),
+        };
+        (result, trace)
+    }
+
+    /// Parse the recorded input again with the given parser, checking that it makes the same decisions as when the
+    /// trace was recorded.
+    ///
+    /// If the decisions differ, such as because the parser has been changed since, the first difference is returned.
+    pub fn replay<'src, Y, O, E, P>(
+        &'src self,
+        parser: &P,
+    ) -> Result<ParseResult<O, E::Error>, Divergence>
+    where
+        X: Borrow<Y>,
+        Y: ?Sized,
+        &'src Y: Input<'src>,
+        E: ParserExtra<'src, &'src Y, State = Recorder>,
+        E::Context: Default,
+        P: Parser<'src, &'src Y, O, E>,
+    {
+        let mut recorder = Recorder::new();
+        let result = parser.parse_with_state(self.input.borrow(), &mut recorder);
+        let events = recorder.take_events();
+
+        let index = self
+            .events
+            .iter()
+            .zip(&events)
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| self.events.len().min(events.len()));
+        if index == self.events.len() && index == events.len() {
+            Ok(result)
+        } else {
+            Err(Divergence {
+                index,
+                expected: self.events.get(index).copied(),
+                found: events.get(index).copied(),
+            })
+        }
+    }
+}
+
+/// Shrink an input to a minimal one for which `fails` still returns `true`, by repeatedly removing parts of it.
+///
+/// This is useful for finding a small reproduction of a bug in a grammar: `fails` might check that the parser panics
+/// (using [`std::panic::catch_unwind`]), or that it produces an error with the wrong span. The result is *1-minimal*:
+/// removing any single element from it makes `fails` return `false`. If `fails` does not return `true` for the
+/// original input, it is returned unchanged.
+///
+/// The search uses the delta debugging algorithm, which removes large chunks of the input first and then smaller ones,
+/// and so calls `fails` far fewer times than trying to remove each element in turn.
+///
+/// To minimize text, see [`minimize_str`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, trace::minimize};
+/// // A parser with a bug: it does not accept nested lists
+/// let parser = just::<_, &[char], extra::Default>('x')
+///     .or(just('[').ignore_then(just('x').repeated()).then_ignore(just(']')).to('l'))
+///     .repeated();
+///
+/// // Only balanced inputs should be accepted, so look for a balanced input that is rejected
+/// let balanced = |input: &[char]| {
+///     input.iter().try_fold(0usize, |depth, c| match c {
+///         '[' => Some(depth + 1),
+///         ']' => depth.checked_sub(1),
+///         _ => Some(depth),
+///     }) == Some(0)
+/// };
+///
+/// let input = "x[xx]x[x[x]xx]x".chars().collect::<Vec<_>>();
+/// let minimal = minimize(&input, |input| balanced(input) && parser.parse(input).has_errors());
+///
+/// assert_eq!(minimal, ['[', '[', ']', ']']);
+/// ```
+pub fn minimize<T: Clone>(input: &[T], mut fails: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut input = input.to_vec();
+    if !fails(&input) {
+        return input;
+    }
+
+    let mut n = 2;
+    while input.len() >= 2 {
+        let chunk = (input.len() + n - 1) / n;
+        let chunks = (0..input.len()).step_by(chunk);
+
+        // Try keeping just one chunk, and then try removing one chunk
+        let reduced = if let Some(start) = chunks
+            .clone()
+            .find(|&start| fails(&input[start..(start + chunk).min(input.len())]))
+        {
+            input = input[start..(start + chunk).min(input.len())].to_vec();
+            n = 2;
+            true
+        } else if let Some(candidate) = chunks
+            .map(|start| {
+                let mut candidate = input[..start].to_vec();
+                candidate.extend_from_slice(&input[(start + chunk).min(input.len())..]);
+                candidate
+            })
+            .find(|candidate| fails(candidate))
+        {
+            input = candidate;
+            n = (n - 1).max(2);
+            true
+        } else {
+            false
+        };
+
+        if !reduced {
+            if n >= input.len() {
+                break;
+            }
+            n = (n * 2).min(input.len());
+        }
+    }
+    input
+}
+
+/// Shrink some text to a minimal text for which `fails` still returns `true`. See [`minimize`].
+///
+/// Whole lines are removed first, and then individual characters.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, trace::minimize_str};
+/// use std::panic::{catch_unwind, set_hook};
+///
+/// // A parser with a bug: it panics when numbers are too large
+/// fn parser<'src>() -> impl Parser<'src, &'src str, Vec<u32>> {
+///     text::int(10)
+///         .from_str()
+///         .unwrapped()
+///         .padded()
+///         .separated_by(just(','))
+///         .allow_trailing()
+///         .collect()
+/// }
+///
+/// set_hook(Box::new(|_| {})); // Keep the output tidy
+/// let input = "1, 2,\n3, 4,\n5, 99999999999, 6\n";
+/// let minimal = minimize_str(input, |input| catch_unwind(|| parser().parse(input)).is_err());
+///
+/// assert_eq!(minimal, "9999999999");
+/// ```
+pub fn minimize_str(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut buf = String::new();
+    let mut fails_with = |parts: &[&str]| {
+        buf.clear();
+        buf.extend(parts.iter().copied());
+        fails(&buf)
+    };
+
+    let lines = minimize(
+        &input.split_inclusive('\n').collect::<Vec<_>>(),
+        &mut fails_with,
+    );
+    let text = lines.concat();
+    let chars = text
+        .char_indices()
+        .map(|(i, c)| &text[i..i + c.len_utf8()])
+        .collect::<Vec<_>>();
+    minimize(&chars, fails_with).concat()
+}
+
+// The number of covered parsers above a parser is part of its coverage, up to this limit
+const MAX_COVER_DEPTH: usize = 32;
+
+/// An [`Inspector`] that records which parsers marked with [`cover`] a parse reached, for a [`Fuzzer`] or
+/// [`measure_coverage`].
+///
+/// A parse only records its coverage if its state is a `Coverage`, or contains one (such as in a tuple with other
+/// inspectors).
+#[derive(Clone, Debug, Default)]
+pub struct Coverage {
+    depth: usize,
+    // The name of each covered parser that was reached, whether it succeeded, and its depth
+    reached: BTreeSet<(&'static str, bool, usize)>,
+    // How often each covered parser was tried and matched, in the order they were first reached
+    counts: Vec<CoverStats>,
+}
+
+impl Coverage {
+    /// Create a new coverage recorder that has not seen any parsers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'src, I: Input<'src>> Inspector<'src, I> for Coverage {
+    type Checkpoint = ();
+
+    #[inline(always)]
+    fn on_token(&mut self, _: &I::Token) {}
+
+    #[inline(always)]
+    fn on_save<'parse>(&self, _: &Cursor<'src, 'parse, I>) {}
+
+    #[inline(always)]
+    fn on_rewind<'parse>(&mut self, _: &Checkpoint<'src, 'parse, I, ()>) {}
+
+    fn on_cover_enter(&mut self, _name: &'static str) {
+        self.depth += 1;
+    }
+
+    fn on_cover_exit(&mut self, name: &'static str, matched: bool) {
+        self.depth -= 1;
+        self.reached
+            .insert((name, matched, self.depth.min(MAX_COVER_DEPTH)));
+        let stats = match self.counts.iter().position(|s| s.name == name) {
+            Some(idx) => &mut self.counts[idx],
+            None => {
+                self.counts.push(CoverStats {
+                    name,
+                    tried: 0,
+                    matched: 0,
+                });
+                self.counts.last_mut().unwrap()
+            }
+        };
+        stats.tried += 1;
+        stats.matched += matched as usize;
+    }
+}
+
+/// See [`cover`].
+#[derive(Copy, Clone)]
+pub struct Cover<A> {
+    name: &'static str,
+    parser: A,
+}
+
+/// Mark a parser with a name, so that a [`Fuzzer`] or [`measure_coverage`] can tell when inputs reach it.
+///
+/// Each time the parser is invoked, the parser's state (see [`Inspector`]) is told its name and whether it succeeded.
+/// When the state is a [`Coverage`], this is recorded along with how many marked parsers it was nested within, so
+/// that inputs which reach the same parser more deeply count as new coverage. Other states ignore it, so marked
+/// parsers can be left in a grammar.
+pub fn cover<A>(name: &'static str, parser: A) -> Cover<A> {
+    Cover { name, parser }
+}
+
+impl<'src, I, O, E, A> Parser<'src, I, O, E> for Cover<A>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    A: Parser<'src, I, O, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        inp.state().on_cover_enter(self.name);
+        let res = self.parser.go::<M>(inp);
+        inp.state().on_cover_exit(self.name, res.is_ok());
+        res
+    }
+
+    fn introspect(&self, cx: &mut introspect::Context<'_, 'src, I>) -> introspect::Node {
+        introspect::wrapper("Cover", self.parser.introspect(cx))
+    }
+
+    go_extra!(O);
+}
+
+/// How often a parser marked with [`cover`] was tried and matched. See [`CoverageReport`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CoverStats {
+    /// The name given to the parser by [`cover`].
+    pub name: &'static str,
+    /// The number of times the parser was tried, including attempts that were later backtracked.
+    pub tried: usize,
+    /// The number of times the parser matched.
+    pub matched: usize,
+}
+
+/// The coverage of a grammar over a corpus of inputs, as measured by [`measure_coverage`].
+#[derive(Clone, Debug, Default)]
+pub struct CoverageReport {
+    /// The number of inputs that were parsed.
+    pub inputs: usize,
+    /// Statistics for each parser marked with [`cover`] that was reached, in the order they were first reached.
+    pub parsers: Vec<CoverStats>,
+}
+
+impl CoverageReport {
+    /// The names of the marked parsers that were tried, but never matched.
+    pub fn never_matched(&self) -> Vec<&'static str> {
+        self.parsers
+            .iter()
+            .filter(|stats| stats.matched == 0)
+            .map(|stats| stats.name)
+            .collect()
+    }
+
+    /// The names among `names` of the marked parsers that were never even tried.
+    ///
+    /// A parser that was never invoked leaves no trace, so the names of every marked parser in the grammar must be
+    /// given. A [`choice`] branch that is never tried is usually shadowed by an earlier branch that always matches.
+    pub fn unreached(&self, names: impl IntoIterator<Item = &'static str>) -> Vec<&'static str> {
+        names
+            .into_iter()
+            .filter(|name| !self.parsers.iter().any(|stats| stats.name == *name))
+            .collect()
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} inputs reached {} covered parsers",
+            self.inputs,
+            self.parsers.len()
+        )?;
+        for stats in &self.parsers {
+            write!(
+                f,
+                "\n  {}: matched {} of {} attempts",
+                stats.name, stats.matched, stats.tried
+            )?;
+            if stats.matched == 0 {
+                write!(f, " (warning: never matched)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse every input of a test corpus, recording how often each parser marked with [`cover`] was tried and matched.
+///
+/// Marked parsers that never match over a corpus that should exercise the whole grammar are likely to be dead rules,
+/// or [`choice`] branches that are shadowed by earlier branches. Shadowed branches may also never be tried at all,
+/// which [`CoverageReport::unreached`] reports.
+///
+/// `parse` should parse the input with a grammar containing parsers marked with [`cover`], using the given
+/// [`Coverage`] as (part of) the parser's state.
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::{prelude::*, trace::{cover, measure_coverage, Coverage}};
+///
+/// fn parser<'src>() -> impl Parser<'src, &'src str, (), extra::State<Coverage>> {
+///     choice((
+///         cover("ident", text::ascii::ident().to(())),
+///         // Oops: keywords are also identifiers, so this branch never matches
+///         cover("keyword", text::ascii::keyword("let").to(())),
+///         cover("number", text::int(10).to(())),
+///     ))
+///     .padded()
+///     .repeated()
+/// }
+///
+/// let report = measure_coverage(["let x", "y 42"], |input, coverage| {
+///     parser().parse_with_state(input, coverage);
+/// });
+///
+/// assert_eq!(report.never_matched(), ["keyword"]);
+/// assert_eq!(
+///     report.to_string().lines().collect::<Vec<_>>(),
+///     [
+///         "2 inputs reached 3 covered parsers",
+///         "  ident: matched 3 of 6 attempts",
+///         "  keyword: matched 0 of 3 attempts (warning: never matched)",
+///         "  number: matched 1 of 3 attempts",
+///     ],
+/// );
+/// ```
+pub fn measure_coverage<S: AsRef<str>>(
+    corpus: impl IntoIterator<Item = S>,
+    mut parse: impl FnMut(&str, &mut Coverage),
+) -> CoverageReport {
+    let mut coverage = Coverage::new();
+    let mut inputs = 0;
+    for input in corpus {
+        inputs += 1;
+        parse(input.as_ref(), &mut coverage);
+    }
+    CoverageReport {
+        inputs,
+        parsers: coverage.counts,
+    }
+}
+
+/// A pair of [`choice`] branches in which the earlier branch shadows the later one, as found by
+/// [`check_branch_order`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShadowedBranch<I> {
+    /// The index of the earlier branch, which matched a strict prefix of what the later branch matched.
+    pub earlier: usize,
+    /// The index of the later branch, which was shadowed.
+    pub later: usize,
+    /// The first input of the corpus on which the later branch was shadowed.
+    pub input: I,
+}
+
+/// Check whether the order of the branches of a [`choice`] matters, using a corpus of sample inputs.
+///
+/// A [`choice`] commits to the first branch that matches, so when an earlier branch matches a strict prefix of what
+/// a later branch would match (such as `just("=")` before `just("==")`, or `just("let")` before an identifier),
+/// the later branch can never match and the rest of the input is left for whatever comes next. This is one of the
+/// most common silent grammar bugs, since it only shows up as a confusing error (or, worse, a different parse) much
+/// later on.
+///
+/// Each branch is run at the start of each sample input, and every pair of branches in which an earlier branch
+/// matched less of a sample than a later branch is reported, along with the first sample that shows it. The branches
+/// should be given in the same order as they are passed to [`choice`]: putting them in a slice, [`Vec`], or array
+/// (using [`Parser::boxed`] if they have different types) allows the same branches to be both checked and used.
+///
+/// Branches that match the same amount of input are not reported, since whether they overlap intentionally depends
+/// on the grammar.
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::{prelude::*, trace::{check_branch_order, ShadowedBranch}};
+///
+/// let ops = [just::<_, &str, extra::Default>("="), just("=="), just("<"), just("<=")];
+/// let op = choice(ops);
+///
+/// let shadowed = check_branch_order(&ops, ["=", "==", "<", "<="]);
+/// assert_eq!(
+///     shadowed,
+///     [
+///         ShadowedBranch { earlier: 0, later: 1, input: "==" },
+///         ShadowedBranch { earlier: 2, later: 3, input: "<=" },
+///     ],
+/// );
+///
+/// // Indeed, `op` cannot parse `==`
+/// assert!(op.parse("==").has_errors());
+/// ```
+#[cfg(feature = "std")]
+pub fn check_branch_order<'src, P, I, O, E>(
+    branches: &[P],
+    corpus: impl IntoIterator<Item = I>,
+) -> Vec<ShadowedBranch<I>>
+where
+    P: Parser<'src, I, O, E>,
+    I: ValueInput<'src> + Clone,
+    E: ParserExtra<'src, I>,
+    E::State: Default,
+    E::Context: Default,
+{
+    let mut shadowed = Vec::<ShadowedBranch<I>>::new();
+    for input in corpus {
+        // The number of tokens left over after each branch, if it matched
+        let remaining = branches
+            .iter()
+            .map(|branch| {
+                branch
+                    .ignore_then(any().repeated().count())
+                    .parse(input.clone())
+                    .into_output()
+            })
+            .collect::<Vec<_>>();
+        for (earlier, earlier_remaining) in remaining.iter().enumerate() {
+            let Some(earlier_remaining) = earlier_remaining else {
+                continue;
+            };
+            for (later, later_remaining) in remaining.iter().enumerate().skip(earlier + 1) {
+                let is_shadowed = later_remaining.map_or(false, |r| r < *earlier_remaining);
+                let is_known = shadowed
+                    .iter()
+                    .any(|s| s.earlier == earlier && s.later == later);
+                if is_shadowed && !is_known {
+                    shadowed.push(ShadowedBranch {
+                        earlier,
+                        later,
+                        input: input.clone(),
+                    });
+                }
+            }
+        }
+    }
+    shadowed.sort_by_key(|s| (s.earlier, s.later));
+    shadowed
+}
+
+/// A deterministic, coverage-guided fuzzer for text grammars.
+///
+/// The fuzzer repeatedly mutates inputs from its corpus (by inserting, deleting, duplicating and splicing text, and by
+/// inserting words from its dictionary) and parses them. Inputs that reach a parser marked with [`cover`] in a way
+/// that no previous input did (succeeding or failing, or nested more deeply) are added to the corpus so that later mutations build on
+/// them. Inputs that cause the parser to panic are reported.
+///
+/// Mutations are chosen by a pseudo-random number generator with the given seed, so a run with the same seed, corpus
+/// and grammar always generates the same inputs.
+///
+/// # Examples
+///
+/// ```
+/// use chumsky::{prelude::*, trace::{cover, Coverage, Fuzzer}};
+///
+/// fn parser<'src>() -> impl Parser<'src, &'src str, (), extra::State<Coverage>> {
+///     recursive(|expr| {
+///         let atom = cover("number", text::int(10).to(()))
+///             .or(cover("group", expr.delimited_by(just('('), just(')'))));
+///         cover(
+///             "sum",
+///             atom.separated_by(cover("plus", just('+').padded())).at_least(1),
+///         )
+///     })
+/// }
+///
+/// let report = Fuzzer::new(42)
+///     .corpus(["1"])
+///     .dictionary(["(", ")", "+"])
+///     .run(1000, |input, coverage| {
+///         parser().parse_with_state(input, coverage);
+///     });
+///
+/// assert!(report.coverage.contains(&("group", true)));
+/// assert!(report.panics.is_empty());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Fuzzer {
+    rng: Rng,
+    corpus: Vec<String>,
+    dictionary: Vec<String>,
+    max_len: usize,
+}
+
+/// The results of a run of a [`Fuzzer`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct FuzzReport {
+    /// The number of inputs that were parsed.
+    pub iterations: usize,
+    /// The names of the parsers marked with [`cover`] that were reached, and whether they succeeded.
+    pub coverage: BTreeSet<(&'static str, bool)>,
+    /// The corpus at the end of the run, including the inputs that reached new coverage.
+    pub corpus: Vec<String>,
+    /// The inputs that caused the parser to panic. These can be shrunk with [`minimize_str`].
+    pub panics: Vec<String>,
+}
+
+#[cfg(feature = "std")]
+impl Fuzzer {
+    /// Create a fuzzer that chooses mutations using the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng(seed),
+            corpus: Vec::new(),
+            dictionary: Vec::new(),
+            max_len: 256,
+        }
+    }
+
+    /// Add inputs to the corpus that mutations start from. Valid inputs that exercise different parts of the grammar
+    /// make the best starting points. If the corpus is empty, fuzzing starts from an empty input.
+    pub fn corpus<S: Into<String>>(mut self, inputs: impl IntoIterator<Item = S>) -> Self {
+        self.corpus.extend(inputs.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add words to the dictionary that mutations may insert, such as the keywords and punctuation of the grammar.
+    pub fn dictionary<S: Into<String>>(mut self, words: impl IntoIterator<Item = S>) -> Self {
+        self.dictionary.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the maximum length, in bytes, of generated inputs (256 by default).
+    pub fn max_len(self, max_len: usize) -> Self {
+        Self { max_len, ..self }
+    }
+
+    /// Fuzz for the given number of iterations, calling `parse` with each generated input.
+    ///
+    /// `parse` should parse the input with a grammar containing parsers marked with [`cover`], using the given
+    /// [`Coverage`] as (part of) the parser's state, and may perform any other checks on the result, panicking if
+    /// they fail. Note that the panic hook is still called for each panic,
+    /// so you may wish to replace it with [`std::panic::set_hook`] to keep output tidy.
+    pub fn run(
+        &mut self,
+        iterations: usize,
+        mut parse: impl FnMut(&str, &mut Coverage),
+    ) -> FuzzReport {
+        let mut report = FuzzReport::default();
+        if self.corpus.is_empty() {
+            self.corpus.push(String::new());
+        }
+        let mut seen = BTreeSet::new();
+        let mut reached = BTreeSet::new();
+        let mut run = |input: &str, report: &mut FuzzReport| {
+            report.iterations += 1;
+            let mut coverage = Coverage::new();
+            let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                parse(input, &mut coverage)
+            }))
+            .is_err();
+            if panicked {
+                if seen.insert(input.to_string()) {
+                    report.panics.push(input.to_string());
+                }
+                return false;
+            }
+            let before = reached.len();
+            for (name, succeeded, depth) in coverage.reached {
+                report.coverage.insert((name, succeeded));
+                reached.insert((name, succeeded, depth));
+            }
+            reached.len() > before
+        };
+
+        for input in self.corpus.clone() {
+            run(&input, &mut report);
+        }
+        while report.iterations < iterations {
+            let input = self.mutate();
+            if run(&input, &mut report) {
+                self.corpus.push(input);
+            }
+        }
+        report.corpus = self.corpus.clone();
+        report
+    }
+
+    fn mutate(&mut self) -> String {
+        let Self {
+            rng,
+            corpus,
+            dictionary,
+            ..
+        } = self;
+        let mut input = corpus[rng.below(corpus.len())].clone();
+        // Apply a few mutations at once, so that changes which only matter together can be found
+        for _ in 0..1 + rng.below(3) {
+            let at = rng.boundary(&input);
+            match rng.below(5) {
+                // Insert a word from the dictionary
+                0 if !dictionary.is_empty() => {
+                    input.insert_str(at, &dictionary[rng.below(dictionary.len())]);
+                }
+                // Insert a character taken from elsewhere in the corpus, or a random ASCII one
+                0 | 1 => {
+                    let other = &corpus[rng.below(corpus.len())];
+                    let c = other[rng.boundary(other)..]
+                        .chars()
+                        .next()
+                        .unwrap_or_else(|| char::from(b' ' + rng.below(95) as u8));
+                    input.insert(at, c);
+                }
+                // Delete a range
+                2 => {
+                    let end = rng.boundary(&input).max(at);
+                    input.replace_range(at..end, "");
+                }
+                // Duplicate a range
+                3 => {
+                    let end = rng.boundary(&input).max(at);
+                    let range = input[at..end].to_string();
+                    input.insert_str(end, &range);
+                }
+                // Splice in part of another input
+                _ => {
+                    let other = &corpus[rng.below(corpus.len())];
+                    let start = rng.boundary(other);
+                    let end = rng.boundary(other).max(start);
+                    input.insert_str(at, &other[start..end]);
+                }
+            }
+        }
+        input.truncate(span::floor_char_boundary(&input, self.max_len));
+        input
+    }
+}
+
+/// A xorshift64* pseudo-random number generator.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+struct Rng(u64);
+
+#[cfg(feature = "std")]
+impl Rng {
+    fn next(&mut self) -> u64 {
+        // The generator gets stuck at zero
+        if self.0 == 0 {
+            self.0 = 0x9E37_79B9_7F4A_7C15;
+        }
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    // A random position in the text that lies on a character boundary
+    fn boundary(&mut self, text: &str) -> usize {
+        span::floor_char_boundary(text, self.below(text.len() + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cover, measure_coverage, minimize, CoverStats, Coverage, Divergence, Event, Recorder, Trace,
+    };
+    use crate::{inspector::SimpleState, prelude::*};
+
+    #[test]
+    fn trace_replay_divergence() {
+        type Extra<'src> = extra::Full<EmptyErr, Recorder, ()>;
+
+        let ab = just::<_, &[u8], Extra>([1, 2]).or(just([1, 3]));
+        let (result, trace) = Trace::record(&ab, &[1u8, 3][..]);
+        assert_eq!(result.into_result(), Ok([1, 3]));
+        assert_eq!(trace.input, vec![1, 3]);
+        assert_eq!(
+            trace.events,
+            [
+                Event::Advance(2),
+                Event::Rewind(1),
+                Event::Rewind(0),
+                Event::Advance(2)
+            ]
+        );
+        assert!(trace.replay(&ab).is_ok());
+
+        // Reordering the branches changes the decisions made
+        let ba = just::<_, &[u8], Extra>([1, 3]).or(just([1, 2]));
+        assert_eq!(
+            trace.replay(&ba).err(),
+            Some(Divergence {
+                index: 1,
+                expected: Some(Event::Rewind(1)),
+                found: None,
+            })
+        );
+    }
+
+    #[test]
+    fn minimize_keeps_failure() {
+        let input = (0..100).collect::<Vec<_>>();
+        let mut calls = 0;
+        let minimal = minimize(&input, |v| {
+            calls += 1;
+            v.contains(&3) && v.contains(&71)
+        });
+        assert_eq!(minimal, [3, 71]);
+        assert!(calls < 100, "{calls} calls");
+
+        // Inputs that don't fail are left alone
+        assert_eq!(minimize(&input[..5], |v| v.len() > 5), input[..5]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn fuzzer_finds_deep_panic() {
+        use super::Fuzzer;
+
+        // Panics on groups nested three deep
+        fn parser<'src>() -> impl Parser<'src, &'src str, usize, extra::State<Coverage>> {
+            recursive(|expr| {
+                cover("digit", text::digits(10).to(0)).or(cover(
+                    "group",
+                    expr.delimited_by(just('('), just(')')).map(|depth: usize| {
+                        assert!(depth < 2, "too deep");
+                        depth + 1
+                    }),
+                ))
+            })
+        }
+
+        let fuzz = || {
+            Fuzzer::new(7)
+                .corpus(["1"])
+                .dictionary(["(", ")"])
+                .run(5000, |input, coverage| {
+                    parser().parse_with_state(input, coverage);
+                })
+        };
+        let report = fuzz();
+        assert!(report.coverage.contains(&("group", true)));
+        assert!(!report.panics.is_empty());
+        assert!(report.panics.iter().all(|input| input.contains("(((")));
+
+        // Runs with the same seed are the same
+        assert_eq!(fuzz().corpus, report.corpus);
+    }
+
+    #[test]
+    fn coverage_of_token_inputs() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token {
+            Num,
+            Plus,
+        }
+
+        // Coverage can be recorded alongside other state, for any kind of input
+        fn sum<'src>(
+        ) -> impl Parser<'src, &'src [Token], Vec<()>, extra::State<(Coverage, SimpleState<usize>)>>
+        {
+            let num = cover("num", just(Token::Num)).map_with(|_, e| {
+                let (_, count): &mut (Coverage, SimpleState<usize>) = e.state();
+                **count += 1;
+            });
+            num.separated_by(cover("plus", just(Token::Plus))).collect()
+        }
+
+        let report = measure_coverage(["1+1", "1"], |input, coverage| {
+            let tokens = input
+                .chars()
+                .map(|c| if c == '+' { Token::Plus } else { Token::Num })
+                .collect::<Vec<_>>();
+            let mut state = (core::mem::take(coverage), SimpleState(0));
+            assert!(!sum().parse_with_state(&tokens, &mut state).has_errors());
+            assert_eq!(state.1 .0, tokens.len() / 2 + 1);
+            *coverage = state.0;
+        });
+        assert_eq!(
+            report.parsers,
+            [
+                CoverStats {
+                    name: "num",
+                    tried: 3,
+                    matched: 3,
+                },
+                CoverStats {
+                    name: "plus",
+                    tried: 3,
+                    matched: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn coverage_finds_dead_branches() {
+        fn parser<'src>() -> impl Parser<'src, &'src str, (), extra::State<Coverage>> {
+            choice((
+                cover("digit", text::digits(10).to(())),
+                cover("word", text::ascii::ident().to(())),
+                cover("other", any().to(())),
+                // Shadowed: `other` always matches first
+                cover("space", just(' ').to(())),
+            ))
+            .separated_by(just(' '))
+        }
+
+        let report = measure_coverage(["12 ab", "if", "x ?"], |input, coverage| {
+            parser().parse_with_state(input, coverage);
+        });
+        assert_eq!(report.inputs, 3);
+        assert_eq!(
+            report.parsers,
+            [
+                CoverStats {
+                    name: "digit",
+                    tried: 5,
+                    matched: 1,
+                },
+                CoverStats {
+                    name: "word",
+                    tried: 4,
+                    matched: 3,
+                },
+                CoverStats {
+                    name: "other",
+                    tried: 1,
+                    matched: 1,
+                },
+            ]
+        );
+        assert_eq!(report.never_matched(), Vec::<&str>::new());
+        assert_eq!(
+            report.unreached(["digit", "word", "other", "space"]),
+            ["space"]
+        );
+        assert!(!report.to_string().contains("warning"));
+
+        // Parsers that are tried but never match are flagged
+        let report = measure_coverage(["-"], |input, coverage| {
+            parser().parse_with_state(input, coverage);
+        });
+        assert_eq!(report.never_matched(), ["digit", "word"]);
+        assert!(report
+            .to_string()
+            .contains("digit: matched 0 of 1 attempts (warning: never matched)"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn branch_order_shadowing() {
+        use super::{check_branch_order, ShadowedBranch};
+
+        let branches = [
+            just::<_, _, extra::Default>("let").to_slice().boxed(),
+            text::ascii::ident().boxed(),
+            text::ascii::keyword("if").boxed(),
+            text::digits(10).to_slice().boxed(),
+        ];
+        let shadowed = check_branch_order(&branches, ["let", "letter", "if", "lettuce", "42"]);
+        assert_eq!(
+            shadowed,
+            [ShadowedBranch {
+                earlier: 0,
+                later: 1,
+                input: "letter",
+            }]
+        );
+
+        // Reordering the branches fixes the problem, and matches of the same length are not reported
+        let reordered = [branches[1].clone(), branches[0].clone()];
+        assert_eq!(
+            check_branch_order(&reordered, ["let", "letter"]),
+            Vec::new()
+        );
+    }
+}
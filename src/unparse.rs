@@ -0,0 +1,645 @@
+//! Experimental support for parsers that can be run in reverse, printing outputs back into text.
+//!
+//! *“The Guide is definitive. Reality is frequently inaccurate.”*
+//!
+//! A [`Bi`] (short for *biparser*) is a parser paired with a printer that inverts it. Biparsers are built up from
+//! smaller biparsers with combinators that mirror those of [`Parser`], such as [`Bi::then`] and [`Bi::or`], so a
+//! grammar written once gives both a parser and a pretty-printer that are guaranteed to agree on its structure.
+//!
+//! Printing can fail: a choice between several constructors of an AST is written with [`Bi::map`], whose inverse
+//! returns `None` for values built by some other constructor, and printing fails with [`PrintError`] if no
+//! alternative accepts the value. Parsers that have no built-in inverse, like [`text::ascii::ident`], can be given one with
+//! [`bi`].
+//!
+//! Printers produce a canonical form of the input, so whitespace and other trivia that the parser skips are printed as
+//! specified by [`ws`], not as they were written.
+//!
+//! # Examples
+//!
+//! ```
+//! use chumsky::{prelude::*, unparse::{bi, lit, ws, Bi}};
+//!
+//! #[derive(Clone, Debug, PartialEq)]
+//! enum Value<'src> { Bool(bool), Name(&'src str), List(Vec<Value<'src>>) }
+//!
+//! let name = bi(text::ascii::ident::<&str, extra::Err<Simple<char>>>(), |name: &&str, out: &mut String| {
+//!     out.push_str(name);
+//!     Ok(())
+//! });
+//!
+//! let atom = lit("true")
+//!     .map(|()| Value::Bool(true), |v: &Value| matches!(v, Value::Bool(true)).then_some(()))
+//!     .or(lit("false").map(|()| Value::Bool(false), |v: &Value| matches!(v, Value::Bool(false)).then_some(())))
+//!     .or(name.map(Value::Name, |v: &Value| match v { Value::Name(n) => Some(*n), _ => None }));
+//!
+//! let list = lit("[")
+//!     .ignore_then(atom.clone().separated_by(lit(",").then_ignore(ws(" "))))
+//!     .then_ignore(lit("]"))
+//!     .map(Value::List, |v: &Value| match v { Value::List(xs) => Some(xs.clone()), _ => None });
+//!
+//! let value = list.parse("[true,  x, false]").into_result().unwrap();
+//! assert_eq!(value, Value::List(vec![Value::Bool(true), Value::Name("x"), Value::Bool(false)]));
+//! assert_eq!(list.print_to_string(&value).as_deref(), Ok("[true, x, false]"));
+//!
+//! // An atom can't be printed as a list
+//! assert!(list.print_to_string(&Value::Bool(true)).is_err());
+//! ```
+
+use super::*;
+
+/// An error produced when a value cannot be printed by a [`Print`]er.
+///
+/// This happens when no alternative of a choice accepts the value (see [`Bi::map`]), or when a custom printer
+/// rejects it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PrintError;
+
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value could not be printed")
+    }
+}
+
+/// A printer, which writes values of type `O` as text. See the [module docs](self).
+pub trait Print<O> {
+    /// Write the value to the given string.
+    ///
+    /// If printing fails, the string may have been partially written to.
+    fn print(&self, value: &O, out: &mut String) -> Result<(), PrintError>;
+}
+
+/// A parser paired with a printer that inverts it. See the [module docs](self).
+#[derive(Copy, Clone)]
+pub struct Bi<P, Pr> {
+    parser: P,
+    printer: Pr,
+}
+
+/// Pair a parser with a printer that inverts it.
+///
+/// The printer may be any [`Print`]er, including a closure of the form `Fn(&O, &mut String) -> Result<(), PrintError>`.
+pub const fn bi<P, Pr>(parser: P, printer: Pr) -> Bi<P, Pr> {
+    Bi { parser, printer }
+}
+
+/// A biparser that accepts (and prints) the given text.
+///
+/// The output type of this biparser is `()`.
+pub fn lit<'src, I, E>(text: &'static str) -> Bi<impl Parser<'src, I, (), E> + Copy, Lit>
+where
+    I: Input<'src, Token = char>,
+    E: ParserExtra<'src, I>,
+{
+    bi(just(text).ignored(), Lit(text))
+}
+
+/// A biparser that accepts any amount of whitespace (including none), and prints the given text in its place.
+///
+/// The output type of this biparser is `()`.
+pub fn ws<'src, I, E>(print_as: &'static str) -> Bi<impl Parser<'src, I, (), E> + Copy, Lit>
+where
+    I: StrInput<'src, Token = char>,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, TextExpected<'src, I>>,
+{
+    bi(text::whitespace(), Lit(print_as))
+}
+
+impl<P, Pr> Bi<P, Pr> {
+    /// Get the parser of this biparser.
+    pub fn parser(&self) -> &P {
+        &self.parser
+    }
+
+    /// Get the printer of this biparser.
+    pub fn printer(&self) -> &Pr {
+        &self.printer
+    }
+
+    /// Print the value to a new string.
+    pub fn print_to_string<O>(&self, value: &O) -> Result<String, PrintError>
+    where
+        Pr: Print<O>,
+    {
+        let mut out = String::new();
+        self.printer.print(value, &mut out)?;
+        Ok(out)
+    }
+
+    /// Parse one biparser followed by another, producing both outputs. See [`Parser::then`].
+    pub fn then<'src, I, E, O, U, Q, QPr>(
+        self,
+        other: Bi<Q, QPr>,
+    ) -> Bi<Then<P, Q, O, U, E>, PrintThen<Pr, QPr>>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        P: Parser<'src, I, O, E>,
+        Q: Parser<'src, I, U, E>,
+    {
+        bi(
+            self.parser.then(other.parser),
+            PrintThen(self.printer, other.printer),
+        )
+    }
+
+    /// Parse one biparser followed by another, producing only the second output. See [`Parser::ignore_then`].
+    ///
+    /// The first biparser must output `()`, so that it can be printed without a value.
+    pub fn ignore_then<'src, I, E, U, Q, QPr>(
+        self,
+        other: Bi<Q, QPr>,
+    ) -> Bi<IgnoreThen<P, Q, (), E>, PrintIgnoreThen<Pr, QPr>>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        P: Parser<'src, I, (), E>,
+        Q: Parser<'src, I, U, E>,
+    {
+        bi(
+            self.parser.ignore_then(other.parser),
+            PrintIgnoreThen(self.printer, other.printer),
+        )
+    }
+
+    /// Parse one biparser followed by another, producing only the first output. See [`Parser::then_ignore`].
+    ///
+    /// The second biparser must output `()`, so that it can be printed without a value.
+    pub fn then_ignore<'src, I, E, O, Q, QPr>(
+        self,
+        other: Bi<Q, QPr>,
+    ) -> Bi<ThenIgnore<P, Q, (), E>, PrintThenIgnore<Pr, QPr>>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        P: Parser<'src, I, O, E>,
+        Q: Parser<'src, I, (), E>,
+    {
+        bi(
+            self.parser.then_ignore(other.parser),
+            PrintThenIgnore(self.printer, other.printer),
+        )
+    }
+
+    /// Parse with this biparser or, failing that, another. See [`Parser::or`].
+    ///
+    /// Printing tries this biparser's printer first, and then the other's.
+    pub fn or<'src, I, E, O, Q, QPr>(self, other: Bi<Q, QPr>) -> Bi<Or<P, Q>, PrintOr<Pr, QPr>>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        P: Parser<'src, I, O, E>,
+        Q: Parser<'src, I, O, E>,
+    {
+        bi(
+            self.parser.or(other.parser),
+            PrintOr(self.printer, other.printer),
+        )
+    }
+
+    /// Map the output of this biparser with a function, and its input when printing with an inverse function. See
+    /// [`Parser::map`].
+    ///
+    /// The inverse returns `None` if the value was not produced by `f`, which is how choices between the constructors
+    /// of an enum are printed: each alternative only accepts values built with its own constructor.
+    pub fn map<'src, I, E, O, U, F, G>(
+        self,
+        f: F,
+        inverse: G,
+    ) -> Bi<Map<P, O, F>, PrintMap<Pr, G, O>>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        P: Parser<'src, I, O, E>,
+        F: Fn(O) -> U,
+        G: Fn(&U) -> Option<O>,
+    {
+        bi(
+            self.parser.map(f),
+            PrintMap {
+                printer: self.printer,
+                inverse,
+                phantom: EmptyPhantom::new(),
+            },
+        )
+    }
+
+    /// Parse this biparser any number of times, separated by another, collecting the outputs into a [`Vec`]. See
+    /// [`Parser::separated_by`].
+    ///
+    /// The separator must output `()`, so that it can be printed without a value.
+    #[allow(clippy::type_complexity)]
+    pub fn separated_by<'src, I, E, O, Q, QPr>(
+        self,
+        separator: Bi<Q, QPr>,
+    ) -> Bi<Collect<SeparatedBy<P, Q, O, (), I, E>, O, Vec<O>>, PrintSeparated<Pr, QPr>>
+    where
+        I: Input<'src>,
+        E: ParserExtra<'src, I>,
+        P: Parser<'src, I, O, E>,
+        Q: Parser<'src, I, (), E>,
+    {
+        bi(
+            self.parser.separated_by(separator.parser).collect(),
+            PrintSeparated(self.printer, separator.printer),
+        )
+    }
+}
+
+impl<P, Pr> Bi<P, Pr> {
+    /// Print each of the values to a new string, for use with [`Bi::check_round_trip`].
+    ///
+    /// If a value cannot be printed, its position in the list is returned along with the error.
+    pub fn print_all<O>(&self, values: &[O]) -> Result<Vec<String>, (usize, PrintError)>
+    where
+        Pr: Print<O>,
+    {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| self.print_to_string(value).map_err(|error| (index, error)))
+            .collect()
+    }
+
+    /// Check that each of the values survives being printed and parsed again unchanged: `parse(print(x)) == x`.
+    ///
+    /// `printed` must contain the printed forms of the values, as produced by [`Bi::print_all`]. They are kept by
+    /// the caller so that parsed values may borrow from them. The first value that fails the check is reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, unparse::{bi, lit, ws, RoundTripError}};
+    /// let int = bi(text::int::<&str, extra::Err<Simple<char>>>(10).from_str::<i64>().unwrapped(), |n: &i64, out: &mut String| {
+    ///     out.push_str(&n.to_string());
+    ///     Ok(())
+    /// });
+    /// let pair = int.then_ignore(lit(",").then_ignore(ws(" "))).then(int);
+    ///
+    /// let values = [(1, 2), (30, 40)];
+    /// let printed = pair.print_all(&values).unwrap();
+    /// assert_eq!(printed, vec!["1, 2", "30, 40"]);
+    /// assert!(pair.check_round_trip(&values, &printed).is_ok());
+    ///
+    /// // `-1` is printed, but `text::int` doesn't parse signs
+    /// let values = [(1, 2), (-1, 2)];
+    /// let printed = pair.print_all(&values).unwrap();
+    /// match pair.check_round_trip(&values, &printed) {
+    ///     Err(RoundTripError::Parse { index, text, .. }) => assert_eq!((index, text.as_str()), (1, "-1, 2")),
+    ///     _ => panic!(),
+    /// }
+    /// ```
+    pub fn check_round_trip<'src, O, E>(
+        &self,
+        values: &[O],
+        printed: &'src [String],
+    ) -> Result<(), RoundTripError<O, E::Error>>
+    where
+        P: Parser<'src, &'src str, O, E>,
+        O: PartialEq,
+        E: ParserExtra<'src, &'src str>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        for (index, (value, text)) in values.iter().zip(printed).enumerate() {
+            let parsed = self.parse_for_check(index, text)?;
+            if parsed != *value {
+                return Err(RoundTripError::Mismatch {
+                    index,
+                    text: text.clone(),
+                    parsed,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse each of the sources and print the results to new strings, for use with [`Bi::check_stable`].
+    ///
+    /// The first source that cannot be parsed (or whose output cannot be printed) is reported.
+    pub fn reprint_all<'src, O, E>(
+        &self,
+        sources: &[&'src str],
+    ) -> Result<Vec<String>, RoundTripError<O, E::Error>>
+    where
+        P: Parser<'src, &'src str, O, E>,
+        Pr: Print<O>,
+        E: ParserExtra<'src, &'src str>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        sources
+            .iter()
+            .enumerate()
+            .map(|(index, src)| {
+                let parsed = self.parse_for_check(index, src)?;
+                self.print_to_string(&parsed)
+                    .map_err(|error| RoundTripError::Print { index, error })
+            })
+            .collect()
+    }
+
+    /// Check that printing is stable: text produced by the printer prints the same way after being parsed again,
+    /// `print(parse(t)) == t`.
+    ///
+    /// `printed` is usually produced by [`Bi::reprint_all`] from a corpus of sources, which together check that
+    /// `print(parse(src))` is a fixed point. This catches printers that drop or add syntax that the parser treats
+    /// differently, such as parentheses or separators. The first text that fails the check is reported, along with the
+    /// span of the first difference between the two printings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, unparse::{bi, lit, ws, RoundTripError}};
+    /// // Oops: the printer writes numbers in hexadecimal, but the parser reads them in decimal
+    /// let int = bi(text::int::<&str, extra::Err<Simple<char>>>(10).from_str::<u32>().unwrapped(), |n: &u32, out: &mut String| {
+    ///     out.push_str(&format!("{:x}", n));
+    ///     Ok(())
+    /// });
+    /// let ints = int.separated_by(lit(",").then_ignore(ws(" ")));
+    ///
+    /// let printed = ints.reprint_all(&["1,2", "3,  4"]).unwrap();
+    /// assert!(ints.check_stable(&printed).is_ok());
+    ///
+    /// // 16 is printed as `10`, which is then parsed as 10 and printed as `a`
+    /// let printed = ints.reprint_all(&["1, 2", "9, 16"]).unwrap();
+    /// assert_eq!(
+    ///     ints.check_stable(&printed),
+    ///     Err(RoundTripError::Unstable {
+    ///         index: 1,
+    ///         first: "9, 10".to_string(),
+    ///         second: "9, a".to_string(),
+    ///         span: SimpleSpan::from(3..5),
+    ///     }),
+    /// );
+    /// ```
+    pub fn check_stable<'src, O, E>(
+        &self,
+        printed: &'src [String],
+    ) -> Result<(), RoundTripError<O, E::Error>>
+    where
+        P: Parser<'src, &'src str, O, E>,
+        Pr: Print<O>,
+        E: ParserExtra<'src, &'src str>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        for (index, first) in printed.iter().enumerate() {
+            let parsed = self.parse_for_check(index, first)?;
+            let second = self
+                .print_to_string(&parsed)
+                .map_err(|error| RoundTripError::Print { index, error })?;
+            if *first != second {
+                return Err(RoundTripError::Unstable {
+                    index,
+                    span: divergence(first, &second),
+                    first: first.clone(),
+                    second,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_for_check<'src, O, E>(
+        &self,
+        index: usize,
+        text: &'src str,
+    ) -> Result<O, RoundTripError<O, E::Error>>
+    where
+        P: Parser<'src, &'src str, O, E>,
+        E: ParserExtra<'src, &'src str>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        match self.parser.parse(text).into_output_errors() {
+            (Some(parsed), errors) if errors.is_empty() => Ok(parsed),
+            (_, errors) => Err(RoundTripError::Parse {
+                index,
+                text: text.into(),
+                errors,
+            }),
+        }
+    }
+}
+
+/// The span of `first` that differs from `second`, after removing their common prefix and suffix.
+fn divergence(first: &str, second: &str) -> SimpleSpan {
+    let prefix = first
+        .char_indices()
+        .zip(second.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(first.len().min(second.len()), |((i, _), _)| i);
+    let suffix = first[prefix..]
+        .chars()
+        .rev()
+        .zip(second[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum::<usize>();
+    SimpleSpan::from(prefix..first.len() - suffix)
+}
+
+/// An error produced by [`Bi::check_round_trip`], [`Bi::reprint_all`], or [`Bi::check_stable`], describing the first
+/// failure.
+///
+/// In each case, `index` is the position of the failing value or source in the list that was checked.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RoundTripError<O, Err> {
+    /// A value could not be printed.
+    Print {
+        /// The position of the value or source.
+        index: usize,
+        /// The error produced by the printer.
+        error: PrintError,
+    },
+    /// Text (either a source or some printed text) could not be parsed.
+    Parse {
+        /// The position of the value or source.
+        index: usize,
+        /// The text that failed to parse.
+        text: String,
+        /// The errors produced by the parser.
+        errors: Vec<Err>,
+    },
+    /// A value was printed and parsed again, but the result differed from the original value.
+    Mismatch {
+        /// The position of the value.
+        index: usize,
+        /// The printed text.
+        text: String,
+        /// The value that was parsed from the printed text.
+        parsed: O,
+    },
+    /// A source was parsed and printed twice, but the two printings differed.
+    Unstable {
+        /// The position of the source.
+        index: usize,
+        /// The first printing.
+        first: String,
+        /// The second printing.
+        second: String,
+        /// The span of the first printing where the two differ.
+        span: SimpleSpan,
+    },
+}
+
+impl<O, Err: fmt::Display> fmt::Display for RoundTripError<O, Err> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Print { index, error } => write!(f, "item {}: {}", index, error),
+            Self::Parse {
+                index,
+                text,
+                errors,
+            } => {
+                write!(f, "item {}: failed to parse {:?}", index, text)?;
+                for error in errors {
+                    write!(f, "\n  {}", error)?;
+                }
+                Ok(())
+            }
+            Self::Mismatch { index, text, .. } => write!(
+                f,
+                "item {}: parsing the printed text {:?} produced a different value",
+                index, text
+            ),
+            Self::Unstable {
+                index,
+                first,
+                second,
+                span,
+            } => write!(
+                f,
+                "item {}: printing is unstable at {}: {:?} became {:?}",
+                index, span, first, second
+            ),
+        }
+    }
+}
+
+impl<'src, P, Pr, I, O, E> Parser<'src, I, O, E> for Bi<P, Pr>
+where
+    I: Input<'src>,
+    E: ParserExtra<'src, I>,
+    P: Parser<'src, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'src, '_, I, E>) -> PResult<M, O> {
+        self.parser.go::<M>(inp)
+    }
+
+    go_extra!(O);
+}
+
+impl<P, Pr: Print<O>, O> Print<O> for Bi<P, Pr> {
+    fn print(&self, value: &O, out: &mut String) -> Result<(), PrintError> {
+        self.printer.print(value, out)
+    }
+}
+
+impl<O, F: Fn(&O, &mut String) -> Result<(), PrintError>> Print<O> for F {
+    fn print(&self, value: &O, out: &mut String) -> Result<(), PrintError> {
+        self(value, out)
+    }
+}
+
+/// See [`lit`] and [`ws`].
+#[derive(Copy, Clone, Debug)]
+pub struct Lit(&'static str);
+
+impl Print<()> for Lit {
+    fn print(&self, _: &(), out: &mut String) -> Result<(), PrintError> {
+        out.push_str(self.0);
+        Ok(())
+    }
+}
+
+/// See [`Bi::then`].
+#[derive(Copy, Clone, Debug)]
+pub struct PrintThen<A, B>(A, B);
+
+impl<A: Print<OA>, B: Print<OB>, OA, OB> Print<(OA, OB)> for PrintThen<A, B> {
+    fn print(&self, (a, b): &(OA, OB), out: &mut String) -> Result<(), PrintError> {
+        self.0.print(a, out)?;
+        self.1.print(b, out)
+    }
+}
+
+/// See [`Bi::ignore_then`].
+#[derive(Copy, Clone, Debug)]
+pub struct PrintIgnoreThen<A, B>(A, B);
+
+impl<A: Print<()>, B: Print<O>, O> Print<O> for PrintIgnoreThen<A, B> {
+    fn print(&self, value: &O, out: &mut String) -> Result<(), PrintError> {
+        self.0.print(&(), out)?;
+        self.1.print(value, out)
+    }
+}
+
+/// See [`Bi::then_ignore`].
+#[derive(Copy, Clone, Debug)]
+pub struct PrintThenIgnore<A, B>(A, B);
+
+impl<A: Print<O>, B: Print<()>, O> Print<O> for PrintThenIgnore<A, B> {
+    fn print(&self, value: &O, out: &mut String) -> Result<(), PrintError> {
+        self.0.print(value, out)?;
+        self.1.print(&(), out)
+    }
+}
+
+/// See [`Bi::or`].
+#[derive(Copy, Clone, Debug)]
+pub struct PrintOr<A, B>(A, B);
+
+impl<A: Print<O>, B: Print<O>, O> Print<O> for PrintOr<A, B> {
+    fn print(&self, value: &O, out: &mut String) -> Result<(), PrintError> {
+        let len = out.len();
+        self.0.print(value, out).or_else(|_| {
+            out.truncate(len);
+            self.1.print(value, out)
+        })
+    }
+}
+
+/// See [`Bi::map`].
+pub struct PrintMap<A, G, OA> {
+    printer: A,
+    inverse: G,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<OA>,
+}
+
+impl<A: Copy, G: Copy, OA> Copy for PrintMap<A, G, OA> {}
+impl<A: Clone, G: Clone, OA> Clone for PrintMap<A, G, OA> {
+    fn clone(&self) -> Self {
+        Self {
+            printer: self.printer.clone(),
+            inverse: self.inverse.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<A: Print<OA>, G: Fn(&U) -> Option<OA>, OA, U> Print<U> for PrintMap<A, G, OA> {
+    fn print(&self, value: &U, out: &mut String) -> Result<(), PrintError> {
+        let inner = (self.inverse)(value).ok_or(PrintError)?;
+        self.printer.print(&inner, out)
+    }
+}
+
+/// See [`Bi::separated_by`].
+#[derive(Copy, Clone, Debug)]
+pub struct PrintSeparated<A, B>(A, B);
+
+impl<A: Print<O>, B: Print<()>, O> Print<Vec<O>> for PrintSeparated<A, B> {
+    fn print(&self, values: &Vec<O>, out: &mut String) -> Result<(), PrintError> {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.1.print(&(), out)?;
+            }
+            self.0.print(value, out)?;
+        }
+        Ok(())
+    }
+}